@@ -15,6 +15,15 @@ mod m20260508_000011_bgm_oauth;
 mod m20260525_000012_move_custom_date_to_games;
 mod m20260706_000013_reconcile_indexes;
 mod m20260706_000014_migrate_game_sources;
+mod m20261016_000015_add_games_archived_at;
+mod m20261016_000016_add_games_primary_source;
+mod m20261016_000017_add_games_favorite;
+mod m20261016_000018_add_collection_view_sort;
+mod m20261016_000019_add_game_media;
+mod m20261016_000020_add_app_meta;
+mod m20261016_000021_add_savedata_base_backup;
+mod m20261016_000022_add_games_launch_args;
+mod m20261016_000023_add_games_launch_hooks;
 
 pub struct Migrator;
 
@@ -36,6 +45,15 @@ impl MigratorTrait for Migrator {
             Box::new(m20260525_000012_move_custom_date_to_games::Migration),
             Box::new(m20260706_000013_reconcile_indexes::Migration),
             Box::new(m20260706_000014_migrate_game_sources::Migration),
+            Box::new(m20261016_000015_add_games_archived_at::Migration),
+            Box::new(m20261016_000016_add_games_primary_source::Migration),
+            Box::new(m20261016_000017_add_games_favorite::Migration),
+            Box::new(m20261016_000018_add_collection_view_sort::Migration),
+            Box::new(m20261016_000019_add_game_media::Migration),
+            Box::new(m20261016_000020_add_app_meta::Migration),
+            Box::new(m20261016_000021_add_savedata_base_backup::Migration),
+            Box::new(m20261016_000022_add_games_launch_args::Migration),
+            Box::new(m20261016_000023_add_games_launch_hooks::Migration),
         ]
     }
 }
@@ -0,0 +1,85 @@
+//! 新增 game_media 表，保存每个游戏的截图等媒体文件及其顺序。
+//!
+//! 只记录文件名，文件位于封面目录的 `game_<id>/media` 下，随游戏封面目录一起迁移与清理。
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GameMedia::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GameMedia::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GameMedia::GameId).integer().not_null())
+                    .col(ColumnDef::new(GameMedia::FileName).text().not_null())
+                    .col(ColumnDef::new(GameMedia::MediaType).text().not_null())
+                    .col(
+                        ColumnDef::new(GameMedia::SortOrder)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GameMedia::CreatedAt)
+                            .integer()
+                            .default(Expr::cust("(strftime('%s', 'now'))")),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_game_media_game")
+                            .from(GameMedia::Table, GameMedia::GameId)
+                            .to(Games::Table, Games::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_game_media_game_sort")
+                    .table(GameMedia::Table)
+                    .col(GameMedia::GameId)
+                    .col(GameMedia::SortOrder)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GameMedia::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GameMedia {
+    Table,
+    Id,
+    GameId,
+    FileName,
+    MediaType,
+    SortOrder,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Games {
+    Table,
+    Id,
+}
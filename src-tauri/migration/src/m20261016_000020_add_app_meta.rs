@@ -0,0 +1,43 @@
+//! 新增 app_meta 键值表，记录最后写入数据库的应用版本等元信息。
+//!
+//! 启动时会先读取该表，数据库由更新版本的应用写入时拒绝自动迁移。
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AppMeta::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AppMeta::Key).text().not_null().primary_key())
+                    .col(ColumnDef::new(AppMeta::Value).text().not_null())
+                    .col(
+                        ColumnDef::new(AppMeta::UpdatedAt)
+                            .integer()
+                            .default(Expr::cust("(strftime('%s', 'now'))")),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AppMeta::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AppMeta {
+    Table,
+    Key,
+    Value,
+    UpdatedAt,
+}
@@ -0,0 +1,39 @@
+//! savedata 表添加 base_backup_id 字段，记录增量备份所依赖的基础备份。
+//!
+//! 完整备份为 NULL；增量备份只包含相对基础备份变化的文件，恢复时需沿该字段回溯。
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Savedata::Table)
+                    .add_column(ColumnDef::new(Savedata::BaseBackupId).integer().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Savedata::Table)
+                    .drop_column(Savedata::BaseBackupId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Savedata {
+    Table,
+    BaseBackupId,
+}
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::RwLock;
 
 /// 数据库相关路径常量
 pub const DB_DATA_DIR: &str = "data";
@@ -7,6 +8,10 @@ pub const DB_FILE_NAME: &str = "reina_manager.db";
 // 基础数据目录下的子目录名称
 pub const BACKUP_SUBDIR: &str = "backups";
 pub const RESOURCE_DIR: &str = "resources";
+pub const COVERS_SUBDIR: &str = "covers";
+
+/// 用户自定义的封面目录，未设置时使用 `<base>/covers`
+static CUSTOM_COVERS_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
 
 /// 判断是否处于便携模式（纯 Rust 版本）
 ///
@@ -87,3 +92,27 @@ pub fn get_default_db_backup_path() -> Result<PathBuf, String> {
 pub fn get_default_savedata_backup_path() -> Result<PathBuf, String> {
     Ok(get_base_data_dir()?.join(BACKUP_SUBDIR))
 }
+
+/// 获取默认的封面目录 `<base>/covers`
+pub fn get_default_covers_dir() -> Result<PathBuf, String> {
+    Ok(get_base_data_dir()?.join(COVERS_SUBDIR))
+}
+
+/// 获取封面目录：优先使用自定义目录，否则为 `<base>/covers`
+pub fn get_covers_dir() -> Result<PathBuf, String> {
+    let custom = CUSTOM_COVERS_DIR
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    match custom {
+        Some(dir) => Ok(dir),
+        None => get_default_covers_dir(),
+    }
+}
+
+/// 设置自定义封面目录，`None` 表示恢复默认目录
+///
+/// 仅修改进程内的路径，持久化由调用方负责。
+pub fn set_custom_covers_dir(dir: Option<PathBuf>) {
+    *CUSTOM_COVERS_DIR.write().unwrap_or_else(|e| e.into_inner()) = dir;
+}
@@ -1,5 +1,14 @@
+pub mod active;
 pub mod archive;
+pub mod autosave;
 pub mod common;
 pub mod covers;
 pub mod database;
+pub mod disk_usage;
+pub mod exclude;
+pub mod incremental;
+pub mod reconcile;
+pub mod save_detect;
 pub mod savedata;
+pub mod size_estimate;
+pub mod verify;
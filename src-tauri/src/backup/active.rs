@@ -0,0 +1,102 @@
+//! 正在进行的存档备份，支持按游戏取消。
+
+use parking_lot::RwLock;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{State, command};
+
+/// 取消备份时返回给前端的错误标识
+pub const BACKUP_CANCELLED: &str = "BACKUP_CANCELLED";
+
+/// 正在进行的存档备份（game_id -> 取消标记）
+#[derive(Default)]
+pub struct ActiveBackups {
+    games: RwLock<BTreeMap<i64, Arc<AtomicBool>>>,
+}
+
+impl ActiveBackups {
+    /// 登记一次备份，同一游戏已有备份进行中时返回 None
+    ///
+    /// 返回的守卫被丢弃时自动移除登记。
+    pub fn start(&self, game_id: i64) -> Option<ActiveBackup<'_>> {
+        let mut games = self.games.write();
+        if games.contains_key(&game_id) {
+            return None;
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        games.insert(game_id, Arc::clone(&cancelled));
+        Some(ActiveBackup {
+            backups: self,
+            game_id,
+            cancelled,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.read().is_empty()
+    }
+
+    /// 设置取消标记，该游戏没有进行中的备份时返回 false
+    fn cancel(&self, game_id: i64) -> bool {
+        match self.games.read().get(&game_id) {
+            Some(cancelled) => {
+                cancelled.store(true, Ordering::Release);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 一次进行中的备份登记
+pub struct ActiveBackup<'a> {
+    backups: &'a ActiveBackups,
+    game_id: i64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ActiveBackup<'_> {
+    pub fn cancelled(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.cancelled)
+    }
+}
+
+impl Drop for ActiveBackup<'_> {
+    fn drop(&mut self) {
+        self.backups.games.write().remove(&self.game_id);
+    }
+}
+
+/// 取消指定游戏正在进行的存档备份
+///
+/// 压缩会在处理下一个文件前停止，未完成的压缩包随即删除。
+#[command]
+pub fn cancel_backup(active: State<'_, ActiveBackups>, game_id: i64) -> Result<(), String> {
+    if active.cancel(game_id) {
+        log::info!("已请求取消存档备份 game_id={}", game_id);
+        Ok(())
+    } else {
+        Err(format!("该游戏没有正在进行的备份: game_id={}", game_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_is_released_when_backup_ends() {
+        let backups = ActiveBackups::default();
+
+        let backup = backups.start(1).expect("首次登记应成功");
+        assert!(backups.start(1).is_none());
+        assert!(backups.cancel(1));
+        assert!(backup.cancelled().load(Ordering::Acquire));
+
+        drop(backup);
+        assert!(backups.is_empty());
+        assert!(!backups.cancel(1));
+    }
+}
@@ -2,12 +2,34 @@
 //!
 //! 提供基于 Zstd 的 7z 压缩与解压功能，供存档备份、自定义封面备份等多处复用。
 
-use sevenz_rust2::{ArchiveWriter, decompress_file, encoder_options::ZstandardOptions};
+use serde::Serialize;
+use sevenz_rust2::{
+    Archive, ArchiveEntry, ArchiveReader, ArchiveWriter, EncoderConfiguration, EncoderMethod,
+    Error as SevenZError, Password, decompress_file, encoder_options::ZstandardOptions,
+};
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
 
 /// 速度与压缩率折中：使用 Zstd 低压缩等级。
 const ZSTD_COMPRESSION_LEVEL: u32 = 3;
+/// 存档备份的默认压缩等级，与其他备份使用的 Zstd 等级一致
+pub const DEFAULT_COMPRESSION_LEVEL: u8 = ZSTD_COMPRESSION_LEVEL as u8;
+/// 存档备份可选的最高压缩等级
+pub const MAX_COMPRESSION_LEVEL: u8 = 9;
+
+/// 按压缩等级选择压缩方法：0 为仅存储，1-9 为对应等级的 Zstd
+fn content_methods(level: u8) -> Vec<EncoderConfiguration> {
+    if level == 0 {
+        vec![EncoderMethod::COPY.into()]
+    } else {
+        vec![ZstandardOptions::from_level(u32::from(level)).into()]
+    }
+}
 
 /// 创建 7z 压缩包（递归压缩整个目录）
 ///
@@ -36,6 +58,141 @@ pub fn create_7z_archive(
     Ok(metadata.len())
 }
 
+/// 压缩被取消
+#[derive(Debug)]
+pub struct ArchiveCancelled;
+
+impl fmt::Display for ArchiveCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("压缩已取消")
+    }
+}
+
+impl std::error::Error for ArchiveCancelled {}
+
+/// 创建可取消的 7z 压缩包，只写入 `include` 返回 true 的条目
+///
+/// 逐个文件写入，每个文件开始前检查 `cancelled`；取消或出错时删除未完成的压缩包，
+/// 取消时返回 [`ArchiveCancelled`]。
+/// `include(相对路径, 是否为目录)` 接收以 `/` 分隔的相对路径；对目录返回 false 时
+/// 整个目录被跳过，不再向下遍历。
+/// `level` 为 0-9 的压缩等级，见 [`content_methods`]。
+/// 每个文件写入前调用 `on_progress(文件相对路径, 已处理文件数, 文件总数)`，
+/// 文件总数在压缩前遍历一次得到，两次遍历使用同一份按文件名排序的列表。
+pub fn create_7z_archive_filtered(
+    source_dir: &Path,
+    archive_path: &Path,
+    level: u8,
+    cancelled: &AtomicBool,
+    include: impl Fn(&str, bool) -> bool,
+    mut on_progress: impl FnMut(&str, usize, usize),
+) -> Result<u64, Box<dyn std::error::Error>> {
+    let result = write_archive_entries(
+        source_dir,
+        archive_path,
+        level,
+        cancelled,
+        &include,
+        &mut on_progress,
+    );
+    if result.is_err()
+        && archive_path.exists()
+        && let Err(e) = fs::remove_file(archive_path)
+    {
+        log::warn!(
+            "删除未完成的压缩包失败 path={} error={}",
+            archive_path.display(),
+            e
+        );
+    }
+    result
+}
+
+fn write_archive_entries(
+    source_dir: &Path,
+    archive_path: &Path,
+    level: u8,
+    cancelled: &AtomicBool,
+    include: &dyn Fn(&str, bool) -> bool,
+    on_progress: &mut dyn FnMut(&str, usize, usize),
+) -> Result<u64, Box<dyn std::error::Error>> {
+    // 预扫描：先确定要写入的条目与文件总数
+    let mut entries = Vec::new();
+    let mut walker = WalkDir::new(source_dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry?;
+        let name = entry
+            .path()
+            .strip_prefix(source_dir)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let is_dir = entry.file_type().is_dir();
+        if !include(&name, is_dir) {
+            if is_dir {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        entries.push((entry, name, is_dir));
+    }
+    let total = entries.iter().filter(|(_, _, is_dir)| !is_dir).count();
+
+    let mut writer = ArchiveWriter::create(archive_path)?;
+    writer.set_content_methods(content_methods(level));
+
+    let mut processed = 0;
+    for (entry, name, is_dir) in entries {
+        if cancelled.load(Ordering::Acquire) {
+            return Err(Box::new(ArchiveCancelled));
+        }
+
+        if !is_dir {
+            processed += 1;
+            on_progress(&name, processed, total);
+        }
+        let archive_entry = ArchiveEntry::from_path(entry.path(), name);
+        if is_dir {
+            writer.push_archive_entry::<fs::File>(archive_entry, None)?;
+        } else {
+            writer.push_archive_entry(archive_entry, Some(fs::File::open(entry.path())?))?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(fs::metadata(archive_path)?.len())
+}
+
+/// 用与备份相同的压缩参数在内存中压缩样本，返回 (样本原始字节数, 压缩后字节数)
+///
+/// 每个文件只读取前 `max_bytes_per_file` 字节，用于估算压缩率；`level` 同备份的压缩等级。
+pub fn compress_sample(
+    files: &[PathBuf],
+    max_bytes_per_file: u64,
+    level: u8,
+) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+    let mut writer = ArchiveWriter::new(Cursor::new(Vec::new()))?;
+    writer.set_content_methods(content_methods(level));
+
+    let mut sampled = 0;
+    for (index, path) in files.iter().enumerate() {
+        let mut data = Vec::new();
+        fs::File::open(path)?
+            .take(max_bytes_per_file)
+            .read_to_end(&mut data)?;
+        sampled += data.len() as u64;
+        writer.push_archive_entry(
+            ArchiveEntry::new_file(&format!("sample_{}", index)),
+            Some(Cursor::new(data)),
+        )?;
+    }
+
+    let compressed = writer.finish()?.into_inner().len() as u64;
+    Ok((sampled, compressed))
+}
+
 /// 解压 7z 压缩包（覆盖模式）
 ///
 /// 解压前会先清空目标目录的所有内容，确保恢复结果完整干净。
@@ -68,3 +225,94 @@ pub fn extract_7z_archive(
     decompress_file(archive_path, target_dir)?;
     Ok(())
 }
+
+/// 解压 7z 压缩包到目标目录，不清空已有内容，同名文件会被覆盖
+///
+/// 用于在完整备份之上依次叠加增量备份。
+pub fn extract_7z_archive_into(
+    archive_path: &Path,
+    target_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(target_dir)?;
+    decompress_file(archive_path, target_dir)?;
+    Ok(())
+}
+
+/// 压缩包内的单个条目
+#[derive(Debug, Serialize)]
+pub struct ArchiveEntryInfo {
+    pub name: String,
+    pub size: u64,
+    pub is_dir: bool,
+    /// 修改时间（Unix 秒），压缩包未记录时为 None
+    pub modified: Option<i64>,
+}
+
+/// 读取 7z 压缩包条目列表时的错误
+#[derive(Debug)]
+pub enum ArchiveListError {
+    /// 压缩包头已加密，需要密码
+    PasswordRequired,
+    /// 提供的密码无法解开压缩包头
+    WrongPassword,
+    Other(String),
+}
+
+/// 列出 7z 压缩包中的条目（仅读取文件头，不解压内容）
+///
+/// # Arguments
+/// * `archive_path` - 压缩包路径
+/// * `password` - 加密压缩包的密码，未加密时传 None
+pub fn list_7z_entries(
+    archive_path: &Path,
+    password: Option<&str>,
+) -> Result<Vec<ArchiveEntryInfo>, ArchiveListError> {
+    let mut file =
+        fs::File::open(archive_path).map_err(|e| ArchiveListError::Other(e.to_string()))?;
+    let password = password.map(Password::from).unwrap_or_else(Password::empty);
+
+    let archive = match Archive::read(&mut file, &password) {
+        Ok(archive) => archive,
+        Err(SevenZError::PasswordRequired) => return Err(ArchiveListError::PasswordRequired),
+        Err(SevenZError::MaybeBadPassword(_)) => return Err(ArchiveListError::WrongPassword),
+        Err(e) => return Err(ArchiveListError::Other(e.to_string())),
+    };
+
+    Ok(archive
+        .files
+        .iter()
+        .map(|entry| ArchiveEntryInfo {
+            name: entry.name.clone(),
+            size: entry.size,
+            is_dir: entry.is_directory,
+            modified: entry
+                .has_last_modified_date
+                .then(|| SystemTime::try_from(entry.last_modified_date).ok())
+                .flatten()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs() as i64),
+        })
+        .collect())
+}
+
+/// 完整解压一遍 7z 压缩包（输出丢弃），借助条目的 CRC 校验发现损坏
+///
+/// # Arguments
+/// * `archive_path` - 压缩包路径
+pub fn verify_7z_archive(archive_path: &Path) -> Result<(), ArchiveListError> {
+    let mut reader = match ArchiveReader::open(archive_path, Password::empty()) {
+        Ok(reader) => reader,
+        Err(SevenZError::PasswordRequired) => return Err(ArchiveListError::PasswordRequired),
+        Err(e) => return Err(ArchiveListError::Other(e.to_string())),
+    };
+
+    reader
+        .for_each_entries(|_, entry_reader| {
+            io::copy(entry_reader, &mut io::sink())?;
+            Ok(true)
+        })
+        .map_err(|e| match e {
+            SevenZError::PasswordRequired => ArchiveListError::PasswordRequired,
+            e => ArchiveListError::Other(e.to_string()),
+        })
+}
@@ -0,0 +1,72 @@
+//! 游戏退出后自动备份存档。
+
+use super::active::ActiveBackups;
+use super::savedata::create_savedata_backup;
+use crate::database::repository::games_repository::GamesRepository;
+use crate::database::service::record_savedata_backup;
+use log::{info, warn};
+use sea_orm::DatabaseConnection;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// 游戏退出后按 `autosave` 设置自动备份存档
+///
+/// 备份流程与手动备份相同（包括按 `maxbackups` 清理旧备份），完成后保存记录并发送
+/// `automatic` 为 true 的 `savedata-backup-created` 事件。未开启自动备份时直接返回；
+/// 未设置存档路径或备份失败时只记录日志。
+pub(crate) async fn backup_on_exit<R: Runtime>(
+    app: &AppHandle<R>,
+    db: &DatabaseConnection,
+    game_id: u32,
+) {
+    let game = match GamesRepository::find_by_id(db, game_id as i32).await {
+        Ok(Some(game)) => game,
+        Ok(None) => return,
+        Err(e) => {
+            warn!("自动备份时查询游戏失败 game_id={}: {}", game_id, e);
+            return;
+        }
+    };
+    if game.autosave != Some(1) {
+        return;
+    }
+    let Some(savepath) = game.savepath.filter(|path| !path.trim().is_empty()) else {
+        info!(
+            "游戏已开启自动备份但未设置存档路径，跳过 game_id={}",
+            game_id
+        );
+        return;
+    };
+    let (Some(db_state), Some(active)) = (
+        app.try_state::<DatabaseConnection>(),
+        app.try_state::<ActiveBackups>(),
+    ) else {
+        warn!("自动备份所需的应用状态未初始化，跳过 game_id={}", game_id);
+        return;
+    };
+
+    info!("开始自动备份 game_id={} savepath={}", game_id, savepath);
+    let backup = match create_savedata_backup(
+        app.clone(),
+        db_state,
+        active,
+        i64::from(game_id),
+        savepath,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    {
+        Ok(backup) => backup,
+        Err(e) => {
+            warn!("自动备份失败 game_id={}: {}", game_id, e);
+            return;
+        }
+    };
+
+    match record_savedata_backup(app, db, game_id as i32, backup, true).await {
+        Ok(record_id) => info!("自动备份完成 game_id={} record_id={}", game_id, record_id),
+        Err(e) => warn!("自动备份记录保存失败 game_id={}: {}", game_id, e),
+    }
+}
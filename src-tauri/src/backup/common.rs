@@ -19,6 +19,14 @@ pub struct BackupResult {
     pub message: String,
 }
 
+/// 读取当前配置的数据库备份目录（未设置时为默认目录），不校验也不创建目录
+pub async fn configured_db_backup_dir(db: &DatabaseConnection) -> Result<PathBuf, String> {
+    match db.get_settings().await?.db_backup_path_value() {
+        Some(custom) => Ok(PathBuf::from(custom)),
+        None => reina_path::get_default_db_backup_path(),
+    }
+}
+
 pub async fn resolve_backup_dir(db: &DatabaseConnection) -> Result<PathBuf, String> {
     let settings = db.get_settings().await?;
 
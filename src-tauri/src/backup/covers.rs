@@ -47,7 +47,7 @@ pub async fn backup_custom_covers_archive(
     auto: bool,
 ) -> Result<BackupResult, String> {
     // 1. 获取封面根目录
-    let covers_dir = reina_path::get_base_data_dir()?.join("covers");
+    let covers_dir = reina_path::get_covers_dir()?;
     if !covers_dir.exists() {
         return Ok(BackupResult {
             success: true,
@@ -124,15 +124,26 @@ pub async fn backup_custom_covers_archive(
     })
 }
 
+/// 删除所有游戏的封面目录
+///
+/// 封面目录可能是用户自定义的位置，因此只删除其中的 `game_*` 子目录。
 pub fn delete_all_covers_dir() -> Result<(), String> {
-    let covers_dir = reina_path::get_base_data_dir()?.join("covers");
+    let covers_dir = reina_path::get_covers_dir()?;
 
     if !covers_dir.exists() {
         return Ok(());
     }
 
-    fs::remove_dir_all(&covers_dir)
-        .map_err(|e| format!("无法删除封面目录 {}: {}", covers_dir.display(), e))?;
+    let entries = fs::read_dir(&covers_dir)
+        .map_err(|e| format!("无法读取封面目录 {}: {}", covers_dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+        let path = entry.path();
+        if path.is_dir() && entry.file_name().to_string_lossy().starts_with("game_") {
+            fs::remove_dir_all(&path)
+                .map_err(|e| format!("无法删除封面目录 {}: {}", path.display(), e))?;
+        }
+    }
 
     Ok(())
 }
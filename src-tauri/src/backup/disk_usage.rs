@@ -0,0 +1,180 @@
+//! 存档备份目录的磁盘占用统计。
+//!
+//! 遍历备份根目录，按 `game_<id>` 子目录汇总每个游戏的占用，供存储管理页面展示。
+
+use super::savedata::resolve_savedata_backup_root;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+use tauri::{State, command};
+use walkdir::WalkDir;
+
+/// 单个游戏备份目录的占用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GameBackupUsage {
+    pub game_id: i32,
+    pub bytes: u64,
+    pub file_count: usize,
+}
+
+/// 最大的单个备份压缩包
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LargestBackupArchive {
+    /// 所在的游戏备份目录对应的游戏 ID，不在 `game_<id>` 目录中时为 None
+    pub game_id: Option<i32>,
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// 备份目录磁盘占用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupDiskUsage {
+    /// 备份根目录
+    pub root: String,
+    pub total_bytes: u64,
+    /// 各游戏的占用，按占用从大到小排列
+    pub games: Vec<GameBackupUsage>,
+    /// 不属于任何 `game_<id>` 目录的文件占用
+    pub other_bytes: u64,
+    pub largest_archive: Option<LargestBackupArchive>,
+}
+
+/// 统计存档备份目录的磁盘占用
+///
+/// 备份目录尚未创建时各项均为 0。
+#[command]
+pub async fn get_backup_disk_usage(
+    db: State<'_, DatabaseConnection>,
+) -> Result<BackupDiskUsage, String> {
+    let backup_root = resolve_savedata_backup_root(&db).await?;
+
+    tokio::task::spawn_blocking(move || scan_backup_root(&backup_root))
+        .await
+        .map_err(|e| format!("统计备份占用任务失败: {}", e))
+}
+
+fn scan_backup_root(root: &Path) -> BackupDiskUsage {
+    let mut total_bytes = 0;
+    let mut other_bytes = 0;
+    let mut games: BTreeMap<i32, GameBackupUsage> = BTreeMap::new();
+    let mut largest_archive: Option<LargestBackupArchive> = None;
+
+    for entry in WalkDir::new(root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let bytes = metadata.len();
+        total_bytes += bytes;
+
+        let game_id = entry
+            .path()
+            .strip_prefix(root)
+            .ok()
+            .and_then(|relative| relative.components().next())
+            .and_then(parse_game_dir);
+        match game_id {
+            Some(game_id) => {
+                let usage = games.entry(game_id).or_insert(GameBackupUsage {
+                    game_id,
+                    bytes: 0,
+                    file_count: 0,
+                });
+                usage.bytes += bytes;
+                usage.file_count += 1;
+            }
+            None => other_bytes += bytes,
+        }
+
+        let is_archive = entry
+            .path()
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("7z"));
+        if is_archive
+            && largest_archive
+                .as_ref()
+                .is_none_or(|largest| bytes > largest.bytes)
+        {
+            largest_archive = Some(LargestBackupArchive {
+                game_id,
+                path: entry.path().to_string_lossy().to_string(),
+                bytes,
+            });
+        }
+    }
+
+    let mut games = games.into_values().collect::<Vec<_>>();
+    games.sort_by(|left, right| {
+        right
+            .bytes
+            .cmp(&left.bytes)
+            .then(left.game_id.cmp(&right.game_id))
+    });
+
+    BackupDiskUsage {
+        root: root.to_string_lossy().to_string(),
+        total_bytes,
+        games,
+        other_bytes,
+        largest_archive,
+    }
+}
+
+/// 解析 `savedata.rs` 使用的 `game_<id>` 目录名
+fn parse_game_dir(component: Component<'_>) -> Option<i32> {
+    let Component::Normal(name) = component else {
+        return None;
+    };
+    name.to_str()?.strip_prefix("game_")?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn usage_is_grouped_by_game_directory() {
+        let root = std::env::temp_dir().join(format!("reina-backup-usage-{}", std::process::id()));
+        fs::create_dir_all(root.join("game_1")).unwrap();
+        fs::create_dir_all(root.join("game_2")).unwrap();
+        fs::create_dir_all(root.join("game_x")).unwrap();
+        fs::write(root.join("game_1").join("a.7z"), vec![0; 100]).unwrap();
+        fs::write(root.join("game_1").join("a.manifest.json"), vec![0; 10]).unwrap();
+        fs::write(root.join("game_2").join("b.7z"), vec![0; 300]).unwrap();
+        fs::write(root.join("game_x").join("c.7z"), vec![0; 50]).unwrap();
+
+        let usage = scan_backup_root(&root);
+
+        assert_eq!(usage.total_bytes, 460);
+        assert_eq!(usage.other_bytes, 50);
+        assert_eq!(
+            usage.games,
+            vec![
+                GameBackupUsage {
+                    game_id: 2,
+                    bytes: 300,
+                    file_count: 1,
+                },
+                GameBackupUsage {
+                    game_id: 1,
+                    bytes: 110,
+                    file_count: 2,
+                },
+            ]
+        );
+        let largest = usage.largest_archive.unwrap();
+        assert_eq!(largest.game_id, Some(2));
+        assert_eq!(largest.bytes, 300);
+
+        let missing = scan_backup_root(&root.join("missing"));
+        assert_eq!(missing.total_bytes, 0);
+        assert!(missing.games.is_empty() && missing.largest_archive.is_none());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
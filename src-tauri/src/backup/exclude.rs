@@ -0,0 +1,113 @@
+//! 备份存档时的排除规则。
+//!
+//! 使用简化的 glob 语法匹配以 `/` 分隔的相对路径：`*` 匹配段内任意字符，
+//! `?` 匹配段内单个字符，`**` 匹配任意层目录。不含 `/` 的规则（如 `*.log`）
+//! 匹配任意层级的文件或目录名；`cache/**` 会连同 `cache` 目录本身一起排除。
+
+/// 一组排除规则，为空时不排除任何文件
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeGlobs {
+    patterns: Vec<Vec<String>>,
+}
+
+impl ExcludeGlobs {
+    /// 解析排除规则，忽略空白规则
+    pub fn new(patterns: &[String]) -> Result<Self, String> {
+        let mut parsed = Vec::new();
+        for pattern in patterns {
+            let pattern = pattern.trim().replace('\\', "/");
+            let pattern = pattern.trim_matches('/');
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut segments = pattern.split('/').map(str::to_string).collect::<Vec<_>>();
+            if segments.iter().any(String::is_empty) {
+                return Err(format!("排除规则格式无效: {}", pattern));
+            }
+            if segments.len() == 1 && segments[0] != "**" {
+                segments.insert(0, "**".to_string());
+            }
+            parsed.push(segments);
+        }
+        Ok(Self { patterns: parsed })
+    }
+
+    /// 相对路径（`/` 分隔）是否被排除；被排除的目录不会再向下遍历
+    pub fn is_excluded(&self, path: &str) -> bool {
+        let segments = path.split('/').collect::<Vec<_>>();
+        self.patterns
+            .iter()
+            .any(|pattern| match_segments(pattern, &segments))
+    }
+}
+
+fn match_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path.split_first().is_some_and(|(segment, path)| {
+            match_segment(first, segment) && match_segments(rest, path)
+        }),
+    }
+}
+
+/// 单个路径段的通配符匹配，支持 `*` 与 `?`
+fn match_segment(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // 最近一个 `*` 的位置，以及它当时对应的文本位置
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(patterns: &[&str]) -> ExcludeGlobs {
+        let patterns = patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        ExcludeGlobs::new(&patterns).unwrap()
+    }
+
+    #[test]
+    fn matches_names_at_any_depth_and_directory_trees() {
+        let globs = globs(&["*.log", "cache/**", "save/tmp?.dat", " "]);
+
+        assert!(globs.is_excluded("game.log"));
+        assert!(globs.is_excluded("logs/2024/run.log"));
+        assert!(!globs.is_excluded("game.log.sav"));
+        assert!(globs.is_excluded("cache"));
+        assert!(globs.is_excluded("cache/shader/a.bin"));
+        assert!(!globs.is_excluded("save/cache"));
+        assert!(globs.is_excluded("save/tmp1.dat"));
+        assert!(!globs.is_excluded("save/tmp12.dat"));
+        assert!(!globs.is_excluded("slot/01.sav"));
+        assert!(!ExcludeGlobs::default().is_excluded("game.log"));
+    }
+
+    #[test]
+    fn segment_wildcards_backtrack() {
+        assert!(match_segment("a*b*c", "aXbYbZc"));
+        assert!(match_segment("*", ""));
+        assert!(!match_segment("a*c", "abcd"));
+        assert!(ExcludeGlobs::new(&["a//b".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,562 @@
+//! 增量存档备份。
+//!
+//! 每个存档备份旁都会写入 `<备份文件名>.manifest.json`，记录备份时存档目录中全部文件的
+//! 大小、修改时间与 CRC32。增量备份与最近一次备份的清单比较：大小与修改时间都未变的文件
+//! 直接沿用清单中的 CRC32，不再读取内容；其余文件重新计算 CRC32，只压缩新增或内容变化的文件，
+//! 并在清单中记录所依赖的基础备份。恢复时从完整备份开始沿依赖链依次解压，
+//! 最后删除最终清单中不存在的文件。
+
+use super::active::ActiveBackups;
+use super::archive::{create_7z_archive, extract_7z_archive, extract_7z_archive_into};
+use super::exclude::ExcludeGlobs;
+use super::savedata::{
+    BackupInfo, delete_backup_record, resolve_savedata_backup_root, unique_backup_file,
+};
+use crate::database::repository::games_repository::GamesRepository;
+use crate::entity::savedata;
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::{State, command};
+use walkdir::WalkDir;
+
+/// 清单文件后缀，追加在备份文件名之后
+pub(super) const MANIFEST_SUFFIX: &str = ".manifest.json";
+
+/// 清单中的单个文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub crc32: u32,
+    /// 修改时间（Unix 毫秒），无法读取时为 None
+    #[serde(default)]
+    pub modified: Option<i64>,
+}
+
+impl ManifestEntry {
+    /// 内容是否相同（不比较修改时间）
+    fn same_content(&self, other: &Self) -> bool {
+        self.size == other.size && self.crc32 == other.crc32
+    }
+}
+
+/// 备份清单
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// 所依赖的基础备份文件名（同一游戏备份目录下），完整备份为 None
+    pub base_file: Option<String>,
+    /// 以 `/` 分隔的相对路径 -> 文件信息，包含备份时存档目录中的全部文件
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// 备份文件对应的清单路径
+pub fn manifest_path(backup_path: &Path) -> PathBuf {
+    let mut name = backup_path.as_os_str().to_os_string();
+    name.push(MANIFEST_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// 读取备份的清单，旧版本创建的备份没有清单时返回 None
+pub fn read_manifest(backup_path: &Path) -> Result<Option<BackupManifest>, String> {
+    let content = match fs::read_to_string(manifest_path(backup_path)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("读取备份清单失败: {}", e)),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("解析备份清单失败: {}", e))
+}
+
+pub fn write_manifest(backup_path: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let content =
+        serde_json::to_string(manifest).map_err(|e| format!("序列化备份清单失败: {}", e))?;
+    fs::write(manifest_path(backup_path), content).map_err(|e| format!("写入备份清单失败: {}", e))
+}
+
+/// 计算存档目录中全部文件的大小、修改时间与 CRC32
+///
+/// 传入基础清单时，大小与修改时间都与基础清单一致的文件直接沿用其 CRC32；
+/// 被排除的文件与目录不会出现在清单中
+pub fn build_manifest_files(
+    source: &Path,
+    base: Option<&BTreeMap<String, ManifestEntry>>,
+    exclude: &ExcludeGlobs,
+) -> Result<BTreeMap<String, ManifestEntry>, String> {
+    let mut files = BTreeMap::new();
+    let mut walker = WalkDir::new(source).min_depth(1).into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry.map_err(|e| format!("遍历存档目录失败: {}", e))?;
+        let name = relative_name(source, entry.path())?;
+        if exclude.is_excluded(&name) {
+            if entry.file_type().is_dir() {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = entry
+            .metadata()
+            .map_err(|e| format!("读取存档文件信息失败 {}: {}", entry.path().display(), e))?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as i64);
+
+        let unchanged = base
+            .and_then(|base| base.get(&name))
+            .filter(|old| {
+                old.modified.is_some() && old.modified == modified && old.size == metadata.len()
+            })
+            .copied();
+        let manifest_entry = match unchanged {
+            Some(old) => old,
+            None => ManifestEntry {
+                modified,
+                ..hash_file(entry.path())
+                    .map_err(|e| format!("读取存档文件失败 {}: {}", entry.path().display(), e))?
+            },
+        };
+        files.insert(name, manifest_entry);
+    }
+    Ok(files)
+}
+
+fn relative_name(root: &Path, path: &Path) -> Result<String, String> {
+    Ok(path
+        .strip_prefix(root)
+        .map_err(|e| format!("计算相对路径失败: {}", e))?
+        .to_string_lossy()
+        .replace('\\', "/"))
+}
+
+fn hash_file(path: &Path) -> io::Result<ManifestEntry> {
+    let mut file = fs::File::open(path)?;
+    let mut crc = flate2::Crc::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        crc.update(&buffer[..read]);
+        size += read as u64;
+    }
+    Ok(ManifestEntry {
+        size,
+        crc32: crc.sum(),
+        modified: None,
+    })
+}
+
+/// 相对基础清单新增或内容变化的文件
+pub fn changed_files(
+    current: &BTreeMap<String, ManifestEntry>,
+    base: &BTreeMap<String, ManifestEntry>,
+) -> BTreeSet<String> {
+    current
+        .iter()
+        .filter(|(name, entry)| !base.get(*name).is_some_and(|old| old.same_content(entry)))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// 从备份沿 `base_file` 回溯，返回按恢复顺序排列（完整备份在前）的备份路径与最终清单
+///
+/// 没有清单的旧备份视为完整备份
+fn resolve_chain(backup_path: &Path) -> Result<(Vec<PathBuf>, Option<BackupManifest>), String> {
+    let backup_dir = backup_path.parent().unwrap_or(Path::new(""));
+    let final_manifest = read_manifest(backup_path)?;
+
+    let mut chain = vec![backup_path.to_path_buf()];
+    let mut base_file = final_manifest
+        .as_ref()
+        .and_then(|manifest| manifest.base_file.clone());
+    let mut visited = HashSet::new();
+    while let Some(file) = base_file {
+        if !visited.insert(file.clone()) {
+            return Err(format!("增量备份依赖链存在循环: {}", file));
+        }
+        let path = backup_dir.join(&file);
+        if !path.is_file() {
+            return Err(format!("增量备份依赖的基础备份不存在: {}", file));
+        }
+        base_file = read_manifest(&path)?.and_then(|manifest| manifest.base_file);
+        chain.push(path);
+    }
+    chain.reverse();
+    Ok((chain, final_manifest))
+}
+
+/// 恢复备份，增量备份会沿依赖链从完整备份开始依次解压
+///
+/// 恢复前会清空目标目录；最终只保留备份时存在的文件
+pub fn restore_chain(backup_path: &Path, target_path: &Path) -> Result<(), String> {
+    let (chain, final_manifest) = resolve_chain(backup_path)?;
+    let (full, incrementals) = chain.split_first().expect("恢复链至少包含备份本身");
+
+    extract_7z_archive(full, target_path).map_err(|e| format!("解压备份失败: {}", e))?;
+    if incrementals.is_empty() {
+        return Ok(());
+    }
+    for path in incrementals {
+        extract_7z_archive_into(path, target_path)
+            .map_err(|e| format!("解压增量备份失败: {}", e))?;
+    }
+    if let Some(manifest) = final_manifest {
+        remove_untracked_files(target_path, &manifest.files)?;
+    }
+    Ok(())
+}
+
+/// 删除目标目录中不在清单里的文件（基础备份中有、之后已被删除的文件）
+fn remove_untracked_files(
+    target: &Path,
+    files: &BTreeMap<String, ManifestEntry>,
+) -> Result<(), String> {
+    for entry in WalkDir::new(target).min_depth(1) {
+        let entry = entry.map_err(|e| format!("遍历恢复目录失败: {}", e))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if !files.contains_key(&relative_name(target, entry.path())?) {
+            fs::remove_file(entry.path()).map_err(|e| format!("删除多余文件失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// 合并备份链的结果
+#[derive(Debug, Serialize)]
+pub struct CompactChainResult {
+    /// 新完整备份的记录 ID
+    pub record_id: i32,
+    pub backup: BackupInfo,
+    /// 删除的增量备份数量
+    pub removed: usize,
+}
+
+/// 将游戏最新备份所在的增量链合并为一个新的完整备份
+///
+/// 新备份沿用最新备份的时间；链上不再被其他备份依赖的增量备份会被删除，
+/// 作为起点的完整备份保留。最新备份本身已是完整备份时返回 None。
+///
+/// # Arguments
+/// * `game_id` - 游戏ID
+#[command]
+pub async fn compact_backup_chain(
+    db: State<'_, DatabaseConnection>,
+    active: State<'_, ActiveBackups>,
+    game_id: i32,
+) -> Result<Option<CompactChainResult>, String> {
+    let _backup = active
+        .start(i64::from(game_id))
+        .ok_or_else(|| "该游戏的备份正在进行中".to_string())?;
+
+    let records = GamesRepository::get_savedata_records(&db, game_id)
+        .await
+        .map_err(|e| format!("获取备份记录失败: {}", e))?;
+    let Some(latest) = records
+        .iter()
+        .max_by_key(|record| (record.backup_time, record.id))
+    else {
+        return Ok(None);
+    };
+    if latest.base_backup_id.is_none() {
+        return Ok(None);
+    }
+
+    let game_backup_dir = resolve_savedata_backup_root(&db)
+        .await?
+        .join(format!("game_{}", game_id));
+    let latest_path = game_backup_dir.join(&latest.file);
+    let (backup_filename, backup_file_path) = unique_backup_file(
+        &game_backup_dir,
+        &format!(
+            "savedata_{}_{}",
+            game_id,
+            Utc::now().format("%Y%m%d_%H%M%S")
+        ),
+    );
+    let staging_dir = std::env::temp_dir().join(format!(
+        "reina_compact_{}_{}",
+        game_id,
+        Utc::now().timestamp_millis()
+    ));
+
+    let backup_size = {
+        let archive_path = backup_file_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = compact_into(&latest_path, &staging_dir, &archive_path);
+            if let Err(e) = fs::remove_dir_all(&staging_dir) {
+                log::warn!("删除合并临时目录失败: {}", e);
+            }
+            result
+        })
+        .await
+        .map_err(|e| format!("合并备份链任务失败: {}", e))??
+    };
+
+    let record_id = GamesRepository::save_savedata_record(
+        &db,
+        game_id,
+        &backup_filename,
+        latest.backup_time,
+        backup_size as i32,
+        None,
+    )
+    .await
+    .map_err(|e| format!("保存存档备份记录失败: {}", e))?;
+
+    let mut errors = Vec::new();
+    let removable = removable_chain_records(&records, latest.id);
+    for record in &removable {
+        if let Some(error) =
+            delete_backup_record(&db, &game_backup_dir.join(&record.file), record.id, false).await
+        {
+            errors.push(error);
+        }
+    }
+    if !errors.is_empty() {
+        log::warn!(
+            "合并备份链后删除增量备份时遇到 {} 个错误:\n{}",
+            errors.len(),
+            errors.join("\n")
+        );
+    }
+
+    log::info!(
+        "存档备份链合并完成 game_id={} file={} removed={}",
+        game_id,
+        backup_filename,
+        removable.len()
+    );
+    Ok(Some(CompactChainResult {
+        record_id,
+        backup: BackupInfo {
+            folder_name: backup_filename,
+            backup_time: i64::from(latest.backup_time),
+            file_size: backup_size,
+            manifest: Some(
+                manifest_path(&backup_file_path)
+                    .to_string_lossy()
+                    .to_string(),
+            ),
+            backup_path: backup_file_path.to_string_lossy().to_string(),
+            base_backup_id: None,
+            verified: false,
+        },
+        removed: removable.len(),
+    }))
+}
+
+/// 将增量链恢复到临时目录，再重新压缩为完整备份并写入清单
+fn compact_into(
+    latest_path: &Path,
+    staging_dir: &Path,
+    archive_path: &Path,
+) -> Result<u64, String> {
+    restore_chain(latest_path, staging_dir)?;
+    let size = create_7z_archive(staging_dir, archive_path)
+        .map_err(|e| format!("创建压缩包失败: {}", e))?;
+    let manifest = BackupManifest {
+        base_file: None,
+        files: build_manifest_files(staging_dir, None, &ExcludeGlobs::default())?,
+    };
+    if let Err(e) = write_manifest(archive_path, &manifest) {
+        fs::remove_file(archive_path).ok();
+        return Err(e);
+    }
+    Ok(size)
+}
+
+/// 链上可删除的增量备份：从 `latest_id` 回溯到完整备份之前的记录中，
+/// 不被链外任何备份（直接或间接）依赖的那些
+fn removable_chain_records(records: &[savedata::Model], latest_id: i32) -> Vec<savedata::Model> {
+    let by_id = records
+        .iter()
+        .map(|record| (record.id, record))
+        .collect::<HashMap<_, _>>();
+    let ancestors = |start: i32| {
+        let mut ids = Vec::new();
+        let mut current = Some(start);
+        while let Some(id) = current {
+            if ids.contains(&id) {
+                break;
+            }
+            ids.push(id);
+            current = by_id.get(&id).and_then(|record| record.base_backup_id);
+        }
+        ids
+    };
+
+    let chain = ancestors(latest_id).into_iter().collect::<HashSet<_>>();
+    let needed = records
+        .iter()
+        .filter(|record| !chain.contains(&record.id))
+        .flat_map(|record| ancestors(record.id))
+        .collect::<HashSet<_>>();
+    records
+        .iter()
+        .filter(|record| {
+            chain.contains(&record.id)
+                && record.base_backup_id.is_some()
+                && !needed.contains(&record.id)
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::archive::{DEFAULT_COMPRESSION_LEVEL, create_7z_archive_filtered};
+    use std::sync::atomic::AtomicBool;
+
+    fn record(id: i32, base_backup_id: Option<i32>) -> savedata::Model {
+        savedata::Model {
+            id,
+            game_id: 1,
+            file: format!("{}.7z", id),
+            backup_time: id,
+            file_size: 1,
+            base_backup_id,
+        }
+    }
+
+    /// 按当前存档目录创建备份：`base` 为 Some 时只压缩相对基础备份变化的文件
+    fn backup(source: &Path, archive: &Path, base: Option<&Path>) {
+        let base_manifest = base.map(|path| read_manifest(path).unwrap().unwrap());
+        let files = build_manifest_files(
+            source,
+            base_manifest.as_ref().map(|manifest| &manifest.files),
+            &ExcludeGlobs::default(),
+        )
+        .unwrap();
+        let include = base_manifest
+            .as_ref()
+            .map(|manifest| changed_files(&files, &manifest.files));
+        let cancelled = AtomicBool::new(false);
+        create_7z_archive_filtered(
+            source,
+            archive,
+            DEFAULT_COMPRESSION_LEVEL,
+            &cancelled,
+            |name, is_dir| is_dir || include.as_ref().is_none_or(|set| set.contains(name)),
+            |_, _, _| {},
+        )
+        .unwrap();
+        let manifest = BackupManifest {
+            base_file: base.map(|path| path.file_name().unwrap().to_string_lossy().to_string()),
+            files,
+        };
+        write_manifest(archive, &manifest).unwrap();
+    }
+
+    #[test]
+    fn restores_incremental_chain_with_changes_and_deletions() {
+        let root =
+            std::env::temp_dir().join(format!("reina-backup-incremental-{}", std::process::id()));
+        let source = root.join("save");
+        let backups = root.join("backups");
+        fs::create_dir_all(source.join("slot")).unwrap();
+        fs::create_dir_all(&backups).unwrap();
+        fs::write(source.join("system.dat"), b"system v1").unwrap();
+        fs::write(source.join("slot").join("01.sav"), b"slot one").unwrap();
+        fs::write(source.join("slot").join("02.sav"), b"slot two").unwrap();
+
+        let full = backups.join("full.7z");
+        backup(&source, &full, None);
+
+        fs::write(source.join("system.dat"), b"system v2").unwrap();
+        fs::remove_file(source.join("slot").join("02.sav")).unwrap();
+        let first = backups.join("inc1.7z");
+        backup(&source, &first, Some(&full));
+
+        fs::write(source.join("slot").join("03.sav"), b"slot three").unwrap();
+        let second = backups.join("inc2.7z");
+        backup(&source, &second, Some(&first));
+
+        let first_entries = crate::backup::archive::list_7z_entries(&first, None).unwrap();
+        assert!(first_entries.iter().any(|entry| entry.name == "system.dat"));
+        assert!(
+            !first_entries
+                .iter()
+                .any(|entry| entry.name == "slot/01.sav")
+        );
+
+        let target = root.join("restore");
+        restore_chain(&second, &target).unwrap();
+        assert_eq!(fs::read(target.join("system.dat")).unwrap(), b"system v2");
+        assert_eq!(
+            fs::read(target.join("slot").join("01.sav")).unwrap(),
+            b"slot one"
+        );
+        assert_eq!(
+            fs::read(target.join("slot").join("03.sav")).unwrap(),
+            b"slot three"
+        );
+        assert!(!target.join("slot").join("02.sav").exists());
+
+        fs::remove_file(&full).unwrap();
+        assert!(restore_chain(&second, &target).is_err());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn keeps_chain_records_other_backups_depend_on() {
+        // 1 <- 2 <- 3 <- 5（最新），4 依赖 2
+        let records = vec![
+            record(1, None),
+            record(2, Some(1)),
+            record(3, Some(2)),
+            record(4, Some(2)),
+            record(5, Some(3)),
+        ];
+
+        let removable = removable_chain_records(&records, 5)
+            .into_iter()
+            .map(|record| record.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(removable, vec![3, 5]);
+    }
+
+    #[test]
+    fn reuses_crc_when_size_and_mtime_are_unchanged() {
+        let root =
+            std::env::temp_dir().join(format!("reina-backup-manifest-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("a.sav"), b"same").unwrap();
+        fs::write(root.join("b.sav"), b"other").unwrap();
+        let mut base = build_manifest_files(&root, None, &ExcludeGlobs::default()).unwrap();
+        assert!(base["a.sav"].modified.is_some());
+
+        // 伪造基础清单中的 CRC：大小与修改时间一致时应直接沿用，不重新读取
+        base.get_mut("a.sav").unwrap().crc32 = 0;
+        base.get_mut("b.sav").unwrap().modified = Some(0);
+        let files = build_manifest_files(&root, Some(&base), &ExcludeGlobs::default()).unwrap();
+
+        assert_eq!(files["a.sav"].crc32, 0);
+        assert_ne!(files["b.sav"].crc32, 0);
+        assert_eq!(files["b.sav"].modified, file_modified(&root.join("b.sav")));
+        assert!(changed_files(&files, &base).is_empty());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    fn file_modified(path: &Path) -> Option<i64> {
+        let modified = fs::metadata(path).unwrap().modified().unwrap();
+        Some(modified.duration_since(UNIX_EPOCH).unwrap().as_millis() as i64)
+    }
+}
@@ -0,0 +1,275 @@
+//! 核对存档备份文件与 savedata 记录。
+//!
+//! 手动删除备份文件或数据库记录后两者会不一致：磁盘上残留没有记录的文件，
+//! 或者记录指向已不存在的文件。这里找出两类不一致，并可选择一并清理。
+
+use super::active::ActiveBackups;
+use super::incremental::MANIFEST_SUFFIX;
+use super::savedata::{remove_backup_files, resolve_savedata_backup_root};
+use crate::database::repository::games_repository::GamesRepository;
+use crate::entity::savedata;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::{State, command};
+
+/// 最近修改时间在此范围内的文件不视为孤立文件
+///
+/// 备份在压缩完成后才写入记录，核对期间开始的备份会短暂出现没有记录的文件
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// 磁盘上存在但没有对应记录的备份文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrphanBackupFile {
+    pub game_id: i32,
+    /// 备份目录中的文件名
+    pub file: String,
+    pub path: String,
+    pub bytes: u64,
+}
+
+/// 备份文件已不存在的记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingBackupRecord {
+    pub backup_id: i32,
+    pub game_id: i32,
+    pub file: String,
+}
+
+/// 核对结果
+#[derive(Debug, Default, Serialize)]
+pub struct BackupReconcileReport {
+    pub orphan_files: Vec<OrphanBackupFile>,
+    pub missing_files: Vec<MissingBackupRecord>,
+    /// 已删除的孤立文件数量
+    pub deleted_files: usize,
+    /// 已删除的记录数量
+    pub deleted_records: usize,
+    /// 清理过程中的错误，不影响其余条目的清理
+    pub errors: Vec<String>,
+}
+
+/// 核对存档备份文件与数据库记录
+///
+/// 只检查备份根目录下 `game_<id>` 目录中的文件，增量备份清单随对应的备份文件处理。
+/// 最近 [`ORPHAN_GRACE_PERIOD`] 内修改过的文件可能属于正在进行的备份，不会被列出。
+///
+/// # Arguments
+/// * `delete_orphans` - 是否将没有记录的文件移入回收站，并删除文件已不存在的记录，默认 false；
+///   有备份正在进行时拒绝清理，避免删除尚未写入记录的新备份
+#[command]
+pub async fn reconcile_savedata_backups(
+    db: State<'_, DatabaseConnection>,
+    active: State<'_, ActiveBackups>,
+    delete_orphans: Option<bool>,
+) -> Result<BackupReconcileReport, String> {
+    let delete_orphans = delete_orphans.unwrap_or(false);
+    if delete_orphans && !active.is_empty() {
+        return Err("有存档备份正在进行，请完成后再清理".to_string());
+    }
+
+    let backup_root = resolve_savedata_backup_root(&db).await?;
+    let records = GamesRepository::get_all_savedata_records(&db)
+        .await
+        .map_err(|e| format!("获取存档备份记录失败: {}", e))?;
+
+    let (orphan_files, missing_files) = tokio::task::spawn_blocking(move || {
+        find_mismatches(&backup_root, &records, ORPHAN_GRACE_PERIOD)
+    })
+    .await
+    .map_err(|e| format!("核对备份任务失败: {}", e))?;
+
+    let mut report = BackupReconcileReport {
+        orphan_files,
+        missing_files,
+        ..Default::default()
+    };
+    if delete_orphans {
+        for orphan in &report.orphan_files {
+            let before = report.errors.len();
+            remove_backup_files(Path::new(&orphan.path), true, &mut report.errors);
+            if report.errors.len() == before {
+                report.deleted_files += 1;
+            }
+        }
+        for missing in &report.missing_files {
+            match GamesRepository::delete_savedata_record(&db, missing.backup_id).await {
+                Ok(_) => report.deleted_records += 1,
+                Err(e) => report.errors.push(format!(
+                    "删除数据库记录失败 (ID: {}): {}",
+                    missing.backup_id, e
+                )),
+            }
+        }
+    }
+
+    log::info!(
+        "存档备份核对完成 orphan_files={} missing_files={} deleted_files={} deleted_records={}",
+        report.orphan_files.len(),
+        report.missing_files.len(),
+        report.deleted_files,
+        report.deleted_records
+    );
+    Ok(report)
+}
+
+/// 找出没有记录的文件和文件已不存在的记录
+///
+/// 清单文件对应的备份文件有记录时不算孤立；备份文件也是孤立文件时随其一起删除，
+/// 不单独列出。修改时间距今不足 `grace_period` 的文件不算孤立。
+fn find_mismatches(
+    root: &Path,
+    records: &[savedata::Model],
+    grace_period: Duration,
+) -> (Vec<OrphanBackupFile>, Vec<MissingBackupRecord>) {
+    let now = SystemTime::now();
+    let mut orphan_files = Vec::new();
+    let mut known = BTreeSet::new();
+    for record in records {
+        known.insert((record.game_id, record.file.as_str()));
+    }
+
+    for (game_id, game_dir) in game_dirs(root) {
+        let Ok(entries) = fs::read_dir(&game_dir) else {
+            continue;
+        };
+        let files = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_ok_and(|ty| ty.is_file()))
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let metadata = entry.metadata().ok();
+                let bytes = metadata.as_ref().map_or(0, |metadata| metadata.len());
+                let recent = metadata
+                    .and_then(|metadata| metadata.modified().ok())
+                    .is_some_and(|modified| {
+                        !now.duration_since(modified)
+                            .is_ok_and(|age| age >= grace_period)
+                    });
+                Some((name, bytes, recent))
+            })
+            .collect::<Vec<_>>();
+        let names = files
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .collect::<BTreeSet<_>>();
+
+        for (name, bytes, recent) in &files {
+            if *recent || known.contains(&(game_id, name.as_str())) {
+                continue;
+            }
+            if let Some(archive) = name.strip_suffix(MANIFEST_SUFFIX)
+                && (known.contains(&(game_id, archive)) || names.contains(archive))
+            {
+                continue;
+            }
+            orphan_files.push(OrphanBackupFile {
+                game_id,
+                file: name.clone(),
+                path: game_dir.join(name).to_string_lossy().to_string(),
+                bytes: *bytes,
+            });
+        }
+    }
+    orphan_files.sort_by(|left, right| {
+        left.game_id
+            .cmp(&right.game_id)
+            .then_with(|| left.file.cmp(&right.file))
+    });
+
+    let mut missing_files = records
+        .iter()
+        .filter(|record| {
+            !root
+                .join(format!("game_{}", record.game_id))
+                .join(&record.file)
+                .is_file()
+        })
+        .map(|record| MissingBackupRecord {
+            backup_id: record.id,
+            game_id: record.game_id,
+            file: record.file.clone(),
+        })
+        .collect::<Vec<_>>();
+    missing_files.sort_by_key(|missing| missing.backup_id);
+
+    (orphan_files, missing_files)
+}
+
+/// 列出备份根目录下的 `game_<id>` 目录
+fn game_dirs(root: &Path) -> Vec<(i32, PathBuf)> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|ty| ty.is_dir()))
+        .filter_map(|entry| {
+            let game_id = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("game_")?
+                .parse()
+                .ok()?;
+            Some((game_id, entry.path()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: i32, game_id: i32, file: &str) -> savedata::Model {
+        savedata::Model {
+            id,
+            game_id,
+            file: file.to_string(),
+            backup_time: 0,
+            file_size: 0,
+            base_backup_id: None,
+        }
+    }
+
+    #[test]
+    fn finds_files_without_records_and_records_without_files() {
+        let root =
+            std::env::temp_dir().join(format!("reina-backup-reconcile-{}", std::process::id()));
+        fs::create_dir_all(root.join("game_1")).unwrap();
+        fs::create_dir_all(root.join("game_2")).unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(root.join("game_1").join("kept.7z"), vec![0; 10]).unwrap();
+        fs::write(root.join("game_1").join("kept.7z.manifest.json"), b"{}").unwrap();
+        fs::write(root.join("game_1").join("stray.7z"), vec![0; 20]).unwrap();
+        fs::write(root.join("game_1").join("stray.7z.manifest.json"), b"{}").unwrap();
+        fs::write(root.join("game_2").join("lost.7z.manifest.json"), b"{}").unwrap();
+        fs::write(root.join("other").join("ignored.7z"), vec![0; 5]).unwrap();
+
+        let records = vec![record(1, 1, "kept.7z"), record(2, 2, "gone.7z")];
+        let (orphans, missing) = find_mismatches(&root, &records, Duration::ZERO);
+
+        assert_eq!(
+            orphans
+                .iter()
+                .map(|orphan| (orphan.game_id, orphan.file.as_str(), orphan.bytes))
+                .collect::<Vec<_>>(),
+            vec![(1, "stray.7z", 20), (2, "lost.7z.manifest.json", 2)]
+        );
+        assert_eq!(
+            missing,
+            vec![MissingBackupRecord {
+                backup_id: 2,
+                game_id: 2,
+                file: "gone.7z".to_string(),
+            }]
+        );
+
+        let (recent_orphans, _) = find_mismatches(&root, &records, ORPHAN_GRACE_PERIOD);
+        assert!(recent_orphans.is_empty());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
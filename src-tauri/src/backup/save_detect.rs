@@ -0,0 +1,253 @@
+//! 根据常见引擎的存档位置推测游戏存档目录。
+//!
+//! 结果只是建议，按可信度排序返回，前端可预填第一项并保留手动选择。
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime, command};
+
+/// 游戏目录下常见的存档子目录（相对路径，引擎说明，可信度）
+const INSTALL_DIR_CANDIDATES: &[(&str, &str, u8)] = &[
+    ("savedata", "KiriKiri 存档目录", 90),
+    ("game/saves", "Ren'Py 存档目录", 90),
+    ("save", "游戏目录下的 save", 80),
+    ("saves", "游戏目录下的 saves", 80),
+    ("savdata", "游戏目录下的 savdata", 70),
+    ("userdata", "游戏目录下的 userdata", 60),
+];
+
+/// 用户目录下按游戏名称匹配时的可信度
+const USER_DIR_MATCH_CONFIDENCE: u8 = 60;
+/// 名称匹配的目录下还有存档子目录时提升的可信度
+const NESTED_SAVE_DIR_BONUS: u8 = 10;
+
+/// 一个可能的存档目录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SaveFolderCandidate {
+    pub path: String,
+    /// 0-100，越高越可信
+    pub confidence: u8,
+    pub reason: String,
+}
+
+/// 需要搜索的用户目录
+#[derive(Debug, Default)]
+struct UserDirs {
+    /// %APPDATA% / AppData\Roaming
+    roaming: Option<PathBuf>,
+    /// AppData\Local
+    local: Option<PathBuf>,
+    /// AppData\LocalLow
+    local_low: Option<PathBuf>,
+    documents: Option<PathBuf>,
+    saved_games: Option<PathBuf>,
+}
+
+/// 自动检测游戏存档目录
+///
+/// 检查游戏目录下常见引擎的存档子目录，以及 `%APPDATA%`、`AppData\LocalLow`、
+/// 文档等用户目录中与游戏名称匹配的文件夹。
+///
+/// # Arguments
+/// * `game_path` - 游戏启动程序或游戏目录路径
+///
+/// # Returns
+/// 按可信度从高到低排列的候选目录，没有找到时返回空列表
+#[command]
+pub async fn detect_save_folder<R: Runtime>(
+    app: AppHandle<R>,
+    game_path: String,
+) -> Result<Vec<SaveFolderCandidate>, String> {
+    let game_path = PathBuf::from(game_path);
+    let game_dir = if game_path.is_file() {
+        game_path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| "无法获取游戏目录".to_string())?
+    } else if game_path.is_dir() {
+        game_path.clone()
+    } else {
+        return Err(format!("游戏路径不存在: {}", game_path.display()));
+    };
+
+    let home = app.path().home_dir().ok();
+    let user_dirs = UserDirs {
+        roaming: app.path().data_dir().ok(),
+        local: app.path().local_data_dir().ok(),
+        local_low: home
+            .as_ref()
+            .map(|home| home.join("AppData").join("LocalLow")),
+        documents: app.path().document_dir().ok(),
+        saved_games: home.as_ref().map(|home| home.join("Saved Games")),
+    };
+    let names = game_names(&game_path, &game_dir);
+
+    tokio::task::spawn_blocking(move || detect_candidates(&game_dir, &names, &user_dirs))
+        .await
+        .map_err(|e| format!("检测存档目录失败: {}", e))
+}
+
+fn detect_candidates(
+    game_dir: &Path,
+    names: &BTreeSet<String>,
+    user_dirs: &UserDirs,
+) -> Vec<SaveFolderCandidate> {
+    let mut candidates = Vec::new();
+
+    for (relative, reason, confidence) in INSTALL_DIR_CANDIDATES {
+        if let Some(dir) = find_dir_ignore_case(game_dir, relative) {
+            candidates.push(candidate(&dir, *confidence, reason));
+        }
+    }
+
+    let user_roots = [
+        (&user_dirs.roaming, "AppData\\Roaming"),
+        (&user_dirs.local, "AppData\\Local"),
+        (&user_dirs.local_low, "AppData\\LocalLow"),
+        (&user_dirs.documents, "文档"),
+        (&user_dirs.saved_games, "Saved Games"),
+    ];
+    for (root, label) in user_roots {
+        let Some(root) = root else {
+            continue;
+        };
+        // Ren'Py 存放于 %APPDATA%\RenPy\<游戏名>，Unity 存放于 LocalLow\<公司>\<游戏名>
+        let mut search_roots = vec![root.clone(), root.join("RenPy")];
+        if label == "AppData\\LocalLow" {
+            search_roots.extend(child_dirs(root));
+        }
+        for search_root in search_roots {
+            for dir in child_dirs(&search_root) {
+                if !dir
+                    .file_name()
+                    .is_some_and(|name| name_matches(&name.to_string_lossy(), names))
+                {
+                    continue;
+                }
+                let confidence = if has_nested_save_dir(&dir) {
+                    USER_DIR_MATCH_CONFIDENCE + NESTED_SAVE_DIR_BONUS
+                } else {
+                    USER_DIR_MATCH_CONFIDENCE
+                };
+                candidates.push(candidate(
+                    &dir,
+                    confidence,
+                    &format!("{} 下与游戏同名的目录", label),
+                ));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.confidence.cmp(&a.confidence).then(a.path.cmp(&b.path)));
+    let mut seen = BTreeSet::new();
+    candidates.retain(|c| seen.insert(c.path.clone()));
+    candidates
+}
+
+fn candidate(dir: &Path, confidence: u8, reason: &str) -> SaveFolderCandidate {
+    // 空目录多半只是占位，降低可信度
+    let is_empty = !fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some());
+    SaveFolderCandidate {
+        path: dir.to_string_lossy().to_string(),
+        confidence: if is_empty { confidence / 2 } else { confidence },
+        reason: reason.to_string(),
+    }
+}
+
+/// 用于匹配用户目录的游戏名称：游戏目录名与启动程序文件名
+fn game_names(game_path: &Path, game_dir: &Path) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    if let Some(name) = game_dir.file_name() {
+        names.insert(normalize_name(&name.to_string_lossy()));
+    }
+    if game_path.is_file()
+        && let Some(stem) = game_path.file_stem()
+    {
+        names.insert(normalize_name(&stem.to_string_lossy()));
+    }
+    // 过短的名称容易误匹配（如 "game"、"a"）
+    names.retain(|name| name.chars().count() >= 3 && name != "game");
+    names
+}
+
+/// 去掉空格与符号并转为小写，用于宽松比较目录名
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 目录名与游戏名称相同，或以游戏名称开头（Ren'Py 默认目录名为 `<游戏名>-<数字>`）
+fn name_matches(dir_name: &str, names: &BTreeSet<String>) -> bool {
+    let dir_name = normalize_name(dir_name);
+    names.iter().any(|name| dir_name.starts_with(name.as_str()))
+}
+
+fn has_nested_save_dir(dir: &Path) -> bool {
+    INSTALL_DIR_CANDIDATES
+        .iter()
+        .any(|(relative, _, _)| find_dir_ignore_case(dir, relative).is_some())
+}
+
+/// 逐级忽略大小写查找子目录
+fn find_dir_ignore_case(base: &Path, relative: &str) -> Option<PathBuf> {
+    let mut current = base.to_path_buf();
+    for segment in relative.split('/') {
+        current = child_dirs(&current).into_iter().find(|dir| {
+            dir.file_name()
+                .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case(segment))
+        })?;
+    }
+    Some(current)
+}
+
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_engine_dirs_above_user_dir_matches() {
+        let root = std::env::temp_dir().join(format!("reina-save-detect-{}", std::process::id()));
+        let game_dir = root.join("Sakura Novel");
+        let appdata = root.join("Roaming");
+        fs::create_dir_all(game_dir.join("SaveData")).unwrap();
+        fs::write(game_dir.join("SaveData").join("data0.sav"), []).unwrap();
+        fs::create_dir_all(game_dir.join("saves")).unwrap();
+        let renpy_dir = appdata.join("RenPy").join("Sakura_Novel-1650000000");
+        fs::create_dir_all(&renpy_dir).unwrap();
+        fs::write(renpy_dir.join("1-1.save"), []).unwrap();
+        fs::create_dir_all(appdata.join("OtherGame")).unwrap();
+
+        let user_dirs = UserDirs {
+            roaming: Some(appdata.clone()),
+            ..Default::default()
+        };
+        let names = game_names(&game_dir.join("Sakura.exe"), &game_dir);
+        let candidates = detect_candidates(&game_dir, &names, &user_dirs);
+
+        let paths: Vec<_> = candidates.iter().map(|c| PathBuf::from(&c.path)).collect();
+        assert_eq!(
+            paths,
+            vec![game_dir.join("SaveData"), renpy_dir, game_dir.join("saves"),]
+        );
+        assert_eq!(candidates[0].confidence, 90);
+        // 空的 saves 目录可信度减半
+        assert_eq!(candidates[2].confidence, 40);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
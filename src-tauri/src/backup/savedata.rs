@@ -1,11 +1,28 @@
-use super::archive::{create_7z_archive, extract_7z_archive};
+use super::active::{ActiveBackups, BACKUP_CANCELLED};
+use super::archive::{
+    ArchiveCancelled, ArchiveEntryInfo, ArchiveListError, DEFAULT_COMPRESSION_LEVEL,
+    MAX_COMPRESSION_LEVEL, create_7z_archive_filtered, list_7z_entries, verify_7z_archive,
+};
+use super::exclude::ExcludeGlobs;
+use super::incremental::{
+    BackupManifest, build_manifest_files, changed_files, manifest_path, read_manifest,
+    restore_chain, write_manifest,
+};
+use super::size_estimate::ensure_backup_fits;
+use crate::database::dto::UpdateSettingsData;
 use crate::database::repository::games_repository::GamesRepository;
+use crate::database::repository::settings_repository::SettingsRepository;
+use crate::entity::savedata;
+use crate::utils::fs::remove_file_or_trash;
 use chrono::Utc;
-use sea_orm::DatabaseConnection;
+use sea_orm::{DatabaseConnection, TransactionTrait};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tauri::{State, command};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime, State, command};
+use walkdir::WalkDir;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BackupInfo {
@@ -13,7 +30,48 @@ pub struct BackupInfo {
     pub backup_time: i64,
     pub file_size: u64,
     pub backup_path: String,
+    /// 增量备份所依赖的基础备份记录 ID，完整备份为 None
+    #[serde(default)]
+    pub base_backup_id: Option<i32>,
+    /// 备份清单文件路径，旧版本创建的备份没有清单时为 None
+    #[serde(default)]
+    pub manifest: Option<String>,
+    /// 创建后是否已重新读取压缩包并通过校验
+    #[serde(default)]
+    pub verified: bool,
 }
+
+/// 存档备份记录保存后发送的事件，自动备份与手动备份都会触发
+pub const SAVEDATA_BACKUP_CREATED_EVENT: &str = "savedata-backup-created";
+
+/// `savedata-backup-created` 事件内容
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedataBackupCreated {
+    pub game_id: i32,
+    /// savedata 表中的记录 ID
+    pub record_id: i32,
+    pub backup: BackupInfo,
+    /// 是否为游戏退出后自动创建的备份
+    pub automatic: bool,
+}
+
+/// 压缩存档时发送的进度事件
+pub const SAVEDATA_BACKUP_PROGRESS_EVENT: &str = "savedata-backup-progress";
+/// 两次进度事件的最小间隔，避免大量小文件时刷屏
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// `savedata-backup-progress` 事件内容
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedataBackupProgress {
+    pub game_id: i64,
+    /// 正在压缩的文件（相对存档目录）
+    pub current_file: String,
+    pub files_processed: usize,
+    pub total_files: usize,
+}
+
 /// 创建游戏存档备份
 ///
 /// 备份目录优先级：
@@ -22,20 +80,50 @@ pub struct BackupInfo {
 ///    - 便携模式：程序目录/backups
 ///    - 非便携模式：AppData/backups
 ///
+/// 备份进行中可通过 `cancel_backup` 取消，取消时删除未完成的压缩包并返回 `BACKUP_CANCELLED`。
+/// 压缩过程中按 `PROGRESS_EMIT_INTERVAL` 节流发送 `savedata-backup-progress` 事件，
+/// 最后一个文件的进度总会发送。
+///
+/// 增量模式下与该游戏最新备份的清单比较，只压缩新增或变化的文件，
+/// 返回值中的 `base_backup_id` 需随记录一起保存；最新备份没有清单（旧版本创建）时退回完整备份。
+///
+/// 压缩等级越高压缩包越小、耗时越长：0 只打包不压缩，速度最快但与原存档一样大；
+/// 9 最慢，对文本类存档收益明显，对已压缩的存档（图片、加密数据）几乎没有收益。
+///
 /// # Arguments
 /// * `app` - Tauri应用句柄
 /// * `game_id` - 游戏ID
 /// * `source_path` - 源存档文件夹路径
+/// * `incremental` - 是否创建增量备份，默认 false
+/// * `compression_level` - 压缩等级 0-9，默认 `DEFAULT_COMPRESSION_LEVEL`
+/// * `verify` - 是否在压缩后重新读取压缩包校验，默认 false；校验失败时删除压缩包并返回错误
+/// * `exclude_globs` - 排除规则（如 `*.log`、`cache/**`），匹配的文件与目录不会写入备份
 ///
 /// # Returns
 /// * `Result<BackupInfo, String>` - 备份信息或错误消息
 #[tauri::command]
-pub async fn create_savedata_backup(
+#[allow(clippy::too_many_arguments)]
+pub async fn create_savedata_backup<R: Runtime>(
+    app: AppHandle<R>,
     db: State<'_, DatabaseConnection>,
+    active: State<'_, ActiveBackups>,
     game_id: i64,
     source_path: String,
+    incremental: Option<bool>,
+    compression_level: Option<u8>,
+    verify: Option<bool>,
+    exclude_globs: Option<Vec<String>>,
 ) -> Result<BackupInfo, String> {
     let source_path = Path::new(&source_path);
+    let verify = verify.unwrap_or(false);
+    let exclude = ExcludeGlobs::new(&exclude_globs.unwrap_or_default())?;
+    let compression_level = compression_level.unwrap_or(DEFAULT_COMPRESSION_LEVEL);
+    if compression_level > MAX_COMPRESSION_LEVEL {
+        return Err(format!(
+            "压缩等级需在 0 到 {} 之间: {}",
+            MAX_COMPRESSION_LEVEL, compression_level
+        ));
+    }
 
     // 验证源路径是否存在
     if !source_path.exists() {
@@ -46,6 +134,9 @@ pub async fn create_savedata_backup(
         return Err("源路径必须是一个文件夹".to_string());
     }
 
+    let backup = active
+        .start(game_id)
+        .ok_or_else(|| "该游戏的备份正在进行中".to_string())?;
     let backup_root = resolve_savedata_backup_root(&db).await?;
 
     // 创建游戏专属备份目录
@@ -53,62 +144,281 @@ pub async fn create_savedata_backup(
 
     fs::create_dir_all(&game_backup_dir).map_err(|e| format!("创建备份目录失败: {}", e))?;
 
-    // 检查并清理超出限制的备份（异步处理）
-    cleanup_old_backups(&db, &game_backup_dir, game_id).await?;
+    let base_record = if incremental.unwrap_or(false) {
+        GamesRepository::get_latest_savedata_records(&db, &[game_id as i32])
+            .await
+            .map_err(|e| format!("获取备份记录失败: {}", e))?
+            .remove(&(game_id as i32))
+    } else {
+        None
+    };
 
-    // 生成备份文件名（带时间戳）
+    // 生成备份文件名（带时间戳，同一秒内重复备份时追加序号）
     let now = Utc::now();
     let timestamp = now.timestamp();
-    let backup_filename = format!("savedata_{}_{}.7z", game_id, now.format("%Y%m%d_%H%M%S"));
-    let backup_file_path = game_backup_dir.join(&backup_filename);
+    let (backup_filename, backup_file_path) = unique_backup_file(
+        &game_backup_dir,
+        &format!("savedata_{}_{}", game_id, now.format("%Y%m%d_%H%M%S")),
+    );
+
+    // 创建7z压缩包（在阻塞线程中执行，取消请求可随时到达）
+    // 压缩前先估算大小，磁盘明显放不下时直接拒绝
+    let (backup_size, base_backup_id) = {
+        let source_path = source_path.to_path_buf();
+        let backup_dir = game_backup_dir.clone();
+        let archive_path = backup_file_path.clone();
+        let cancelled = backup.cancelled();
+        tokio::task::spawn_blocking(move || {
+            // 没有更早的备份时 base_record 为 None，直接创建完整备份
+            let mut base = None;
+            if let Some(record) = base_record {
+                match read_manifest(&backup_dir.join(&record.file))? {
+                    Some(manifest) => base = Some((record, manifest)),
+                    None => log::warn!(
+                        "最新备份没有清单，改为完整备份 game_id={} file={}",
+                        record.game_id,
+                        record.file
+                    ),
+                }
+            }
+            let files = build_manifest_files(
+                &source_path,
+                base.as_ref().map(|(_, manifest)| &manifest.files),
+                &exclude,
+            )?;
+            let include = base
+                .as_ref()
+                .map(|(_, manifest)| changed_files(&files, &manifest.files));
+            let keep = |name: &str, is_dir: bool| {
+                !exclude.is_excluded(name)
+                    && (is_dir
+                        || include
+                            .as_ref()
+                            .is_none_or(|changed| changed.contains(name)))
+            };
+            ensure_backup_fits(&source_path, &backup_dir, compression_level, &keep)?;
+
+            let mut last_emit = None;
+            let on_progress = |current_file: &str, processed: usize, total: usize| {
+                let now = Instant::now();
+                if !should_emit_progress(last_emit, now, processed, total) {
+                    return;
+                }
+                last_emit = Some(now);
+                let progress = SavedataBackupProgress {
+                    game_id,
+                    current_file: current_file.to_string(),
+                    files_processed: processed,
+                    total_files: total,
+                };
+                if let Err(e) = app.emit(SAVEDATA_BACKUP_PROGRESS_EVENT, &progress) {
+                    log::warn!("发送存档备份进度事件失败: {}", e);
+                }
+            };
+
+            let size = create_7z_archive_filtered(
+                &source_path,
+                &archive_path,
+                compression_level,
+                &cancelled,
+                keep,
+                on_progress,
+            )
+            .map_err(|e| {
+                if e.is::<ArchiveCancelled>() {
+                    BACKUP_CANCELLED.to_string()
+                } else {
+                    format!("创建压缩包失败: {}", e)
+                }
+            })?;
+
+            if verify {
+                let expected_files = include.as_ref().map_or(files.len(), BTreeSet::len);
+                if let Err(e) = verify_backup_archive(&archive_path, expected_files) {
+                    fs::remove_file(&archive_path).ok();
+                    return Err(format!("备份校验失败，已删除损坏的压缩包: {}", e));
+                }
+            }
+
+            let manifest = BackupManifest {
+                base_file: base.as_ref().map(|(record, _)| record.file.clone()),
+                files,
+            };
+            if let Err(e) = write_manifest(&archive_path, &manifest) {
+                fs::remove_file(&archive_path).ok();
+                return Err(e);
+            }
+            Ok((size, base.map(|(record, _)| record.id)))
+        })
+        .await
+        .map_err(|e| format!("创建压缩包任务失败: {}", e))??
+    };
 
-    // 创建7z压缩包
-    let backup_size = create_7z_archive(source_path, &backup_file_path)
-        .map_err(|e| format!("创建压缩包失败: {}", e))?;
+    // 新备份写入完成后再清理超出限制的旧备份，取消时不会误删旧备份
+    cleanup_old_backups(&db, &game_backup_dir, game_id, base_backup_id).await?;
 
     log::info!(
-        "存档备份创建成功 game_id={} file={} size={} bytes",
+        "存档备份创建成功 game_id={} file={} size={} bytes base_backup_id={:?}",
         game_id,
         backup_filename,
-        backup_size
+        backup_size,
+        base_backup_id
     );
 
     Ok(BackupInfo {
         folder_name: backup_filename,
         backup_time: timestamp,
-        file_size: backup_size,
+        file_size: backup_size,
+        verified: verify,
+        manifest: Some(
+            manifest_path(&backup_file_path)
+                .to_string_lossy()
+                .to_string(),
+        ),
         backup_path: backup_file_path.to_string_lossy().to_string(),
+        base_backup_id,
     })
 }
 
+/// 距上次发送已超过节流间隔，或已是最后一个文件时发送进度
+fn should_emit_progress(
+    last_emit: Option<Instant>,
+    now: Instant,
+    processed: usize,
+    total: usize,
+) -> bool {
+    processed >= total
+        || last_emit.is_none_or(|last| now.duration_since(last) >= PROGRESS_EMIT_INTERVAL)
+}
+
+/// 生成不与已有文件冲突的备份文件名
+///
+/// 目标文件已存在时依次尝试 `{stem}_1.7z`、`{stem}_2.7z` ...
+pub(super) fn unique_backup_file(backup_dir: &Path, stem: &str) -> (String, PathBuf) {
+    let mut filename = format!("{}.7z", stem);
+    let mut suffix = 1;
+    while backup_dir.join(&filename).exists() {
+        filename = format!("{}_{}.7z", stem, suffix);
+        suffix += 1;
+    }
+
+    let path = backup_dir.join(&filename);
+    (filename, path)
+}
+
+/// 存档恢复结果
+#[derive(Debug, Default, Serialize)]
+pub struct RestoreResult {
+    /// 恢复后目标目录中的文件数量
+    pub files_extracted: u64,
+    /// 写入的总字节数
+    pub bytes_written: u64,
+}
+
 /// 恢复存档备份
 ///
+/// 目标目录不为空且 `overwrite` 为 false 时直接返回错误，不会改动已有文件；
+/// 为 true 时先清空目标目录再解压。压缩包中的子目录会按原结构重建。
+///
 /// # Arguments
 /// * `backup_file_path` - 备份文件完整路径
 /// * `target_path` - 目标恢复路径
+/// * `overwrite` - 是否覆盖目标目录中的已有内容
 ///
 /// # Returns
-/// * `Result<(), String>` - 成功或错误消息
+/// * `Result<RestoreResult, String>` - 恢复的文件数量与字节数，或错误消息
 #[tauri::command]
 pub async fn restore_savedata_backup(
     backup_file_path: String,
     target_path: String,
-) -> Result<(), String> {
-    let backup_path = Path::new(&backup_file_path);
-    let target_path = Path::new(&target_path);
+    overwrite: bool,
+) -> Result<RestoreResult, String> {
+    tokio::task::spawn_blocking(move || {
+        restore_backup_file(
+            Path::new(&backup_file_path),
+            Path::new(&target_path),
+            overwrite,
+        )
+    })
+    .await
+    .map_err(|e| format!("恢复存档任务失败: {}", e))?
+}
+
+/// 将存档备份恢复到另一个游戏当前的存档目录
+///
+/// 游戏重装到新位置后，备份记录对应的原存档路径可能已失效，
+/// 这里始终以目标游戏当前设置的 `savepath` 为准。
+///
+/// # Arguments
+/// * `db` - 数据库连接
+/// * `backup_id` - 备份记录ID
+/// * `game_id` - 恢复到的目标游戏ID
+///
+/// # Returns
+/// * `Result<String, String>` - 实际恢复到的路径或错误消息
+#[tauri::command]
+pub async fn restore_to_game(
+    db: State<'_, DatabaseConnection>,
+    backup_id: i32,
+    game_id: i32,
+) -> Result<String, String> {
+    let record = GamesRepository::get_savedata_record_by_id(&db, backup_id)
+        .await
+        .map_err(|e| format!("获取备份记录失败: {}", e))?
+        .ok_or_else(|| "备份记录不存在".to_string())?;
+    let game = GamesRepository::find_by_id(&db, game_id)
+        .await
+        .map_err(|e| format!("获取游戏数据失败: {}", e))?
+        .ok_or_else(|| format!("游戏不存在: {}", game_id))?;
+    let savepath = game
+        .savepath
+        .filter(|path| !path.trim().is_empty())
+        .ok_or_else(|| "目标游戏未设置存档路径".to_string())?;
+
+    let backup_path = resolve_savedata_backup_root(&db)
+        .await?
+        .join(format!("game_{}", record.game_id))
+        .join(&record.file);
+    let target_path = savepath.clone();
+    tokio::task::spawn_blocking(move || {
+        restore_backup_file(&backup_path, Path::new(&target_path), true)
+    })
+    .await
+    .map_err(|e| format!("恢复存档任务失败: {}", e))??;
 
+    log::info!(
+        "存档备份已恢复到游戏 backup_id={} from_game_id={} to_game_id={}",
+        backup_id,
+        record.game_id,
+        game_id
+    );
+    Ok(savepath)
+}
+
+fn restore_backup_file(
+    backup_path: &Path,
+    target_path: &Path,
+    overwrite: bool,
+) -> Result<RestoreResult, String> {
     // 验证备份文件是否存在
     if !backup_path.exists() {
         return Err("备份文件不存在".to_string());
     }
 
-    // 确保目标路径存在
+    // 确保目标路径存在；不覆盖时目标目录必须为空
     if !target_path.exists() {
         fs::create_dir_all(target_path).map_err(|e| format!("创建目标目录失败: {}", e))?;
+    } else if !overwrite
+        && fs::read_dir(target_path)
+            .map_err(|e| format!("读取目标目录失败: {}", e))?
+            .next()
+            .is_some()
+    {
+        return Err("目标存档目录不为空，如需覆盖请确认后重试".to_string());
     }
 
-    // 解压7z文件
-    extract_7z_archive(backup_path, target_path).map_err(|e| format!("解压备份失败: {}", e))?;
+    // 解压7z文件，增量备份会沿依赖链从完整备份开始依次解压
+    restore_chain(backup_path, target_path)?;
 
     log::info!(
         "存档备份恢复成功 file={}",
@@ -119,70 +429,344 @@ pub async fn restore_savedata_backup(
     );
     log::debug!("存档备份恢复目标路径: {}", target_path.display());
 
-    Ok(())
+    let stats = directory_stats(target_path);
+    Ok(RestoreResult {
+        files_extracted: stats.items,
+        bytes_written: stats.bytes,
+    })
+}
+
+/// 列出存档备份压缩包的内容（不解压），用于预览
+///
+/// # Arguments
+/// * `backup_file_path` - 备份文件完整路径
+/// * `password` - 加密备份的密码，未加密时不传
+///
+/// # Returns
+/// * `Result<Vec<ArchiveEntryInfo>, String>` - 条目列表或错误消息
+#[tauri::command]
+pub async fn list_backup_contents(
+    backup_file_path: String,
+    password: Option<String>,
+) -> Result<Vec<ArchiveEntryInfo>, String> {
+    let backup_path = PathBuf::from(&backup_file_path);
+    if !backup_path.exists() {
+        return Err("备份文件不存在".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || list_7z_entries(&backup_path, password.as_deref()))
+        .await
+        .map_err(|e| format!("读取备份内容任务失败: {}", e))?
+        .map_err(describe_list_error)
+}
+
+/// 重新打开刚创建的压缩包，确认文件条目数量与写入时一致，并完整解压一遍校验 CRC
+fn verify_backup_archive(archive_path: &Path, expected_files: usize) -> Result<(), String> {
+    let files = list_7z_entries(archive_path, None)
+        .map_err(describe_list_error)?
+        .iter()
+        .filter(|entry| !entry.is_dir)
+        .count();
+    if files != expected_files {
+        return Err(format!(
+            "文件数量不一致: 应为 {}，实际 {}",
+            expected_files, files
+        ));
+    }
+    verify_7z_archive(archive_path).map_err(describe_list_error)
+}
+
+fn describe_list_error(error: ArchiveListError) -> String {
+    match error {
+        ArchiveListError::PasswordRequired => "备份文件已加密，请提供密码".to_string(),
+        ArchiveListError::WrongPassword => "备份密码错误".to_string(),
+        ArchiveListError::Other(e) => format!("读取备份内容失败: {}", e),
+    }
+}
+
+/// 比较修改时间时允许的误差（秒），兼容 FAT 等文件系统的 2 秒精度
+const MTIME_TOLERANCE_SECS: i64 = 2;
+
+/// 文件快照：相对路径（`/` 分隔）-> (大小, 修改时间)
+type FileSnapshot = BTreeMap<String, (u64, Option<i64>)>;
+
+/// 存档与备份中大小或修改时间不同的文件
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct ChangedSaveFile {
+    pub name: String,
+    pub save_size: u64,
+    pub backup_size: u64,
+    pub save_modified: Option<i64>,
+    pub backup_modified: Option<i64>,
+}
+
+/// 存档目录相对备份的差异
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SaveBackupDiff {
+    /// 仅存在于存档目录中的文件
+    pub added: Vec<String>,
+    /// 仅存在于备份中的文件
+    pub removed: Vec<String>,
+    pub changed: Vec<ChangedSaveFile>,
+}
+
+/// 比较存档目录与备份压缩包的差异
+///
+/// 只读取压缩包文件头，不解压内容；加密压缩包需要提供密码
+#[tauri::command]
+pub async fn diff_save_against_backup(
+    save_path: String,
+    backup_file_path: String,
+    password: Option<String>,
+) -> Result<SaveBackupDiff, String> {
+    let save_dir = PathBuf::from(&save_path);
+    if !save_dir.is_dir() {
+        return Err("存档目录不存在".to_string());
+    }
+    let backup_path = PathBuf::from(&backup_file_path);
+    if !backup_path.exists() {
+        return Err("备份文件不存在".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let backup = list_7z_entries(&backup_path, password.as_deref())
+            .map_err(describe_list_error)?
+            .into_iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| (entry.name.replace('\\', "/"), (entry.size, entry.modified)))
+            .collect();
+        let save = snapshot_save_dir(&save_dir)?;
+        Ok(diff_file_snapshots(&save, &backup))
+    })
+    .await
+    .map_err(|e| format!("比较存档任务失败: {}", e))?
+}
+
+fn snapshot_save_dir(save_dir: &Path) -> Result<FileSnapshot, String> {
+    let mut snapshot = FileSnapshot::new();
+    for entry in WalkDir::new(save_dir).min_depth(1) {
+        let entry = entry.map_err(|e| format!("遍历存档目录失败: {}", e))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = entry
+            .metadata()
+            .map_err(|e| format!("读取存档文件信息失败: {}", e))?;
+        let name = entry
+            .path()
+            .strip_prefix(save_dir)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64);
+        snapshot.insert(name, (metadata.len(), modified));
+    }
+    Ok(snapshot)
+}
+
+fn diff_file_snapshots(save: &FileSnapshot, backup: &FileSnapshot) -> SaveBackupDiff {
+    let mut diff = SaveBackupDiff {
+        removed: backup
+            .keys()
+            .filter(|name| !save.contains_key(*name))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+
+    for (name, &(save_size, save_modified)) in save {
+        let Some(&(backup_size, backup_modified)) = backup.get(name) else {
+            diff.added.push(name.clone());
+            continue;
+        };
+        let mtime_differs = match (save_modified, backup_modified) {
+            (Some(save), Some(backup)) => (save - backup).abs() > MTIME_TOLERANCE_SECS,
+            _ => false,
+        };
+        if save_size != backup_size || mtime_differs {
+            diff.changed.push(ChangedSaveFile {
+                name: name.clone(),
+                save_size,
+                backup_size,
+                save_modified,
+                backup_modified,
+            });
+        }
+    }
+
+    diff
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MoveResult {
     pub success: bool,
     pub message: String,
+    /// 已移动的字节数
+    #[serde(default)]
+    pub bytes_moved: u64,
+    /// 已移动的文件数量
+    #[serde(default)]
+    pub items_moved: u64,
+}
+
+impl MoveResult {
+    fn succeeded(message: impl Into<String>, bytes_moved: u64, items_moved: u64) -> Self {
+        Self {
+            success: true,
+            message: message.into(),
+            bytes_moved,
+            items_moved,
+        }
+    }
+
+    fn failed(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            bytes_moved: 0,
+            items_moved: 0,
+        }
+    }
+}
+
+/// 复制过程中累计的字节数与文件数
+#[derive(Debug, Default, Clone, Copy)]
+struct CopyStats {
+    bytes: u64,
+    items: u64,
 }
 
 /// 移动存档备份文件夹到新位置
 #[command]
 pub async fn move_backup_folder(old_path: String, new_path: String) -> Result<MoveResult, String> {
-    let old_backup_path = Path::new(&old_path);
-    let new_backup_path = Path::new(&new_path);
+    Ok(move_backup_dir(Path::new(&old_path), Path::new(&new_path)))
+}
+
+/// 修改存档根目录，并将现有备份迁移到新根目录下的 `backups`
+///
+/// 仅在迁移成功后才写入设置；迁移失败时设置保持不变，避免设置与备份实际位置不一致。
+/// `new_root` 为空时恢复默认目录。
+///
+/// # Returns
+/// * `Result<MoveResult, String>` - 迁移结果（`success` 为 false 时设置未修改）
+#[tauri::command]
+pub async fn change_save_root_path(
+    db: State<'_, DatabaseConnection>,
+    new_root: Option<String>,
+) -> Result<MoveResult, String> {
+    let new_root = new_root
+        .map(|root| root.trim().to_string())
+        .filter(|root| !root.is_empty());
+    let old_backup_root = resolve_savedata_backup_root(&db).await?;
+    let new_backup_root = match &new_root {
+        Some(root) => PathBuf::from(root).join("backups"),
+        None => reina_path::get_base_data_dir()?.join("backups"),
+    };
+
+    let result = if old_backup_root == new_backup_root {
+        MoveResult::succeeded("备份目录未变化，无需移动", 0, 0)
+    } else {
+        let (old, new) = (old_backup_root.clone(), new_backup_root.clone());
+        tokio::task::spawn_blocking(move || move_backup_dir(&old, &new))
+            .await
+            .map_err(|e| format!("移动备份文件夹任务失败: {}", e))?
+    };
+    if !result.success {
+        log::warn!("存档根目录未修改，备份迁移失败: {}", result.message);
+        return Ok(result);
+    }
 
+    SettingsRepository::update_settings(
+        &db,
+        UpdateSettingsData {
+            save_root_path: Some(new_root),
+            ..Default::default()
+        },
+    )
+    .await
+    .map_err(|e| {
+        format!(
+            "备份已迁移到 {}，但保存设置失败: {}",
+            new_backup_root.display(),
+            e
+        )
+    })?;
+
+    log::info!(
+        "存档根目录已修改 old={} new={}",
+        old_backup_root.display(),
+        new_backup_root.display()
+    );
+    Ok(result)
+}
+
+fn move_backup_dir(old_backup_path: &Path, new_backup_path: &Path) -> MoveResult {
     if !old_backup_path.exists() {
-        return Ok(MoveResult {
-            success: true,
-            message: "旧备份文件夹不存在，无需移动".to_string(),
-        });
+        return MoveResult::succeeded("旧备份文件夹不存在，无需移动", 0, 0);
     }
 
     if let Some(parent) = new_backup_path.parent()
         && !parent.exists()
         && let Err(e) = fs::create_dir_all(parent)
     {
-        return Ok(MoveResult {
-            success: false,
-            message: format!("无法创建目标目录: {}", e),
-        });
+        return MoveResult::failed(format!("无法创建目标目录: {}", e));
     }
 
     if new_backup_path.exists() {
-        return Ok(MoveResult {
-            success: false,
-            message: "目标位置已存在备份文件夹，请手动处理".to_string(),
-        });
+        return MoveResult::failed("目标位置已存在备份文件夹，请手动处理");
     }
 
     match fs::rename(old_backup_path, new_backup_path) {
-        Ok(_) => Ok(MoveResult {
-            success: true,
-            message: "备份文件夹移动成功".to_string(),
-        }),
-        Err(_) => match copy_dir_recursive(old_backup_path, new_backup_path) {
-            Ok(_) => match fs::remove_dir_all(old_backup_path) {
-                Ok(_) => Ok(MoveResult {
-                    success: true,
-                    message: "备份文件夹移动成功（通过复制）".to_string(),
-                }),
-                Err(e) => Ok(MoveResult {
-                    success: false,
-                    message: format!("文件夹已复制到新位置，但删除旧文件夹失败: {}", e),
-                }),
-            },
-            Err(e) => Ok(MoveResult {
-                success: false,
-                message: format!("移动文件夹失败: {}", e),
-            }),
-        },
+        Ok(_) => {
+            // rename 不经过逐文件复制，移动后统计目标目录
+            let stats = directory_stats(new_backup_path);
+            MoveResult::succeeded("备份文件夹移动成功", stats.bytes, stats.items)
+        }
+        Err(_) => {
+            let mut stats = CopyStats::default();
+            match copy_dir_recursive(old_backup_path, new_backup_path, &mut stats) {
+                Ok(_) => match fs::remove_dir_all(old_backup_path) {
+                    Ok(_) => MoveResult::succeeded(
+                        "备份文件夹移动成功（通过复制）",
+                        stats.bytes,
+                        stats.items,
+                    ),
+                    Err(e) => MoveResult {
+                        success: false,
+                        message: format!("文件夹已复制到新位置，但删除旧文件夹失败: {}", e),
+                        bytes_moved: stats.bytes,
+                        items_moved: stats.items,
+                    },
+                },
+                Err(e) => MoveResult::failed(format!("移动文件夹失败: {}", e)),
+            }
+        }
     }
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Box<dyn std::error::Error>> {
+fn directory_stats(dir: &Path) -> CopyStats {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .fold(CopyStats::default(), |stats, metadata| CopyStats {
+            bytes: stats.bytes + metadata.len(),
+            items: stats.items + 1,
+        })
+}
+
+fn copy_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    stats: &mut CopyStats,
+) -> Result<(), Box<dyn std::error::Error>> {
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
@@ -192,9 +776,10 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Box<dyn std::error::
         let dst_path = dst.join(entry.file_name());
 
         if ty.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_dir_recursive(&src_path, &dst_path, stats)?;
         } else {
-            fs::copy(&src_path, &dst_path)?;
+            stats.bytes += fs::copy(&src_path, &dst_path)?;
+            stats.items += 1;
         }
     }
 
@@ -209,20 +794,18 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Box<dyn std::error::
 /// * `db` - 数据库连接
 /// * `backup_file_path` - 备份文件完整路径
 /// * `backup_id` - 数据库记录 ID
+/// * `recycle` - 是否将文件移入系统回收站而不是永久删除
 ///
 /// # Returns
 /// * `Option<String>` - 如果有错误返回错误信息，否则返回 None
-async fn delete_backup_record(
+pub(super) async fn delete_backup_record(
     db: &DatabaseConnection,
     backup_file_path: &Path,
     backup_id: i32,
+    recycle: bool,
 ) -> Option<String> {
     let mut errors: Vec<String> = Vec::new();
-    // 删除备份文件（如果存在），失败时收集错误
-
-    if let Err(e) = fs::remove_file(backup_file_path) {
-        errors.push(format!("删除备份文件失败 {:?}: {}", backup_file_path, e));
-    }
+    remove_backup_files(backup_file_path, recycle, &mut errors);
 
     // 无论文件删除是否成功，都继续删除数据库记录
     if let Err(e) = GamesRepository::delete_savedata_record(db, backup_id).await {
@@ -236,6 +819,22 @@ async fn delete_backup_record(
     }
 }
 
+/// 删除备份文件及其清单，失败时收集错误
+///
+/// 文件已不存在时视为成功，便于清理校验中发现文件缺失的记录
+pub(super) fn remove_backup_files(
+    backup_file_path: &Path,
+    recycle: bool,
+    errors: &mut Vec<String>,
+) {
+    if let Err(e) = remove_file_or_trash(backup_file_path, recycle) {
+        errors.push(format!("删除备份文件失败 {:?}: {}", backup_file_path, e));
+    }
+    if let Err(e) = remove_file_or_trash(&manifest_path(backup_file_path), recycle) {
+        errors.push(format!("删除备份清单失败 {:?}: {}", backup_file_path, e));
+    }
+}
+
 /// 删除备份文件和数据库记录
 ///
 /// 二合一功能：同时删除备份文件和对应的数据库记录
@@ -245,6 +844,7 @@ async fn delete_backup_record(
 /// * `app` - Tauri应用句柄
 /// * `db` - 数据库连接
 /// * `backup_id` - 备份记录ID
+/// * `recycle` - 是否将备份文件移入系统回收站，默认 false（永久删除）
 ///
 /// # Returns
 /// * `Result<(), String>` - 成功或错误消息
@@ -252,6 +852,7 @@ async fn delete_backup_record(
 pub async fn delete_savedata_backup(
     db: State<'_, DatabaseConnection>,
     backup_id: i32,
+    recycle: Option<bool>,
 ) -> Result<(), String> {
     // 先从数据库获取备份记录
     let record = GamesRepository::get_savedata_record_by_id(&db, backup_id)
@@ -259,12 +860,24 @@ pub async fn delete_savedata_backup(
         .map_err(|e| format!("获取备份记录失败: {}", e))?
         .ok_or_else(|| "备份记录不存在".to_string())?;
 
+    // 被增量备份依赖的基础备份删除后，依赖它的备份将无法恢复
+    let has_dependents = GamesRepository::get_savedata_records(&db, record.game_id)
+        .await
+        .map_err(|e| format!("获取备份记录失败: {}", e))?
+        .iter()
+        .any(|other| other.base_backup_id == Some(backup_id));
+    if has_dependents {
+        return Err("该备份被增量备份依赖，请先合并备份链后再删除".to_string());
+    }
+
     let backup_root = resolve_savedata_backup_root(&db).await?;
     let game_backup_dir = backup_root.join(format!("game_{}", record.game_id));
     let backup_path = game_backup_dir.join(&record.file);
 
     // 使用通用函数删除备份记录
-    if let Some(error) = delete_backup_record(&db, &backup_path, backup_id).await {
+    if let Some(error) =
+        delete_backup_record(&db, &backup_path, backup_id, recycle.unwrap_or(false)).await
+    {
         return Err(error);
     }
 
@@ -277,7 +890,84 @@ pub async fn delete_savedata_backup(
     Ok(())
 }
 
-async fn resolve_savedata_backup_root(db: &DatabaseConnection) -> Result<PathBuf, String> {
+/// 按保留数量清理游戏的旧存档备份
+///
+/// 数据库记录在同一事务中删除，事务提交后再删除备份文件；
+/// 文件删除失败只记录日志，不会恢复已删除的记录。
+/// 仍被保留的增量备份依赖的基础备份不会删除。
+///
+/// # Arguments
+/// * `game_id` - 游戏ID
+/// * `keep_count` - 保留的最新备份数量，不能为 0
+///
+/// # Returns
+/// * `Result<Vec<savedata::Model>, String>` - 被删除的备份记录（从新到旧）
+#[tauri::command]
+pub async fn prune_savedata_backups(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    keep_count: u32,
+) -> Result<Vec<savedata::Model>, String> {
+    if keep_count == 0 {
+        return Err("保留数量不能为 0，如需删除全部备份请逐个删除".to_string());
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+    let pruned = GamesRepository::prune_savedata_records(&txn, game_id, keep_count as usize)
+        .await
+        .map_err(|e| format!("删除旧备份记录失败: {}", e))?;
+    txn.commit()
+        .await
+        .map_err(|e| format!("提交事务失败: {}", e))?;
+
+    let game_backup_dir = resolve_savedata_backup_root(&db)
+        .await?
+        .join(format!("game_{}", game_id));
+    let mut errors = Vec::new();
+    for record in &pruned {
+        remove_backup_files(&game_backup_dir.join(&record.file), false, &mut errors);
+    }
+    if !errors.is_empty() {
+        log::warn!(
+            "清理旧备份文件时遇到 {} 个错误:\n{}",
+            errors.len(),
+            errors.join("\n")
+        );
+    }
+
+    log::info!(
+        "旧存档备份清理完成 game_id={} keep_count={} deleted_count={}",
+        game_id,
+        keep_count,
+        pruned.len()
+    );
+    Ok(pruned)
+}
+
+/// 删除指定游戏的整个存档备份目录（游戏被彻底删除时调用）
+///
+/// 数据库中的 savedata 记录随 games 外键级联删除，这里只负责清理文件
+pub(crate) async fn delete_game_backup_dir(
+    db: &DatabaseConnection,
+    game_id: i32,
+) -> Result<(), String> {
+    let game_backup_dir = resolve_savedata_backup_root(db)
+        .await?
+        .join(format!("game_{}", game_id));
+
+    if !game_backup_dir.exists() {
+        return Ok(());
+    }
+
+    fs::remove_dir_all(&game_backup_dir).map_err(|e| format!("删除存档备份目录失败: {}", e))
+}
+
+pub(crate) async fn resolve_savedata_backup_root(
+    db: &DatabaseConnection,
+) -> Result<PathBuf, String> {
     use crate::database::repository::settings_repository::DbSettingsExt;
     let settings = db.get_settings().await?;
 
@@ -298,6 +988,7 @@ async fn resolve_savedata_backup_root(db: &DatabaseConnection) -> Result<PathBuf
 /// * `db` - 数据库连接
 /// * `backup_dir` - 备份目录路径
 /// * `game_id` - 游戏ID
+/// * `pending_base` - 新增量备份依赖的备份ID，新备份尚未写入记录，需显式保护其依赖链
 ///
 /// # Returns
 /// * `Result<(), String>` - 成功或错误消息
@@ -305,6 +996,7 @@ async fn cleanup_old_backups(
     db: &DatabaseConnection,
     backup_dir: &Path,
     game_id: i64,
+    pending_base: Option<i32>,
 ) -> Result<(), String> {
     // 从数据库获取游戏信息，读取 maxbackups 设置
     let game = GamesRepository::find_by_id(db, game_id as i32)
@@ -317,30 +1009,26 @@ async fn cleanup_old_backups(
         .expect("maxbackups should not be null") as usize;
 
     // 从数据库获取该游戏的所有备份记录
-    let mut records = GamesRepository::get_savedata_records(db, game_id as i32)
+    let records = GamesRepository::get_savedata_records(db, game_id as i32)
         .await
         .map_err(|e| format!("获取备份记录失败: {}", e))?;
 
-    // 如果备份数量未超过限制，直接返回
-    if records.len() < max_backups {
-        return Ok(());
-    }
-
-    // 按备份时间排序（最旧的在前）
-    records.sort_by_key(|r| r.backup_time);
-
-    // 计算需要删除的备份数量（保留最新的 max_backups - 1 个，为新备份留出空间）
-    let to_delete_count = records.len() - (max_backups - 1);
-    let records_to_delete = &records[..to_delete_count];
+    // 保留最新的 max_backups - 1 个，为新备份留出空间；
+    // 被保留备份与新增量备份依赖的基础备份不会删除
+    let records_to_delete = GamesRepository::prunable_savedata_records(
+        records,
+        max_backups.saturating_sub(1),
+        pending_base,
+    );
 
     // 收集错误信息，不中断循环
     let mut errors: Vec<String> = Vec::new();
 
-    // 使用通用函数删除文件和数据库记录
-    for record in records_to_delete {
+    // 使用通用函数删除文件和数据库记录
+    for record in &records_to_delete {
         let backup_file_path = backup_dir.join(&record.file);
 
-        if let Some(error) = delete_backup_record(db, &backup_file_path, record.id).await {
+        if let Some(error) = delete_backup_record(db, &backup_file_path, record.id, false).await {
             errors.push(error);
         }
     }
@@ -362,3 +1050,124 @@ async fn cleanup_old_backups(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::archive::create_7z_archive;
+
+    #[test]
+    fn diff_file_snapshots_reports_added_removed_and_changed() {
+        let snapshot = |entries: &[(&str, u64, i64)]| -> FileSnapshot {
+            entries
+                .iter()
+                .map(|&(name, size, modified)| (name.to_string(), (size, Some(modified))))
+                .collect()
+        };
+        let save = snapshot(&[
+            ("new.sav", 1, 100),
+            ("same.sav", 10, 100),
+            ("touched.sav", 10, 200),
+            ("dir/grown.sav", 20, 100),
+        ]);
+        let backup = snapshot(&[
+            ("gone.sav", 1, 100),
+            ("same.sav", 10, 101),
+            ("touched.sav", 10, 100),
+            ("dir/grown.sav", 10, 100),
+        ]);
+
+        let diff = diff_file_snapshots(&save, &backup);
+
+        assert_eq!(diff.added, vec!["new.sav"]);
+        assert_eq!(diff.removed, vec!["gone.sav"]);
+        let changed: Vec<&str> = diff.changed.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(changed, vec!["dir/grown.sav", "touched.sav"]);
+    }
+
+    #[test]
+    fn restore_recreates_nested_dirs_and_respects_overwrite() {
+        let root =
+            std::env::temp_dir().join(format!("reina-backup-restore-{}", std::process::id()));
+        let source = root.join("save");
+        fs::create_dir_all(source.join("slot").join("auto")).unwrap();
+        fs::write(source.join("system.dat"), b"system").unwrap();
+        fs::write(source.join("slot").join("auto").join("01.sav"), b"slot").unwrap();
+        let archive = root.join("backup.7z");
+        create_7z_archive(&source, &archive).unwrap();
+
+        let target = root.join("restore");
+        let result = restore_backup_file(&archive, &target, false).unwrap();
+        assert_eq!(result.files_extracted, 2);
+        assert_eq!(result.bytes_written, 10);
+        assert_eq!(
+            fs::read(target.join("slot").join("auto").join("01.sav")).unwrap(),
+            b"slot"
+        );
+
+        fs::write(target.join("system.dat"), b"changed").unwrap();
+        assert!(restore_backup_file(&archive, &target, false).is_err());
+        assert_eq!(fs::read(target.join("system.dat")).unwrap(), b"changed");
+
+        fs::write(target.join("extra.sav"), b"extra").unwrap();
+        restore_backup_file(&archive, &target, true).unwrap();
+        assert_eq!(fs::read(target.join("system.dat")).unwrap(), b"system");
+        assert!(!target.join("extra.sav").exists());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn progress_is_throttled_but_always_reports_last_file() {
+        let start = Instant::now();
+        let soon = start + PROGRESS_EMIT_INTERVAL / 2;
+        let later = start + PROGRESS_EMIT_INTERVAL;
+
+        assert!(should_emit_progress(None, start, 1, 1000));
+        assert!(!should_emit_progress(Some(start), soon, 2, 1000));
+        assert!(should_emit_progress(Some(start), later, 3, 1000));
+        assert!(should_emit_progress(Some(start), soon, 1000, 1000));
+    }
+
+    #[test]
+    fn verify_backup_archive_checks_file_count_and_content() {
+        let root = std::env::temp_dir().join(format!("reina-backup-check-{}", std::process::id()));
+        let source = root.join("save");
+        fs::create_dir_all(source.join("slot")).unwrap();
+        fs::write(source.join("a.sav"), vec![1u8; 2048]).unwrap();
+        fs::write(source.join("slot").join("b.sav"), vec![2u8; 2048]).unwrap();
+        let archive = root.join("backup.7z");
+        create_7z_archive(&source, &archive).unwrap();
+
+        assert!(verify_backup_archive(&archive, 2).is_ok());
+        assert!(verify_backup_archive(&archive, 3).is_err());
+
+        let truncated = root.join("truncated.7z");
+        let bytes = fs::read(&archive).unwrap();
+        fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(verify_backup_archive(&truncated, 2).is_err());
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn store_level_archive_restores() {
+        let root = std::env::temp_dir().join(format!("reina-backup-store-{}", std::process::id()));
+        let source = root.join("save");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("slot.sav"), vec![b'a'; 4096]).unwrap();
+        let archive = root.join("store.7z");
+        let cancelled = std::sync::atomic::AtomicBool::new(false);
+
+        let size =
+            create_7z_archive_filtered(&source, &archive, 0, &cancelled, |_, _| true, |_, _, _| {})
+                .unwrap();
+        assert!(size >= 4096);
+
+        let target = root.join("restore");
+        restore_backup_file(&archive, &target, false).unwrap();
+        assert_eq!(fs::read(target.join("slot.sav")).unwrap(), vec![b'a'; 4096]);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
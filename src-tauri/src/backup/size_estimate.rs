@@ -0,0 +1,248 @@
+//! 存档备份大小估算。
+//!
+//! 未压缩大小通过遍历目录得到；压缩后大小从部分文件中抽样压缩，按压缩率推算，
+//! 只作为磁盘空间提示与“明显放不下”时的拦截依据。
+
+use super::archive::{DEFAULT_COMPRESSION_LEVEL, MAX_COMPRESSION_LEVEL, compress_sample};
+use super::exclude::ExcludeGlobs;
+use super::savedata::resolve_savedata_backup_root;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{State, command};
+use walkdir::WalkDir;
+
+/// 最多抽样的文件数量
+const MAX_SAMPLE_FILES: usize = 32;
+/// 每个样本文件最多读取的字节数
+const SAMPLE_BYTES_PER_FILE: u64 = 256 * 1024;
+
+/// 备份大小估算结果
+#[derive(Debug, Serialize)]
+pub struct BackupSizeEstimate {
+    pub file_count: usize,
+    /// 未压缩的总大小
+    pub uncompressed_size: u64,
+    /// 按抽样压缩率推算的压缩包大小，未抽样时为 None
+    pub estimated_size: Option<u64>,
+    /// 备份目录所在磁盘的剩余空间
+    pub available_space: u64,
+    /// 剩余空间是否足够（有估算值时按估算值，否则按未压缩大小）
+    pub fits: bool,
+}
+
+/// 估算存档备份大小，并与备份目录所在磁盘的剩余空间比较
+///
+/// # Arguments
+/// * `source_path` - 存档目录
+/// * `sample` - 是否抽样压缩以估算压缩后大小，默认 true
+/// * `compression_level` - 抽样使用的压缩等级，与创建备份时相同，默认 `DEFAULT_COMPRESSION_LEVEL`
+/// * `exclude_globs` - 排除规则，与创建备份时相同，匹配的文件不计入
+#[command]
+pub async fn estimate_backup_size(
+    db: State<'_, DatabaseConnection>,
+    source_path: String,
+    sample: Option<bool>,
+    compression_level: Option<u8>,
+    exclude_globs: Option<Vec<String>>,
+) -> Result<BackupSizeEstimate, String> {
+    let source = PathBuf::from(source_path);
+    if !source.is_dir() {
+        return Err("源存档文件夹不存在".to_string());
+    }
+    let compression_level = compression_level.unwrap_or(DEFAULT_COMPRESSION_LEVEL);
+    if compression_level > MAX_COMPRESSION_LEVEL {
+        return Err(format!(
+            "压缩等级需在 0 到 {} 之间: {}",
+            MAX_COMPRESSION_LEVEL, compression_level
+        ));
+    }
+    let exclude = ExcludeGlobs::new(&exclude_globs.unwrap_or_default())?;
+    let backup_root = resolve_savedata_backup_root(&db).await?;
+    let sample = sample.unwrap_or(true);
+
+    tokio::task::spawn_blocking(move || {
+        estimate(
+            &source,
+            &backup_root,
+            sample.then_some(compression_level),
+            &|name, _| !exclude.is_excluded(name),
+        )
+    })
+    .await
+    .map_err(|e| format!("估算备份大小任务失败: {}", e))?
+}
+
+/// 剩余空间明显不足以容纳备份时返回错误
+///
+/// 以抽样估算的压缩后大小为准，避免把压缩率高的存档误判为放不下。
+/// `include` 与 `level` 与实际创建压缩包时相同，只统计会写入备份的文件。
+pub(crate) fn ensure_backup_fits(
+    source: &Path,
+    backup_dir: &Path,
+    level: u8,
+    include: &dyn Fn(&str, bool) -> bool,
+) -> Result<(), String> {
+    let estimate = estimate(source, backup_dir, Some(level), include)?;
+    if !estimate.fits {
+        return Err(format!(
+            "备份目录磁盘空间不足：预计需要约 {} MB，剩余 {} MB",
+            estimate
+                .estimated_size
+                .unwrap_or(estimate.uncompressed_size)
+                .div_ceil(1024 * 1024),
+            estimate.available_space / 1024 / 1024
+        ));
+    }
+    Ok(())
+}
+
+/// `sample_level` 为抽样压缩使用的压缩等级，为 None 时不抽样
+fn estimate(
+    source: &Path,
+    backup_dir: &Path,
+    sample_level: Option<u8>,
+    include: &dyn Fn(&str, bool) -> bool,
+) -> Result<BackupSizeEstimate, String> {
+    let files = list_files(source, include);
+    let uncompressed_size = files.iter().map(|(_, size)| size).sum::<u64>();
+
+    let estimated_size = match sample_level {
+        Some(level) if uncompressed_size > 0 => {
+            let samples = pick_samples(&files);
+            let (sampled, compressed) = compress_sample(&samples, SAMPLE_BYTES_PER_FILE, level)
+                .map_err(|e| format!("抽样压缩失败: {}", e))?;
+            Some(scale_estimate(uncompressed_size, sampled, compressed))
+        }
+        Some(_) => Some(0),
+        None => None,
+    };
+
+    let existing = backup_dir
+        .ancestors()
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("备份目录无效: {}", backup_dir.display()))?;
+    let available_space =
+        fs4::available_space(existing).map_err(|e| format!("获取磁盘剩余空间失败: {}", e))?;
+
+    Ok(BackupSizeEstimate {
+        file_count: files.len(),
+        uncompressed_size,
+        estimated_size,
+        available_space,
+        fits: available_space >= estimated_size.unwrap_or(uncompressed_size),
+    })
+}
+
+/// 列出目录下的文件及其大小，按路径排序；无法读取的条目会被跳过
+///
+/// `include` 的含义同 [`super::archive::create_7z_archive_filtered`]，对目录返回 false 时跳过整个目录
+fn list_files(source: &Path, include: &dyn Fn(&str, bool) -> bool) -> Vec<(PathBuf, u64)> {
+    let mut files = Vec::new();
+    let mut walker = WalkDir::new(source).min_depth(1).into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(relative) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        let name = relative.to_string_lossy().replace('\\', "/");
+        let is_dir = entry.file_type().is_dir();
+        if !include(&name, is_dir) {
+            if is_dir {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if entry.file_type().is_file()
+            && let Ok(metadata) = entry.metadata()
+        {
+            files.push((entry.into_path(), metadata.len()));
+        }
+    }
+    files.sort();
+    files
+}
+
+/// 在文件列表中等间隔抽样，最多 `MAX_SAMPLE_FILES` 个，跳过空文件
+fn pick_samples(files: &[(PathBuf, u64)]) -> Vec<PathBuf> {
+    let non_empty = files
+        .iter()
+        .filter(|(_, size)| *size > 0)
+        .collect::<Vec<_>>();
+    let step = non_empty.len().div_ceil(MAX_SAMPLE_FILES).max(1);
+    non_empty
+        .into_iter()
+        .step_by(step)
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+/// 按样本压缩率推算总压缩大小
+fn scale_estimate(total: u64, sampled: u64, compressed: u64) -> u64 {
+    if sampled == 0 {
+        return total;
+    }
+    let ratio = compressed as f64 / sampled as f64;
+    (total as f64 * ratio).ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn samples_are_spread_across_non_empty_files() {
+        let files = (0..100)
+            .map(|i| {
+                (
+                    PathBuf::from(format!("{:03}", i)),
+                    if i == 0 { 0 } else { 10 },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let samples = pick_samples(&files);
+
+        assert!(samples.len() <= MAX_SAMPLE_FILES);
+        assert_eq!(samples[0], PathBuf::from("001"));
+        assert!(samples.last().unwrap() > &PathBuf::from("090"));
+        assert_eq!(scale_estimate(1000, 100, 25), 250);
+        assert_eq!(scale_estimate(1000, 0, 0), 1000);
+    }
+
+    #[test]
+    fn estimates_compressible_saves_below_uncompressed_size() {
+        let root =
+            std::env::temp_dir().join(format!("reina-backup-estimate-{}", std::process::id()));
+        let source = root.join("save");
+        fs::create_dir_all(source.join("slot")).unwrap();
+        fs::write(source.join("a.sav"), vec![b'a'; 64 * 1024]).unwrap();
+        fs::write(source.join("slot").join("b.sav"), vec![b'b'; 32 * 1024]).unwrap();
+
+        let estimate = estimate(
+            &source,
+            &root.join("backups"),
+            Some(DEFAULT_COMPRESSION_LEVEL),
+            &|_, _| true,
+        )
+        .unwrap();
+
+        assert_eq!(estimate.file_count, 2);
+        assert_eq!(estimate.uncompressed_size, 96 * 1024);
+        assert!(estimate.estimated_size.unwrap() < estimate.uncompressed_size);
+        assert!(estimate.fits);
+
+        let excluded = super::estimate(&source, &root.join("backups"), None, &|name, is_dir| {
+            !(is_dir && name == "slot")
+        })
+        .unwrap();
+        assert_eq!(excluded.file_count, 1);
+        assert_eq!(excluded.uncompressed_size, 64 * 1024);
+        assert_eq!(excluded.estimated_size, None);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -1,6 +1,11 @@
+pub mod app_meta;
+pub mod collection_import;
 pub mod db;
 pub mod dto;
+pub mod library_export;
+pub mod migration_guard;
 pub mod repository;
+pub mod retry;
 pub mod service;
 
 // 重新导出 service 中的所有内容方便使用
@@ -0,0 +1,150 @@
+//! 数据库元信息（`app_meta` 键值表）。
+//!
+//! 每次启动迁移完成后记录当前应用版本。若数据库由更新版本的应用写入，
+//! 旧版本无法识别其中的迁移，启动时会跳过自动迁移并保留原记录，
+//! 前端通过 `get_schema_owner_version` 读取状态并提示用户升级应用。
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, Statement};
+use serde::Serialize;
+use std::cmp::Ordering;
+use tauri::{State, command};
+
+/// 当前运行的应用版本
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// 记录最后写入数据库的应用版本的键
+const SCHEMA_OWNER_VERSION_KEY: &str = "schema_owner_version";
+
+/// 数据库版本归属信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaOwnerVersion {
+    /// 最后写入数据库的应用版本，旧数据库没有记录时为 None
+    pub owner_version: Option<String>,
+    /// 当前运行的应用版本
+    pub app_version: String,
+    /// 数据库是否由更新版本的应用写入；为 true 时本次启动未执行迁移
+    pub newer_than_app: bool,
+}
+
+/// 获取最后写入数据库的应用版本，并与当前版本比较
+#[command]
+pub async fn get_schema_owner_version(
+    db: State<'_, DatabaseConnection>,
+) -> Result<SchemaOwnerVersion, String> {
+    let owner_version = read_schema_owner_version(&db)
+        .await
+        .map_err(|e| format!("读取数据库版本信息失败: {}", e))?;
+    Ok(SchemaOwnerVersion {
+        newer_than_app: owner_version
+            .as_deref()
+            .is_some_and(|version| is_newer_version(version, APP_VERSION)),
+        owner_version,
+        app_version: APP_VERSION.to_string(),
+    })
+}
+
+/// 读取最后写入数据库的应用版本
+///
+/// `app_meta` 表尚未创建（迁移前的旧数据库）时返回 None
+pub async fn read_schema_owner_version(db: &DatabaseConnection) -> Result<Option<String>, DbErr> {
+    let table_exists = db
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'app_meta'",
+        ))
+        .await?
+        .is_some();
+    if !table_exists {
+        return Ok(None);
+    }
+
+    db.query_one(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "SELECT value FROM app_meta WHERE key = ?",
+        [SCHEMA_OWNER_VERSION_KEY.into()],
+    ))
+    .await?
+    .map(|row| row.try_get_by_index::<String>(0))
+    .transpose()
+}
+
+/// 记录当前应用版本为数据库的最后写入版本，需在迁移完成后调用
+pub async fn record_schema_owner_version(db: &DatabaseConnection) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "INSERT INTO app_meta (key, value, updated_at) VALUES (?, ?, strftime('%s', 'now'))
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        [SCHEMA_OWNER_VERSION_KEY.into(), APP_VERSION.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// `version` 是否比 `current` 更新
+///
+/// 按 `主.次.修订` 逐段比较，核心版本相同时带预发布后缀（如 `-beta.1`）的更旧；
+/// 无法解析的版本视为不更新，避免因格式异常阻止启动
+pub fn is_newer_version(version: &str, current: &str) -> bool {
+    match (parse_version(version), parse_version(current)) {
+        (Some(version), Some(current)) => version.cmp(&current) == Ordering::Greater,
+        _ => false,
+    }
+}
+
+/// 解析为 `([主, 次, 修订], 是否为正式版)`，缺失的段按 0 处理
+fn parse_version(version: &str) -> Option<([u64; 3], bool)> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next()?;
+    let (core, pre_release) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let mut parts = [0u64; 3];
+    for (index, part) in core.split('.').enumerate() {
+        *parts.get_mut(index)? = part.parse().ok()?;
+    }
+    Some((parts, pre_release.is_none()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::Database;
+
+    #[test]
+    fn compares_versions_by_numeric_parts() {
+        assert!(is_newer_version("0.25.0", "0.24.3"));
+        assert!(is_newer_version("0.24.10", "0.24.9"));
+        assert!(is_newer_version("1.0", "0.99.99"));
+        assert!(is_newer_version("0.25.0", "0.25.0-beta.1"));
+        assert!(!is_newer_version("0.25.0-beta.1", "0.25.0"));
+        assert!(!is_newer_version("0.24.3", "0.24.3"));
+        assert!(!is_newer_version("0.24.2", "0.24.3"));
+        assert!(!is_newer_version("unknown", "0.24.3"));
+        assert!(!is_newer_version("1.2.3.4", "0.24.3"));
+    }
+
+    #[tokio::test]
+    async fn records_and_reads_owner_version() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("内存数据库应连接成功");
+        assert_eq!(read_schema_owner_version(&db).await.unwrap(), None);
+
+        db.execute_unprepared(
+            "CREATE TABLE app_meta (key TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL, updated_at INTEGER)",
+        )
+        .await
+        .expect("应创建 app_meta 表");
+        assert_eq!(read_schema_owner_version(&db).await.unwrap(), None);
+
+        record_schema_owner_version(&db).await.expect("应记录版本");
+        record_schema_owner_version(&db)
+            .await
+            .expect("重复记录应覆盖");
+        assert_eq!(
+            read_schema_owner_version(&db).await.unwrap().as_deref(),
+            Some(APP_VERSION)
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! 按分享清单导入合集。
+//!
+//! 清单中的每一项先按 `bgm_id` / `vndb_id` 精确匹配库中游戏，匹配到的直接加入合集；
+//! 没有 ID 匹配时再按标题相似度（Jaro-Winkler）查找候选，候选只返回给前端确认，不会自动关联。
+
+use crate::database::repository::collections_repository::CollectionsRepository;
+use crate::database::repository::games_repository::{GameTitles, GamesRepository};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{State, command};
+
+/// 默认的标题相似度阈值（0-1）
+pub const DEFAULT_FUZZY_THRESHOLD: f64 = 0.88;
+/// 每个条目最多返回的候选数量
+const MAX_FUZZY_CANDIDATES: usize = 5;
+
+/// 分享清单中的一个游戏
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SharedCollectionEntry {
+    pub bgm_id: Option<String>,
+    pub vndb_id: Option<String>,
+    pub title: Option<String>,
+}
+
+/// 按标题找到的候选游戏
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FuzzyCandidate {
+    pub game_id: i32,
+    pub name: Option<String>,
+    /// 命中的标题
+    pub matched_title: String,
+    /// 相似度（0-1）
+    pub score: f64,
+}
+
+/// 需要用户确认的条目
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FuzzyMatch {
+    /// 条目在清单中的下标
+    pub index: usize,
+    pub title: String,
+    /// 按相似度降序
+    pub candidates: Vec<FuzzyCandidate>,
+}
+
+/// 合集导入结果
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct CollectionImportResult {
+    /// 按 ID 精确匹配并已加入合集的游戏
+    pub linked: Vec<i32>,
+    /// 仅有标题相似的候选，需前端确认后再加入合集
+    pub fuzzy: Vec<FuzzyMatch>,
+    /// 没有任何匹配的条目下标
+    pub unmatched: Vec<usize>,
+}
+
+/// 将分享清单导入到指定合集
+///
+/// # Arguments
+/// * `collection_id` - 目标合集
+/// * `entries` - 清单条目
+/// * `fuzzy_threshold` - 标题相似度阈值（0-1），默认 `DEFAULT_FUZZY_THRESHOLD`
+#[command]
+pub async fn import_collection(
+    db: State<'_, DatabaseConnection>,
+    collection_id: i32,
+    entries: Vec<SharedCollectionEntry>,
+    fuzzy_threshold: Option<f64>,
+) -> Result<CollectionImportResult, String> {
+    let threshold = fuzzy_threshold.unwrap_or(DEFAULT_FUZZY_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!("相似度阈值需在 0 到 1 之间: {}", threshold));
+    }
+
+    let mut source_index = HashMap::new();
+    for source in ["bgm", "vndb"] {
+        let bindings = GamesRepository::get_source_bindings(&db, source)
+            .await
+            .map_err(|e| format!("读取数据源绑定失败: {}", e))?;
+        for (game_id, external_id) in bindings {
+            source_index.entry((source, external_id)).or_insert(game_id);
+        }
+    }
+    let needs_titles = entries
+        .iter()
+        .any(|entry| find_by_id(entry, &source_index).is_none() && entry.title.is_some());
+    let games = if needs_titles {
+        GamesRepository::find_game_titles(&db)
+            .await
+            .map_err(|e| format!("读取游戏标题失败: {}", e))?
+    } else {
+        Vec::new()
+    };
+
+    let result = match_entries(&entries, &source_index, &games, threshold);
+    if !result.linked.is_empty() {
+        CollectionsRepository::add_games_to_collections(
+            &db,
+            result.linked.clone(),
+            vec![collection_id],
+        )
+        .await
+        .map_err(|e| format!("添加游戏到合集失败: {}", e))?;
+    }
+    log::info!(
+        "导入合集 collection_id={} linked={} fuzzy={} unmatched={}",
+        collection_id,
+        result.linked.len(),
+        result.fuzzy.len(),
+        result.unmatched.len()
+    );
+    Ok(result)
+}
+
+fn find_by_id(
+    entry: &SharedCollectionEntry,
+    source_index: &HashMap<(&str, String), i32>,
+) -> Option<i32> {
+    [("bgm", &entry.bgm_id), ("vndb", &entry.vndb_id)]
+        .into_iter()
+        .find_map(|(source, id)| {
+            let id = id.as_deref()?.trim();
+            source_index.get(&(source, id.to_string())).copied()
+        })
+}
+
+/// 精确 ID 匹配优先；没有 ID 匹配时才按标题查找候选
+fn match_entries(
+    entries: &[SharedCollectionEntry],
+    source_index: &HashMap<(&str, String), i32>,
+    games: &[GameTitles],
+    threshold: f64,
+) -> CollectionImportResult {
+    let normalized_games = games
+        .iter()
+        .map(|game| {
+            let titles = game
+                .titles
+                .iter()
+                .map(|title| (title, normalize_title(title)))
+                .collect::<Vec<_>>();
+            (game, titles)
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = CollectionImportResult::default();
+    for (index, entry) in entries.iter().enumerate() {
+        if let Some(game_id) = find_by_id(entry, source_index) {
+            if !result.linked.contains(&game_id) {
+                result.linked.push(game_id);
+            }
+            continue;
+        }
+
+        let title = entry.title.as_deref().map(str::trim).unwrap_or_default();
+        let normalized = normalize_title(title);
+        if normalized.is_empty() {
+            result.unmatched.push(index);
+            continue;
+        }
+        let mut candidates = normalized_games
+            .iter()
+            .filter_map(|(game, titles)| {
+                let (matched_title, score) = titles
+                    .iter()
+                    .map(|(original, key)| (*original, jaro_winkler(&normalized, key)))
+                    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+                (score >= threshold).then(|| FuzzyCandidate {
+                    game_id: game.id,
+                    name: game.display_name.clone(),
+                    matched_title: matched_title.clone(),
+                    score,
+                })
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            result.unmatched.push(index);
+            continue;
+        }
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.game_id.cmp(&b.game_id)));
+        candidates.truncate(MAX_FUZZY_CANDIDATES);
+        result.fuzzy.push(FuzzyMatch {
+            index,
+            title: title.to_string(),
+            candidates,
+        });
+    }
+    result
+}
+
+/// 去掉空白与符号并转为小写，只比较文字本身
+fn normalize_title(title: &str) -> Vec<char> {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Jaro-Winkler 相似度（0-1），前缀加权系数 0.1，最多计 4 个字符
+fn jaro_winkler(a: &[char], b: &[char]) -> f64 {
+    let jaro = jaro(a, b);
+    let prefix = a.iter().zip(b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+fn jaro(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, ca) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        for (j, matched) in b_matched.iter_mut().enumerate().take(end).skip(start) {
+            if !*matched && b[j] == *ca {
+                *matched = true;
+                a_matches.push(*ca);
+                break;
+            }
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    let b_matches = b
+        .iter()
+        .zip(&b_matched)
+        .filter_map(|(c, matched)| matched.then_some(*c));
+    let transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(x, y)| **x != *y)
+        .count()
+        / 2;
+    let m = a_matches.len() as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    fn entry(bgm_id: Option<&str>, title: Option<&str>) -> SharedCollectionEntry {
+        SharedCollectionEntry {
+            bgm_id: bgm_id.map(str::to_string),
+            vndb_id: None,
+            title: title.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn jaro_winkler_matches_reference_values() {
+        let score = jaro_winkler(&chars("martha"), &chars("marhta"));
+        assert!((score - 0.9611).abs() < 1e-4);
+        assert_eq!(jaro_winkler(&chars("abc"), &chars("abc")), 1.0);
+        assert_eq!(jaro_winkler(&chars("abc"), &chars("xyz")), 0.0);
+    }
+
+    #[test]
+    fn id_matches_win_and_titles_only_offer_candidates() {
+        let source_index = HashMap::from([(("bgm", "100".to_string()), 1)]);
+        let games = vec![
+            GameTitles {
+                id: 1,
+                display_name: Some("Summer Pockets".to_string()),
+                titles: vec!["Summer Pockets".to_string()],
+            },
+            GameTitles {
+                id: 2,
+                display_name: Some("サクラノ詩".to_string()),
+                titles: vec!["サクラノ詩".to_string(), "Sakura no Uta".to_string()],
+            },
+        ];
+        let entries = vec![
+            // ID 匹配优先，即使标题更像另一个游戏
+            entry(Some("100"), Some("Sakura no Uta")),
+            entry(None, Some("sakura no uta -")),
+            entry(Some("999"), Some("完全不同的游戏")),
+            entry(None, None),
+        ];
+
+        let result = match_entries(&entries, &source_index, &games, DEFAULT_FUZZY_THRESHOLD);
+
+        assert_eq!(result.linked, vec![1]);
+        assert_eq!(result.fuzzy.len(), 1);
+        assert_eq!(result.fuzzy[0].index, 1);
+        assert_eq!(result.fuzzy[0].candidates[0].game_id, 2);
+        assert_eq!(result.fuzzy[0].candidates[0].matched_title, "Sakura no Uta");
+        assert_eq!(result.unmatched, vec![2, 3]);
+    }
+}
@@ -2,6 +2,7 @@ use sea_orm::{
     ConnectOptions, ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, DbErr,
     RuntimeErr, Statement,
 };
+use serde::Serialize;
 use std::fs;
 use std::time::Duration;
 use url::Url;
@@ -68,6 +69,10 @@ pub async fn establish_connection() -> Result<DatabaseConnection, DbErr> {
     connection
         .execute_unprepared("PRAGMA foreign_keys = ON")
         .await?;
+    // 并发写入（如备份与查询同时进行）时先等待锁释放，而不是立即返回 SQLITE_BUSY
+    connection
+        .execute_unprepared("PRAGMA busy_timeout = 5000")
+        .await?;
 
     let foreign_keys = connection
         .query_one(Statement::from_string(
@@ -90,3 +95,91 @@ pub async fn close_connection(conn: DatabaseConnection) -> Result<(), DbErr> {
     conn.close().await?;
     Ok(())
 }
+
+// ==================== 连接诊断 ====================
+
+/// 数据库连接诊断信息，用于排查 "database is locked" 等锁问题
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionDiagnostics {
+    /// `PRAGMA journal_mode` 的结果（如 `delete`、`wal`）
+    pub journal_mode: String,
+    /// `PRAGMA busy_timeout`，单位毫秒
+    pub busy_timeout_ms: i64,
+    pub wal_enabled: bool,
+    /// `-wal` 文件大小，文件不存在时为 None
+    pub wal_file_size: Option<u64>,
+    /// 连接池中正在使用的连接数（不含本次诊断自身）
+    pub connections_in_use: u32,
+    pub connections_idle: u32,
+    pub max_connections: u32,
+}
+
+/// 读取当前连接的日志模式、忙等待超时与连接池状态（只读）
+pub async fn collect_connection_diagnostics(
+    db: &DatabaseConnection,
+) -> Result<ConnectionDiagnostics, DbErr> {
+    // 先记录连接池状态，避免把下面的 PRAGMA 查询计入使用中的连接
+    let pool = db.get_sqlite_connection_pool();
+    let size = pool.size();
+    let connections_idle = pool.num_idle() as u32;
+
+    let journal_mode = query_pragma(db, "journal_mode")
+        .await?
+        .try_get_by_index::<String>(0)?
+        .to_lowercase();
+    let busy_timeout_ms = query_pragma(db, "busy_timeout")
+        .await?
+        .try_get_by_index::<i64>(0)?;
+
+    let wal_enabled = journal_mode == "wal";
+    let wal_file_size = get_db_path()
+        .ok()
+        .and_then(|path| fs::metadata(format!("{}-wal", path.display())).ok())
+        .map(|metadata| metadata.len());
+
+    Ok(ConnectionDiagnostics {
+        journal_mode,
+        busy_timeout_ms,
+        wal_enabled,
+        wal_file_size,
+        connections_in_use: size.saturating_sub(connections_idle),
+        connections_idle,
+        max_connections: pool.options().get_max_connections(),
+    })
+}
+
+async fn query_pragma(
+    db: &DatabaseConnection,
+    pragma: &str,
+) -> Result<sea_orm::QueryResult, DbErr> {
+    db.query_one(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        format!("PRAGMA {}", pragma),
+    ))
+    .await?
+    .ok_or_else(|| DbErr::Custom(format!("无法读取 PRAGMA {}", pragma)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn diagnostics_report_journal_mode_and_pool_state() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("内存数据库应连接成功");
+        db.execute_unprepared("PRAGMA busy_timeout = 1234")
+            .await
+            .expect("应设置 busy_timeout");
+
+        let diagnostics = collect_connection_diagnostics(&db)
+            .await
+            .expect("应读取连接诊断");
+
+        assert_eq!(diagnostics.journal_mode, "memory");
+        assert!(!diagnostics.wal_enabled);
+        assert_eq!(diagnostics.busy_timeout_ms, 1234);
+        assert!(diagnostics.max_connections >= 1);
+    }
+}
@@ -4,7 +4,9 @@
 //! 重构后采用单表架构，元数据以 JSON 列形式嵌入 games 表。
 
 use crate::entity::custom_data::CustomData;
+use crate::entity::game_sessions;
 use crate::entity::user::BgmAuth;
+use crate::utils::fs::FileCleanupReport;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use std::path::PathBuf;
@@ -36,7 +38,7 @@ fn clean_double_option_string(s: Option<Option<String>>) -> Option<Option<String
 }
 
 /// 清洗并按当前平台的路径组件规则规范化本地路径。
-fn clean_local_path(value: String) -> Option<String> {
+pub(crate) fn clean_local_path(value: String) -> Option<String> {
     let trimmed = value.trim();
     let normalized: PathBuf = PathBuf::from(trimmed).components().collect();
     let normalized = normalized.to_string_lossy().to_string();
@@ -219,6 +221,45 @@ pub struct FullGameData {
     pub sources: Vec<GameSourceData>,
     pub created_at: Option<i32>,
     pub updated_at: Option<i32>,
+    pub archived_at: Option<i32>,
+    pub primary_source: Option<String>,
+    pub favorite: bool,
+}
+
+/// 首页仪表盘的游戏摘要，名称与封面已按显示优先级解析。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DashboardGameData {
+    pub id: i32,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub total_time: Option<i32>,
+    /// 格式化后的总游玩时长（如 `1h 23m`）
+    pub total_time_display: String,
+    pub session_count: Option<i32>,
+    pub last_played: Option<i32>,
+}
+
+/// 全库活动流中的一条会话，附带游戏的显示名称与封面。
+#[derive(Clone, Debug, Serialize)]
+pub struct SessionFeedItem {
+    pub session: game_sessions::Model,
+    pub game_name: Option<String>,
+    pub cover: Option<String>,
+}
+
+/// 全库累计游玩时长。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TotalPlaytime {
+    /// 累计时长（分钟）
+    pub total_time: i64,
+    pub total_time_display: String,
+}
+
+/// 某一天游玩过的游戏及当天游玩时长（分钟）。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GamePlayedOnDate {
+    pub game: FullGameData,
+    pub playtime: i32,
 }
 
 /// 用于插入游戏聚合的数据结构。
@@ -239,6 +280,32 @@ pub struct InsertGameData {
     pub custom_data: Option<CustomData>,
     #[serde(default)]
     pub sources: Vec<UpsertGameSourceData>,
+    /// 导入已导出的游戏时保留收藏标记
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// 带重复检查的插入结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CheckedInsertResult {
+    Inserted {
+        game: FullGameData,
+    },
+    /// 已有游戏绑定了相同的来源 ID，未插入
+    Duplicate {
+        game_id: i32,
+        source: String,
+        external_id: String,
+    },
+}
+
+/// 彻底删除游戏的结果
+#[derive(Debug, Serialize)]
+pub struct DeleteGameCompleteResult {
+    pub rows_affected: u64,
+    /// 封面与存档备份文件的清理情况
+    pub files: FileCleanupReport,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -247,6 +314,36 @@ pub struct BatchOperationError {
     pub message: String,
 }
 
+/// 关键词搜索结果，附带命中的字段与关键词附近的片段
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub game: FullGameData,
+    /// 命中字段，如 `custom.name`、`vndb.aliases`
+    pub matched_field: String,
+    /// 命中字段中关键词前后的文本，截断处以 `…` 表示
+    pub snippet: String,
+}
+
+/// 分页读取的游戏数据
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GamePage {
+    pub games: Vec<FullGameData>,
+    /// 符合筛选条件的游戏总数
+    pub total: u64,
+}
+
+/// 按备份记录中的 `file_size` 统计的存档备份占用
+#[derive(Clone, Debug, Serialize)]
+pub struct BackupSizeSummary {
+    /// 记录中的备份大小之和（字节）
+    pub total_size: i64,
+    pub record_count: usize,
+    /// 记录仍在但备份文件已不存在（如被手动删除）的数量
+    pub stale_count: usize,
+    /// 失效记录的备份大小之和（字节）
+    pub stale_size: i64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BatchOperationResult {
     pub total: usize,
@@ -0,0 +1,1280 @@
+//! 游戏库的 JSON 导出与导入。
+//!
+//! 文件结构为单个 JSON 对象，按 `games`、`sources`、`collections`、`links`、`sessions`
+//! 的顺序写出各表数组。导出在只读事务中按页查询，由阻塞线程写入文件；导入逐条解析并写入
+//! 数据库，替换模式下内存占用只与单页大小和 ID 映射有关，不随会话数量增长。
+//!
+//! 导出时可选 gzip / zstd 压缩（`.json.gz` / `.json.zst`），导入时按文件头自动识别并解压。
+//!
+//! `import_library` 额外支持合并（跳过来源 ID 已存在的游戏）与替换（先清空游戏库）两种模式。
+//! 合并模式需要游戏的全部来源 ID 才能判断是否已存在，而 `sources` 排在 `games` 之后，
+//! 因此会在内存中缓存全部游戏与数据源记录，直到读到 `sources` 之后的部分；
+//! 这部分内存占用随游戏与数据源数量增长，会话仍逐批写入。合集同样先缓存，
+//! 按父级先于子级的顺序匹配现有合集。
+//!
+//! 启动前后执行的命令（`pre_launch_cmd` / `post_exit_cmd`）不会随导入写入，避免导入来历不明的
+//! 文件后在启动游戏时执行其中的命令。
+
+use crate::database::dto::UpsertGameSourceData;
+use crate::database::repository::game_stats_repository::GameStatsRepository;
+use crate::database::repository::games_repository::GamesRepository;
+use crate::entity::prelude::*;
+use crate::entity::{collections, game_collection_link, game_sessions, game_sources, games};
+use sea_orm::ActiveValue::{NotSet, Set};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, TransactionTrait,
+};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Cursor, Read, Write};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use tauri::{State, command};
+use tokio::sync::mpsc;
+
+const LIBRARY_FORMAT: &str = "reina-library";
+const LIBRARY_VERSION: u32 = 1;
+/// 导出时每次查询的行数
+const EXPORT_PAGE_SIZE: u64 = 500;
+/// 导出查询与写出线程之间的缓冲页数
+const EXPORT_CHANNEL_CAPACITY: usize = 4;
+/// 导入时批量插入的行数
+const IMPORT_BATCH_SIZE: usize = 500;
+/// 解析线程与写库任务之间的缓冲记录数
+const IMPORT_CHANNEL_CAPACITY: usize = 256;
+/// zstd 压缩等级，与存档备份一致
+const ZSTD_LEVEL: i32 = 3;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// 导出文件的压缩方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// `.json.gz`
+    Gzip,
+    /// `.json.zst`
+    Zstd,
+}
+
+impl Compression {
+    /// 按文件头识别压缩方式，未压缩时返回 None
+    fn detect(header: &[u8]) -> Option<Self> {
+        if header.starts_with(&GZIP_MAGIC) {
+            Some(Self::Gzip)
+        } else if header.starts_with(&ZSTD_MAGIC) {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// 导出或导入的各表记录数
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct LibraryCounts {
+    pub games: usize,
+    pub sources: usize,
+    pub collections: usize,
+    pub links: usize,
+    pub sessions: usize,
+}
+
+/// `import_library` 的导入模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// 保留现有数据，跳过来源 ID 已存在的游戏及其数据源与会话，同名的同级合集合并
+    Merge,
+    /// 先清空游戏、数据源、合集、关联与会话，再导入全部记录
+    Replace,
+}
+
+/// 导入结果：写入的记录数与合并模式下跳过的记录数
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct LibraryImportResult {
+    pub inserted: LibraryCounts,
+    pub skipped: LibraryCounts,
+}
+
+/// 将整个游戏库流式导出到 JSON 文件
+///
+/// 先写入同目录的 `.part` 临时文件，完成后再替换目标文件，失败时不会留下半个文件。
+/// `compress` 为压缩方式，文件名由调用方决定（建议 `.json.gz` / `.json.zst`）。
+#[command]
+pub async fn export_library_to_file(
+    db: State<'_, DatabaseConnection>,
+    path: String,
+    compress: Option<Compression>,
+) -> Result<LibraryCounts, String> {
+    let target = PathBuf::from(&path);
+    let part_path = PathBuf::from(format!("{}.part", path));
+
+    let write_path = part_path.clone();
+    let result = export_library_with(&db, move |chunks| {
+        let file = File::create(&write_path).map_err(|e| format!("创建导出文件失败: {}", e))?;
+        write_compressed(chunks, BufWriter::new(file), compress)?
+            .into_inner()
+            .map_err(|e| format!("写入导出文件失败: {}", e.error()))?
+            .sync_all()
+            .map_err(|e| format!("写入导出文件失败: {}", e))
+    })
+    .await;
+
+    match result {
+        Ok((counts, ())) => {
+            tokio::fs::rename(&part_path, &target)
+                .await
+                .map_err(|e| format!("保存导出文件失败: {}", e))?;
+            log::info!("游戏库已导出到 {}: {:?}", target.display(), counts);
+            Ok(counts)
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&part_path).await;
+            Err(e)
+        }
+    }
+}
+
+/// 从 JSON 文件流式导入游戏库
+///
+/// 导入的游戏、合集与会话均分配新的 ID，并按文件中的关联关系重新映射；
+/// 全部写入在同一事务中完成，任一记录失败时不会留下部分数据。导入后重建统计数据。
+/// gzip / zstd 压缩的文件按文件头识别后透明解压。
+#[command]
+pub async fn import_library_from_file(
+    db: State<'_, DatabaseConnection>,
+    path: String,
+) -> Result<LibraryCounts, String> {
+    let file = File::open(&path).map_err(|e| format!("打开导入文件失败: {}", e))?;
+    let reader = decompressing_reader(BufReader::new(file))?;
+    Ok(import_library_from_reader(&db, reader, None)
+        .await?
+        .inserted)
+}
+
+/// 从导出的 JSON 字符串导入游戏库
+///
+/// 合并模式下，数据源中任一来源 ID（如 bgm_id、vndb_id）已被现有游戏绑定的游戏不会导入，
+/// 其数据源与会话一并跳过，合集关联改为指向现有游戏；替换模式先清空现有游戏库，
+/// 被清空游戏的封面与存档备份文件不会删除。
+/// 合集按新 ID 重新映射，全部写入在同一事务中完成。
+#[command]
+pub async fn import_library(
+    db: State<'_, DatabaseConnection>,
+    json: String,
+    mode: ImportMode,
+) -> Result<LibraryImportResult, String> {
+    import_library_from_reader(&db, Cursor::new(json.into_bytes()), Some(mode)).await
+}
+
+/// 将导出内容按压缩方式写入 `writer`，返回压缩结束后的原始写入器
+fn write_compressed<R: Read, W: Write>(
+    chunks: &mut R,
+    mut writer: W,
+    compress: Option<Compression>,
+) -> Result<W, String> {
+    let write_err = |e: std::io::Error| format!("写入导出文件失败: {}", e);
+    match compress {
+        None => {
+            std::io::copy(chunks, &mut writer).map_err(write_err)?;
+            writer.flush().map_err(write_err)?;
+            Ok(writer)
+        }
+        Some(Compression::Gzip) => {
+            let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+            std::io::copy(chunks, &mut encoder).map_err(write_err)?;
+            encoder.finish().map_err(write_err)
+        }
+        Some(Compression::Zstd) => {
+            let mut encoder = zstd::Encoder::new(writer, ZSTD_LEVEL).map_err(write_err)?;
+            std::io::copy(chunks, &mut encoder).map_err(write_err)?;
+            encoder.finish().map_err(write_err)
+        }
+    }
+}
+
+/// 按文件头识别压缩方式，返回解压后的读取器
+fn decompressing_reader<R: BufRead + Send + 'static>(
+    mut reader: R,
+) -> Result<Box<dyn Read + Send>, String> {
+    let header = reader
+        .fill_buf()
+        .map_err(|e| format!("读取导入文件失败: {}", e))?;
+    Ok(match Compression::detect(header) {
+        None => Box::new(reader),
+        Some(Compression::Gzip) => Box::new(flate2::bufread::GzDecoder::new(reader)),
+        Some(Compression::Zstd) => Box::new(
+            zstd::Decoder::with_buffer(reader).map_err(|e| format!("读取导入文件失败: {}", e))?,
+        ),
+    })
+}
+
+/// 在只读事务中分页读取各表并序列化，交给阻塞线程中的 `write` 写出
+///
+/// 所有查询共享同一事务，导出内容是同一时刻的一致快照；文件写入与压缩不占用异步运行时。
+async fn export_library_with<T, F>(
+    db: &DatabaseConnection,
+    write: F,
+) -> Result<(LibraryCounts, T), String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ChunkReader) -> Result<T, String> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+    let writer = tokio::task::spawn_blocking(move || {
+        write(&mut ChunkReader {
+            receiver,
+            current: Cursor::default(),
+        })
+    });
+
+    let export_result = async {
+        let transaction = db
+            .begin()
+            .await
+            .map_err(|e| format!("开启事务失败: {}", e))?;
+        let counts = export_tables(&transaction, &sender).await?;
+        transaction
+            .commit()
+            .await
+            .map_err(|e| format!("结束导出事务失败: {}", e))?;
+        Ok::<_, String>(counts)
+    }
+    .await;
+    // 关闭通道，写出线程读到末尾后结束
+    drop(sender);
+
+    // 写出失败时通道提前关闭，读取一侧只会报告“导出已中止”，因此优先返回写出错误
+    let output = writer
+        .await
+        .map_err(|e| format!("写入导出文件失败: {}", e))??;
+    Ok((export_result?, output))
+}
+
+/// 按 `games`、`sources`、`collections`、`links`、`sessions` 的顺序序列化各表
+async fn export_tables(
+    transaction: &DatabaseTransaction,
+    sender: &mpsc::Sender<Vec<u8>>,
+) -> Result<LibraryCounts, String> {
+    let header = format!(
+        "{{\"format\":\"{}\",\"version\":{},\"exported_at\":{}",
+        LIBRARY_FORMAT,
+        LIBRARY_VERSION,
+        chrono::Utc::now().timestamp()
+    );
+    send_chunk(sender, header.into_bytes()).await?;
+
+    let counts = LibraryCounts {
+        games: write_table(
+            transaction,
+            sender,
+            "games",
+            Games::find().order_by_asc(games::Column::Id),
+        )
+        .await?,
+        sources: write_table(
+            transaction,
+            sender,
+            "sources",
+            GameSources::find()
+                .order_by_asc(game_sources::Column::GameId)
+                .order_by_asc(game_sources::Column::Source),
+        )
+        .await?,
+        collections: write_table(
+            transaction,
+            sender,
+            "collections",
+            Collections::find().order_by_asc(collections::Column::Id),
+        )
+        .await?,
+        links: write_table(
+            transaction,
+            sender,
+            "links",
+            GameCollectionLink::find().order_by_asc(game_collection_link::Column::Id),
+        )
+        .await?,
+        sessions: write_table(
+            transaction,
+            sender,
+            "sessions",
+            GameSessions::find().order_by_asc(game_sessions::Column::SessionId),
+        )
+        .await?,
+    };
+
+    send_chunk(sender, b"}".to_vec()).await?;
+    Ok(counts)
+}
+
+/// 分页查询并以 `,"key":[...]` 的形式序列化一张表，每页发送一次
+async fn write_table<E>(
+    transaction: &DatabaseTransaction,
+    sender: &mpsc::Sender<Vec<u8>>,
+    key: &str,
+    select: sea_orm::Select<E>,
+) -> Result<usize, String>
+where
+    E: EntityTrait,
+    E::Model: Serialize + Send + Sync,
+{
+    let mut chunk = format!(",\"{}\":[", key).into_bytes();
+    let mut count = 0;
+    let mut pages = select.paginate(transaction, EXPORT_PAGE_SIZE);
+    while let Some(rows) = pages
+        .fetch_and_next()
+        .await
+        .map_err(|e| format!("读取 {} 失败: {}", key, e))?
+    {
+        for row in rows {
+            if count > 0 {
+                chunk.push(b',');
+            }
+            serde_json::to_writer(&mut chunk, &row)
+                .map_err(|e| format!("序列化 {} 失败: {}", key, e))?;
+            count += 1;
+        }
+        send_chunk(sender, std::mem::take(&mut chunk)).await?;
+    }
+
+    chunk.push(b']');
+    send_chunk(sender, chunk).await?;
+    Ok(count)
+}
+
+async fn send_chunk(sender: &mpsc::Sender<Vec<u8>>, chunk: Vec<u8>) -> Result<(), String> {
+    sender
+        .send(chunk)
+        .await
+        .map_err(|_| "导出已中止".to_string())
+}
+
+/// 从通道逐块读取导出内容，发送端关闭后视为读到末尾
+struct ChunkReader {
+    receiver: mpsc::Receiver<Vec<u8>>,
+    current: Cursor<Vec<u8>>,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let read = self.current.read(buf)?;
+            if read > 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.current = Cursor::new(chunk),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+/// 导入文件中的一条记录
+#[derive(Debug)]
+enum LibraryRecord {
+    Game(Box<games::Model>),
+    Source(game_sources::Model),
+    Collection(collections::Model),
+    Link(game_collection_link::Model),
+    Session(game_sessions::Model),
+}
+
+/// 流式导入游戏库，`mode` 为 None 时不检查重复，全部作为新记录导入
+pub(crate) async fn import_library_from_reader<R: Read + Send + 'static>(
+    db: &DatabaseConnection,
+    reader: R,
+    mode: Option<ImportMode>,
+) -> Result<LibraryImportResult, String> {
+    let (sender, mut receiver) = mpsc::channel(IMPORT_CHANNEL_CAPACITY);
+    let parser = tokio::task::spawn_blocking(move || {
+        read_library(reader, |record| {
+            sender
+                .blocking_send(record)
+                .map_err(|_| "导入已中止".to_string())
+        })
+    });
+
+    let transaction = db
+        .begin()
+        .await
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+    if mode == Some(ImportMode::Replace) {
+        clear_library(&transaction).await?;
+    }
+    let mut importer = LibraryImporter::new(&transaction, mode == Some(ImportMode::Merge));
+    let mut import_result = Ok(());
+    while let Some(record) = receiver.recv().await {
+        if let Err(e) = importer.import(record).await {
+            import_result = Err(e);
+            break;
+        }
+    }
+    // 写库失败时关闭通道，让解析线程尽快退出
+    drop(receiver);
+
+    let parse_result = parser
+        .await
+        .map_err(|e| format!("解析导入文件失败: {}", e))?;
+    import_result?;
+    parse_result?;
+
+    let (result, game_ids) = importer.finish().await?;
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("提交导入失败: {}", e))?;
+
+    for game_id in game_ids {
+        GameStatsRepository::rebuild_statistics(db, game_id)
+            .await
+            .map_err(|e| format!("重建统计数据失败: {}", e))?;
+    }
+    log::info!("游戏库导入完成: {:?}", result);
+    Ok(result)
+}
+
+/// 替换导入前清空游戏库，其余引用游戏的表随外键级联删除
+async fn clear_library(transaction: &DatabaseTransaction) -> Result<(), String> {
+    let clear_err = |e: sea_orm::DbErr| format!("清空游戏库失败: {}", e);
+    GameCollectionLink::delete_many()
+        .exec(transaction)
+        .await
+        .map_err(clear_err)?;
+    GameSessions::delete_many()
+        .exec(transaction)
+        .await
+        .map_err(clear_err)?;
+    GameSources::delete_many()
+        .exec(transaction)
+        .await
+        .map_err(clear_err)?;
+    Collections::delete_many()
+        .exec(transaction)
+        .await
+        .map_err(clear_err)?;
+    Games::delete_many()
+        .exec(transaction)
+        .await
+        .map_err(clear_err)?;
+    Ok(())
+}
+
+/// 将导入记录写入事务，并维护旧 ID 到新 ID 的映射
+struct LibraryImporter<'a> {
+    transaction: &'a DatabaseTransaction,
+    /// 合并模式：跳过来源 ID 已存在的游戏
+    merge: bool,
+    counts: LibraryCounts,
+    skipped: LibraryCounts,
+    game_ids: HashMap<i32, i32>,
+    /// 合并模式下被跳过的游戏（旧 ID，现有游戏 ID）
+    skipped_games: HashMap<i32, i32>,
+    /// 合并模式下游戏的来源 ID 排在游戏之后，读到其他记录前先缓存游戏与数据源，
+    /// 占用内存随游戏数量增长，见模块文档
+    pending_games: Vec<games::Model>,
+    pending_game_sources: HashMap<i32, Vec<game_sources::Model>>,
+    collection_ids: HashMap<i32, i32>,
+    /// 合并模式下合集按父级先于子级的顺序匹配，读到其他记录前先缓存
+    pending_collections: Vec<collections::Model>,
+    /// 合集的父级可能排在自身之后，全部插入后再回填（新 ID，旧父级 ID）
+    collection_parents: Vec<(i32, i32)>,
+    pending_sources: Vec<game_sources::ActiveModel>,
+    pending_sessions: Vec<game_sessions::ActiveModel>,
+}
+
+impl<'a> LibraryImporter<'a> {
+    fn new(transaction: &'a DatabaseTransaction, merge: bool) -> Self {
+        Self {
+            transaction,
+            merge,
+            counts: LibraryCounts::default(),
+            skipped: LibraryCounts::default(),
+            game_ids: HashMap::new(),
+            skipped_games: HashMap::new(),
+            pending_games: Vec::new(),
+            pending_game_sources: HashMap::new(),
+            collection_ids: HashMap::new(),
+            pending_collections: Vec::new(),
+            collection_parents: Vec::new(),
+            pending_sources: Vec::new(),
+            pending_sessions: Vec::new(),
+        }
+    }
+
+    async fn import(&mut self, record: LibraryRecord) -> Result<(), String> {
+        if !matches!(record, LibraryRecord::Game(_) | LibraryRecord::Source(_)) {
+            self.resolve_pending_games().await?;
+        }
+        if !matches!(
+            record,
+            LibraryRecord::Game(_) | LibraryRecord::Source(_) | LibraryRecord::Collection(_)
+        ) {
+            self.resolve_pending_collections().await?;
+        }
+        match record {
+            LibraryRecord::Game(game) if self.merge => self.pending_games.push(*game),
+            LibraryRecord::Game(game) => self.insert_game(*game).await?,
+            LibraryRecord::Source(source) if self.merge => self
+                .pending_game_sources
+                .entry(source.game_id)
+                .or_default()
+                .push(source),
+            LibraryRecord::Source(source) => self.push_source(source).await?,
+            LibraryRecord::Collection(collection) if self.merge => {
+                self.pending_collections.push(collection)
+            }
+            LibraryRecord::Collection(collection) => self.import_collection(collection).await?,
+            LibraryRecord::Link(link) => {
+                let game_id = match self.skipped_games.get(&link.game_id) {
+                    Some(&existing_id) => existing_id,
+                    None => self.map_game("合集关联", link.game_id)?,
+                };
+                let collection_id = self
+                    .collection_ids
+                    .get(&link.collection_id)
+                    .copied()
+                    .ok_or_else(|| format!("合集关联引用了不存在的合集: {}", link.collection_id))?;
+                // 合并模式下现有游戏可能已在现有合集中
+                let inserted = GameCollectionLink::insert(game_collection_link::ActiveModel {
+                    id: NotSet,
+                    game_id: Set(game_id),
+                    collection_id: Set(collection_id),
+                    sort_order: Set(link.sort_order),
+                    created_at: Set(link.created_at),
+                })
+                .on_conflict(
+                    OnConflict::columns([
+                        game_collection_link::Column::GameId,
+                        game_collection_link::Column::CollectionId,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(self.transaction)
+                .await
+                .map_err(|e| format!("导入合集关联失败: {}", e))?;
+                if inserted == 0 {
+                    self.skipped.links += 1;
+                } else {
+                    self.counts.links += 1;
+                }
+            }
+            LibraryRecord::Session(session) => {
+                if self.skipped_games.contains_key(&session.game_id) {
+                    self.skipped.sessions += 1;
+                    return Ok(());
+                }
+                let game_id = self.map_game("会话", session.game_id)?;
+                self.pending_sessions.push(game_sessions::ActiveModel {
+                    session_id: NotSet,
+                    game_id: Set(game_id),
+                    start_time: Set(session.start_time),
+                    end_time: Set(session.end_time),
+                    duration: Set(session.duration),
+                    date: Set(session.date),
+                });
+                self.counts.sessions += 1;
+                if self.pending_sessions.len() >= IMPORT_BATCH_SIZE {
+                    self.flush().await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn insert_game(&mut self, game: games::Model) -> Result<(), String> {
+        let old_id = game.id;
+        let inserted = Games::insert(games::ActiveModel {
+            id: NotSet,
+            id_type: Set(game.id_type),
+            date: Set(game.date),
+            localpath: Set(game.localpath),
+            savepath: Set(game.savepath),
+            autosave: Set(game.autosave),
+            maxbackups: Set(game.maxbackups),
+            clear: Set(game.clear),
+            le_launch: Set(game.le_launch),
+            magpie: Set(game.magpie),
+            custom_data: Set(game.custom_data),
+            // 生成列，由 custom_data 计算
+            user_rating: NotSet,
+            created_at: Set(game.created_at),
+            updated_at: Set(game.updated_at),
+            archived_at: Set(game.archived_at),
+            primary_source: Set(game.primary_source),
+            favorite: Set(game.favorite),
+            launch_args: Set(game.launch_args),
+            // 不导入启动前后执行的命令，需由用户在本机重新设置
+            pre_launch_cmd: Set(None),
+            post_exit_cmd: Set(None),
+        })
+        .exec(self.transaction)
+        .await
+        .map_err(|e| format!("导入游戏 {} 失败: {}", old_id, e))?;
+        self.game_ids.insert(old_id, inserted.last_insert_id);
+        self.counts.games += 1;
+        Ok(())
+    }
+
+    async fn push_source(&mut self, source: game_sources::Model) -> Result<(), String> {
+        let game_id = self.map_game("数据源", source.game_id)?;
+        self.pending_sources.push(game_sources::ActiveModel {
+            game_id: Set(game_id),
+            source: Set(source.source),
+            external_id: Set(source.external_id),
+            data: Set(source.data),
+            score: NotSet,
+            rank: NotSet,
+        });
+        self.counts.sources += 1;
+        if self.pending_sources.len() >= IMPORT_BATCH_SIZE {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// 合并模式下按来源 ID 判断缓存的游戏是否已存在，已存在的跳过，其余插入
+    async fn resolve_pending_games(&mut self) -> Result<(), String> {
+        for game in std::mem::take(&mut self.pending_games) {
+            let sources = self
+                .pending_game_sources
+                .remove(&game.id)
+                .unwrap_or_default();
+            let source_ids = sources
+                .iter()
+                .map(|source| UpsertGameSourceData {
+                    source: source.source.clone(),
+                    external_id: source.external_id.clone(),
+                    data: None,
+                })
+                .collect::<Vec<_>>();
+            let existing = GamesRepository::find_existing_by_sources(self.transaction, &source_ids)
+                .await
+                .map_err(|e| format!("查找已存在的游戏失败: {}", e))?;
+            if let Some(existing) = existing {
+                self.skipped_games.insert(game.id, existing.game_id);
+                self.skipped.games += 1;
+                self.skipped.sources += sources.len();
+                continue;
+            }
+
+            self.insert_game(game).await?;
+            for source in sources {
+                self.push_source(source).await?;
+            }
+            // 立即写入数据源，文件中来源 ID 重复的后续游戏也会被跳过
+            self.flush().await?;
+        }
+        if let Some(&game_id) = self.pending_game_sources.keys().next() {
+            return Err(format!("数据源引用了不存在的游戏: {}", game_id));
+        }
+        Ok(())
+    }
+
+    /// 合并模式下已存在同名同父级合集时跳过，否则插入；父级在全部合集插入后回填
+    async fn import_collection(&mut self, collection: collections::Model) -> Result<(), String> {
+        let old_id = collection.id;
+        if self.merge
+            && let Some(existing_id) = self.find_existing_collection(&collection).await?
+        {
+            self.collection_ids.insert(old_id, existing_id);
+            self.skipped.collections += 1;
+            return Ok(());
+        }
+        let inserted = Collections::insert(collections::ActiveModel {
+            id: NotSet,
+            name: Set(collection.name),
+            parent_id: Set(None),
+            sort_order: Set(collection.sort_order),
+            icon: Set(collection.icon),
+            created_at: Set(collection.created_at),
+            updated_at: Set(collection.updated_at),
+            view_sort_option: Set(collection.view_sort_option),
+            view_sort_order: Set(collection.view_sort_order),
+        })
+        .exec(self.transaction)
+        .await
+        .map_err(|e| format!("导入合集 {} 失败: {}", old_id, e))?;
+        self.collection_ids.insert(old_id, inserted.last_insert_id);
+        if let Some(parent_id) = collection.parent_id {
+            self.collection_parents
+                .push((inserted.last_insert_id, parent_id));
+        }
+        self.counts.collections += 1;
+        Ok(())
+    }
+
+    /// 合并模式下按层级处理缓存的合集：父级确定新 ID 后再匹配子级，
+    /// 子级在文件中排在父级之前时也能匹配到现有合集
+    async fn resolve_pending_collections(&mut self) -> Result<(), String> {
+        let mut pending = std::mem::take(&mut self.pending_collections);
+        while !pending.is_empty() {
+            let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|collection| {
+                collection
+                    .parent_id
+                    .is_none_or(|parent_id| self.collection_ids.contains_key(&parent_id))
+            });
+            pending = waiting;
+            if ready.is_empty() {
+                break;
+            }
+            for collection in ready {
+                self.import_collection(collection).await?;
+            }
+        }
+        // 父级不在文件中或存在循环引用时无法匹配，直接插入
+        for collection in pending {
+            self.import_collection(collection).await?;
+        }
+        Ok(())
+    }
+
+    /// 查找与导入合集同名且父级相同的现有合集，父级尚未导入时视为不存在
+    async fn find_existing_collection(
+        &self,
+        collection: &collections::Model,
+    ) -> Result<Option<i32>, String> {
+        let parent_condition = match collection.parent_id {
+            None => collections::Column::ParentId.is_null(),
+            Some(old_parent_id) => match self.collection_ids.get(&old_parent_id) {
+                Some(&parent_id) => collections::Column::ParentId.eq(parent_id),
+                None => return Ok(None),
+            },
+        };
+        Collections::find()
+            .filter(collections::Column::Name.eq(collection.name.as_str()))
+            .filter(parent_condition)
+            .order_by_asc(collections::Column::Id)
+            .one(self.transaction)
+            .await
+            .map(|existing| existing.map(|existing| existing.id))
+            .map_err(|e| format!("查找已存在的合集失败: {}", e))
+    }
+
+    fn map_game(&self, kind: &str, old_id: i32) -> Result<i32, String> {
+        self.game_ids
+            .get(&old_id)
+            .copied()
+            .ok_or_else(|| format!("{}引用了不存在的游戏: {}", kind, old_id))
+    }
+
+    async fn flush(&mut self) -> Result<(), String> {
+        if !self.pending_sources.is_empty() {
+            GameSources::insert_many(std::mem::take(&mut self.pending_sources))
+                .exec(self.transaction)
+                .await
+                .map_err(|e| format!("导入数据源失败: {}", e))?;
+        }
+        if !self.pending_sessions.is_empty() {
+            GameSessions::insert_many(std::mem::take(&mut self.pending_sessions))
+                .exec(self.transaction)
+                .await
+                .map_err(|e| format!("导入会话失败: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// 写入剩余批次并回填合集父级，返回计数与导入的游戏新 ID
+    async fn finish(mut self) -> Result<(LibraryImportResult, Vec<i32>), String> {
+        self.resolve_pending_games().await?;
+        self.resolve_pending_collections().await?;
+        self.flush().await?;
+        for (collection_id, old_parent_id) in std::mem::take(&mut self.collection_parents) {
+            let parent_id = self.collection_ids.get(&old_parent_id).copied();
+            Collections::update(collections::ActiveModel {
+                id: Set(collection_id),
+                parent_id: Set(parent_id),
+                ..Default::default()
+            })
+            .exec(self.transaction)
+            .await
+            .map_err(|e| format!("更新合集父级失败: {}", e))?;
+        }
+        let game_ids = self.game_ids.into_values().collect();
+        let result = LibraryImportResult {
+            inserted: self.counts,
+            skipped: self.skipped,
+        };
+        Ok((result, game_ids))
+    }
+}
+
+/// 逐条解析导入文件，每解析出一条记录就交给 `sink`
+fn read_library<R: Read>(
+    reader: R,
+    mut sink: impl FnMut(LibraryRecord) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    deserializer
+        .deserialize_map(LibraryVisitor { sink: &mut sink })
+        .map_err(|e| format!("解析导入文件失败: {}", e))?;
+    deserializer
+        .end()
+        .map_err(|e| format!("解析导入文件失败: {}", e))
+}
+
+struct LibraryVisitor<'a, F> {
+    sink: &'a mut F,
+}
+
+impl<'de, F> de::Visitor<'de> for LibraryVisitor<'_, F>
+where
+    F: FnMut(LibraryRecord) -> Result<(), String>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("ReinaManager 游戏库导出文件")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let sink = self.sink;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "format" => {
+                    let format: String = map.next_value()?;
+                    if format != LIBRARY_FORMAT {
+                        return Err(de::Error::custom(format!("不支持的文件格式: {}", format)));
+                    }
+                }
+                "version" => {
+                    let version: u32 = map.next_value()?;
+                    if version > LIBRARY_VERSION {
+                        return Err(de::Error::custom(format!(
+                            "导入文件版本 {} 高于当前支持的版本 {}",
+                            version, LIBRARY_VERSION
+                        )));
+                    }
+                }
+                "games" => map
+                    .next_value_seed(RecordSeq::new(&mut *sink, |game: games::Model| {
+                        LibraryRecord::Game(Box::new(game))
+                    }))?,
+                "sources" => {
+                    map.next_value_seed(RecordSeq::new(&mut *sink, LibraryRecord::Source))?
+                }
+                "collections" => {
+                    map.next_value_seed(RecordSeq::new(&mut *sink, LibraryRecord::Collection))?
+                }
+                "links" => map.next_value_seed(RecordSeq::new(&mut *sink, LibraryRecord::Link))?,
+                "sessions" => {
+                    map.next_value_seed(RecordSeq::new(&mut *sink, LibraryRecord::Session))?
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 逐个元素反序列化数组，不在内存中保留整个数组
+struct RecordSeq<'a, F, T> {
+    sink: &'a mut F,
+    wrap: fn(T) -> LibraryRecord,
+    marker: PhantomData<T>,
+}
+
+impl<'a, F, T> RecordSeq<'a, F, T> {
+    fn new(sink: &'a mut F, wrap: fn(T) -> LibraryRecord) -> Self {
+        Self {
+            sink,
+            wrap,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, F, T> DeserializeSeed<'de> for RecordSeq<'_, F, T>
+where
+    F: FnMut(LibraryRecord) -> Result<(), String>,
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F, T> de::Visitor<'de> for RecordSeq<'_, F, T>
+where
+    F: FnMut(LibraryRecord) -> Result<(), String>,
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("记录数组")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let sink = self.sink;
+        while let Some(item) = seq.next_element::<T>()? {
+            sink((self.wrap)(item)).map_err(de::Error::custom)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ConnectionTrait, Database};
+
+    async fn setup_database() -> DatabaseConnection {
+        let database = Database::connect("sqlite::memory:").await.unwrap();
+        database
+            .execute_unprepared(
+                r#"
+                PRAGMA foreign_keys = ON;
+                CREATE TABLE games (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    id_type TEXT NOT NULL,
+                    date TEXT,
+                    localpath TEXT,
+                    savepath TEXT,
+                    autosave INTEGER,
+                    maxbackups INTEGER,
+                    clear INTEGER,
+                    le_launch INTEGER,
+                    magpie INTEGER,
+                    custom_data TEXT,
+                    user_rating REAL GENERATED ALWAYS AS (
+                        CAST(json_extract(custom_data, '$.user_rating') AS REAL)
+                    ) VIRTUAL,
+                    created_at INTEGER,
+                    updated_at INTEGER,
+                    archived_at INTEGER,
+                    primary_source TEXT,
+                    favorite INTEGER NOT NULL DEFAULT 0,
+                    launch_args TEXT,
+                    pre_launch_cmd TEXT,
+                    post_exit_cmd TEXT
+                );
+                CREATE TABLE game_sources (
+                    game_id INTEGER NOT NULL,
+                    source TEXT NOT NULL,
+                    external_id TEXT,
+                    data TEXT,
+                    score REAL GENERATED ALWAYS AS (
+                        CAST(json_extract(data, '$.score') AS REAL)
+                    ) VIRTUAL,
+                    rank INTEGER GENERATED ALWAYS AS (
+                        CAST(json_extract(data, '$.rank') AS INTEGER)
+                    ) VIRTUAL,
+                    PRIMARY KEY (game_id, source),
+                    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+                );
+                CREATE TABLE collections (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    parent_id INTEGER,
+                    sort_order INTEGER NOT NULL DEFAULT 0,
+                    icon TEXT,
+                    created_at INTEGER,
+                    updated_at INTEGER,
+                    view_sort_option TEXT,
+                    view_sort_order TEXT,
+                    FOREIGN KEY (parent_id) REFERENCES collections(id) ON DELETE CASCADE
+                );
+                CREATE TABLE game_collection_link (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    game_id INTEGER NOT NULL,
+                    collection_id INTEGER NOT NULL,
+                    sort_order INTEGER NOT NULL DEFAULT 0,
+                    created_at INTEGER,
+                    UNIQUE (game_id, collection_id),
+                    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+                    FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+                );
+                CREATE TABLE game_sessions (
+                    session_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    game_id INTEGER NOT NULL,
+                    start_time INTEGER NOT NULL,
+                    end_time INTEGER NOT NULL,
+                    duration INTEGER NOT NULL,
+                    date TEXT NOT NULL,
+                    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+                );
+                CREATE TABLE game_statistics (
+                    game_id INTEGER PRIMARY KEY,
+                    total_time INTEGER,
+                    session_count INTEGER,
+                    last_played INTEGER,
+                    daily_stats TEXT,
+                    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+                );
+                "#,
+            )
+            .await
+            .unwrap();
+        database
+    }
+
+    async fn export_bytes(
+        db: &DatabaseConnection,
+        compress: Option<Compression>,
+    ) -> (LibraryCounts, Vec<u8>) {
+        export_library_with(db, move |chunks| {
+            write_compressed(chunks, Vec::new(), compress)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// 导出为 JSON 值并去掉导出时间，便于比较
+    async fn export_value(db: &DatabaseConnection) -> serde_json::Value {
+        let (_, bytes) = export_bytes(db, None).await;
+        let mut value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        value.as_object_mut().unwrap().remove("exported_at");
+        value
+    }
+
+    #[tokio::test]
+    async fn round_trip_preserves_large_library() {
+        let source = setup_database().await;
+        source
+            .execute_unprepared(
+                r#"
+                WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 2000)
+                INSERT INTO games (id_type, custom_data, clear, created_at, updated_at, favorite)
+                SELECT 'bgm', json_object('name', '游戏' || n, 'user_rating', n % 10),
+                       n % 3, n, n, n % 2 FROM seq;
+                INSERT INTO game_sources (game_id, source, external_id, data)
+                SELECT id, 'bgm', 'b' || id, json_object('score', id % 10, 'rank', id) FROM games;
+                WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 10)
+                INSERT INTO game_sessions (game_id, start_time, end_time, duration, date)
+                SELECT g.id, g.id * 1000 + seq.n * 100, g.id * 1000 + seq.n * 100 + 60, 1,
+                       '2026-01-01' FROM games g, seq ORDER BY g.id, seq.n;
+                INSERT INTO collections (id, name, parent_id, sort_order) VALUES (1, '子分类', NULL, 0);
+                INSERT INTO collections (id, name, parent_id, sort_order) VALUES (2, '分组', NULL, 1);
+                UPDATE collections SET parent_id = 2 WHERE id = 1;
+                INSERT INTO game_collection_link (game_id, collection_id, sort_order)
+                SELECT id, 1, id FROM games WHERE id % 5 = 0;
+                "#,
+            )
+            .await
+            .unwrap();
+        let expected = export_value(&source).await;
+
+        let (exported, bytes) = export_bytes(&source, None).await;
+        assert_eq!(exported.games, 2000);
+        assert_eq!(exported.sessions, 20000);
+
+        let target = setup_database().await;
+        let imported = import_library_from_reader(&target, Cursor::new(bytes), None)
+            .await
+            .unwrap();
+        assert_eq!(imported.inserted, exported);
+        assert_eq!(export_value(&target).await, expected);
+
+        let statistics = GameStatistics::find_by_id(1).one(&target).await.unwrap();
+        assert_eq!(statistics.and_then(|s| s.session_count), Some(10));
+    }
+
+    #[tokio::test]
+    async fn compressed_exports_round_trip() {
+        let source = setup_database().await;
+        source
+            .execute_unprepared(
+                r#"
+                INSERT INTO games (id_type, custom_data, created_at, updated_at, favorite)
+                VALUES ('bgm', json_object('name', '游戏'), 1, 1, 0);
+                INSERT INTO game_sessions (game_id, start_time, end_time, duration, date)
+                VALUES (1, 1000, 1060, 1, '2026-01-01');
+                "#,
+            )
+            .await
+            .unwrap();
+        let expected = export_value(&source).await;
+
+        for (compress, magic) in [
+            (Compression::Gzip, &GZIP_MAGIC[..]),
+            (Compression::Zstd, &ZSTD_MAGIC[..]),
+        ] {
+            let (exported, bytes) = export_bytes(&source, Some(compress)).await;
+            assert!(bytes.starts_with(magic), "{compress:?}");
+
+            let target = setup_database().await;
+            let reader = decompressing_reader(Cursor::new(bytes)).unwrap();
+            let imported = import_library_from_reader(&target, reader, None)
+                .await
+                .unwrap();
+            assert_eq!(imported.inserted, exported);
+            assert_eq!(export_value(&target).await, expected, "{compress:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn import_drops_launch_hooks() {
+        let target = setup_database().await;
+        let json = r#"{"format":"reina-library","version":1,"games":[{"id":1,"id_type":"custom",
+            "favorite":false,"pre_launch_cmd":"calc.exe","post_exit_cmd":"calc.exe"}]}"#;
+
+        import_library_from_reader(&target, Cursor::new(json.as_bytes().to_vec()), None)
+            .await
+            .unwrap();
+
+        let game = Games::find().one(&target).await.unwrap().unwrap();
+        assert_eq!(game.pre_launch_cmd, None);
+        assert_eq!(game.post_exit_cmd, None);
+    }
+
+    #[tokio::test]
+    async fn rejects_dangling_references_without_partial_import() {
+        let target = setup_database().await;
+        let json = r#"{"format":"reina-library","version":1,"games":[],
+            "sessions":[{"session_id":1,"game_id":7,"start_time":0,"end_time":60,"duration":1,"date":"2026-01-01"}]}"#;
+
+        let error =
+            import_library_from_reader(&target, Cursor::new(json.as_bytes().to_vec()), None)
+                .await
+                .unwrap_err();
+        assert!(error.contains("不存在的游戏"));
+    }
+
+    #[tokio::test]
+    async fn merge_matches_child_collection_listed_before_parent() {
+        let source = setup_database().await;
+        source
+            .execute_unprepared(
+                r#"
+                INSERT INTO games (id_type, custom_data, favorite) VALUES
+                    ('custom', json_object('name', '游戏'), 0);
+                INSERT INTO collections (id, name, parent_id, sort_order) VALUES
+                    (1, '子分类', NULL, 0), (2, '分组', NULL, 0);
+                UPDATE collections SET parent_id = 2 WHERE id = 1;
+                INSERT INTO game_collection_link (game_id, collection_id) VALUES (1, 1);
+                "#,
+            )
+            .await
+            .unwrap();
+        let (_, bytes) = export_bytes(&source, None).await;
+
+        let target = setup_database().await;
+        target
+            .execute_unprepared(
+                r#"
+                INSERT INTO collections (id, name, parent_id, sort_order) VALUES
+                    (1, '分组', NULL, 0), (2, '子分类', 1, 0);
+                "#,
+            )
+            .await
+            .unwrap();
+
+        let merged =
+            import_library_from_reader(&target, Cursor::new(bytes), Some(ImportMode::Merge))
+                .await
+                .unwrap();
+        assert_eq!(merged.inserted.collections, 0);
+        assert_eq!(merged.skipped.collections, 2);
+        assert_eq!(Collections::find().count(&target).await.unwrap(), 2);
+        let link = GameCollectionLink::find()
+            .one(&target)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(link.collection_id, 2);
+    }
+
+    #[tokio::test]
+    async fn merge_skips_existing_games_and_replace_clears_library() {
+        let source = setup_database().await;
+        source
+            .execute_unprepared(
+                r#"
+                INSERT INTO games (id_type, custom_data, favorite) VALUES
+                    ('bgm', json_object('name', '已有'), 0),
+                    ('vndb', json_object('name', '新游戏'), 0);
+                INSERT INTO game_sources (game_id, source, external_id) VALUES
+                    (1, 'bgm', '100'), (2, 'vndb', 'v200');
+                INSERT INTO collections (id, name, parent_id, sort_order) VALUES
+                    (1, '分组', NULL, 0), (2, '子分类', 1, 0);
+                INSERT INTO game_collection_link (game_id, collection_id) VALUES (1, 2), (2, 2);
+                INSERT INTO game_sessions (game_id, start_time, end_time, duration, date) VALUES
+                    (1, 1000, 1060, 1, '2026-01-01'), (2, 2000, 2060, 1, '2026-01-01');
+                "#,
+            )
+            .await
+            .unwrap();
+        let (_, bytes) = export_bytes(&source, None).await;
+
+        let target = setup_database().await;
+        target
+            .execute_unprepared(
+                r#"
+                INSERT INTO games (id_type, custom_data, favorite) VALUES
+                    ('bgm', json_object('name', '本地'), 0);
+                INSERT INTO game_sources (game_id, source, external_id) VALUES (1, 'bgm', '100');
+                INSERT INTO collections (id, name, parent_id, sort_order) VALUES (1, '分组', NULL, 0);
+                INSERT INTO game_sessions (game_id, start_time, end_time, duration, date) VALUES
+                    (1, 500, 560, 1, '2026-01-01');
+                "#,
+            )
+            .await
+            .unwrap();
+
+        let merged = import_library_from_reader(
+            &target,
+            Cursor::new(bytes.clone()),
+            Some(ImportMode::Merge),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            merged.inserted,
+            LibraryCounts {
+                games: 1,
+                sources: 1,
+                collections: 1,
+                links: 2,
+                sessions: 1,
+            }
+        );
+        assert_eq!(
+            merged.skipped,
+            LibraryCounts {
+                games: 1,
+                sources: 1,
+                collections: 1,
+                links: 0,
+                sessions: 1,
+            }
+        );
+        assert_eq!(Games::find().count(&target).await.unwrap(), 2);
+        // 子分类挂在现有的同名分组下，现有游戏也加入了子分类
+        let child = Collections::find()
+            .filter(collections::Column::Name.eq("子分类"))
+            .one(&target)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(child.parent_id, Some(1));
+        let linked_games = GameCollectionLink::find()
+            .filter(game_collection_link::Column::CollectionId.eq(child.id))
+            .count(&target)
+            .await
+            .unwrap();
+        assert_eq!(linked_games, 2);
+
+        let replaced =
+            import_library_from_reader(&target, Cursor::new(bytes), Some(ImportMode::Replace))
+                .await
+                .unwrap();
+        assert_eq!(replaced.inserted.games, 2);
+        assert_eq!(replaced.skipped, LibraryCounts::default());
+        assert_eq!(Games::find().count(&target).await.unwrap(), 2);
+        assert_eq!(Collections::find().count(&target).await.unwrap(), 2);
+        assert_eq!(GameSessions::find().count(&target).await.unwrap(), 2);
+    }
+}
@@ -0,0 +1,168 @@
+//! 启动时执行数据库迁移前后的保护措施。
+//!
+//! 有待执行的迁移时，先用 `VACUUM INTO` 将数据库快照写入数据库备份目录，迁移完成后运行
+//! `PRAGMA integrity_check`。检查结果保存在应用状态中，前端通过
+//! `get_migration_integrity_report` 读取并提示用户使用迁移前的备份恢复。
+
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, DbErr, Statement};
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{State, command};
+
+/// 本次启动的迁移保护结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationIntegrityReport {
+    /// 本次启动执行的迁移数量
+    pub executed_migrations: usize,
+    /// 迁移前的数据库备份，没有待执行的迁移时为 None
+    pub backup_path: Option<String>,
+    /// 迁移前备份失败的原因，备份失败不会阻止迁移
+    pub backup_error: Option<String>,
+    /// 迁移后 `PRAGMA integrity_check` 的结果，未执行迁移时为空
+    pub integrity_check: Vec<String>,
+    /// 完整性检查是否通过，未执行迁移时为 true
+    pub integrity_ok: bool,
+}
+
+/// 获取本次启动的迁移保护结果
+///
+/// `integrity_ok` 为 false 时应提示用户数据库可能已损坏，并给出 `backup_path`
+#[command]
+pub fn get_migration_integrity_report(
+    report: State<'_, MigrationIntegrityReport>,
+) -> MigrationIntegrityReport {
+    report.inner().clone()
+}
+
+/// 备份数据库后执行迁移，并在迁移后检查数据库完整性
+///
+/// 迁移失败时返回的错误中包含迁移前备份的路径；完整性检查失败不视为错误，
+/// 记录在返回的报告中。
+pub async fn run_guarded_migrations(
+    conn: &DatabaseConnection,
+) -> Result<MigrationIntegrityReport, String> {
+    let pending = Migrator::get_pending_migrations(conn)
+        .await
+        .map_err(|e| format!("读取待执行迁移失败: {}", e))?
+        .len();
+    if pending == 0 {
+        log::debug!("没有待执行的数据库迁移");
+        return Ok(MigrationIntegrityReport {
+            integrity_ok: true,
+            ..Default::default()
+        });
+    }
+
+    let mut report = MigrationIntegrityReport {
+        executed_migrations: pending,
+        ..Default::default()
+    };
+    match backup_before_migrations(conn).await {
+        Ok(Some(path)) => {
+            log::info!("迁移前数据库备份完成: {}", path.display());
+            report.backup_path = Some(path.to_string_lossy().to_string());
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::warn!("迁移前数据库备份失败，继续执行迁移: {}", e);
+            report.backup_error = Some(e);
+        }
+    }
+
+    log::debug!("开始执行数据库迁移，待执行 {} 个", pending);
+    if let Err(e) = Migrator::up(conn, None).await {
+        return Err(match &report.backup_path {
+            Some(path) => format!("{}（迁移前备份: {}）", e, path),
+            None => e.to_string(),
+        });
+    }
+    log::info!("数据库迁移完成");
+
+    report.integrity_check = check_integrity(conn)
+        .await
+        .unwrap_or_else(|e| vec![format!("完整性检查执行失败: {}", e)]);
+    report.integrity_ok = is_integrity_ok(&report.integrity_check);
+    if !report.integrity_ok {
+        log::error!(
+            "迁移后数据库完整性检查失败: {}",
+            report.integrity_check.join("; ")
+        );
+    }
+    Ok(report)
+}
+
+/// 执行 `PRAGMA integrity_check`，正常时返回 `["ok"]`
+pub async fn check_integrity(conn: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+    Ok(conn
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "PRAGMA integrity_check",
+        ))
+        .await?
+        .iter()
+        .filter_map(|row| row.try_get_by_index::<String>(0).ok())
+        .collect())
+}
+
+fn is_integrity_ok(result: &[String]) -> bool {
+    matches!(result, [line] if line.eq_ignore_ascii_case("ok"))
+}
+
+/// 用 `VACUUM INTO` 将数据库快照写入数据库备份目录，首次启动（尚未执行过迁移）时不备份
+///
+/// 快照通过当前连接生成，包含 WAL 中尚未写回主文件的内容，不受其他连接写入影响。
+/// 直接读取 `user.db_backup_path`，而不是通过实体查询设置，
+/// 因为待执行的迁移可能正要修改 user 表。
+async fn backup_before_migrations(conn: &DatabaseConnection) -> Result<Option<PathBuf>, String> {
+    let applied = Migrator::get_applied_migrations(conn)
+        .await
+        .map_err(|e| format!("读取已应用迁移失败: {}", e))?;
+    if applied.is_empty() {
+        return Ok(None);
+    }
+
+    let custom_dir = conn
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "SELECT db_backup_path FROM user LIMIT 1",
+        ))
+        .await
+        .ok()
+        .flatten()
+        .and_then(|row| row.try_get_by_index::<Option<String>>(0).ok().flatten())
+        .filter(|path| !path.trim().is_empty());
+    let backup_dir = match custom_dir {
+        Some(path) => PathBuf::from(path.trim()),
+        None => reina_path::get_default_db_backup_path()?,
+    };
+    fs::create_dir_all(&backup_dir).map_err(|e| format!("无法创建备份目录: {}", e))?;
+
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let backup_path = backup_dir.join(format!("reina_manager_premigration_{}.db", timestamp));
+    conn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "VACUUM INTO ?",
+        [backup_path.to_string_lossy().to_string().into()],
+    ))
+    .await
+    .map_err(|e| format!("创建数据库快照失败: {}", e))?;
+
+    Ok(Some(backup_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrity_result_is_ok_only_for_single_ok_row() {
+        assert!(is_integrity_ok(&["ok".to_string()]));
+        assert!(!is_integrity_ok(&[]));
+        assert!(!is_integrity_ok(&[
+            "row 3 missing from index idx_games_name".to_string()
+        ]));
+        assert!(!is_integrity_ok(&["ok".to_string(), "ok".to_string()]));
+    }
+}
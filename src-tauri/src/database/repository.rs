@@ -1,4 +1,5 @@
 pub mod collections_repository;
+pub mod game_media_repository;
 pub mod game_stats_repository;
 pub mod games_repository;
 pub mod settings_repository;
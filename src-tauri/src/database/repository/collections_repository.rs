@@ -1,4 +1,5 @@
 use crate::database::dto::{InsertCollectionData, UpdateCollectionData};
+use crate::database::repository::games_repository::{SortOption, SortOrder};
 use crate::entity::prelude::*;
 use crate::entity::{collections, game_collection_link};
 use sea_orm::{sea_query::Expr, *};
@@ -17,6 +18,34 @@ pub struct CategoryWithCount {
     pub game_count: u64,
 }
 
+/// 新建的合集及其层级：根合集为分组（depth 0），分组下的为分类（depth 1）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionWithDepth {
+    #[serde(flatten)]
+    pub collection: collections::Model,
+    pub depth: u8,
+    pub is_group: bool,
+}
+
+/// 在合集间移动游戏的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MoveGamesResult {
+    pub moved: u64,
+    /// 不在源合集中而跳过的游戏数量
+    pub skipped: u64,
+}
+
+/// 合并合集的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MergeCollectionsResult {
+    /// 追加到保留合集的游戏数量
+    pub added: u64,
+    /// 已在保留合集中而跳过的游戏数量
+    pub skipped: u64,
+    /// 合并分组时移动到保留分组下的分类数量
+    pub moved_children: u64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct GameCollectionPair {
     game_id: i32,
@@ -236,10 +265,24 @@ impl CollectionsRepository {
     // ==================== 合集 CRUD 操作 ====================
 
     /// 创建合集
+    ///
+    /// 合集只有分组、分类两级：父级必须是根合集，否则返回校验错误
     pub async fn create(
         db: &DatabaseConnection,
         data: InsertCollectionData,
-    ) -> Result<collections::Model, DbErr> {
+    ) -> Result<CollectionWithDepth, DbErr> {
+        if let Some(parent_id) = data.parent_id {
+            let parent = Collections::find_by_id(parent_id)
+                .one(db)
+                .await?
+                .ok_or_else(|| DbErr::Custom(format!("父合集不存在: {}", parent_id)))?;
+            if parent.parent_id.is_some() {
+                return Err(DbErr::Custom(format!(
+                    "不能在分类「{}」下创建合集，合集最多两级",
+                    parent.name
+                )));
+            }
+        }
         let now = chrono::Utc::now().timestamp() as i32;
 
         let collection = collections::ActiveModel {
@@ -250,9 +293,25 @@ impl CollectionsRepository {
             icon: Set(data.icon),
             created_at: Set(Some(now)),
             updated_at: Set(Some(now)),
+            view_sort_option: Set(None),
+            view_sort_order: Set(None),
         };
 
-        collection.insert(db).await
+        let collection = collection.insert(db).await?;
+        let is_group = collection.parent_id.is_none();
+        Ok(CollectionWithDepth {
+            collection,
+            depth: if is_group { 0 } else { 1 },
+            is_group,
+        })
+    }
+
+    /// 根据 ID 获取合集
+    pub async fn find_by_id(
+        db: &DatabaseConnection,
+        id: i32,
+    ) -> Result<Option<collections::Model>, DbErr> {
+        Collections::find_by_id(id).one(db).await
     }
 
     /// 获取根合集（parent_id 为 NULL）
@@ -309,6 +368,51 @@ impl CollectionsRepository {
         active.update(db).await
     }
 
+    /// 设置合集视图的排序方式，`None` 表示清除并恢复为合集内的手动顺序
+    pub async fn set_view_sort(
+        db: &DatabaseConnection,
+        id: i32,
+        sort: Option<(SortOption, SortOrder)>,
+    ) -> Result<collections::Model, DbErr> {
+        let existing = Collections::find_by_id(id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Collection not found".to_string()))?;
+
+        let (sort_option, sort_order) = match sort {
+            Some((option, order)) => (
+                Some(Self::enum_to_text(option)?),
+                Some(Self::enum_to_text(order)?),
+            ),
+            None => (None, None),
+        };
+        let mut active: collections::ActiveModel = existing.into();
+        active.view_sort_option = Set(sort_option);
+        active.view_sort_order = Set(sort_order);
+        active.updated_at = Set(Some(chrono::Utc::now().timestamp() as i32));
+
+        active.update(db).await
+    }
+
+    /// 读取合集保存的排序方式，未设置或无法识别时返回 `None`
+    pub fn view_sort(collection: &collections::Model) -> Option<(SortOption, SortOrder)> {
+        let option = Self::text_to_enum(collection.view_sort_option.as_deref()?)?;
+        let order = Self::text_to_enum(collection.view_sort_order.as_deref()?)?;
+        Some((option, order))
+    }
+
+    /// 与前端传参一致，按 serde 的小写名称保存
+    fn enum_to_text<T: Serialize>(value: T) -> Result<String, DbErr> {
+        match serde_json::to_value(value) {
+            Ok(serde_json::Value::String(text)) => Ok(text),
+            _ => Err(DbErr::Custom("排序方式序列化失败".to_string())),
+        }
+    }
+
+    fn text_to_enum<T: for<'de> Deserialize<'de>>(text: &str) -> Option<T> {
+        serde_json::from_value(serde_json::Value::String(text.to_string())).ok()
+    }
+
     /// 删除合集（会级联删除子合集和游戏关联）
     pub async fn delete(db: &DatabaseConnection, id: i32) -> Result<DeleteResult, DbErr> {
         Collections::delete_by_id(id).exec(db).await
@@ -425,6 +529,166 @@ impl CollectionsRepository {
         Ok(())
     }
 
+    /// 在同一事务内将游戏从源合集移动到目标合集
+    ///
+    /// 不在源合集中的游戏会被跳过；已在目标合集中的游戏只从源合集移除，
+    /// 其余游戏追加到目标合集末尾。
+    pub async fn move_games_between_collections(
+        db: &DatabaseConnection,
+        game_ids: Vec<i32>,
+        from_collection_id: i32,
+        to_collection_id: i32,
+    ) -> Result<MoveGamesResult, DbErr> {
+        if from_collection_id == to_collection_id {
+            return Err(DbErr::Custom("源合集与目标合集相同".to_string()));
+        }
+        let game_ids = Self::unique_ids(game_ids);
+        if game_ids.is_empty() {
+            return Ok(MoveGamesResult {
+                moved: 0,
+                skipped: 0,
+            });
+        }
+
+        let txn = db.begin().await?;
+        let source_links = GameCollectionLink::find()
+            .filter(game_collection_link::Column::CollectionId.eq(from_collection_id))
+            .filter(game_collection_link::Column::GameId.is_in(game_ids.clone()))
+            .all(&txn)
+            .await?;
+        let in_source = source_links
+            .iter()
+            .map(|link| link.game_id)
+            .collect::<std::collections::HashSet<_>>();
+        let to_move = game_ids
+            .iter()
+            .copied()
+            .filter(|game_id| in_source.contains(game_id))
+            .collect::<Vec<_>>();
+
+        Self::delete_game_collection_links(&txn, source_links.iter().map(|link| link.id).collect())
+            .await?;
+
+        let in_target = GameCollectionLink::find()
+            .filter(game_collection_link::Column::CollectionId.eq(to_collection_id))
+            .filter(game_collection_link::Column::GameId.is_in(to_move.clone()))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|link| link.game_id)
+            .collect::<std::collections::HashSet<_>>();
+        let pairs = to_move
+            .iter()
+            .filter(|game_id| !in_target.contains(game_id))
+            .map(|game_id| GameCollectionPair {
+                game_id: *game_id,
+                collection_id: to_collection_id,
+            })
+            .collect();
+        let inserts = Self::build_append_inserts(&txn, pairs).await?;
+        Self::insert_game_collection_links(&txn, inserts).await?;
+
+        txn.commit().await?;
+        Ok(MoveGamesResult {
+            moved: to_move.len() as u64,
+            skipped: (game_ids.len() - to_move.len()) as u64,
+        })
+    }
+
+    /// 在同一事务内将合集 `remove_id` 合并到 `keep_id` 并删除 `remove_id`
+    ///
+    /// 两者必须同为分组或同为分类。被删除合集中的游戏按原顺序追加到保留合集末尾，
+    /// 已存在的游戏跳过；合并分组时，其下的分类按原顺序移动到保留分组末尾。
+    pub async fn merge_collections(
+        db: &DatabaseConnection,
+        keep_id: i32,
+        remove_id: i32,
+    ) -> Result<MergeCollectionsResult, DbErr> {
+        if keep_id == remove_id {
+            return Err(DbErr::Custom("不能将合集合并到自身".to_string()));
+        }
+
+        let txn = db.begin().await?;
+        let keep = Collections::find_by_id(keep_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::Custom(format!("合集不存在: {}", keep_id)))?;
+        let remove = Collections::find_by_id(remove_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::Custom(format!("合集不存在: {}", remove_id)))?;
+        if keep.parent_id.is_none() != remove.parent_id.is_none() {
+            return Err(DbErr::Custom(format!(
+                "「{}」与「{}」层级不同，只能合并同为分组或同为分类的合集",
+                keep.name, remove.name
+            )));
+        }
+
+        let remove_links = GameCollectionLink::find()
+            .filter(game_collection_link::Column::CollectionId.eq(remove_id))
+            .order_by_asc(game_collection_link::Column::SortOrder)
+            .order_by_asc(game_collection_link::Column::Id)
+            .all(&txn)
+            .await?;
+        let in_keep = GameCollectionLink::find()
+            .filter(game_collection_link::Column::CollectionId.eq(keep_id))
+            .filter(
+                game_collection_link::Column::GameId
+                    .is_in(remove_links.iter().map(|link| link.game_id)),
+            )
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|link| link.game_id)
+            .collect::<std::collections::HashSet<_>>();
+        let pairs = remove_links
+            .iter()
+            .filter(|link| !in_keep.contains(&link.game_id))
+            .map(|link| GameCollectionPair {
+                game_id: link.game_id,
+                collection_id: keep_id,
+            })
+            .collect::<Vec<_>>();
+        let added = pairs.len() as u64;
+        Self::delete_game_collection_links(&txn, remove_links.iter().map(|link| link.id).collect())
+            .await?;
+        let inserts = Self::build_append_inserts(&txn, pairs).await?;
+        Self::insert_game_collection_links(&txn, inserts).await?;
+
+        let mut moved_children = 0;
+        if remove.parent_id.is_none() {
+            let next_order = Collections::find()
+                .filter(collections::Column::ParentId.eq(keep_id))
+                .order_by_desc(collections::Column::SortOrder)
+                .one(&txn)
+                .await?
+                .map_or(0, |child| child.sort_order + 1);
+            let children = Collections::find()
+                .filter(collections::Column::ParentId.eq(remove_id))
+                .order_by_asc(collections::Column::SortOrder)
+                .order_by_asc(collections::Column::Id)
+                .all(&txn)
+                .await?;
+            let now = chrono::Utc::now().timestamp() as i32;
+            for (offset, child) in children.into_iter().enumerate() {
+                let mut active: collections::ActiveModel = child.into();
+                active.parent_id = Set(Some(keep_id));
+                active.sort_order = Set(next_order + offset as i32);
+                active.updated_at = Set(Some(now));
+                active.update(&txn).await?;
+                moved_children += 1;
+            }
+        }
+
+        Collections::delete_by_id(remove_id).exec(&txn).await?;
+        txn.commit().await?;
+        Ok(MergeCollectionsResult {
+            added,
+            skipped: remove_links.len() as u64 - added,
+            moved_children,
+        })
+    }
+
     /// 批量更新分类中的游戏列表（差异计算优化版）
     /// 将分类中的游戏完全替换为 game_ids
     ///
@@ -572,3 +836,231 @@ impl CollectionsRepository {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::Database;
+
+    async fn setup_database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("内存数据库应连接成功");
+        db.execute_unprepared(
+            r#"CREATE TABLE collections (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                parent_id INTEGER,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                icon TEXT,
+                created_at INTEGER,
+                updated_at INTEGER,
+                view_sort_option TEXT,
+                view_sort_order TEXT
+            )"#,
+        )
+        .await
+        .expect("应创建 collections 表");
+        db.execute_unprepared(
+            r#"CREATE TABLE game_collection_link (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                game_id INTEGER NOT NULL,
+                collection_id INTEGER NOT NULL,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER,
+                UNIQUE (game_id, collection_id)
+            )"#,
+        )
+        .await
+        .expect("应创建 game_collection_link 表");
+        db
+    }
+
+    #[tokio::test]
+    async fn view_sort_round_trips_and_clears() {
+        let db = setup_database().await;
+        let collection = CollectionsRepository::create(
+            &db,
+            InsertCollectionData {
+                name: "排行".to_string(),
+                parent_id: None,
+                sort_order: 0,
+                icon: None,
+            },
+        )
+        .await
+        .expect("应创建合集")
+        .collection;
+        assert!(CollectionsRepository::view_sort(&collection).is_none());
+
+        let updated = CollectionsRepository::set_view_sort(
+            &db,
+            collection.id,
+            Some((SortOption::UserRatingRank, SortOrder::Desc)),
+        )
+        .await
+        .expect("应保存排序方式");
+        assert_eq!(updated.view_sort_option.as_deref(), Some("userratingrank"));
+        assert!(matches!(
+            CollectionsRepository::view_sort(&updated),
+            Some((SortOption::UserRatingRank, SortOrder::Desc))
+        ));
+
+        let cleared = CollectionsRepository::set_view_sort(&db, collection.id, None)
+            .await
+            .expect("应清除排序方式");
+        assert!(cleared.view_sort_option.is_none() && cleared.view_sort_order.is_none());
+    }
+
+    #[tokio::test]
+    async fn create_reports_depth_and_rejects_third_level() {
+        let db = setup_database().await;
+        let insert = |name: &str, parent_id| InsertCollectionData {
+            name: name.to_string(),
+            parent_id,
+            sort_order: 0,
+            icon: None,
+        };
+
+        let group = CollectionsRepository::create(&db, insert("分组", None))
+            .await
+            .expect("应创建分组");
+        assert_eq!((group.depth, group.is_group), (0, true));
+
+        let category =
+            CollectionsRepository::create(&db, insert("分类", Some(group.collection.id)))
+                .await
+                .expect("应创建分类");
+        assert_eq!((category.depth, category.is_group), (1, false));
+
+        let error =
+            CollectionsRepository::create(&db, insert("第三级", Some(category.collection.id)))
+                .await
+                .expect_err("不应允许第三级合集");
+        assert!(error.to_string().contains("最多两级"));
+        assert!(
+            CollectionsRepository::create(&db, insert("孤儿", Some(999)))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn move_games_skips_missing_and_keeps_target_unique() {
+        let db = setup_database().await;
+        // 源合集 1: 游戏 10, 11, 12；目标合集 2: 游戏 12, 20
+        CollectionsRepository::update_category_games(&db, vec![10, 11, 12], 1)
+            .await
+            .expect("应写入源合集");
+        CollectionsRepository::update_category_games(&db, vec![12, 20], 2)
+            .await
+            .expect("应写入目标合集");
+
+        let result =
+            CollectionsRepository::move_games_between_collections(&db, vec![10, 12, 99, 10], 1, 2)
+                .await
+                .expect("应移动游戏");
+        assert_eq!(
+            result,
+            MoveGamesResult {
+                moved: 2,
+                skipped: 1,
+            }
+        );
+        assert_eq!(
+            CollectionsRepository::get_games_in_collection(&db, 1)
+                .await
+                .unwrap(),
+            vec![11]
+        );
+        assert_eq!(
+            CollectionsRepository::get_games_in_collection(&db, 2)
+                .await
+                .unwrap(),
+            vec![12, 20, 10]
+        );
+        assert!(
+            CollectionsRepository::move_games_between_collections(&db, vec![11], 1, 1)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_appends_unique_games_and_rejects_mixed_levels() {
+        let db = setup_database().await;
+        let insert = |name: &str, parent_id| InsertCollectionData {
+            name: name.to_string(),
+            parent_id,
+            sort_order: 0,
+            icon: None,
+        };
+        let create = |data| CollectionsRepository::create(&db, data);
+        let group_a = create(insert("A", None)).await.unwrap().collection.id;
+        let group_b = create(insert("B", None)).await.unwrap().collection.id;
+        let keep = create(insert("保留", Some(group_a)))
+            .await
+            .unwrap()
+            .collection
+            .id;
+        let remove = create(insert("删除", Some(group_b)))
+            .await
+            .unwrap()
+            .collection
+            .id;
+        CollectionsRepository::update_category_games(&db, vec![1, 2], keep)
+            .await
+            .unwrap();
+        CollectionsRepository::update_category_games(&db, vec![3, 2, 4], remove)
+            .await
+            .unwrap();
+
+        assert!(
+            CollectionsRepository::merge_collections(&db, group_a, keep)
+                .await
+                .is_err()
+        );
+        let result = CollectionsRepository::merge_collections(&db, keep, remove)
+            .await
+            .expect("应合并分类");
+        assert_eq!(
+            result,
+            MergeCollectionsResult {
+                added: 2,
+                skipped: 1,
+                moved_children: 0,
+            }
+        );
+        assert_eq!(
+            CollectionsRepository::get_games_in_collection(&db, keep)
+                .await
+                .unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        assert!(
+            CollectionsRepository::find_by_id(&db, remove)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let other = create(insert("其他", Some(group_b)))
+            .await
+            .unwrap()
+            .collection
+            .id;
+        let result = CollectionsRepository::merge_collections(&db, group_a, group_b)
+            .await
+            .expect("应合并分组");
+        assert_eq!(result.moved_children, 1);
+        assert_eq!(
+            CollectionsRepository::find_children(&db, group_a)
+                .await
+                .unwrap()
+                .iter()
+                .map(|child| child.id)
+                .collect::<Vec<_>>(),
+            vec![keep, other]
+        );
+    }
+}
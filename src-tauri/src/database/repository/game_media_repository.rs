@@ -0,0 +1,198 @@
+use crate::entity::game_media;
+use crate::entity::prelude::*;
+use sea_orm::{sea_query::Expr, *};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 媒体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameMediaType {
+    Screenshot,
+    Cover,
+    Other,
+}
+
+impl GameMediaType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Screenshot => "screenshot",
+            Self::Cover => "cover",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// 游戏媒体（截图等）数据仓库
+pub struct GameMediaRepository;
+
+impl GameMediaRepository {
+    /// 按顺序获取游戏的全部媒体
+    pub async fn find_by_game(
+        db: &DatabaseConnection,
+        game_id: i32,
+    ) -> Result<Vec<game_media::Model>, DbErr> {
+        GameMedia::find()
+            .filter(game_media::Column::GameId.eq(game_id))
+            .order_by_asc(game_media::Column::SortOrder)
+            .order_by_asc(game_media::Column::Id)
+            .all(db)
+            .await
+    }
+
+    /// 添加媒体，排在该游戏现有媒体之后
+    pub async fn add(
+        db: &DatabaseConnection,
+        game_id: i32,
+        file_name: String,
+        media_type: GameMediaType,
+    ) -> Result<game_media::Model, DbErr> {
+        let max_sort_order = GameMedia::find()
+            .select_only()
+            .column_as(
+                Expr::col(game_media::Column::SortOrder).max(),
+                "max_sort_order",
+            )
+            .filter(game_media::Column::GameId.eq(game_id))
+            .into_tuple::<Option<i32>>()
+            .one(db)
+            .await?
+            .flatten();
+
+        game_media::ActiveModel {
+            id: NotSet,
+            game_id: Set(game_id),
+            file_name: Set(file_name),
+            media_type: Set(media_type.as_str().to_string()),
+            sort_order: Set(max_sort_order.map_or(0, |max| max + 1)),
+            created_at: Set(Some(chrono::Utc::now().timestamp() as i32)),
+        }
+        .insert(db)
+        .await
+    }
+
+    /// 删除媒体记录，返回被删除的记录；不存在时返回 None
+    pub async fn remove(
+        db: &DatabaseConnection,
+        media_id: i32,
+    ) -> Result<Option<game_media::Model>, DbErr> {
+        let Some(media) = GameMedia::find_by_id(media_id).one(db).await? else {
+            return Ok(None);
+        };
+        GameMedia::delete_by_id(media_id).exec(db).await?;
+        Ok(Some(media))
+    }
+
+    /// 按给定 ID 顺序重排游戏的媒体
+    ///
+    /// `media_ids` 必须恰好包含该游戏的全部媒体
+    pub async fn reorder(
+        db: &DatabaseConnection,
+        game_id: i32,
+        media_ids: Vec<i32>,
+    ) -> Result<(), DbErr> {
+        let txn = db.begin().await?;
+        let current: HashSet<i32> = GameMedia::find()
+            .select_only()
+            .column(game_media::Column::Id)
+            .filter(game_media::Column::GameId.eq(game_id))
+            .into_tuple::<i32>()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+        let requested: HashSet<i32> = media_ids.iter().copied().collect();
+        if requested.len() != media_ids.len() || requested != current {
+            return Err(DbErr::Custom(
+                "排序列表必须恰好包含该游戏的全部媒体".to_string(),
+            ));
+        }
+
+        for (index, media_id) in media_ids.into_iter().enumerate() {
+            GameMedia::update_many()
+                .col_expr(game_media::Column::SortOrder, Expr::value(index as i32))
+                .filter(game_media::Column::Id.eq(media_id))
+                .exec(&txn)
+                .await?;
+        }
+
+        txn.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::Database;
+
+    #[tokio::test]
+    async fn media_is_appended_reordered_and_removed() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("内存数据库应连接成功");
+        db.execute_unprepared(
+            r#"CREATE TABLE game_media (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                game_id INTEGER NOT NULL,
+                file_name TEXT NOT NULL,
+                media_type TEXT NOT NULL,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER
+            )"#,
+        )
+        .await
+        .expect("应创建 game_media 表");
+
+        let mut ids = Vec::new();
+        for name in ["a.png", "b.png", "c.png"] {
+            let media =
+                GameMediaRepository::add(&db, 1, name.to_string(), GameMediaType::Screenshot)
+                    .await
+                    .expect("应添加媒体");
+            ids.push(media.id);
+        }
+        GameMediaRepository::add(&db, 2, "other.png".to_string(), GameMediaType::Other)
+            .await
+            .expect("应添加其他游戏的媒体");
+
+        let names = |media: Vec<game_media::Model>| {
+            media
+                .into_iter()
+                .map(|item| item.file_name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(GameMediaRepository::find_by_game(&db, 1).await.unwrap()),
+            ["a.png", "b.png", "c.png"]
+        );
+
+        GameMediaRepository::reorder(&db, 1, vec![ids[2], ids[0], ids[1]])
+            .await
+            .expect("应重排媒体");
+        assert_eq!(
+            names(GameMediaRepository::find_by_game(&db, 1).await.unwrap()),
+            ["c.png", "a.png", "b.png"]
+        );
+        assert!(
+            GameMediaRepository::reorder(&db, 1, vec![ids[0], ids[1]])
+                .await
+                .is_err()
+        );
+
+        let removed = GameMediaRepository::remove(&db, ids[0])
+            .await
+            .unwrap()
+            .expect("应返回被删除的媒体");
+        assert_eq!(removed.file_name, "a.png");
+        assert!(
+            GameMediaRepository::remove(&db, ids[0])
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            names(GameMediaRepository::find_by_game(&db, 1).await.unwrap()),
+            ["c.png", "b.png"]
+        );
+    }
+}
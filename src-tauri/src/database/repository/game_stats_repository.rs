@@ -1,6 +1,8 @@
+use crate::database::repository::games_repository::PlayStatus;
+use crate::database::retry::with_write_retry;
 use crate::entity::prelude::*;
-use crate::entity::{game_sessions, game_statistics};
-use chrono::{Local, LocalResult, NaiveTime, TimeZone};
+use crate::entity::{game_sessions, game_statistics, games};
+use chrono::{Local, LocalResult, NaiveDate, NaiveTime, TimeZone};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -32,6 +34,144 @@ pub struct GameLastPlayed {
     pub last_played: Option<i32>,
 }
 
+/// 全库活跃度热力图的单日数据
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub total_playtime: i32,
+    pub games_played: i32,
+}
+
+/// 全库完成情况，不含已归档游戏
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompletionStats {
+    pub total_games: i64,
+    /// 状态为“玩过”的游戏
+    pub cleared: i64,
+    /// 未通关但有游玩会话
+    pub in_progress: i64,
+    /// 未通关且没有游玩会话
+    pub untouched: i64,
+    /// 通关百分比（0-100），没有游戏时为 0
+    pub cleared_percent: f64,
+}
+
+/// 单个游戏在某一时间点之后的游玩时长
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromQueryResult)]
+pub struct GamePlaytimeSince {
+    pub game_id: i32,
+    pub seconds: i64,
+    pub session_count: i64,
+}
+
+/// 全库在某一时间点之后的游玩时长
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlaytimeSince {
+    pub total_seconds: i64,
+    /// 按游玩时长降序
+    pub games: Vec<GamePlaytimeSince>,
+}
+
+/// 导入的单条游玩会话，时间为 Unix 秒，`duration` 单位为分钟，缺省时按起止时间计算
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ImportedSession {
+    pub start_time: i64,
+    pub end_time: i64,
+    #[serde(default)]
+    pub duration: Option<i64>,
+}
+
+/// 解析会话导入内容：JSON 数组或带表头（`start_time,end_time[,duration]`）的 CSV
+///
+/// 出错时返回的信息包含源内容中的行号（从 1 开始，空行与表头均计入），便于用户修正
+pub fn parse_imported_sessions(content: &str) -> Result<Vec<ImportedSession>, String> {
+    let content = content.trim_start_matches('\u{feff}');
+    if content.trim_start().starts_with('[') {
+        return serde_json::from_str(content).map_err(|e| {
+            format!(
+                "第 {} 行第 {} 列 JSON 格式错误: {}",
+                e.line(),
+                e.column(),
+                e
+            )
+        });
+    }
+
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines.next().ok_or_else(|| "导入内容为空".to_string())?;
+    let header: Vec<String> = header
+        .split(',')
+        .map(|column| column.trim().to_ascii_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let (Some(start_column), Some(end_column)) = (column("start_time"), column("end_time")) else {
+        return Err("CSV 表头需包含 start_time 与 end_time 列".to_string());
+    };
+    let duration_column = column("duration");
+
+    lines
+        .map(|(line_number, line)| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |position: usize, name: &str| -> Result<i64, String> {
+                fields
+                    .get(position)
+                    .ok_or_else(|| format!("第 {} 行缺少 {}", line_number, name))?
+                    .parse()
+                    .map_err(|_| format!("第 {} 行 {} 不是整数", line_number, name))
+            };
+            Ok(ImportedSession {
+                start_time: field(start_column, "start_time")?,
+                end_time: field(end_column, "end_time")?,
+                duration: match duration_column {
+                    Some(position) if fields.get(position).is_some_and(|v| !v.is_empty()) => {
+                        Some(field(position, "duration")?)
+                    }
+                    _ => None,
+                },
+            })
+        })
+        .collect()
+}
+
+/// 校验导入会话并换算为 `(start_time, end_time, duration)`
+///
+/// 要求 `end_time >= start_time`；时长缺省时按起止时间计算（向上取整到分钟），
+/// 提供时不得超过起止时间跨度
+fn validate_imported_sessions(sessions: &[ImportedSession]) -> Result<Vec<(i32, i32, i32)>, DbErr> {
+    sessions
+        .iter()
+        .enumerate()
+        .map(|(index, session)| {
+            let invalid =
+                |reason: &str| custom_error(format!("第 {} 条会话无效: {}", index + 1, reason));
+            let start_time = i32::try_from(session.start_time)
+                .ok()
+                .filter(|time| *time > 0)
+                .ok_or_else(|| invalid("开始时间超出范围"))?;
+            let end_time =
+                i32::try_from(session.end_time).map_err(|_| invalid("结束时间超出范围"))?;
+            if end_time < start_time {
+                return Err(invalid("结束时间早于开始时间"));
+            }
+
+            let span_minutes = i64::from(end_time - start_time).div_ceil(60);
+            let duration = session.duration.unwrap_or(span_minutes);
+            if duration <= 0 {
+                return Err(invalid("游玩时长必须大于零"));
+            }
+            if duration > span_minutes {
+                return Err(invalid("游玩时长超过起止时间跨度"));
+            }
+
+            Ok((start_time, end_time, duration as i32))
+        })
+        .collect()
+}
+
 fn custom_error(message: impl Into<String>) -> DbErr {
     DbErr::Custom(message.into())
 }
@@ -200,6 +340,59 @@ fn daily_stats_map(daily_stats: &[DailyStats]) -> Result<BTreeMap<String, i32>,
     Ok(result)
 }
 
+/// 热力图单次查询最多覆盖的天数（约五年），避免生成过大的稠密结果
+const MAX_HEATMAP_DAYS: i64 = 366 * 5;
+
+/// 校验热力图日期区间：开始日期不晚于结束日期，且跨度不超过 `MAX_HEATMAP_DAYS`
+fn validate_heatmap_range(start_date: NaiveDate, end_date: NaiveDate) -> Result<(), DbErr> {
+    if start_date > end_date {
+        return Err(custom_error("开始日期不能晚于结束日期"));
+    }
+    let days = (end_date - start_date).num_days() + 1;
+    if days > MAX_HEATMAP_DAYS {
+        return Err(custom_error(format!(
+            "日期区间不能超过 {} 天（当前 {} 天）",
+            MAX_HEATMAP_DAYS, days
+        )));
+    }
+    Ok(())
+}
+
+/// 合并各游戏的每日统计，生成包含区间内每一天的稠密热力图数据
+fn build_activity_heatmap(
+    games_daily_stats: &[Vec<DailyStats>],
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Vec<HeatmapDay> {
+    let start = start_date.format("%Y-%m-%d").to_string();
+    let end = end_date.format("%Y-%m-%d").to_string();
+    let mut totals: BTreeMap<String, (i32, i32)> = BTreeMap::new();
+    for daily_stats in games_daily_stats {
+        for item in daily_stats {
+            if item.playtime <= 0 || item.date < start || item.date > end {
+                continue;
+            }
+            let (playtime, games) = totals.entry(item.date.clone()).or_default();
+            *playtime = playtime.saturating_add(item.playtime);
+            *games += 1;
+        }
+    }
+
+    start_date
+        .iter_days()
+        .take_while(|date| *date <= end_date)
+        .map(|date| {
+            let date = date.format("%Y-%m-%d").to_string();
+            let (total_playtime, games_played) = totals.get(&date).copied().unwrap_or_default();
+            HeatmapDay {
+                date,
+                total_playtime,
+                games_played,
+            }
+        })
+        .collect()
+}
+
 fn sorted_daily_stats(daily_stats: BTreeMap<String, i32>) -> Vec<DailyStats> {
     daily_stats
         .into_iter()
@@ -371,25 +564,37 @@ impl GameStatsRepository {
         duration: i32,
     ) -> Result<game_sessions::Model, DbErr> {
         let date = local_date_from_timestamp(end_time)?;
-        let transaction = db.begin().await?;
-        let session =
-            Self::insert_session(&transaction, game_id, start_time, end_time, duration, date)
+        with_write_retry(|| {
+            let date = date.clone();
+            async move {
+                let transaction = db.begin().await?;
+                let session = Self::insert_session(
+                    &transaction,
+                    game_id,
+                    start_time,
+                    end_time,
+                    duration,
+                    date,
+                )
                 .await?;
 
-        let projection = match Self::get_projection(&transaction, game_id).await {
-            Ok(Some(mut projection)) => {
-                if apply_session_insert(&mut projection, &session, &Local).is_ok() {
-                    projection
-                } else {
-                    Self::calculate_projection(&transaction, game_id).await?
-                }
-            }
-            Ok(None) | Err(_) => Self::calculate_projection(&transaction, game_id).await?,
-        };
+                let projection = match Self::get_projection(&transaction, game_id).await {
+                    Ok(Some(mut projection)) => {
+                        if apply_session_insert(&mut projection, &session, &Local).is_ok() {
+                            projection
+                        } else {
+                            Self::calculate_projection(&transaction, game_id).await?
+                        }
+                    }
+                    Ok(None) | Err(_) => Self::calculate_projection(&transaction, game_id).await?,
+                };
 
-        Self::upsert_projection(&transaction, game_id, projection).await?;
-        transaction.commit().await?;
-        Ok(session)
+                Self::upsert_projection(&transaction, game_id, projection).await?;
+                transaction.commit().await?;
+                Ok(session)
+            }
+        })
+        .await
     }
 
     /// 根据开始时间和分钟数创建手动会话
@@ -410,6 +615,50 @@ impl GameStatsRepository {
         Self::record_session_with_statistics(db, game_id, start_time, end_time, duration).await
     }
 
+    /// 批量导入游玩会话，并在同一事务内从全部会话重建统计
+    ///
+    /// 任一会话校验失败时整体拒绝，返回导入的会话数量
+    pub async fn import_sessions(
+        db: &DatabaseConnection,
+        game_id: i32,
+        sessions: &[ImportedSession],
+    ) -> Result<usize, DbErr> {
+        if game_id <= 0 {
+            return Err(custom_error("游戏 ID 必须大于零"));
+        }
+        let sessions = validate_imported_sessions(sessions)?;
+        if sessions.is_empty() {
+            return Ok(0);
+        }
+        let models = sessions
+            .iter()
+            .map(|&(start_time, end_time, duration)| {
+                Ok(game_sessions::ActiveModel {
+                    session_id: NotSet,
+                    game_id: Set(game_id),
+                    start_time: Set(start_time),
+                    end_time: Set(end_time),
+                    duration: Set(duration),
+                    date: Set(local_date_from_timestamp(end_time)?),
+                })
+            })
+            .collect::<Result<Vec<_>, DbErr>>()?;
+
+        with_write_retry(|| {
+            let models = models.clone();
+            async move {
+                let transaction = db.begin().await?;
+                GameSessions::insert_many(models).exec(&transaction).await?;
+                let projection = Self::calculate_projection(&transaction, game_id).await?;
+                Self::upsert_projection(&transaction, game_id, projection).await?;
+                transaction.commit().await
+            }
+        })
+        .await?;
+
+        Ok(sessions.len())
+    }
+
     /// 从事实会话重建指定游戏的统计投影
     pub async fn rebuild_statistics(db: &DatabaseConnection, game_id: i32) -> Result<(), DbErr> {
         if game_id <= 0 {
@@ -417,11 +666,33 @@ impl GameStatsRepository {
         }
 
         let transaction = db.begin().await?;
-        let projection = Self::calculate_projection(&transaction, game_id).await?;
-        Self::upsert_projection(&transaction, game_id, projection).await?;
+        Self::rebuild_statistics_in(&transaction, game_id).await?;
         transaction.commit().await
     }
 
+    /// 在调用方的事务中重建统计投影，供合并游戏等需要与其他写入一起提交的操作使用
+    pub(crate) async fn rebuild_statistics_in(
+        transaction: &DatabaseTransaction,
+        game_id: i32,
+    ) -> Result<(), DbErr> {
+        let projection = Self::calculate_projection(transaction, game_id).await?;
+        Self::upsert_projection(transaction, game_id, projection).await
+    }
+
+    /// 重建所有游戏的统计投影，修正增量统计可能积累的偏差，返回处理的游戏数量
+    pub async fn rebuild_all_statistics(db: &DatabaseConnection) -> Result<usize, DbErr> {
+        let game_ids: Vec<i32> = Games::find()
+            .select_only()
+            .column(games::Column::Id)
+            .into_tuple()
+            .all(db)
+            .await?;
+        for game_id in &game_ids {
+            Self::rebuild_statistics(db, *game_id).await?;
+        }
+        Ok(game_ids.len())
+    }
+
     /// 获取游戏会话历史
     pub async fn get_sessions(
         db: &DatabaseConnection,
@@ -438,6 +709,21 @@ impl GameStatsRepository {
             .await
     }
 
+    /// 按开始时间倒序分页获取全库的游玩会话
+    pub async fn get_all_sessions_paged(
+        db: &DatabaseConnection,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<game_sessions::Model>, DbErr> {
+        GameSessions::find()
+            .order_by_desc(game_sessions::Column::StartTime)
+            .order_by_desc(game_sessions::Column::SessionId)
+            .limit(limit)
+            .offset(offset)
+            .all(db)
+            .await
+    }
+
     /// 获取指定游戏范围内的全局最近会话
     pub async fn get_recent_sessions_for_all(
         db: &DatabaseConnection,
@@ -572,12 +858,192 @@ impl GameStatsRepository {
         GameStatistics::find_by_id(game_id).one(db).await
     }
 
+    /// 获取游戏今日（本地时区）的游玩时长（分钟）
+    pub async fn get_today_playtime(db: &DatabaseConnection, game_id: i32) -> Result<i32, DbErr> {
+        let daily_stats = Self::get_statistics(db, game_id)
+            .await?
+            .and_then(|statistics| statistics.daily_stats);
+        let Some(daily_stats) = daily_stats else {
+            return Ok(0);
+        };
+
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        Ok(Self::parse_daily_stats(&daily_stats)
+            .map_err(custom_error)?
+            .into_iter()
+            .find(|item| item.date == today)
+            .map_or(0, |item| item.playtime))
+    }
+
     /// 解析每日统计数据
     fn parse_daily_stats(daily_stats_json: &str) -> Result<Vec<DailyStats>, String> {
         serde_json::from_str(daily_stats_json)
             .map_err(|e| format!("Failed to parse daily_stats: {}", e))
     }
 
+    /// 获取全库活跃度热力图数据（日期闭区间），无游玩的日期以 0 填充
+    pub async fn get_activity_heatmap(
+        db: &DatabaseConnection,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<HeatmapDay>, DbErr> {
+        validate_heatmap_range(start_date, end_date)?;
+
+        let games_daily_stats = GameStatistics::find()
+            .select_only()
+            .column(game_statistics::Column::DailyStats)
+            .filter(game_statistics::Column::DailyStats.is_not_null())
+            .into_tuple::<String>()
+            .all(db)
+            .await?
+            .iter()
+            .map(|daily_stats| Self::parse_daily_stats(daily_stats).map_err(custom_error))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(build_activity_heatmap(
+            &games_daily_stats,
+            start_date,
+            end_date,
+        ))
+    }
+
+    /// 获取指定日期（本地时区）有游玩记录的游戏及当天时长（分钟），按时长降序
+    pub async fn get_playtime_on(
+        db: &DatabaseConnection,
+        date: NaiveDate,
+    ) -> Result<Vec<(i32, i32)>, DbErr> {
+        let rows = GameStatistics::find()
+            .select_only()
+            .column(game_statistics::Column::GameId)
+            .column(game_statistics::Column::DailyStats)
+            .filter(game_statistics::Column::DailyStats.is_not_null())
+            .into_tuple::<(i32, String)>()
+            .all(db)
+            .await?;
+
+        let date = date.format("%Y-%m-%d").to_string();
+        let mut played = Vec::new();
+        for (game_id, daily_stats) in rows {
+            let playtime = Self::parse_daily_stats(&daily_stats)
+                .map_err(custom_error)?
+                .into_iter()
+                .find(|item| item.date == date)
+                .map_or(0, |item| item.playtime);
+            if playtime > 0 {
+                played.push((game_id, playtime));
+            }
+        }
+        played.sort_by(|left, right| right.1.cmp(&left.1).then(left.0.cmp(&right.0)));
+        Ok(played)
+    }
+
+    /// 统计 `start_time >= since` 的会话时长（秒），按游戏汇总
+    ///
+    /// 直接汇总原始会话而非 `daily_stats`，滚动时间窗口不受按日分桶的时区影响
+    pub async fn get_playtime_since(
+        db: &DatabaseConnection,
+        since: i32,
+    ) -> Result<PlaytimeSince, DbErr> {
+        let games = Self::playtime_since_query(since)
+            .into_model::<GamePlaytimeSince>()
+            .all(db)
+            .await?;
+        Ok(PlaytimeSince {
+            total_seconds: games.iter().map(|game| game.seconds).sum(),
+            games,
+        })
+    }
+
+    /// 单个游戏在 `since` 之后的游玩时长（秒）
+    pub async fn get_game_playtime_since(
+        db: &DatabaseConnection,
+        game_id: i32,
+        since: i32,
+    ) -> Result<GamePlaytimeSince, DbErr> {
+        let played = Self::playtime_since_query(since)
+            .filter(game_sessions::Column::GameId.eq(game_id))
+            .into_model::<GamePlaytimeSince>()
+            .one(db)
+            .await?;
+        Ok(played.unwrap_or(GamePlaytimeSince {
+            game_id,
+            seconds: 0,
+            session_count: 0,
+        }))
+    }
+
+    fn playtime_since_query(since: i32) -> Select<GameSessions> {
+        // duration 单位为分钟
+        let seconds = sea_query::Expr::cust("SUM(duration) * 60");
+        GameSessions::find()
+            .select_only()
+            .column(game_sessions::Column::GameId)
+            .column_as(seconds.clone(), "seconds")
+            .column_as(
+                sea_query::Expr::col(game_sessions::Column::SessionId).count(),
+                "session_count",
+            )
+            .filter(game_sessions::Column::StartTime.gte(since))
+            .group_by(game_sessions::Column::GameId)
+            .order_by_desc(seconds)
+            .order_by_asc(game_sessions::Column::GameId)
+    }
+
+    /// 获取全库累计游玩时长（分钟）
+    pub async fn get_total_playtime(db: &DatabaseConnection) -> Result<i64, DbErr> {
+        let total = GameStatistics::find()
+            .select_only()
+            .column_as(
+                sea_query::Expr::col(game_statistics::Column::TotalTime).sum(),
+                "total",
+            )
+            .into_tuple::<Option<i64>>()
+            .one(db)
+            .await?;
+        Ok(total.flatten().unwrap_or(0))
+    }
+
+    /// 统计全库完成率，已归档的游戏不计入
+    pub async fn get_completion_stats(db: &DatabaseConnection) -> Result<CompletionStats, DbErr> {
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                r#"
+                SELECT
+                    COUNT(*) AS total_games,
+                    COALESCE(SUM(g.clear = ?), 0) AS cleared,
+                    COALESCE(SUM(
+                        COALESCE(g.clear, 0) != ? AND COALESCE(s.session_count, 0) > 0
+                    ), 0) AS in_progress
+                FROM games g
+                LEFT JOIN game_statistics s ON s.game_id = g.id
+                WHERE g.archived_at IS NULL
+                "#,
+                [
+                    PlayStatus::Cleared.value().into(),
+                    PlayStatus::Cleared.value().into(),
+                ],
+            ))
+            .await?
+            .ok_or_else(|| custom_error("统计完成率失败"))?;
+
+        let total_games: i64 = row.try_get("", "total_games")?;
+        let cleared: i64 = row.try_get("", "cleared")?;
+        let in_progress: i64 = row.try_get("", "in_progress")?;
+        let cleared_percent = if total_games == 0 {
+            0.0
+        } else {
+            cleared as f64 * 100.0 / total_games as f64
+        };
+        Ok(CompletionStats {
+            total_games,
+            cleared,
+            in_progress,
+            untouched: total_games - cleared - in_progress,
+            cleared_percent,
+        })
+    }
+
     /// 获取所有游戏统计数据
     pub async fn get_all_statistics(
         db: &DatabaseConnection,
@@ -849,6 +1315,58 @@ mod tests {
         assert!(manual_session_end_time(i32::MAX - 30, 1, i32::MAX).is_err());
     }
 
+    #[test]
+    fn activity_heatmap_is_dense_and_sums_across_games() {
+        let day = |date: &str, playtime| DailyStats {
+            date: date.to_string(),
+            playtime,
+        };
+        let games = vec![
+            vec![day("2026-01-01", 30), day("2026-01-03", 10)],
+            vec![day("2026-01-03", 20), day("2026-01-09", 50)],
+        ];
+
+        let heatmap = build_activity_heatmap(
+            &games,
+            NaiveDate::from_ymd_opt(2026, 1, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 1, 4).unwrap(),
+        );
+
+        assert_eq!(
+            heatmap,
+            vec![
+                HeatmapDay {
+                    date: "2026-01-02".to_string(),
+                    total_playtime: 0,
+                    games_played: 0,
+                },
+                HeatmapDay {
+                    date: "2026-01-03".to_string(),
+                    total_playtime: 30,
+                    games_played: 2,
+                },
+                HeatmapDay {
+                    date: "2026-01-04".to_string(),
+                    total_playtime: 0,
+                    games_played: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn activity_heatmap_range_rejects_reversed_and_oversized_spans() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+        assert!(validate_heatmap_range(date(2026, 1, 1), date(2026, 1, 1)).is_ok());
+        assert!(validate_heatmap_range(date(2026, 1, 2), date(2026, 1, 1)).is_err());
+
+        let start = date(2020, 1, 1);
+        let last_allowed = start + chrono::Duration::days(MAX_HEATMAP_DAYS - 1);
+        assert!(validate_heatmap_range(start, last_allowed).is_ok());
+        assert!(validate_heatmap_range(start, last_allowed + chrono::Duration::days(1)).is_err());
+    }
+
     #[tokio::test]
     async fn session_insert_and_delete_update_statistics_atomically() {
         let db = test_database().await;
@@ -884,6 +1402,39 @@ mod tests {
         assert_eq!(statistics.daily_stats.as_deref(), Some("[]"));
     }
 
+    #[tokio::test]
+    async fn completion_stats_split_games_and_skip_archived() {
+        let db = test_database().await;
+        db.execute_unprepared(
+            r#"ALTER TABLE games ADD COLUMN clear INTEGER;
+               ALTER TABLE games ADD COLUMN archived_at INTEGER;
+               INSERT INTO games (id, id_type, clear) VALUES (2, 'custom', 2);
+               INSERT INTO games (id, id_type, clear) VALUES (3, 'custom', 3);
+               INSERT INTO games (id, id_type, clear, archived_at) VALUES (4, 'custom', 2, 1);"#,
+        )
+        .await
+        .expect("应插入测试游戏");
+        GameStatsRepository::record_session_with_statistics(
+            &db,
+            3,
+            timestamp(1, 10),
+            timestamp(1, 12),
+            90,
+        )
+        .await
+        .expect("会话写入应成功");
+
+        let stats = GameStatsRepository::get_completion_stats(&db)
+            .await
+            .expect("完成率统计应成功");
+
+        assert_eq!(stats.total_games, 3);
+        assert_eq!(stats.cleared, 1);
+        assert_eq!(stats.in_progress, 1);
+        assert_eq!(stats.untouched, 1);
+        assert!((stats.cleared_percent - 100.0 / 3.0).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn statistics_failure_rolls_back_session_insert() {
         let db = test_database().await;
@@ -943,4 +1494,194 @@ mod tests {
         assert_eq!(statistics.session_count, Some(1));
         assert_eq!(statistics.last_played, Some(end_time));
     }
+
+    #[tokio::test]
+    async fn playtime_since_sums_raw_sessions_per_game() {
+        let db = test_database().await;
+        db.execute_unprepared("INSERT INTO games (id, id_type) VALUES (2, 'custom')")
+            .await
+            .expect("应插入测试游戏");
+        for (game_id, start_day, minutes) in [(1, 1, 30), (1, 3, 20), (2, 3, 60), (2, 4, 15)] {
+            GameStatsRepository::record_session_with_statistics(
+                &db,
+                game_id,
+                timestamp(start_day, 10),
+                timestamp(start_day, 12),
+                minutes,
+            )
+            .await
+            .expect("会话写入应成功");
+        }
+
+        let since = timestamp(3, 0);
+        let played = GameStatsRepository::get_playtime_since(&db, since)
+            .await
+            .expect("统计应成功");
+        assert_eq!(played.total_seconds, (20 + 60 + 15) * 60);
+        assert_eq!(
+            played.games,
+            vec![
+                GamePlaytimeSince {
+                    game_id: 2,
+                    seconds: 75 * 60,
+                    session_count: 2,
+                },
+                GamePlaytimeSince {
+                    game_id: 1,
+                    seconds: 20 * 60,
+                    session_count: 1,
+                },
+            ]
+        );
+
+        let single = GameStatsRepository::get_game_playtime_since(&db, 1, since)
+            .await
+            .expect("统计应成功");
+        assert_eq!(single.seconds, 20 * 60);
+        let none = GameStatsRepository::get_game_playtime_since(&db, 1, timestamp(5, 0))
+            .await
+            .expect("统计应成功");
+        assert_eq!((none.seconds, none.session_count), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn all_sessions_are_paged_across_games_by_start_time() {
+        let db = test_database().await;
+        db.execute_unprepared("INSERT INTO games (id, id_type) VALUES (2, 'custom')")
+            .await
+            .expect("应插入测试游戏");
+        for (game_id, start_day) in [(1, 1), (2, 3), (1, 4), (2, 2)] {
+            GameStatsRepository::record_session_with_statistics(
+                &db,
+                game_id,
+                timestamp(start_day, 10),
+                timestamp(start_day, 11),
+                60,
+            )
+            .await
+            .expect("会话写入应成功");
+        }
+
+        let page = |offset| GameStatsRepository::get_all_sessions_paged(&db, 3, offset);
+        let first = page(0).await.expect("分页查询应成功");
+        assert_eq!(
+            first
+                .iter()
+                .map(|session| (session.game_id, session.start_time))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, timestamp(4, 10)),
+                (2, timestamp(3, 10)),
+                (2, timestamp(2, 10)),
+            ]
+        );
+        let second = page(3).await.expect("分页查询应成功");
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].start_time, timestamp(1, 10));
+    }
+
+    #[tokio::test]
+    async fn playtime_on_date_is_sorted_by_minutes() {
+        let db = test_database().await;
+        db.execute_unprepared(
+            r#"INSERT INTO games (id, id_type) VALUES (2, 'custom'), (3, 'custom');
+            INSERT INTO game_statistics (game_id, daily_stats) VALUES
+                (1, '[{"date":"2026-01-02","playtime":30}]'),
+                (2, '[{"date":"2026-01-01","playtime":15},{"date":"2026-01-02","playtime":45}]'),
+                (3, '[{"date":"2026-01-03","playtime":60}]')"#,
+        )
+        .await
+        .expect("应写入测试统计");
+
+        let played = GameStatsRepository::get_playtime_on(
+            &db,
+            NaiveDate::from_ymd_opt(2026, 1, 2).expect("测试日期应有效"),
+        )
+        .await
+        .expect("当日游玩查询应成功");
+
+        assert_eq!(played, vec![(2, 45), (1, 30)]);
+    }
+
+    #[test]
+    fn imported_sessions_parse_from_csv_and_json() {
+        let csv = "Start_Time, end_time, duration\n100,4000,\n200,800,5\n";
+        let json = r#"[{"start_time":100,"end_time":4000},{"start_time":200,"end_time":800,"duration":5}]"#;
+        let expected = vec![
+            ImportedSession {
+                start_time: 100,
+                end_time: 4000,
+                duration: None,
+            },
+            ImportedSession {
+                start_time: 200,
+                end_time: 800,
+                duration: Some(5),
+            },
+        ];
+
+        assert_eq!(parse_imported_sessions(csv), Ok(expected.clone()));
+        assert_eq!(parse_imported_sessions(json), Ok(expected));
+        assert_eq!(
+            parse_imported_sessions("start_time,end_time\n1,abc"),
+            Err("第 2 行 end_time 不是整数".to_string())
+        );
+        assert_eq!(
+            parse_imported_sessions("\nstart_time,end_time\n1,2\n\n3"),
+            Err("第 5 行缺少 end_time".to_string())
+        );
+        let json_error = parse_imported_sessions("[\n{\"start_time\": 1},\n{}\n]")
+            .expect_err("缺少字段的 JSON 应被拒绝");
+        assert!(json_error.starts_with("第 2 行"), "{json_error}");
+    }
+
+    #[tokio::test]
+    async fn import_sessions_rejects_malformed_rows_and_rebuilds_statistics() {
+        let db = test_database().await;
+        let imported = |start_time, end_time, duration| ImportedSession {
+            start_time: i64::from(start_time),
+            end_time: i64::from(end_time),
+            duration,
+        };
+
+        let error = GameStatsRepository::import_sessions(
+            &db,
+            1,
+            &[
+                imported(timestamp(1, 10), timestamp(1, 11), None),
+                imported(timestamp(1, 12), timestamp(1, 11), None),
+            ],
+        )
+        .await
+        .expect_err("结束时间早于开始时间应被拒绝");
+        assert!(error.to_string().contains("第 2 条会话无效"));
+        assert_eq!(
+            GameSessions::find()
+                .count(&db)
+                .await
+                .expect("会话计数应成功"),
+            0
+        );
+
+        let count = GameStatsRepository::import_sessions(
+            &db,
+            1,
+            &[
+                imported(timestamp(1, 10), timestamp(1, 11), None),
+                imported(timestamp(2, 10), timestamp(2, 12), Some(90)),
+            ],
+        )
+        .await
+        .expect("会话导入应成功");
+        let statistics = GameStatistics::find_by_id(1)
+            .one(&db)
+            .await
+            .expect("统计查询应成功")
+            .expect("统计记录应存在");
+
+        assert_eq!(count, 2);
+        assert_eq!(statistics.total_time, Some(150));
+        assert_eq!(statistics.session_count, Some(2));
+        assert_eq!(statistics.last_played, Some(timestamp(2, 12)));
+    }
 }
@@ -1,16 +1,22 @@
 //! 游戏聚合仓库。
 
 use crate::database::dto::{
-    BatchOperationError, BatchOperationResult, FullGameData, GameSourceData, InsertGameData,
-    UpdateGameData, UpsertGameSourceData,
+    BatchOperationError, BatchOperationResult, CheckedInsertResult, DashboardGameData,
+    FullGameData, GamePage, GameSourceData, InsertGameData, SearchHit, UpdateGameData,
+    UpsertGameSourceData,
 };
+use crate::database::repository::game_stats_repository::GameStatsRepository;
+use crate::database::retry::with_write_retry;
 use crate::entity::prelude::*;
-use crate::entity::{game_sources, game_statistics, games, savedata};
+use crate::entity::{
+    game_collection_link, game_sessions, game_sources, game_statistics, games, savedata,
+};
+use crate::utils::playtime::format_playtime;
 use sea_orm::sea_query::{Expr, OnConflict};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{HashMap, HashSet};
 
 /// 游戏数据排序选项
@@ -24,6 +30,12 @@ pub enum SortOption {
     VNDBRank,
     UserRatingRank,
     Namesort,
+    /// 按关键词匹配程度排序：完全匹配 > 前缀匹配 > 包含匹配，无关键词时按添加时间排序
+    Relevance,
+    /// 按游戏状态分组：在玩 > 搁置 > 想玩 > 玩过 > 弃坑，降序时分组顺序反转，组内按添加时间
+    Status,
+    /// 按累计游玩时长排序，没有游玩统计的游戏始终置末尾
+    Playtime,
 }
 
 /// 排序方向
@@ -42,12 +54,183 @@ pub enum GameType {
     Local,
     Online,
     IsCustom,
+    /// 仅收藏的游戏
+    Favorite,
+    /// 最近在玩的游戏：`GameFilter::playing_days` 天内（默认 14 天）有游玩记录
+    Playing,
+}
+
+/// 归档（软删除）游戏的筛选方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchivedFilter {
+    /// 排除已归档游戏（默认）
+    #[default]
+    Exclude,
+    /// 包含已归档游戏
+    Include,
+    /// 仅返回已归档游戏
+    Only,
+}
+
+/// 游戏状态，存储在 `games.clear`（1-5），与前端 `PlayStatus` 一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayStatus {
+    /// 想玩
+    Planning,
+    /// 玩过（已通关）
+    Cleared,
+    /// 在玩
+    Playing,
+    /// 搁置
+    OnHold,
+    /// 弃坑
+    Dropped,
+}
+
+impl PlayStatus {
+    /// `games.clear` 中的取值
+    pub fn value(self) -> i32 {
+        match self {
+            Self::Planning => 1,
+            Self::Cleared => 2,
+            Self::Playing => 3,
+            Self::OnHold => 4,
+            Self::Dropped => 5,
+        }
+    }
+
+    pub fn from_value(value: i32) -> Option<Self> {
+        match value {
+            1 => Some(Self::Planning),
+            2 => Some(Self::Cleared),
+            3 => Some(Self::Playing),
+            4 => Some(Self::OnHold),
+            5 => Some(Self::Dropped),
+            _ => None,
+        }
+    }
+
+    /// 原生 SQL 查询中的筛选条件（列名带 `g.` 前缀）
+    fn sql_condition(self) -> &'static str {
+        match self {
+            Self::Planning => "g.clear = 1",
+            Self::Cleared => "g.clear = 2",
+            Self::Playing => "g.clear = 3",
+            Self::OnHold => "g.clear = 4",
+            Self::Dropped => "g.clear = 5",
+        }
+    }
+}
+
+/// 游戏列表查询的附加筛选条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameFilter {
+    pub archived: ArchivedFilter,
+    /// 关键词，匹配自定义名称、各来源的 name / name_cn / aliases / all_titles，
+    /// 以及自定义数据中的开发商、别名与标签（不区分 ASCII 大小写）
+    pub keyword: Option<String>,
+    /// 收藏的游戏排在最前，作为所选排序之前的第一排序键
+    pub favorites_first: bool,
+    /// 仅返回指定状态的游戏
+    pub play_status: Option<PlayStatus>,
+    /// 仅返回带有该标签的游戏（不区分 ASCII 大小写，忽略首尾空白）
+    ///
+    /// 标签以 JSON 字符串数组存储在 `custom_data.tags` 与各来源 `data.tags` 中，
+    /// 按数组元素整项匹配，与 `find_tag_counts` 返回的筛选项一致
+    pub tag: Option<String>,
+    /// `GameType::Playing` 的时间窗口（天），为空时使用 14 天
+    pub playing_days: Option<u32>,
+}
+
+impl GameFilter {
+    /// 去除首尾空白并转为 ASCII 小写后的关键词，与 SQLite `lower()` 行为一致
+    fn normalized_keyword(&self) -> Option<String> {
+        self.keyword
+            .as_deref()
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(str::to_ascii_lowercase)
+    }
+
+    /// `GameType::Playing` 的起始时间（Unix 秒），最近游玩时间不早于该值的游戏视为在玩
+    fn playing_since(&self) -> i64 {
+        let days = self.playing_days.unwrap_or(DEFAULT_PLAYING_DAYS);
+        chrono::Utc::now().timestamp() - i64::from(days) * 86_400
+    }
+
+    /// 去除首尾空白并转为 ASCII 小写后的标签
+    fn normalized_tag(&self) -> Option<String> {
+        self.tag
+            .as_deref()
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_ascii_lowercase)
+    }
+
+    /// 以 SQL 片段形式返回筛选条件（列名带 `g.` 前缀），用于原生 SQL 查询
+    fn sql_conditions(&self) -> Vec<&'static str> {
+        let mut conditions = Vec::new();
+        match self.archived {
+            ArchivedFilter::Exclude => conditions.push("g.archived_at IS NULL"),
+            ArchivedFilter::Include => {}
+            ArchivedFilter::Only => conditions.push("g.archived_at IS NOT NULL"),
+        }
+        if let Some(status) = self.play_status {
+            conditions.push(status.sql_condition());
+        }
+        conditions
+    }
+}
+
+/// 游戏的全部已知标题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameTitles {
+    pub id: i32,
+    pub display_name: Option<String>,
+    pub titles: Vec<String>,
+}
+
+/// 按显示优先级解析后的游戏名称与封面
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameDisplaySummary {
+    pub name: Option<String>,
+    pub image: Option<String>,
+}
+
+/// 筛选项取值及拥有该值的游戏数量
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromQueryResult)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// 合并重复游戏的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MergeGamesResult {
+    /// 转移到保留游戏的游玩会话数量
+    pub sessions_moved: u64,
+    /// 转移到保留游戏的存档备份记录数量
+    pub backups_moved: u64,
+    /// 转移到保留游戏的合集关联数量
+    pub links_moved: u64,
+    /// 保留游戏已在同一合集中而删除的关联数量
+    pub links_dropped: u64,
+}
+
+/// 游戏启动前与退出后执行的命令，`None` 表示未设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchHooks {
+    pub pre_launch_cmd: Option<String>,
+    pub post_exit_cmd: Option<String>,
 }
 
 pub struct GamesRepository;
 
 impl GamesRepository {
-    /// 缺省游戏状态：想玩 / WISH
+    /// 缺省游戏状态：想玩 / WISH，与 `PlayStatus::Planning` 一致
     const DEFAULT_PLAY_STATUS: i32 = 1;
     const MIXED_NAME_PRIORITY: [&str; 4] = ["bgm", "vndb", "ymgal", "kun"];
     const FULL_GAME_SELECT: &str = r#"
@@ -65,6 +248,9 @@ impl GamesRepository {
             g.custom_data,
             g.created_at,
             g.updated_at,
+            g.archived_at,
+            g.primary_source,
+            g.favorite,
             (
                 SELECT json_group_array(
                     json_object(
@@ -255,6 +441,12 @@ impl GamesRepository {
             user_rating: NotSet,
             created_at: Set(Some(now)),
             updated_at: Set(Some(now)),
+            archived_at: NotSet,
+            primary_source: NotSet,
+            favorite: Set(game.favorite),
+            launch_args: NotSet,
+            pre_launch_cmd: NotSet,
+            post_exit_cmd: NotSet,
         }
     }
 
@@ -362,15 +554,81 @@ impl GamesRepository {
         db: &DatabaseConnection,
         game: InsertGameData,
     ) -> Result<FullGameData, DbErr> {
-        let transaction = db.begin().await?;
-        let result = Self::insert_aggregate(
-            &transaction,
-            game.cleaned(),
-            chrono::Utc::now().timestamp() as i32,
-        )
-        .await?;
-        transaction.commit().await?;
-        Ok(result)
+        let game = game.cleaned();
+        let now = chrono::Utc::now().timestamp() as i32;
+        with_write_retry(|| {
+            let game = game.clone();
+            async move {
+                let transaction = db.begin().await?;
+                let result = Self::insert_aggregate(&transaction, game, now).await?;
+                transaction.commit().await?;
+                Ok(result)
+            }
+        })
+        .await
+    }
+
+    /// 查找已绑定任一相同来源 ID（`source` + `external_id`）的游戏，包括已归档的游戏
+    ///
+    /// 返回第一个冲突的来源绑定；没有 `external_id` 的来源不参与比较
+    pub async fn find_existing_by_sources<C>(
+        db: &C,
+        sources: &[UpsertGameSourceData],
+    ) -> Result<Option<game_sources::Model>, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let mut condition = Condition::any();
+        let mut has_external_id = false;
+        for source in sources {
+            if let Some(external_id) = source.external_id.as_deref() {
+                has_external_id = true;
+                condition = condition.add(
+                    Condition::all()
+                        .add(game_sources::Column::Source.eq(source.source.as_str()))
+                        .add(game_sources::Column::ExternalId.eq(external_id)),
+                );
+            }
+        }
+        if !has_external_id {
+            return Ok(None);
+        }
+
+        GameSources::find()
+            .filter(condition)
+            .order_by_asc(game_sources::Column::GameId)
+            .one(db)
+            .await
+    }
+
+    /// 插入游戏前检查来源 ID 是否已被其他游戏绑定，重复时不插入并返回冲突的游戏
+    ///
+    /// 检查与插入在同一事务中完成
+    pub async fn insert_checked(
+        db: &DatabaseConnection,
+        game: InsertGameData,
+    ) -> Result<CheckedInsertResult, DbErr> {
+        let game = game.cleaned();
+        let now = chrono::Utc::now().timestamp() as i32;
+        with_write_retry(|| {
+            let game = game.clone();
+            async move {
+                let transaction = db.begin().await?;
+                if let Some(existing) =
+                    Self::find_existing_by_sources(&transaction, &game.sources).await?
+                {
+                    return Ok(CheckedInsertResult::Duplicate {
+                        game_id: existing.game_id,
+                        source: existing.source,
+                        external_id: existing.external_id.unwrap_or_default(),
+                    });
+                }
+                let result = Self::insert_aggregate(&transaction, game, now).await?;
+                transaction.commit().await?;
+                Ok(CheckedInsertResult::Inserted { game: result })
+            }
+        })
+        .await
     }
 
     pub async fn insert_batch(
@@ -476,18 +734,23 @@ impl GamesRepository {
         game_id: i32,
         updates: UpdateGameData,
     ) -> Result<FullGameData, DbErr> {
-        let transaction = db.begin().await?;
-        let result = Self::update_aggregate(
-            &transaction,
-            game_id,
-            updates.cleaned(),
-            chrono::Utc::now().timestamp() as i32,
-        )
-        .await?;
-        transaction.commit().await?;
-        Ok(result)
+        let updates = updates.cleaned();
+        let now = chrono::Utc::now().timestamp() as i32;
+        with_write_retry(|| {
+            let updates = updates.clone();
+            async move {
+                let transaction = db.begin().await?;
+                let result = Self::update_aggregate(&transaction, game_id, updates, now).await?;
+                transaction.commit().await?;
+                Ok(result)
+            }
+        })
+        .await
     }
 
+    /// 在单个事务中批量更新游戏
+    ///
+    /// 任一游戏更新失败时整批回滚，错误信息中带有失败的游戏 ID
     pub async fn update_batch(
         db: &DatabaseConnection,
         updates: Vec<(i32, UpdateGameData)>,
@@ -496,17 +759,30 @@ impl GamesRepository {
             return Ok(Vec::new());
         }
 
-        let transaction = db.begin().await?;
+        let updates: Vec<_> = updates
+            .into_iter()
+            .map(|(game_id, update)| (game_id, update.cleaned()))
+            .collect();
         let now = chrono::Utc::now().timestamp() as i32;
-        let mut updated_games = Vec::with_capacity(updates.len());
-
-        for (game_id, update) in updates {
-            updated_games
-                .push(Self::update_aggregate(&transaction, game_id, update.cleaned(), now).await?);
-        }
-
-        transaction.commit().await?;
-        Ok(updated_games)
+        with_write_retry(|| {
+            let updates = updates.clone();
+            async move {
+                let transaction = db.begin().await?;
+                let mut updated_games = Vec::with_capacity(updates.len());
+                for (game_id, update) in updates {
+                    // 提前返回时事务随之丢弃并回滚；保留原错误文本，忙/锁定错误仍会重试
+                    let game = Self::update_aggregate(&transaction, game_id, update, now)
+                        .await
+                        .map_err(|error| {
+                            DbErr::Custom(format!("更新游戏 {} 失败: {}", game_id, error))
+                        })?;
+                    updated_games.push(game);
+                }
+                transaction.commit().await?;
+                Ok(updated_games)
+            }
+        })
+        .await
     }
 
     async fn find_full_by_id<C>(db: &C, id: i32) -> Result<Option<FullGameData>, DbErr>
@@ -533,28 +809,89 @@ impl GamesRepository {
         sort_option: SortOption,
         sort_order: SortOrder,
         language: Option<String>,
+        filter: &GameFilter,
     ) -> Result<Vec<FullGameData>, DbErr> {
-        let ids = Self::find_ids(db, game_type, sort_option, sort_order, language.clone()).await?;
+        let ids = Self::find_ids(
+            db,
+            game_type,
+            sort_option,
+            sort_order,
+            language.clone(),
+            filter,
+        )
+        .await?;
         Self::find_full_games_in_order(db, &ids).await
     }
 
+    /// 分页读取完整游戏数据，返回当前页数据与符合条件的总数
+    ///
+    /// `page` 从 0 开始；SQL 可排序的方式直接在 ID 查询上加 `LIMIT/OFFSET`，
+    /// 关联数据只为当前页的 ID 批量读取。名称排序在应用层完成，需先排好全部 ID 再截取。
+    #[allow(clippy::too_many_arguments)]
+    pub async fn find_page(
+        db: &DatabaseConnection,
+        game_type: GameType,
+        sort_option: SortOption,
+        sort_order: SortOrder,
+        language: Option<String>,
+        filter: &GameFilter,
+        page: u64,
+        page_size: u64,
+    ) -> Result<GamePage, DbErr> {
+        let offset = page.saturating_mul(page_size);
+        let (ids, total) = if matches!(sort_option, SortOption::Namesort) {
+            let ids =
+                Self::find_name_sorted_ids(db, game_type, sort_order, language, filter).await?;
+            let total = ids.len() as u64;
+            let ids = ids
+                .into_iter()
+                .skip(offset.try_into().unwrap_or(usize::MAX))
+                .take(page_size.try_into().unwrap_or(usize::MAX))
+                .collect::<Vec<_>>();
+            (ids, total)
+        } else {
+            let total = Self::count_matches(db, game_type, filter).await?;
+            let ids = Self::sorted_id_query(game_type, sort_option, sort_order, filter)
+                .offset(offset)
+                .limit(page_size)
+                .into_tuple::<i32>()
+                .all(db)
+                .await?;
+            (ids, total)
+        };
+
+        Ok(GamePage {
+            games: Self::find_full_games_in_order(db, &ids).await?,
+            total,
+        })
+    }
+
     pub async fn find_ids(
         db: &DatabaseConnection,
         game_type: GameType,
         sort_option: SortOption,
         sort_order: SortOrder,
         language: Option<String>,
+        filter: &GameFilter,
     ) -> Result<Vec<i32>, DbErr> {
         // 名称排序：应用层排序，名称来自 JSON 列
         if matches!(sort_option, SortOption::Namesort) {
-            return Self::find_name_sorted_ids(db, game_type, sort_order, language).await;
+            return Self::find_name_sorted_ids(db, game_type, sort_order, language, filter).await;
         }
 
-        Self::find_ids_sql(db, game_type, sort_option, sort_order).await
+        Self::find_ids_sql(db, game_type, sort_option, sort_order, filter).await
     }
 
     // ==================== 查询操作 ====================
 
+    /// 按给定 ID 顺序批量读取完整游戏数据，不存在的 ID 会被跳过
+    pub async fn find_by_ids(
+        db: &DatabaseConnection,
+        ids: &[i32],
+    ) -> Result<Vec<FullGameData>, DbErr> {
+        Self::find_full_games_in_order(db, ids).await
+    }
+
     async fn find_full_games_in_order<C>(db: &C, ids: &[i32]) -> Result<Vec<FullGameData>, DbErr>
     where
         C: ConnectionTrait,
@@ -608,6 +945,91 @@ impl GamesRepository {
             sources,
             created_at: row.try_get("", "created_at")?,
             updated_at: row.try_get("", "updated_at")?,
+            archived_at: row.try_get("", "archived_at")?,
+            primary_source: row.try_get("", "primary_source")?,
+            favorite: row.try_get("", "favorite")?,
+        })
+    }
+
+    /// 在同一事务内将游戏 `remove_id` 合并到 `keep_id` 并删除 `remove_id`
+    ///
+    /// 游玩会话、存档备份记录与合集关联转移到保留游戏，保留游戏已在同一合集中的关联直接删除；
+    /// 统计投影按合并后的会话重建。保留游戏缺少本地路径或存档路径时沿用被删除游戏的值，
+    /// 被删除游戏的元数据来源与媒体随游戏一起删除。
+    pub async fn merge(
+        db: &DatabaseConnection,
+        keep_id: i32,
+        remove_id: i32,
+    ) -> Result<MergeGamesResult, DbErr> {
+        if keep_id == remove_id {
+            return Err(DbErr::Custom("不能将游戏合并到自身".to_string()));
+        }
+
+        let txn = db.begin().await?;
+        let keep = Games::find_by_id(keep_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::Custom(format!("游戏不存在: {}", keep_id)))?;
+        let remove = Games::find_by_id(remove_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| DbErr::Custom(format!("游戏不存在: {}", remove_id)))?;
+
+        let keep_collections = GameCollectionLink::find()
+            .select_only()
+            .column(game_collection_link::Column::CollectionId)
+            .filter(game_collection_link::Column::GameId.eq(keep_id))
+            .into_tuple::<i32>()
+            .all(&txn)
+            .await?;
+        let links_dropped = GameCollectionLink::delete_many()
+            .filter(game_collection_link::Column::GameId.eq(remove_id))
+            .filter(game_collection_link::Column::CollectionId.is_in(keep_collections))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let links_moved = GameCollectionLink::update_many()
+            .col_expr(game_collection_link::Column::GameId, Expr::value(keep_id))
+            .filter(game_collection_link::Column::GameId.eq(remove_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let sessions_moved = GameSessions::update_many()
+            .col_expr(game_sessions::Column::GameId, Expr::value(keep_id))
+            .filter(game_sessions::Column::GameId.eq(remove_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let backups_moved = Savedata::update_many()
+            .col_expr(savedata::Column::GameId, Expr::value(keep_id))
+            .filter(savedata::Column::GameId.eq(remove_id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        if (keep.localpath.is_none() && remove.localpath.is_some())
+            || (keep.savepath.is_none() && remove.savepath.is_some())
+        {
+            let mut active: games::ActiveModel = keep.clone().into();
+            if keep.localpath.is_none() {
+                active.localpath = Set(remove.localpath.clone());
+            }
+            if keep.savepath.is_none() {
+                active.savepath = Set(remove.savepath.clone());
+            }
+            active.updated_at = Set(Some(chrono::Utc::now().timestamp() as i32));
+            active.update(&txn).await?;
+        }
+
+        Games::delete_by_id(remove_id).exec(&txn).await?;
+        GameStatsRepository::rebuild_statistics_in(&txn, keep_id).await?;
+        txn.commit().await?;
+
+        Ok(MergeGamesResult {
+            sessions_moved,
+            backups_moved,
+            links_moved,
+            links_dropped,
         })
     }
 
@@ -625,105 +1047,674 @@ impl GamesRepository {
             .await
     }
 
+    /// 关键词搜索，返回每个游戏命中的字段与片段
+    ///
+    /// 按 `SortOption::Relevance` 排序，名称命中优先于别名、标签等其他字段；
+    /// 关键词为空时返回空列表。`limit` 为空时不限制数量。
+    pub async fn search(
+        db: &DatabaseConnection,
+        keyword: &str,
+        game_type: GameType,
+        filter: &GameFilter,
+        limit: Option<u64>,
+    ) -> Result<Vec<SearchHit>, DbErr> {
+        let filter = GameFilter {
+            keyword: Some(keyword.to_string()),
+            ..filter.clone()
+        };
+        let Some(keyword) = filter.normalized_keyword() else {
+            return Ok(Vec::new());
+        };
+
+        let mut query =
+            Self::sorted_id_query(game_type, SortOption::Relevance, SortOrder::Asc, &filter);
+        if let Some(limit) = limit {
+            query = query.limit(limit);
+        }
+        let ids = query.into_tuple::<i32>().all(db).await?;
+
+        Ok(Self::find_full_games_in_order(db, &ids)
+            .await?
+            .into_iter()
+            .filter_map(|game| {
+                let (matched_field, snippet) = search_match(&game, &keyword)?;
+                Some(SearchHit {
+                    game,
+                    matched_field,
+                    snippet,
+                })
+            })
+            .collect())
+    }
+
+    /// 统计符合筛选条件的游戏数量，条件与 `find_ids` 一致，但不排序也不加载关联数据
+    pub async fn count_matches(
+        db: &DatabaseConnection,
+        game_type: GameType,
+        filter: &GameFilter,
+    ) -> Result<u64, DbErr> {
+        Self::build_base_query(game_type, filter).count(db).await
+    }
+
+    /// 统计未归档的游戏数量
     pub async fn count(db: &DatabaseConnection) -> Result<u64, DbErr> {
-        Games::find().count(db).await
+        Games::find()
+            .filter(games::Column::ArchivedAt.is_null())
+            .count(db)
+            .await
     }
 
-    pub async fn get_source_bindings(
+    /// 归档（软删除）游戏，保留游玩记录与存档备份
+    ///
+    /// 已归档的游戏不会刷新归档时间，返回受影响的行数
+    pub async fn archive(db: &DatabaseConnection, id: i32) -> Result<u64, DbErr> {
+        let now = chrono::Utc::now().timestamp() as i32;
+        Games::update_many()
+            .col_expr(games::Column::ArchivedAt, Expr::value(now))
+            .filter(games::Column::Id.eq(id))
+            .filter(games::Column::ArchivedAt.is_null())
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    /// 查询在指定时间之后归档的游戏，按归档时间倒序
+    pub async fn find_archived_since(
         db: &DatabaseConnection,
-        source: &str,
-    ) -> Result<Vec<(i32, String)>, DbErr> {
-        GameSources::find()
+        since: i32,
+    ) -> Result<Vec<FullGameData>, DbErr> {
+        let ids = Games::find()
             .select_only()
-            .column(game_sources::Column::GameId)
-            .column(game_sources::Column::ExternalId)
-            .filter(game_sources::Column::Source.eq(source))
-            .filter(game_sources::Column::ExternalId.is_not_null())
-            .into_tuple::<(i32, String)>()
+            .column(games::Column::Id)
+            .filter(games::Column::ArchivedAt.gte(since))
+            .order_by_desc(games::Column::ArchivedAt)
+            .order_by_asc(games::Column::Id)
+            .into_tuple::<i32>()
             .all(db)
-            .await
+            .await?;
+        Self::find_full_games_in_order(db, &ids).await
     }
 
-    /// 获取所有非空本地路径，用于扫描去重
-    ///
-    /// 返回数据库中所有 `localpath` 字段的集合（仅非 NULL 值），
-    /// 使用 `HashSet` 以便调用方做 O(1) 精确匹配；前缀检查由调用方负责。
-    pub async fn get_all_localpaths(db: &DatabaseConnection) -> Result<HashSet<String>, DbErr> {
+    /// 查询在指定时间之前归档的游戏 ID
+    pub async fn find_archived_before(
+        db: &DatabaseConnection,
+        before: i32,
+    ) -> Result<Vec<i32>, DbErr> {
         Games::find()
             .select_only()
-            .column(games::Column::Localpath)
-            .filter(games::Column::Localpath.is_not_null())
-            .into_tuple::<String>()
+            .column(games::Column::Id)
+            .filter(games::Column::ArchivedAt.lt(before))
+            .order_by_asc(games::Column::Id)
+            .into_tuple::<i32>()
             .all(db)
             .await
-            .map(|paths| paths.into_iter().collect())
     }
 
-    fn build_base_query(game_type: GameType) -> Select<Games> {
-        let query = Games::find();
-        match game_type {
-            GameType::All => query,
-            GameType::Local => query.filter(games::Column::Localpath.is_not_null()),
-            GameType::Online => query.filter(games::Column::Localpath.is_null()),
-            GameType::IsCustom => query.filter(
-                Condition::any()
-                    .add(games::Column::IdType.eq("custom"))
-                    .add(games::Column::IdType.eq("Whitecloud")),
-            ),
-        }
-    }
+    /// 读取游戏保存的启动参数，未保存时返回 `None`
+    pub async fn get_launch_args(
+        db: &DatabaseConnection,
+        game_id: i32,
+    ) -> Result<Option<Vec<String>>, DbErr> {
+        let launch_args: Option<String> = Games::find_by_id(game_id)
+            .select_only()
+            .column(games::Column::LaunchArgs)
+            .into_tuple()
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("游戏不存在: {}", game_id)))?;
 
-    /// 发行日期排序：无日期的游戏始终置末尾，升序/降序只影响非空日期。
-    fn apply_date_order(query: Select<Games>, sort_order: SortOrder) -> Select<Games> {
-        let query = query.order_by(Expr::col(games::Column::Date).is_null(), Order::Asc);
-        match sort_order {
-            SortOrder::Asc => query.order_by_asc(games::Column::Date),
-            SortOrder::Desc => query.order_by_desc(games::Column::Date),
-        }
-        .order_by_asc(games::Column::Id)
+        launch_args
+            .map(|value| {
+                serde_json::from_str::<Vec<String>>(&value)
+                    .map_err(|error| DbErr::Custom(format!("启动参数解析失败: {}", error)))
+            })
+            .transpose()
     }
 
-    /// 最近游玩排序：无游玩记录始终置末尾，升序按最久优先，降序按最近优先。
-    fn apply_last_played_order(query: Select<Games>, sort_order: SortOrder) -> Select<Games> {
-        let query = query.left_join(game_statistics::Entity).order_by(
-            Expr::col(game_statistics::Column::LastPlayed).is_null(),
-            Order::Asc,
-        );
-        match sort_order {
-            SortOrder::Asc => query.order_by_asc(game_statistics::Column::LastPlayed),
-            SortOrder::Desc => query.order_by_desc(game_statistics::Column::LastPlayed),
-        }
-        .order_by_asc(games::Column::Id)
-    }
+    /// 保存游戏的启动参数，空列表清除已保存的参数，返回受影响的行数
+    pub async fn set_launch_args(
+        db: &DatabaseConnection,
+        game_id: i32,
+        args: Vec<String>,
+    ) -> Result<u64, DbErr> {
+        let launch_args = if args.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&args)
+                    .map_err(|error| DbErr::Custom(format!("启动参数序列化失败: {}", error)))?,
+            )
+        };
 
-    /// 应用层排序：按可选数值键排序，None 值统一置末尾
-    fn apply_optional_expression_order(
-        query: Select<Games>,
-        expression: &str,
-        direction: Order,
-    ) -> Select<Games> {
-        query
-            .order_by(Expr::cust(format!("({expression}) IS NULL")), Order::Asc)
-            .order_by(Expr::cust(format!("({expression})")), direction)
+        Games::update_many()
+            .col_expr(games::Column::LaunchArgs, Expr::value(launch_args))
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
     }
 
-    async fn find_ids_sql(
+    /// 读取游戏的启动前与退出后命令
+    pub async fn get_launch_hooks(
         db: &DatabaseConnection,
-        game_type: GameType,
-        sort_option: SortOption,
-        sort_order: SortOrder,
-    ) -> Result<Vec<i32>, DbErr> {
-        let query = Self::build_base_query(game_type)
+        game_id: i32,
+    ) -> Result<LaunchHooks, DbErr> {
+        let (pre_launch_cmd, post_exit_cmd) = Games::find_by_id(game_id)
             .select_only()
-            .column(games::Column::Id);
+            .column(games::Column::PreLaunchCmd)
+            .column(games::Column::PostExitCmd)
+            .into_tuple::<(Option<String>, Option<String>)>()
+            .one(db)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("游戏不存在: {}", game_id)))?;
 
-        let query = match sort_option {
-            SortOption::Addtime => match sort_order {
+        Ok(LaunchHooks {
+            pre_launch_cmd,
+            post_exit_cmd,
+        })
+    }
+
+    /// 保存游戏的启动前与退出后命令，空白命令视为未设置，返回受影响的行数
+    pub async fn set_launch_hooks(
+        db: &DatabaseConnection,
+        game_id: i32,
+        hooks: LaunchHooks,
+    ) -> Result<u64, DbErr> {
+        let normalize = |command: Option<String>| {
+            command
+                .map(|command| command.trim().to_string())
+                .filter(|command| !command.is_empty())
+        };
+
+        Games::update_many()
+            .col_expr(
+                games::Column::PreLaunchCmd,
+                Expr::value(normalize(hooks.pre_launch_cmd)),
+            )
+            .col_expr(
+                games::Column::PostExitCmd,
+                Expr::value(normalize(hooks.post_exit_cmd)),
+            )
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    /// 设置游戏的主数据源，`None` 表示恢复自动选择
+    ///
+    /// 只接受 `custom` 或该游戏已绑定的来源，返回受影响的行数
+    pub async fn set_primary_source(
+        db: &DatabaseConnection,
+        game_id: i32,
+        source: Option<String>,
+    ) -> Result<u64, DbErr> {
+        if let Some(source) = source.as_deref()
+            && source != "custom"
+        {
+            let bound = GameSources::find()
+                .filter(game_sources::Column::GameId.eq(game_id))
+                .filter(game_sources::Column::Source.eq(source))
+                .count(db)
+                .await?;
+            if bound == 0 {
+                return Err(DbErr::Custom(format!("游戏未绑定数据源: {}", source)));
+            }
+        }
+
+        Games::update_many()
+            .col_expr(games::Column::PrimarySource, Expr::value(source))
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    /// 设置自定义封面标识（`custom_data.image`），传 None 清除，返回受影响的行数
+    pub async fn set_custom_image(
+        db: &DatabaseConnection,
+        game_id: i32,
+        image: Option<String>,
+    ) -> Result<u64, DbErr> {
+        Self::set_custom_field(db, game_id, "$.image", image).await
+    }
+
+    /// 设置自定义名称（`custom_data.name`），传 None 清除以回退到数据源名称，返回受影响的行数
+    pub async fn set_custom_name(
+        db: &DatabaseConnection,
+        game_id: i32,
+        name: Option<String>,
+    ) -> Result<u64, DbErr> {
+        Self::set_custom_field(db, game_id, "$.name", name).await
+    }
+
+    /// 写入或移除 `custom_data` 中的单个字符串字段，保留其他字段并更新 `updated_at`
+    async fn set_custom_field(
+        db: &DatabaseConnection,
+        game_id: i32,
+        json_path: &'static str,
+        value: Option<String>,
+    ) -> Result<u64, DbErr> {
+        let custom_data = match value {
+            Some(value) => Expr::cust_with_values(
+                format!(
+                    "json_set(COALESCE(games.custom_data, '{{}}'), '{}', ?)",
+                    json_path
+                ),
+                [value],
+            ),
+            None => Expr::cust(format!("json_remove(games.custom_data, '{}')", json_path)),
+        };
+
+        Games::update_many()
+            .col_expr(games::Column::CustomData, custom_data)
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    /// 设置或清除本地路径，返回受影响的行数
+    ///
+    /// 清除后游戏在 `GameType` 筛选中归为 `Online`
+    pub async fn set_local_path(
+        db: &DatabaseConnection,
+        game_id: i32,
+        path: Option<String>,
+    ) -> Result<u64, DbErr> {
+        Games::update_many()
+            .col_expr(games::Column::Localpath, Expr::value(path))
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    /// 切换收藏标记，返回切换后的值；游戏不存在时返回 None
+    pub async fn toggle_favorite(
+        db: &DatabaseConnection,
+        game_id: i32,
+    ) -> Result<Option<bool>, DbErr> {
+        let result = Games::update_many()
+            .col_expr(games::Column::Favorite, Expr::cust("NOT games.favorite"))
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+
+        Games::find_by_id(game_id)
+            .select_only()
+            .column(games::Column::Favorite)
+            .into_tuple::<bool>()
+            .one(db)
+            .await
+    }
+
+    /// 获取游戏状态；游戏不存在或状态值无效时返回 None
+    pub async fn get_play_status(
+        db: &DatabaseConnection,
+        game_id: i32,
+    ) -> Result<Option<PlayStatus>, DbErr> {
+        Ok(Games::find_by_id(game_id)
+            .select_only()
+            .column(games::Column::Clear)
+            .into_tuple::<Option<i32>>()
+            .one(db)
+            .await?
+            .flatten()
+            .and_then(PlayStatus::from_value))
+    }
+
+    /// 设置游戏状态，返回受影响的行数
+    pub async fn set_play_status(
+        db: &DatabaseConnection,
+        game_id: i32,
+        status: PlayStatus,
+    ) -> Result<u64, DbErr> {
+        Games::update_many()
+            .col_expr(games::Column::Clear, Expr::value(status.value()))
+            .col_expr(
+                games::Column::UpdatedAt,
+                Expr::value(chrono::Utc::now().timestamp() as i32),
+            )
+            .filter(games::Column::Id.eq(game_id))
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    /// 取消归档，返回受影响的行数
+    pub async fn unarchive(db: &DatabaseConnection, id: i32) -> Result<u64, DbErr> {
+        Games::update_many()
+            .col_expr(games::Column::ArchivedAt, Expr::value(Option::<i32>::None))
+            .filter(games::Column::Id.eq(id))
+            .filter(games::Column::ArchivedAt.is_not_null())
+            .exec(db)
+            .await
+            .map(|result| result.rows_affected)
+    }
+
+    pub async fn get_source_bindings(
+        db: &DatabaseConnection,
+        source: &str,
+    ) -> Result<Vec<(i32, String)>, DbErr> {
+        GameSources::find()
+            .select_only()
+            .column(game_sources::Column::GameId)
+            .column(game_sources::Column::ExternalId)
+            .filter(game_sources::Column::Source.eq(source))
+            .filter(game_sources::Column::ExternalId.is_not_null())
+            .into_tuple::<(i32, String)>()
+            .all(db)
+            .await
+    }
+
+    /// 汇总未归档游戏的开发商（自定义数据与各来源），按名称排序并统计游戏数
+    pub async fn find_developer_counts(db: &DatabaseConnection) -> Result<Vec<FacetCount>, DbErr> {
+        Self::find_facet_counts(
+            db,
+            r#"
+            SELECT g.id AS game_id, json_extract(g.custom_data, '$.developer') AS value
+            FROM games AS g
+            WHERE g.archived_at IS NULL
+            UNION ALL
+            SELECT s.game_id, json_extract(s.data, '$.developer')
+            FROM game_sources AS s
+            JOIN games AS g ON g.id = s.game_id
+            WHERE g.archived_at IS NULL
+            "#,
+        )
+        .await
+    }
+
+    /// 将开发商统一改名，返回受影响的游戏数
+    ///
+    /// 同时修改自定义数据与各来源数据中的 `developer`，去除首尾空白后与 `from` 完全相同才会修改，
+    /// 包括已归档的游戏；全部修改在同一事务中完成
+    pub async fn rename_developer(
+        db: &DatabaseConnection,
+        from: &str,
+        to: &str,
+    ) -> Result<u64, DbErr> {
+        let now = chrono::Utc::now().timestamp() as i32;
+        let transaction = db.begin().await?;
+        let statement = |sql: &str, values: Vec<sea_orm::Value>| {
+            Statement::from_sql_and_values(DatabaseBackend::Sqlite, sql, values)
+        };
+
+        let game_ids = transaction
+            .query_all(statement(
+                r#"
+                SELECT id AS game_id FROM games
+                WHERE json_type(custom_data, '$.developer') = 'text'
+                    AND trim(json_extract(custom_data, '$.developer')) = ?
+                UNION
+                SELECT game_id FROM game_sources
+                WHERE json_type(data, '$.developer') = 'text'
+                    AND trim(json_extract(data, '$.developer')) = ?
+                "#,
+                vec![from.into(), from.into()],
+            ))
+            .await?
+            .iter()
+            .map(|row| row.try_get::<i32>("", "game_id"))
+            .collect::<Result<Vec<_>, _>>()?;
+        if game_ids.is_empty() {
+            return Ok(0);
+        }
+
+        transaction
+            .execute(statement(
+                r#"
+                UPDATE games SET custom_data = json_set(custom_data, '$.developer', ?)
+                WHERE json_type(custom_data, '$.developer') = 'text'
+                    AND trim(json_extract(custom_data, '$.developer')) = ?
+                "#,
+                vec![to.into(), from.into()],
+            ))
+            .await?;
+        transaction
+            .execute(statement(
+                r#"
+                UPDATE game_sources SET data = json_set(data, '$.developer', ?)
+                WHERE json_type(data, '$.developer') = 'text'
+                    AND trim(json_extract(data, '$.developer')) = ?
+                "#,
+                vec![to.into(), from.into()],
+            ))
+            .await?;
+        Games::update_many()
+            .col_expr(games::Column::UpdatedAt, Expr::value(now))
+            .filter(games::Column::Id.is_in(game_ids.iter().copied()))
+            .exec(&transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(game_ids.len() as u64)
+    }
+
+    /// 汇总未归档游戏的标签（自定义数据与各来源的标签数组），按名称排序并统计游戏数
+    pub async fn find_tag_counts(db: &DatabaseConnection) -> Result<Vec<FacetCount>, DbErr> {
+        Self::find_facet_counts(
+            db,
+            r#"
+            SELECT g.id AS game_id, tag.value AS value
+            FROM games AS g, json_each(g.custom_data, '$.tags') AS tag
+            WHERE g.archived_at IS NULL
+                AND json_type(g.custom_data, '$.tags') = 'array'
+            UNION ALL
+            SELECT s.game_id, tag.value
+            FROM game_sources AS s
+            JOIN games AS g ON g.id = s.game_id,
+                json_each(s.data, '$.tags') AS tag
+            WHERE g.archived_at IS NULL
+                AND json_type(s.data, '$.tags') = 'array'
+            "#,
+        )
+        .await
+    }
+
+    /// 对 `(game_id, value)` 子查询去除空白后按值分组，同一游戏多处出现只计一次
+    async fn find_facet_counts(
+        db: &DatabaseConnection,
+        values_query: &str,
+    ) -> Result<Vec<FacetCount>, DbErr> {
+        let sql = format!(
+            r#"
+            SELECT trim(value) AS value, COUNT(DISTINCT game_id) AS count
+            FROM ({values_query})
+            WHERE typeof(value) = 'text' AND trim(value) <> ''
+            GROUP BY trim(value)
+            ORDER BY trim(value)
+            "#
+        );
+        FacetCount::find_by_statement(Statement::from_string(DatabaseBackend::Sqlite, sql))
+            .all(db)
+            .await
+    }
+
+    /// 获取所有非空本地路径，用于扫描去重
+    ///
+    /// 返回数据库中所有 `localpath` 字段的集合（仅非 NULL 值），
+    /// 使用 `HashSet` 以便调用方做 O(1) 精确匹配；前缀检查由调用方负责。
+    pub async fn get_all_localpaths(db: &DatabaseConnection) -> Result<HashSet<String>, DbErr> {
+        Games::find()
+            .select_only()
+            .column(games::Column::Localpath)
+            .filter(games::Column::Localpath.is_not_null())
+            .into_tuple::<String>()
+            .all(db)
+            .await
+            .map(|paths| paths.into_iter().collect())
+    }
+
+    /// 获取所有设置了本地路径的游戏 ID 与路径，用于统计磁盘占用
+    pub async fn get_local_game_paths(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<(i32, String)>, DbErr> {
+        Games::find()
+            .select_only()
+            .column(games::Column::Id)
+            .column(games::Column::Localpath)
+            .filter(games::Column::Localpath.is_not_null())
+            .order_by_asc(games::Column::Id)
+            .into_tuple::<(i32, String)>()
+            .all(db)
+            .await
+    }
+
+    fn build_base_query(game_type: GameType, filter: &GameFilter) -> Select<Games> {
+        let mut query = match filter.archived {
+            ArchivedFilter::Exclude => Games::find().filter(games::Column::ArchivedAt.is_null()),
+            ArchivedFilter::Include => Games::find(),
+            ArchivedFilter::Only => Games::find().filter(games::Column::ArchivedAt.is_not_null()),
+        };
+        if let Some(keyword) = filter.normalized_keyword() {
+            let pattern = format!("%{}%", escape_like(&keyword));
+            query = query.filter(Expr::cust_with_values(
+                format!(
+                    "({} OR {} OR {})",
+                    any_name_matches(LIKE_PREDICATE),
+                    source_titles_matches(LIKE_PREDICATE),
+                    custom_metadata_matches(LIKE_PREDICATE)
+                ),
+                std::iter::repeat_n(pattern, 8),
+            ));
+        }
+        if let Some(status) = filter.play_status {
+            query = query.filter(games::Column::Clear.eq(status.value()));
+        }
+        if let Some(tag) = filter.normalized_tag() {
+            query = query.filter(Expr::cust_with_values(TAG_MATCHES, [tag.clone(), tag]));
+        }
+        match game_type {
+            GameType::All => query,
+            GameType::Local => query.filter(games::Column::Localpath.is_not_null()),
+            GameType::Online => query.filter(games::Column::Localpath.is_null()),
+            GameType::IsCustom => query.filter(
+                Condition::any()
+                    .add(games::Column::IdType.eq("custom"))
+                    .add(games::Column::IdType.eq("Whitecloud")),
+            ),
+            GameType::Favorite => query.filter(games::Column::Favorite.eq(true)),
+            GameType::Playing => query.filter(Expr::cust_with_values(
+                "EXISTS (SELECT 1 FROM game_statistics AS ps \
+                 WHERE ps.game_id = games.id AND ps.last_played >= ?)",
+                [filter.playing_since()],
+            )),
+        }
+    }
+
+    /// 发行日期排序：无日期的游戏始终置末尾，升序/降序只影响非空日期。
+    fn apply_date_order(query: Select<Games>, sort_order: SortOrder) -> Select<Games> {
+        let query = query.order_by(Expr::col(games::Column::Date).is_null(), Order::Asc);
+        match sort_order {
+            SortOrder::Asc => query.order_by_asc(games::Column::Date),
+            SortOrder::Desc => query.order_by_desc(games::Column::Date),
+        }
+        .order_by_asc(games::Column::Id)
+    }
+
+    /// 最近游玩排序：无游玩记录始终置末尾，升序按最久优先，降序按最近优先。
+    fn apply_last_played_order(query: Select<Games>, sort_order: SortOrder) -> Select<Games> {
+        let query = query.left_join(game_statistics::Entity).order_by(
+            Expr::col(game_statistics::Column::LastPlayed).is_null(),
+            Order::Asc,
+        );
+        match sort_order {
+            SortOrder::Asc => query.order_by_asc(game_statistics::Column::LastPlayed),
+            SortOrder::Desc => query.order_by_desc(game_statistics::Column::LastPlayed),
+        }
+        .order_by_asc(games::Column::Id)
+    }
+
+    /// 游玩时长排序：无统计记录始终置末尾，升序按最短优先，降序按最长优先。
+    fn apply_playtime_order(query: Select<Games>, sort_order: SortOrder) -> Select<Games> {
+        let query = query.left_join(game_statistics::Entity).order_by(
+            Expr::col(game_statistics::Column::TotalTime).is_null(),
+            Order::Asc,
+        );
+        match sort_order {
+            SortOrder::Asc => query.order_by_asc(game_statistics::Column::TotalTime),
+            SortOrder::Desc => query.order_by_desc(game_statistics::Column::TotalTime),
+        }
+        .order_by_asc(games::Column::Id)
+    }
+
+    /// 应用层排序：按可选数值键排序，None 值统一置末尾
+    fn apply_optional_expression_order(
+        query: Select<Games>,
+        expression: &str,
+        direction: Order,
+    ) -> Select<Games> {
+        query
+            .order_by(Expr::cust(format!("({expression}) IS NULL")), Order::Asc)
+            .order_by(Expr::cust(format!("({expression})")), direction)
+    }
+
+    async fn find_ids_sql(
+        db: &DatabaseConnection,
+        game_type: GameType,
+        sort_option: SortOption,
+        sort_order: SortOrder,
+        filter: &GameFilter,
+    ) -> Result<Vec<i32>, DbErr> {
+        Self::sorted_id_query(game_type, sort_option, sort_order, filter)
+            .into_tuple::<i32>()
+            .all(db)
+            .await
+    }
+
+    /// 构建只选取 ID 的排序查询，名称排序不走 SQL，不能使用
+    fn sorted_id_query(
+        game_type: GameType,
+        sort_option: SortOption,
+        sort_order: SortOrder,
+        filter: &GameFilter,
+    ) -> Select<Games> {
+        let query = Self::build_base_query(game_type, filter)
+            .select_only()
+            .column(games::Column::Id);
+        let query = if filter.favorites_first {
+            query.order_by_desc(games::Column::Favorite)
+        } else {
+            query
+        };
+
+        let query = match sort_option {
+            SortOption::Addtime => match sort_order {
                 SortOrder::Asc => query.order_by_asc(games::Column::Id),
                 SortOrder::Desc => query.order_by_desc(games::Column::Id),
             },
             SortOption::Datetime => Self::apply_date_order(query, sort_order),
             SortOption::LastPlayed => Self::apply_last_played_order(query, sort_order),
+            SortOption::Playtime => Self::apply_playtime_order(query, sort_order),
             SortOption::BGMRank => {
                 let score = "SELECT NULLIF(score, 0) FROM game_sources \
                              WHERE game_id = games.id AND source = 'bgm'";
@@ -760,10 +1751,45 @@ impl GamesRepository {
                     .order_by(games::Column::UserRating, direction)
                     .order_by_asc(games::Column::Id)
             }
+            SortOption::Relevance => match filter.normalized_keyword() {
+                Some(keyword) => {
+                    let prefix = format!("{}%", escape_like(&keyword));
+                    let rank = format!(
+                        "CASE WHEN {} THEN 0 WHEN {} THEN 1 ELSE 2 END",
+                        any_name_matches("= ?"),
+                        any_name_matches(LIKE_PREDICATE)
+                    );
+                    let values = [
+                        keyword.clone(),
+                        keyword.clone(),
+                        keyword,
+                        prefix.clone(),
+                        prefix.clone(),
+                        prefix,
+                    ];
+                    query
+                        .order_by(Expr::cust_with_values(rank, values), Order::Asc)
+                        .order_by_asc(games::Column::Id)
+                }
+                None => match sort_order {
+                    SortOrder::Asc => query.order_by_asc(games::Column::Id),
+                    SortOrder::Desc => query.order_by_desc(games::Column::Id),
+                },
+            },
+            SortOption::Status => {
+                let group = "CASE games.clear WHEN 3 THEN 0 WHEN 4 THEN 1 WHEN 1 THEN 2 \
+                             WHEN 2 THEN 3 WHEN 5 THEN 4 END";
+                let direction = match sort_order {
+                    SortOrder::Asc => Order::Asc,
+                    SortOrder::Desc => Order::Desc,
+                };
+                Self::apply_optional_expression_order(query, group, direction)
+                    .order_by_asc(games::Column::Id)
+            }
             SortOption::Namesort => unreachable!(),
         };
 
-        query.into_tuple::<i32>().all(db).await
+        query
     }
 
     /// 从游戏记录中提取用于排序的显示名称
@@ -777,22 +1803,214 @@ impl GamesRepository {
         game_type: GameType,
         sort_order: SortOrder,
         language: Option<String>,
+        filter: &GameFilter,
     ) -> Result<Vec<i32>, DbErr> {
-        let where_clause = match game_type {
-            GameType::All => "",
-            GameType::Local => "WHERE g.localpath IS NOT NULL",
-            GameType::Online => "WHERE g.localpath IS NULL",
-            GameType::IsCustom => "WHERE g.id_type IN ('custom', 'Whitecloud')",
-        };
-        let sql = format!(
-            r#"
+        let playing_condition = format!(
+            "EXISTS (SELECT 1 FROM game_statistics AS ps \
+             WHERE ps.game_id = g.id AND ps.last_played >= {})",
+            filter.playing_since()
+        );
+        let mut conditions: Vec<&str> = filter.sql_conditions();
+        match game_type {
+            GameType::All => {}
+            GameType::Local => conditions.push("g.localpath IS NOT NULL"),
+            GameType::Online => conditions.push("g.localpath IS NULL"),
+            GameType::IsCustom => conditions.push("g.id_type IN ('custom', 'Whitecloud')"),
+            GameType::Favorite => conditions.push("g.favorite = 1"),
+            GameType::Playing => conditions.push(&playing_condition),
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let mut entries = Self::load_name_entries(db, &where_clause).await?;
+        if let Some(keyword) = filter.normalized_keyword() {
+            entries.retain(|entry| entry.matches_keyword(&keyword));
+        }
+        if let Some(tag) = filter.normalized_tag() {
+            let tagged = Games::find()
+                .select_only()
+                .column(games::Column::Id)
+                .filter(Expr::cust_with_values(TAG_MATCHES, [tag.clone(), tag]))
+                .into_tuple::<i32>()
+                .all(db)
+                .await?
+                .into_iter()
+                .collect::<HashSet<_>>();
+            entries.retain(|entry| tagged.contains(&entry.id));
+        }
+
+        let use_cn = language.as_deref() == Some("zh-CN");
+        let descending = matches!(sort_order, SortOrder::Desc);
+        let favorites_first = filter.favorites_first;
+        entries.sort_by(|left, right| {
+            if favorites_first && left.favorite != right.favorite {
+                return right.favorite.cmp(&left.favorite);
+            }
+            let left_key = Self::name_sort_key(left, use_cn);
+            let right_key = Self::name_sort_key(right, use_cn);
+            match (left_key, right_key) {
+                (None, None) => left.id.cmp(&right.id),
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(left_key), Some(right_key)) => {
+                    let order = left_key.cmp(&right_key);
+                    let order = if descending { order.reverse() } else { order };
+                    order.then_with(|| left.id.cmp(&right.id))
+                }
+            }
+        });
+
+        Ok(entries.into_iter().map(|entry| entry.id).collect())
+    }
+
+    /// 获取游戏的显示名称，优先级与名称排序一致
+    pub async fn find_display_name(
+        db: &DatabaseConnection,
+        id: i32,
+        language: Option<&str>,
+    ) -> Result<Option<String>, DbErr> {
+        let where_clause = format!("WHERE g.id = {}", id);
+        let entries = Self::load_name_entries(db, &where_clause).await?;
+        let use_cn = language == Some("zh-CN");
+
+        Ok(entries
+            .first()
+            .and_then(|entry| Self::display_name(entry, use_cn))
+            .map(str::to_string))
+    }
+
+    /// 获取未归档游戏的全部已知标题（自定义名称与各来源的 name / name_cn），用于按标题匹配
+    pub async fn find_game_titles(db: &DatabaseConnection) -> Result<Vec<GameTitles>, DbErr> {
+        let entries = Self::load_name_entries(db, "WHERE g.archived_at IS NULL").await?;
+        Ok(entries
+            .iter()
+            .map(|entry| {
+                let mut titles = Vec::new();
+                let names = std::iter::once(entry.custom_name.as_deref()).chain(
+                    entry
+                        .sources
+                        .values()
+                        .flat_map(|display| [display.name.as_deref(), display.name_cn.as_deref()]),
+                );
+                for name in names.filter_map(non_empty) {
+                    if !titles.iter().any(|title| title == name) {
+                        titles.push(name.to_string());
+                    }
+                }
+                GameTitles {
+                    id: entry.id,
+                    display_name: Self::display_name(entry, false).map(str::to_string),
+                    titles,
+                }
+            })
+            .collect())
+    }
+
+    /// 首页仪表盘数据：按最近游玩倒序取前 `limit` 个未归档游戏
+    ///
+    /// 一次性批量读取统计、显示名称与封面，未游玩的游戏排在末尾。
+    pub async fn find_dashboard_games(
+        db: &DatabaseConnection,
+        limit: u64,
+        language: Option<&str>,
+    ) -> Result<Vec<DashboardGameData>, DbErr> {
+        let ids = Self::apply_last_played_order(
+            Self::build_base_query(GameType::All, &GameFilter::default()),
+            SortOrder::Desc,
+        )
+        .select_only()
+        .column(games::Column::Id)
+        .limit(limit)
+        .into_tuple::<i32>()
+        .all(db)
+        .await?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut summaries = Self::find_display_summaries(db, &ids, language).await?;
+        let mut statistics: HashMap<i32, game_statistics::Model> = GameStatistics::find()
+            .filter(game_statistics::Column::GameId.is_in(ids.clone()))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|stats| (stats.game_id, stats))
+            .collect();
+
+        Ok(ids
+            .into_iter()
+            .map(|id| {
+                let summary = summaries.remove(&id).unwrap_or_default();
+                let stats = statistics.remove(&id);
+                let total_time = stats.as_ref().and_then(|stats| stats.total_time);
+                DashboardGameData {
+                    id,
+                    name: summary.name,
+                    image: summary.image,
+                    total_time,
+                    total_time_display: format_playtime(i64::from(total_time.unwrap_or(0)) * 60),
+                    session_count: stats.as_ref().and_then(|stats| stats.session_count),
+                    last_played: stats.and_then(|stats| stats.last_played),
+                }
+            })
+            .collect())
+    }
+
+    /// 批量获取游戏的显示名称与封面，不存在的游戏不会出现在结果中
+    pub async fn find_display_summaries(
+        db: &DatabaseConnection,
+        ids: &[i32],
+        language: Option<&str>,
+    ) -> Result<HashMap<i32, GameDisplaySummary>, DbErr> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let id_list = ids
+            .iter()
+            .map(i32::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let where_clause = format!("WHERE g.id IN ({})", id_list);
+        let use_cn = language == Some("zh-CN");
+        Ok(Self::load_name_entries(db, &where_clause)
+            .await?
+            .into_iter()
+            .map(|entry| {
+                let summary = GameDisplaySummary {
+                    name: Self::display_name(&entry, use_cn).map(str::to_string),
+                    image: Self::display_image(&entry).map(str::to_string),
+                };
+                (entry.id, summary)
+            })
+            .collect())
+    }
+
+    async fn load_name_entries(
+        db: &DatabaseConnection,
+        where_clause: &str,
+    ) -> Result<Vec<NameSortEntry>, DbErr> {
+        let sql = format!(
+            r#"
             SELECT
                 g.id,
                 g.id_type,
                 json_extract(g.custom_data, '$.name') AS custom_name,
+                json_extract(g.custom_data, '$.image') AS custom_image,
+                json_extract(g.custom_data, '$.cover_source') AS cover_source,
+                json_extract(g.custom_data, '$.developer') AS custom_developer,
+                json_extract(g.custom_data, '$.aliases') AS custom_aliases,
+                json_extract(g.custom_data, '$.tags') AS custom_tags,
+                g.primary_source,
+                g.favorite,
                 s.source,
                 json_extract(s.data, '$.name') AS source_name,
-                json_extract(s.data, '$.name_cn') AS source_name_cn
+                json_extract(s.data, '$.name_cn') AS source_name_cn,
+                json_extract(s.data, '$.image') AS source_image,
+                json_extract(s.data, '$.aliases') AS source_aliases,
+                json_extract(s.data, '$.all_titles') AS source_titles
             FROM games AS g
             LEFT JOIN game_sources AS s ON s.game_id = g.id
             {where_clause}
@@ -814,6 +2032,12 @@ impl GamesRepository {
                         id: game_id,
                         id_type: row.try_get("", "id_type")?,
                         custom_name: row.try_get("", "custom_name")?,
+                        custom_image: row.try_get("", "custom_image")?,
+                        cover_source: row.try_get("", "cover_source")?,
+                        primary_source: row.try_get("", "primary_source")?,
+                        favorite: row.try_get("", "favorite")?,
+                        custom_keywords: custom_keywords(&row)?,
+                        source_titles: Vec::new(),
                         sources: HashMap::new(),
                     });
                     entries.last_mut().expect("刚插入的名称排序项应存在")
@@ -821,62 +2045,74 @@ impl GamesRepository {
             };
 
             if let Some(source) = row.try_get::<Option<String>>("", "source")? {
+                for column in ["source_aliases", "source_titles"] {
+                    entry.source_titles.extend(json_string_list(&row, column)?);
+                }
                 entry.sources.insert(
                     source,
-                    (
-                        row.try_get("", "source_name")?,
-                        row.try_get("", "source_name_cn")?,
-                    ),
+                    SourceDisplay {
+                        name: row.try_get("", "source_name")?,
+                        name_cn: row.try_get("", "source_name_cn")?,
+                        image: row.try_get("", "source_image")?,
+                    },
                 );
             }
         }
 
-        let use_cn = language.as_deref() == Some("zh-CN");
-        let descending = matches!(sort_order, SortOrder::Desc);
-        entries.sort_by(|left, right| {
-            let left_key = Self::name_sort_key(left, use_cn);
-            let right_key = Self::name_sort_key(right, use_cn);
-            match (left_key, right_key) {
-                (None, None) => left.id.cmp(&right.id),
-                (None, Some(_)) => Ordering::Greater,
-                (Some(_), None) => Ordering::Less,
-                (Some(left_key), Some(right_key)) => {
-                    let order = left_key.cmp(&right_key);
-                    let order = if descending { order.reverse() } else { order };
-                    order.then_with(|| left.id.cmp(&right.id))
-                }
-            }
-        });
-
-        Ok(entries.into_iter().map(|entry| entry.id).collect())
+        Ok(entries)
     }
 
     fn name_sort_key(entry: &NameSortEntry, use_cn: bool) -> Option<String> {
+        Self::display_name(entry, use_cn).map(|name| Self::to_sort_key(name, use_cn))
+    }
+
+    fn display_name(entry: &NameSortEntry, use_cn: bool) -> Option<&str> {
         if let Some(custom_name) = non_empty(entry.custom_name.as_deref()) {
-            return Some(Self::to_sort_key(custom_name, use_cn));
+            return Some(custom_name);
         }
 
-        let source_name = |source: &str| {
-            entry.sources.get(source).and_then(|(name, name_cn)| {
-                if use_cn {
-                    non_empty(name_cn.as_deref()).or_else(|| non_empty(name.as_deref()))
-                } else {
-                    non_empty(name.as_deref())
-                }
+        Self::find_source_value(entry, |display| display.name(use_cn))
+    }
+
+    /// 选取封面：自定义封面 > `primary_source` > `custom_data.cover_source` 指定的来源 >
+    /// 与名称规则一致的来源
+    fn display_image(entry: &NameSortEntry) -> Option<&str> {
+        if let Some(custom_image) = non_empty(entry.custom_image.as_deref()) {
+            return Some(custom_image);
+        }
+
+        entry
+            .primary_display()
+            .and_then(SourceDisplay::image)
+            .or_else(|| {
+                entry
+                    .cover_source
+                    .as_deref()
+                    .and_then(|source| entry.sources.get(source))
+                    .and_then(SourceDisplay::image)
             })
-        };
+            .or_else(|| Self::find_source_value(entry, SourceDisplay::image))
+    }
+
+    /// 优先取 `primary_source` 指定的来源；单一来源游戏取 `id_type` 对应来源，
+    /// mixed/custom 按 `MIXED_NAME_PRIORITY` 依次回退
+    fn find_source_value<'a>(
+        entry: &'a NameSortEntry,
+        value: impl Fn(&'a SourceDisplay) -> Option<&'a str>,
+    ) -> Option<&'a str> {
+        if let Some(primary) = entry.primary_display().and_then(&value) {
+            return Some(primary);
+        }
 
-        let name = if entry.sources.contains_key(entry.id_type.as_str())
+        if entry.sources.contains_key(entry.id_type.as_str())
             && !matches!(entry.id_type.as_str(), "mixed" | "custom" | "Whitecloud")
         {
-            source_name(&entry.id_type)
+            entry.sources.get(entry.id_type.as_str()).and_then(value)
         } else {
             Self::MIXED_NAME_PRIORITY
                 .iter()
-                .find_map(|source| source_name(source))
-        };
-
-        name.map(|name| Self::to_sort_key(name, use_cn))
+                .find_map(|source| entry.sources.get(*source).and_then(&value))
+        }
     }
 
     fn to_sort_key(value: &str, use_cn: bool) -> String {
@@ -903,6 +2139,7 @@ impl GamesRepository {
         file_name: &str,
         backup_time: i32,
         file_size: i32,
+        base_backup_id: Option<i32>,
     ) -> Result<i32, DbErr> {
         let savedata_record = savedata::ActiveModel {
             id: NotSet,
@@ -910,6 +2147,7 @@ impl GamesRepository {
             file: Set(file_name.to_string()),
             backup_time: Set(backup_time),
             file_size: Set(file_size),
+            base_backup_id: Set(base_backup_id),
         };
         let result = savedata_record.insert(db).await?;
         Ok(result.id)
@@ -933,6 +2171,70 @@ impl GamesRepository {
             .await
     }
 
+    /// 全部存档备份记录的 `file_size` 之和（字节），不访问文件系统
+    pub async fn get_total_backup_size(db: &DatabaseConnection) -> Result<i64, DbErr> {
+        let total = Savedata::find()
+            .select_only()
+            .column_as(Expr::col(savedata::Column::FileSize).sum(), "total")
+            .into_tuple::<Option<i64>>()
+            .one(db)
+            .await?;
+        Ok(total.flatten().unwrap_or(0))
+    }
+
+    /// 指定游戏的存档备份记录 `file_size` 之和（字节），不访问文件系统
+    pub async fn get_backup_size_by_game(
+        db: &DatabaseConnection,
+        game_id: i32,
+    ) -> Result<i64, DbErr> {
+        let total = Savedata::find()
+            .select_only()
+            .column_as(Expr::col(savedata::Column::FileSize).sum(), "total")
+            .filter(savedata::Column::GameId.eq(game_id))
+            .into_tuple::<Option<i64>>()
+            .one(db)
+            .await?;
+        Ok(total.flatten().unwrap_or(0))
+    }
+
+    /// 批量获取各游戏最新的一条备份记录（按 `backup_time`，相同时取 id 较大者）
+    ///
+    /// 单次查询完成，没有备份的游戏不会出现在结果中
+    pub async fn get_latest_savedata_records(
+        db: &DatabaseConnection,
+        game_ids: &[i32],
+    ) -> Result<HashMap<i32, savedata::Model>, DbErr> {
+        if game_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let records = Savedata::find()
+            .filter(savedata::Column::GameId.is_in(game_ids.iter().copied()))
+            .filter(Expr::cust(
+                "savedata.id = (SELECT latest.id FROM savedata AS latest \
+                 WHERE latest.game_id = savedata.game_id \
+                 ORDER BY latest.backup_time DESC, latest.id DESC LIMIT 1)",
+            ))
+            .all(db)
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| (record.game_id, record))
+            .collect())
+    }
+
+    /// 获取全部存档备份记录，按游戏分组，同一游戏内按备份时间从新到旧排列
+    pub async fn get_all_savedata_records(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<savedata::Model>, DbErr> {
+        Savedata::find()
+            .order_by_asc(savedata::Column::GameId)
+            .order_by_desc(savedata::Column::BackupTime)
+            .all(db)
+            .await
+    }
+
     pub async fn get_savedata_record_by_id(
         db: &DatabaseConnection,
         backup_id: i32,
@@ -946,6 +2248,212 @@ impl GamesRepository {
     ) -> Result<DeleteResult, DbErr> {
         Savedata::delete_by_id(backup_id).exec(db).await
     }
+
+    /// 删除超出保留数量的最旧备份记录，返回被删除的记录（从新到旧）
+    ///
+    /// 在调用方的事务中执行，备份文件由调用方删除。
+    /// 仍被保留的增量备份依赖的基础备份不会被删除，因此实际保留数量可能多于 `keep_count`。
+    pub async fn prune_savedata_records(
+        txn: &DatabaseTransaction,
+        game_id: i32,
+        keep_count: usize,
+    ) -> Result<Vec<savedata::Model>, DbErr> {
+        let records = Savedata::find()
+            .filter(savedata::Column::GameId.eq(game_id))
+            .all(txn)
+            .await?;
+        let prunable = Self::prunable_savedata_records(records, keep_count, None);
+        if !prunable.is_empty() {
+            Savedata::delete_many()
+                .filter(savedata::Column::Id.is_in(prunable.iter().map(|record| record.id)))
+                .exec(txn)
+                .await?;
+        }
+        Ok(prunable)
+    }
+
+    /// 选出保留最新 `keep_count` 个后可删除的备份（从新到旧）
+    ///
+    /// 从较新的开始检查，依赖某个基础备份的增量备份先被选中后，该基础备份才可删除；
+    /// 仍被保留的备份依赖的基础备份不会被选中。
+    /// `pending_base` 为尚未写入记录的新增量备份所依赖的备份，它及其依赖链都不会被选中
+    pub fn prunable_savedata_records(
+        mut records: Vec<savedata::Model>,
+        keep_count: usize,
+        pending_base: Option<i32>,
+    ) -> Vec<savedata::Model> {
+        records.sort_by_key(|record| Reverse((record.backup_time, record.id)));
+        if records.len() <= keep_count {
+            return Vec::new();
+        }
+        let candidates = records.split_off(keep_count);
+
+        let mut prunable = Vec::new();
+        for record in candidates {
+            if pending_base == Some(record.id)
+                || records
+                    .iter()
+                    .any(|other| other.base_backup_id == Some(record.id))
+            {
+                records.push(record);
+            } else {
+                prunable.push(record);
+            }
+        }
+        prunable
+    }
+}
+
+/// `GameType::Playing` 默认的时间窗口（天）
+const DEFAULT_PLAYING_DAYS: u32 = 14;
+
+/// 带转义的 LIKE 谓词，配合 `escape_like` 使用
+const LIKE_PREDICATE: &str = "LIKE ? ESCAPE '\\'";
+
+/// 自定义数据或任一来源的标签数组中存在小写后等于参数的元素，共含两个占位符
+const TAG_MATCHES: &str = "(EXISTS (SELECT 1 FROM json_each(games.custom_data, '$.tags') AS tag \
+     WHERE json_type(games.custom_data, '$.tags') = 'array' AND lower(trim(tag.value)) = ?) \
+     OR EXISTS (SELECT 1 FROM game_sources AS ts, json_each(ts.data, '$.tags') AS tag \
+     WHERE ts.game_id = games.id AND json_type(ts.data, '$.tags') = 'array' \
+     AND lower(trim(tag.value)) = ?))";
+
+/// 转义 LIKE 通配符，使关键词按字面匹配
+fn escape_like(keyword: &str) -> String {
+    keyword
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// 任一名称列（自定义名称、各来源的 name / name_cn）小写后满足 `predicate` 的 SQL 片段
+///
+/// `predicate` 含一个占位符，生成的片段共含三个占位符
+fn any_name_matches(predicate: &str) -> String {
+    format!(
+        "(lower(json_extract(games.custom_data, '$.name')) {predicate} \
+         OR EXISTS (SELECT 1 FROM game_sources AS ns WHERE ns.game_id = games.id \
+         AND (lower(json_extract(ns.data, '$.name')) {predicate} \
+         OR lower(json_extract(ns.data, '$.name_cn')) {predicate})))"
+    )
+}
+
+/// 任一来源的别名或全部标题（`aliases` / `all_titles`）小写后满足 `predicate` 的 SQL 片段
+///
+/// 多语言游戏库常按日文原名或别名搜索。只匹配数组，与名称排序路径的解析保持一致，
+/// 生成的片段共含两个占位符
+fn source_titles_matches(predicate: &str) -> String {
+    let matches = |path: &str| {
+        format!(
+            "EXISTS (SELECT 1 FROM game_sources AS ts, json_each(ts.data, '{path}') AS title \
+             WHERE ts.game_id = games.id AND json_type(ts.data, '{path}') = 'array' \
+             AND lower(title.value) {predicate})"
+        )
+    };
+    format!("({} OR {})", matches("$.aliases"), matches("$.all_titles"))
+}
+
+/// 自定义数据中的开发商、别名、标签任一项小写后满足 `predicate` 的 SQL 片段
+///
+/// 覆盖没有 BGM / VNDB 等来源的手动添加游戏，生成的片段共含三个占位符
+fn custom_metadata_matches(predicate: &str) -> String {
+    format!(
+        "(lower(json_extract(games.custom_data, '$.developer')) {predicate} \
+         OR EXISTS (SELECT 1 FROM json_each(games.custom_data, '$.aliases') AS alias \
+         WHERE lower(alias.value) {predicate}) \
+         OR EXISTS (SELECT 1 FROM json_each(games.custom_data, '$.tags') AS tag \
+         WHERE lower(tag.value) {predicate}))"
+    )
+}
+
+/// 从名称查询行中收集自定义开发商、别名与标签
+fn custom_keywords(row: &QueryResult) -> Result<Vec<String>, DbErr> {
+    let mut keywords = Vec::new();
+    if let Some(developer) = row.try_get::<Option<String>>("", "custom_developer")? {
+        keywords.push(developer);
+    }
+    for column in ["custom_aliases", "custom_tags"] {
+        keywords.extend(json_string_list(row, column)?);
+    }
+    Ok(keywords)
+}
+
+/// 读取 JSON 字符串数组列，非字符串数组（如手动编辑导致的脏数据）按空列表处理
+fn json_string_list(row: &QueryResult, column: &str) -> Result<Vec<String>, DbErr> {
+    Ok(row
+        .try_get::<Option<String>>("", column)?
+        .and_then(|list| serde_json::from_str::<Vec<String>>(&list).ok())
+        .unwrap_or_default())
+}
+
+/// 搜索片段中关键词前后各保留的字符数
+const SNIPPET_CONTEXT_CHARS: usize = 16;
+
+/// 按与关键词筛选相同的字段找出命中字段与片段，名称优先（`keyword` 需已转为 ASCII 小写）
+fn search_match(game: &FullGameData, keyword: &str) -> Option<(String, String)> {
+    let custom = game.custom_data.as_ref();
+    let string_list = |data: &Value, key: &str| -> Vec<String> {
+        data.get(key)
+            .and_then(|value| serde_json::from_value::<Vec<String>>(value.clone()).ok())
+            .unwrap_or_default()
+    };
+
+    let mut candidates: Vec<(String, String)> = Vec::new();
+    if let Some(name) = custom.and_then(|custom| custom.name.clone()) {
+        candidates.push(("custom.name".to_string(), name));
+    }
+    for source in &game.sources {
+        let Some(data) = &source.data else { continue };
+        for key in ["name", "name_cn"] {
+            if let Some(value) = data.get(key).and_then(Value::as_str) {
+                candidates.push((format!("{}.{}", source.source, key), value.to_string()));
+            }
+        }
+    }
+    for source in &game.sources {
+        let Some(data) = &source.data else { continue };
+        for key in ["aliases", "all_titles"] {
+            for value in string_list(data, key) {
+                candidates.push((format!("{}.{}", source.source, key), value));
+            }
+        }
+    }
+    if let Some(custom) = custom {
+        for alias in custom.aliases.iter().flatten() {
+            candidates.push(("custom.aliases".to_string(), alias.clone()));
+        }
+        if let Some(developer) = &custom.developer {
+            candidates.push(("custom.developer".to_string(), developer.clone()));
+        }
+        for tag in custom.tags.iter().flatten() {
+            candidates.push(("custom.tags".to_string(), tag.clone()));
+        }
+    }
+
+    candidates
+        .into_iter()
+        .find_map(|(field, text)| keyword_snippet(&text, keyword).map(|snippet| (field, snippet)))
+}
+
+/// 截取关键词前后各 `SNIPPET_CONTEXT_CHARS` 个字符，未包含关键词时返回 None
+fn keyword_snippet(text: &str, keyword: &str) -> Option<String> {
+    // ASCII 小写不改变字节长度，匹配位置可直接用于原文
+    let start = text.to_ascii_lowercase().find(keyword)?;
+    let end = start + keyword.len();
+    let before = text[..start].chars().count();
+    let skip = before.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let after = text[end..].chars().count();
+
+    let mut snippet = String::new();
+    if skip > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(text[..start].chars().skip(skip));
+    snippet.push_str(&text[start..end]);
+    snippet.extend(text[end..].chars().take(SNIPPET_CONTEXT_CHARS));
+    if after > SNIPPET_CONTEXT_CHARS {
+        snippet.push('…');
+    }
+    Some(snippet)
 }
 
 fn non_empty(value: Option<&str>) -> Option<&str> {
@@ -956,13 +2464,67 @@ struct NameSortEntry {
     id: i32,
     id_type: String,
     custom_name: Option<String>,
-    sources: HashMap<String, (Option<String>, Option<String>)>,
+    custom_image: Option<String>,
+    cover_source: Option<String>,
+    primary_source: Option<String>,
+    favorite: bool,
+    /// 自定义数据中的开发商、别名与标签，仅用于关键词筛选
+    custom_keywords: Vec<String>,
+    /// 各来源的别名与全部标题，仅用于关键词筛选
+    source_titles: Vec<String>,
+    sources: HashMap<String, SourceDisplay>,
+}
+
+struct SourceDisplay {
+    name: Option<String>,
+    name_cn: Option<String>,
+    image: Option<String>,
+}
+
+impl NameSortEntry {
+    fn primary_display(&self) -> Option<&SourceDisplay> {
+        self.primary_source
+            .as_deref()
+            .and_then(|source| self.sources.get(source))
+    }
+
+    /// 与 SQL 关键词筛选一致：任一名称或自定义元数据包含关键词（`keyword` 需已转为 ASCII 小写）
+    fn matches_keyword(&self, keyword: &str) -> bool {
+        let contains = |name: &Option<String>| {
+            name.as_deref()
+                .is_some_and(|name| name.to_ascii_lowercase().contains(keyword))
+        };
+        contains(&self.custom_name)
+            || self
+                .custom_keywords
+                .iter()
+                .chain(&self.source_titles)
+                .any(|value| value.to_ascii_lowercase().contains(keyword))
+            || self
+                .sources
+                .values()
+                .any(|display| contains(&display.name) || contains(&display.name_cn))
+    }
+}
+
+impl SourceDisplay {
+    fn name(&self, use_cn: bool) -> Option<&str> {
+        if use_cn {
+            non_empty(self.name_cn.as_deref()).or_else(|| non_empty(self.name.as_deref()))
+        } else {
+            non_empty(self.name.as_deref())
+        }
+    }
+
+    fn image(&self) -> Option<&str> {
+        non_empty(self.image.as_deref())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entity::custom_data::CustomData;
+    use crate::entity::custom_data::{CustomData, SourceType};
     use sea_orm::Database;
     use serde_json::json;
 
@@ -988,7 +2550,13 @@ mod tests {
                         CAST(json_extract(custom_data, '$.user_rating') AS REAL)
                     ) VIRTUAL,
                     created_at INTEGER,
-                    updated_at INTEGER
+                    updated_at INTEGER,
+                    archived_at INTEGER,
+                    primary_source TEXT,
+                    favorite INTEGER NOT NULL DEFAULT 0,
+                    launch_args TEXT,
+                    pre_launch_cmd TEXT,
+                    post_exit_cmd TEXT
                 );
                 CREATE TABLE game_sources (
                     game_id INTEGER NOT NULL,
@@ -1020,6 +2588,7 @@ mod tests {
                     file TEXT NOT NULL,
                     backup_time INTEGER NOT NULL,
                     file_size INTEGER NOT NULL,
+                    base_backup_id INTEGER,
                     FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
                 );
                 "#,
@@ -1046,6 +2615,7 @@ mod tests {
             magpie: None,
             custom_data,
             sources,
+            favorite: false,
         }
     }
 
@@ -1142,139 +2712,1684 @@ mod tests {
         )
         .await
         .unwrap();
-        assert_eq!(removed.date, None);
-        assert_eq!(removed.sources.len(), 1);
+        assert_eq!(removed.date, None);
+        assert_eq!(removed.sources.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn insert_checked_rejects_games_bound_to_existing_source_ids() {
+        let database = setup_database().await;
+        let existing = GamesRepository::insert(
+            &database,
+            insert_data(
+                "bgm",
+                None,
+                vec![source("bgm", "1", json!({"name": "Kanon"}))],
+            ),
+        )
+        .await
+        .unwrap();
+
+        let duplicate = GamesRepository::insert_checked(
+            &database,
+            insert_data(
+                "mixed",
+                None,
+                vec![
+                    source("vndb", "v9", json!({"name": "Kanon"})),
+                    source("bgm", "1", json!({"name": "Kanon"})),
+                ],
+            ),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            duplicate,
+            CheckedInsertResult::Duplicate { game_id, ref source, ref external_id }
+                if game_id == existing.id && source == "bgm" && external_id == "1"
+        ));
+        assert_eq!(GamesRepository::count(&database).await.unwrap(), 1);
+
+        // 相同 external_id 但来源不同不算重复
+        let inserted = GamesRepository::insert_checked(
+            &database,
+            insert_data(
+                "vndb",
+                None,
+                vec![source("vndb", "1", json!({"name": "Air"}))],
+            ),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(inserted, CheckedInsertResult::Inserted { .. }));
+        assert_eq!(GamesRepository::count(&database).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn merge_moves_related_rows_and_rebuilds_statistics() {
+        let database = setup_database().await;
+        database
+            .execute_unprepared(
+                r#"
+                CREATE TABLE game_sessions (
+                    session_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    game_id INTEGER NOT NULL,
+                    start_time INTEGER NOT NULL,
+                    end_time INTEGER NOT NULL,
+                    duration INTEGER NOT NULL,
+                    date TEXT NOT NULL,
+                    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+                );
+                CREATE TABLE game_collection_link (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    game_id INTEGER NOT NULL,
+                    collection_id INTEGER NOT NULL,
+                    sort_order INTEGER NOT NULL DEFAULT 0,
+                    created_at INTEGER,
+                    UNIQUE (game_id, collection_id),
+                    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE
+                );
+                "#,
+            )
+            .await
+            .unwrap();
+        let keep = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let mut online = insert_data("bgm", None, vec![source("bgm", "1", json!({}))]);
+        online.localpath = Some("C:/Games/Kanon".to_string());
+        let remove = GamesRepository::insert(&database, online).await.unwrap();
+
+        database
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO game_collection_link (game_id, collection_id) VALUES
+                    ({keep}, 1), ({remove}, 1), ({remove}, 2);
+                INSERT INTO game_sessions (game_id, start_time, end_time, duration, date) VALUES
+                    ({keep}, 1000, 1600, 10, '2024-01-01'),
+                    ({remove}, 2000, 5600, 60, '2024-01-02'),
+                    ({remove}, 9000, 10800, 30, '2024-01-03');
+                INSERT INTO savedata (game_id, file, backup_time, file_size)
+                    VALUES ({remove}, 'savedata_{remove}.7z', 2000, 1);
+                "#,
+                keep = keep.id,
+                remove = remove.id,
+            ))
+            .await
+            .unwrap();
+
+        assert!(
+            GamesRepository::merge(&database, keep.id, keep.id)
+                .await
+                .is_err()
+        );
+        let result = GamesRepository::merge(&database, keep.id, remove.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            MergeGamesResult {
+                sessions_moved: 2,
+                backups_moved: 1,
+                links_moved: 1,
+                links_dropped: 1,
+            }
+        );
+
+        assert!(
+            GamesRepository::find_by_id(&database, remove.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let merged = GamesRepository::find_by_id(&database, keep.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(merged.localpath.is_some());
+        assert_eq!(merged.localpath, remove.localpath);
+        assert!(merged.sources.is_empty());
+
+        let statistics = GameStatistics::find_by_id(keep.id)
+            .one(&database)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(statistics.total_time, Some(100));
+        assert_eq!(statistics.session_count, Some(3));
+        let links = GameCollectionLink::find()
+            .filter(game_collection_link::Column::GameId.eq(keep.id))
+            .count(&database)
+            .await
+            .unwrap();
+        assert_eq!(links, 2);
+        assert_eq!(
+            GamesRepository::get_savedata_count(&database, keep.id)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn update_batch_rolls_back_and_names_failed_game() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(
+            &database,
+            insert_data("bgm", None, vec![source("bgm", "1", json!({"name": "旧"}))]),
+        )
+        .await
+        .unwrap();
+
+        let error = GamesRepository::update_batch(
+            &database,
+            vec![
+                (
+                    game.id,
+                    UpdateGameData {
+                        upsert_sources: Some(vec![source("bgm", "1", json!({"name": "新"}))]),
+                        ..Default::default()
+                    },
+                ),
+                (9999, UpdateGameData::default()),
+            ],
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("更新游戏 9999 失败"), "{error}");
+
+        let stored = GamesRepository::find_by_id(&database, game.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            stored.sources[0]
+                .data
+                .as_ref()
+                .and_then(|data| data.get("name")),
+            Some(&json!("旧"))
+        );
+    }
+
+    #[tokio::test]
+    async fn every_source_shares_insert_and_update_paths() {
+        let database = setup_database().await;
+
+        for name in GamesRepository::MIXED_NAME_PRIORITY {
+            let inserted = GamesRepository::insert(
+                &database,
+                insert_data(name, None, vec![source(name, "1", json!({"name": "旧"}))]),
+            )
+            .await
+            .unwrap();
+            assert_eq!(inserted.sources.len(), 1, "{name} 插入");
+
+            let updated = GamesRepository::update(
+                &database,
+                inserted.id,
+                UpdateGameData {
+                    upsert_sources: Some(vec![source(name, "2", json!({"name": "新"}))]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            let stored = &updated.sources[0];
+            assert_eq!(updated.sources.len(), 1, "{name} 更新");
+            assert_eq!(stored.external_id.as_deref(), Some("2"));
+            assert_eq!(
+                stored.data.as_ref().and_then(|data| data.get("name")),
+                Some(&json!("新"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn sorts_names_with_custom_override_and_stable_id_tie_breaker() {
+        let database = setup_database().await;
+        let first = GamesRepository::insert(
+            &database,
+            insert_data(
+                "bgm",
+                None,
+                vec![source("bgm", "1", json!({"name": "Beta", "name_cn": "乙"}))],
+            ),
+        )
+        .await
+        .unwrap();
+        let second = GamesRepository::insert(
+            &database,
+            insert_data(
+                "bgm",
+                Some(CustomData {
+                    name: Some("Alpha".to_string()),
+                    ..Default::default()
+                }),
+                vec![source("bgm", "2", json!({"name": "Zulu", "name_cn": "甲"}))],
+            ),
+        )
+        .await
+        .unwrap();
+
+        let ids = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::Namesort,
+            SortOrder::Asc,
+            Some("en-US".to_string()),
+            &GameFilter::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids, vec![second.id, first.id]);
+    }
+
+    #[tokio::test]
+    async fn name_sort_keeps_unnamed_games_last_in_both_directions() {
+        let database = setup_database().await;
+        let unnamed = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let mut named = Vec::new();
+        for name in ["Alpha", "Beta"] {
+            let custom_data = CustomData {
+                name: Some(name.to_string()),
+                ..Default::default()
+            };
+            let game = GamesRepository::insert(
+                &database,
+                insert_data("custom", Some(custom_data), Vec::new()),
+            )
+            .await
+            .unwrap();
+            named.push(game.id);
+        }
+
+        let sorted = |sort_order| {
+            GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                SortOption::Namesort,
+                sort_order,
+                None,
+                &GameFilter::default(),
+            )
+        };
+        assert_eq!(
+            sorted(SortOrder::Asc).await.unwrap(),
+            vec![named[0], named[1], unnamed.id]
+        );
+        assert_eq!(
+            sorted(SortOrder::Desc).await.unwrap(),
+            vec![named[1], named[0], unnamed.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn sorts_user_rating_from_generated_column() {
+        let database = setup_database().await;
+        let low = GamesRepository::insert(
+            &database,
+            insert_data(
+                "custom",
+                Some(CustomData {
+                    name: Some("Low".to_string()),
+                    user_rating: Some(4.0),
+                    ..Default::default()
+                }),
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+        let high = GamesRepository::insert(
+            &database,
+            insert_data(
+                "custom",
+                Some(CustomData {
+                    name: Some("High".to_string()),
+                    user_rating: Some(9.0),
+                    ..Default::default()
+                }),
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let ids = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::UserRatingRank,
+            SortOrder::Asc,
+            None,
+            &GameFilter::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids, vec![high.id, low.id]);
+    }
+
+    #[tokio::test]
+    async fn sorts_last_played_chronologically_with_unplayed_last() {
+        let database = setup_database().await;
+        let oldest = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let newest = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let unplayed = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+
+        for (game_id, last_played) in [(oldest.id, 100), (newest.id, 200)] {
+            game_statistics::ActiveModel {
+                game_id: Set(game_id),
+                total_time: Set(Some(0)),
+                session_count: Set(Some(1)),
+                last_played: Set(Some(last_played)),
+                daily_stats: Set(None),
+            }
+            .insert(&database)
+            .await
+            .unwrap();
+        }
+
+        let ascending = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::LastPlayed,
+            SortOrder::Asc,
+            None,
+            &GameFilter::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ascending, vec![oldest.id, newest.id, unplayed.id]);
+
+        let descending = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::LastPlayed,
+            SortOrder::Desc,
+            None,
+            &GameFilter::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(descending, vec![newest.id, oldest.id, unplayed.id]);
+    }
+
+    #[tokio::test]
+    async fn sorts_playtime_with_unplayed_last_and_id_tiebreak() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+                .await
+                .unwrap();
+            ids.push(game.id);
+        }
+        for (game_id, total_time) in [(ids[1], 30), (ids[2], 90), (ids[3], 30)] {
+            game_statistics::ActiveModel {
+                game_id: Set(game_id),
+                total_time: Set(Some(total_time)),
+                session_count: Set(Some(1)),
+                last_played: Set(Some(100)),
+                daily_stats: Set(None),
+            }
+            .insert(&database)
+            .await
+            .unwrap();
+        }
+
+        let sorted = |sort_order| {
+            GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                SortOption::Playtime,
+                sort_order,
+                None,
+                &GameFilter::default(),
+            )
+        };
+        assert_eq!(
+            sorted(SortOrder::Desc).await.unwrap(),
+            vec![ids[2], ids[1], ids[3], ids[0]]
+        );
+        assert_eq!(
+            sorted(SortOrder::Asc).await.unwrap(),
+            vec![ids[1], ids[3], ids[2], ids[0]]
+        );
+    }
+
+    #[tokio::test]
+    async fn playing_type_keeps_games_played_within_window() {
+        let database = setup_database().await;
+        let now = chrono::Utc::now().timestamp() as i32;
+        let mut ids = Vec::new();
+        for last_played in [Some(now - 2 * 86_400), Some(now - 20 * 86_400), None] {
+            let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+                .await
+                .unwrap();
+            if let Some(last_played) = last_played {
+                game_statistics::ActiveModel {
+                    game_id: Set(game.id),
+                    total_time: Set(Some(0)),
+                    session_count: Set(Some(1)),
+                    last_played: Set(Some(last_played)),
+                    daily_stats: Set(None),
+                }
+                .insert(&database)
+                .await
+                .unwrap();
+            }
+            ids.push(game.id);
+        }
+
+        let wider = GameFilter {
+            playing_days: Some(30),
+            ..Default::default()
+        };
+        for sort_option in [SortOption::LastPlayed, SortOption::Namesort] {
+            let playing = |filter| {
+                GamesRepository::find_ids(
+                    &database,
+                    GameType::Playing,
+                    sort_option,
+                    SortOrder::Desc,
+                    None,
+                    filter,
+                )
+            };
+            assert_eq!(playing(&GameFilter::default()).await.unwrap(), vec![ids[0]]);
+            let mut within_month = playing(&wider).await.unwrap();
+            within_month.sort();
+            assert_eq!(within_month, vec![ids[0], ids[1]]);
+        }
+        assert_eq!(
+            GamesRepository::count_matches(&database, GameType::Playing, &wider)
+                .await
+                .unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn dashboard_games_resolve_name_and_cover_by_recent_play() {
+        let database = setup_database().await;
+        let played = GamesRepository::insert(
+            &database,
+            insert_data(
+                "mixed",
+                Some(CustomData {
+                    cover_source: Some(SourceType::Vndb),
+                    ..Default::default()
+                }),
+                vec![
+                    source("bgm", "1", json!({"name": "标题", "image": "bgm.jpg"})),
+                    source("vndb", "v1", json!({"name": "Title", "image": "vndb.jpg"})),
+                ],
+            ),
+        )
+        .await
+        .unwrap();
+        let unplayed = GamesRepository::insert(
+            &database,
+            insert_data(
+                "custom",
+                Some(CustomData {
+                    name: Some("自定义".to_string()),
+                    image: Some("custom.png".to_string()),
+                    ..Default::default()
+                }),
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+        game_statistics::ActiveModel {
+            game_id: Set(played.id),
+            total_time: Set(Some(90)),
+            session_count: Set(Some(3)),
+            last_played: Set(Some(100)),
+            daily_stats: Set(None),
+        }
+        .insert(&database)
+        .await
+        .unwrap();
+
+        let dashboard = GamesRepository::find_dashboard_games(&database, 10, None)
+            .await
+            .unwrap();
+
+        assert_eq!(dashboard.len(), 2);
+        assert_eq!(dashboard[0].id, played.id);
+        assert_eq!(dashboard[0].name.as_deref(), Some("标题"));
+        assert_eq!(dashboard[0].image.as_deref(), Some("vndb.jpg"));
+        assert_eq!(dashboard[0].total_time, Some(90));
+        assert_eq!(dashboard[1].id, unplayed.id);
+        assert_eq!(dashboard[1].name.as_deref(), Some("自定义"));
+        assert_eq!(dashboard[1].image.as_deref(), Some("custom.png"));
+        assert_eq!(dashboard[1].last_played, None);
+
+        let limited = GamesRepository::find_dashboard_games(&database, 1, None)
+            .await
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn set_custom_image_keeps_other_custom_fields() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(
+            &database,
+            insert_data(
+                "custom",
+                Some(CustomData {
+                    name: Some("Title".to_string()),
+                    ..Default::default()
+                }),
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+        let bare = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+
+        for id in [game.id, bare.id] {
+            GamesRepository::set_custom_image(&database, id, Some("png_1".to_string()))
+                .await
+                .unwrap();
+        }
+        let custom_data = |id| {
+            let database = &database;
+            async move {
+                GamesRepository::find_by_id(database, id)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .custom_data
+                    .unwrap_or_default()
+            }
+        };
+        let updated = custom_data(game.id).await;
+        assert_eq!(updated.image.as_deref(), Some("png_1"));
+        assert_eq!(updated.name.as_deref(), Some("Title"));
+        assert_eq!(custom_data(bare.id).await.image.as_deref(), Some("png_1"));
+
+        GamesRepository::set_custom_image(&database, game.id, None)
+            .await
+            .unwrap();
+        let cleared = custom_data(game.id).await;
+        assert_eq!(cleared.image, None);
+        assert_eq!(cleared.name.as_deref(), Some("Title"));
+    }
+
+    #[tokio::test]
+    async fn set_custom_name_overrides_and_falls_back_to_source_name() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(
+            &database,
+            insert_data(
+                "bgm",
+                None,
+                vec![source("bgm", "1", json!({"name": "源名称"}))],
+            ),
+        )
+        .await
+        .unwrap();
+        let display_name = || GamesRepository::find_display_name(&database, game.id, None);
+
+        GamesRepository::set_custom_name(&database, game.id, Some("自定义".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(display_name().await.unwrap().as_deref(), Some("自定义"));
+
+        GamesRepository::set_custom_name(&database, game.id, None)
+            .await
+            .unwrap();
+        assert_eq!(display_name().await.unwrap().as_deref(), Some("源名称"));
+        assert_eq!(
+            GamesRepository::set_custom_name(&database, 999, None)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn latest_savedata_records_pick_newest_backup_per_game() {
+        let database = setup_database().await;
+        let first = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let second = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let without_backup =
+            GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+                .await
+                .unwrap();
+
+        for (game_id, file, backup_time) in [
+            (first.id, "old.7z", 100),
+            (first.id, "new.7z", 300),
+            (first.id, "middle.7z", 200),
+            (second.id, "only.7z", 50),
+        ] {
+            GamesRepository::save_savedata_record(&database, game_id, file, backup_time, 1, None)
+                .await
+                .unwrap();
+        }
+
+        let latest = GamesRepository::get_latest_savedata_records(
+            &database,
+            &[first.id, second.id, without_backup.id],
+        )
+        .await
+        .unwrap();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest[&first.id].file, "new.7z");
+        assert_eq!(latest[&second.id].file, "only.7z");
+        assert!(!latest.contains_key(&without_backup.id));
+    }
+
+    #[tokio::test]
+    async fn backup_sizes_are_summed_from_records() {
+        let database = setup_database().await;
+        let first = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let second = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        assert_eq!(
+            GamesRepository::get_total_backup_size(&database)
+                .await
+                .unwrap(),
+            0
+        );
+
+        for (game_id, file, size) in [(first.id, "a.7z", 100), (first.id, "b.7z", 50)] {
+            GamesRepository::save_savedata_record(&database, game_id, file, 1, size, None)
+                .await
+                .unwrap();
+        }
+        GamesRepository::save_savedata_record(&database, second.id, "c.7z", 1, 25, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            GamesRepository::get_total_backup_size(&database)
+                .await
+                .unwrap(),
+            175
+        );
+        assert_eq!(
+            GamesRepository::get_backup_size_by_game(&database, first.id)
+                .await
+                .unwrap(),
+            150
+        );
+        assert_eq!(
+            GamesRepository::get_backup_size_by_game(&database, 999)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            GamesRepository::get_all_savedata_records(&database)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_savedata_records_keeps_newest_and_their_bases() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let full =
+            GamesRepository::save_savedata_record(&database, game.id, "full.7z", 100, 1, None)
+                .await
+                .unwrap();
+        let old = GamesRepository::save_savedata_record(&database, game.id, "old.7z", 50, 1, None)
+            .await
+            .unwrap();
+        GamesRepository::save_savedata_record(&database, game.id, "inc1.7z", 200, 1, Some(full))
+            .await
+            .unwrap();
+        let inc2 = GamesRepository::save_savedata_record(
+            &database,
+            game.id,
+            "inc2.7z",
+            300,
+            1,
+            Some(full),
+        )
+        .await
+        .unwrap();
+
+        let txn = database.begin().await.unwrap();
+        let pruned = GamesRepository::prune_savedata_records(&txn, game.id, 1)
+            .await
+            .unwrap();
+        txn.commit().await.unwrap();
+
+        let pruned_files = pruned
+            .iter()
+            .map(|record| record.file.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(pruned_files, vec!["inc1.7z", "old.7z"]);
+        let remaining = GamesRepository::get_savedata_records(&database, game.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.id)
+            .collect::<Vec<_>>();
+        assert_eq!(remaining, vec![inc2, full]);
+        assert!(!remaining.contains(&old));
+    }
+
+    #[tokio::test]
+    async fn pending_incremental_base_survives_cleanup_with_single_backup_limit() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let full =
+            GamesRepository::save_savedata_record(&database, game.id, "full.7z", 100, 1, None)
+                .await
+                .unwrap();
+
+        // maxbackups = 1：新备份写入记录前只保留 0 个，待写入的增量备份依赖 full
+        let records = GamesRepository::get_savedata_records(&database, game.id)
+            .await
+            .unwrap();
+        assert!(GamesRepository::prunable_savedata_records(records, 0, Some(full)).is_empty());
+        let inc1 = GamesRepository::save_savedata_record(
+            &database,
+            game.id,
+            "inc1.7z",
+            200,
+            1,
+            Some(full),
+        )
+        .await
+        .unwrap();
+
+        // 下一次增量备份依赖 inc1，inc1 与其基础备份 full 都应保留
+        let records = GamesRepository::get_savedata_records(&database, game.id)
+            .await
+            .unwrap();
+        assert!(GamesRepository::prunable_savedata_records(records, 0, Some(inc1)).is_empty());
+
+        // 完整备份不依赖旧备份，旧的增量链可以整体删除
+        let records = GamesRepository::get_savedata_records(&database, game.id)
+            .await
+            .unwrap();
+        let pruned = GamesRepository::prunable_savedata_records(records, 0, None)
+            .into_iter()
+            .map(|record| record.id)
+            .collect::<Vec<_>>();
+        assert_eq!(pruned, vec![inc1, full]);
+    }
+
+    #[tokio::test]
+    async fn launch_args_round_trip_and_clear() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            GamesRepository::get_launch_args(&database, game.id)
+                .await
+                .unwrap(),
+            None
+        );
+        let args = vec!["-windowed".to_string(), "--lang ja".to_string()];
+        assert_eq!(
+            GamesRepository::set_launch_args(&database, game.id, args.clone())
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            GamesRepository::get_launch_args(&database, game.id)
+                .await
+                .unwrap(),
+            Some(args)
+        );
+
+        GamesRepository::set_launch_args(&database, game.id, Vec::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            GamesRepository::get_launch_args(&database, game.id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(matches!(
+            GamesRepository::get_launch_args(&database, 999).await,
+            Err(DbErr::RecordNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn launch_hooks_are_trimmed_and_blank_commands_cleared() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+
+        GamesRepository::set_launch_hooks(
+            &database,
+            game.id,
+            LaunchHooks {
+                pre_launch_cmd: Some("  mount.sh game.iso ".to_string()),
+                post_exit_cmd: Some("   ".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            GamesRepository::get_launch_hooks(&database, game.id)
+                .await
+                .unwrap(),
+            LaunchHooks {
+                pre_launch_cmd: Some("mount.sh game.iso".to_string()),
+                post_exit_cmd: None,
+            }
+        );
+        assert!(matches!(
+            GamesRepository::get_launch_hooks(&database, 999).await,
+            Err(DbErr::RecordNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn primary_source_overrides_display_resolution() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(
+            &database,
+            insert_data(
+                "mixed",
+                None,
+                vec![
+                    source("bgm", "1", json!({"name": "标题", "image": "bgm.jpg"})),
+                    source("vndb", "v1", json!({"name": "Title", "image": "vndb.jpg"})),
+                ],
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            GamesRepository::set_primary_source(&database, game.id, Some("kun".to_string()))
+                .await
+                .is_err()
+        );
+        GamesRepository::set_primary_source(&database, game.id, Some("vndb".to_string()))
+            .await
+            .unwrap();
+
+        let dashboard = GamesRepository::find_dashboard_games(&database, 1, None)
+            .await
+            .unwrap();
+        assert_eq!(dashboard[0].name.as_deref(), Some("Title"));
+        assert_eq!(dashboard[0].image.as_deref(), Some("vndb.jpg"));
+        let full = GamesRepository::find_by_id(&database, game.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(full.primary_source.as_deref(), Some("vndb"));
+
+        GamesRepository::set_primary_source(&database, game.id, None)
+            .await
+            .unwrap();
+        let name = GamesRepository::find_display_name(&database, game.id, None)
+            .await
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("标题"));
+    }
+
+    #[tokio::test]
+    async fn relevance_sort_ranks_exact_then_prefix_then_substring() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for name in ["The Fate", "Fate/stay night", "Other", "fate"] {
+            let game = GamesRepository::insert(
+                &database,
+                insert_data(
+                    "bgm",
+                    None,
+                    vec![source("bgm", name, json!({ "name": name }))],
+                ),
+            )
+            .await
+            .unwrap();
+            ids.push(game.id);
+        }
+        let filter = GameFilter {
+            keyword: Some(" FATE ".to_string()),
+            ..Default::default()
+        };
+
+        let relevance = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::Relevance,
+            SortOrder::Asc,
+            None,
+            &filter,
+        )
+        .await
+        .unwrap();
+        assert_eq!(relevance, vec![ids[3], ids[1], ids[0]]);
+
+        let by_name = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::Namesort,
+            SortOrder::Asc,
+            None,
+            &filter,
+        )
+        .await
+        .unwrap();
+        assert_eq!(by_name.len(), 3);
+        assert!(!by_name.contains(&ids[2]));
+        assert_eq!(
+            GamesRepository::count_matches(&database, GameType::All, &filter)
+                .await
+                .unwrap(),
+            3
+        );
+
+        let without_keyword = GamesRepository::find_ids(
+            &database,
+            GameType::All,
+            SortOption::Relevance,
+            SortOrder::Asc,
+            None,
+            &GameFilter::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(without_keyword, ids);
+    }
+
+    #[tokio::test]
+    async fn keyword_matches_custom_developer_aliases_and_tags() {
+        let database = setup_database().await;
+        let manual = GamesRepository::insert(
+            &database,
+            insert_data(
+                "custom",
+                Some(CustomData {
+                    name: Some("手动添加".to_string()),
+                    developer: Some("Key".to_string()),
+                    aliases: Some(vec!["Kanon Remake".to_string()]),
+                    tags: Some(vec!["Nakige".to_string()]),
+                    ..Default::default()
+                }),
+                Vec::new(),
+            ),
+        )
+        .await
+        .unwrap();
+        GamesRepository::insert(
+            &database,
+            insert_data(
+                "bgm",
+                None,
+                vec![source("bgm", "1", json!({ "name": "Other" }))],
+            ),
+        )
+        .await
+        .unwrap();
+
+        for keyword in ["key", "REMAKE", "nakige"] {
+            let filter = GameFilter {
+                keyword: Some(keyword.to_string()),
+                ..Default::default()
+            };
+            for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+                let ids = GamesRepository::find_ids(
+                    &database,
+                    GameType::All,
+                    sort_option,
+                    SortOrder::Asc,
+                    None,
+                    &filter,
+                )
+                .await
+                .unwrap();
+                assert_eq!(ids, vec![manual.id], "keyword={keyword}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn keyword_matches_source_aliases_and_all_titles_once_per_game() {
+        let database = setup_database().await;
+        let multilingual = GamesRepository::insert(
+            &database,
+            insert_data(
+                "mixed",
+                None,
+                vec![
+                    source(
+                        "vndb",
+                        "v1",
+                        json!({
+                            "name": "Kanon",
+                            "aliases": ["カノン"],
+                            "all_titles": ["Kanon", "カノン 旧版"]
+                        }),
+                    ),
+                    source(
+                        "bgm",
+                        "1",
+                        json!({ "name": "Kanon", "aliases": ["カノン"] }),
+                    ),
+                ],
+            ),
+        )
+        .await
+        .unwrap();
+        GamesRepository::insert(
+            &database,
+            insert_data(
+                "bgm",
+                None,
+                vec![source(
+                    "bgm",
+                    "2",
+                    json!({ "name": "Air", "aliases": "カノン" }),
+                )],
+            ),
+        )
+        .await
+        .unwrap();
+
+        for keyword in ["カノン", "旧版"] {
+            let filter = GameFilter {
+                keyword: Some(keyword.to_string()),
+                ..Default::default()
+            };
+            for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+                let ids = GamesRepository::find_ids(
+                    &database,
+                    GameType::All,
+                    sort_option,
+                    SortOrder::Asc,
+                    None,
+                    &filter,
+                )
+                .await
+                .unwrap();
+                assert_eq!(ids, vec![multilingual.id], "keyword={keyword}");
+            }
+        }
     }
 
     #[tokio::test]
-    async fn sorts_names_with_custom_override_and_stable_id_tie_breaker() {
+    async fn search_reports_matched_field_with_name_hits_first() {
         let database = setup_database().await;
-        let first = GamesRepository::insert(
+        let by_alias = GamesRepository::insert(
             &database,
             insert_data(
-                "bgm",
+                "vndb",
                 None,
-                vec![source("bgm", "1", json!({"name": "Beta", "name_cn": "乙"}))],
+                vec![source(
+                    "vndb",
+                    "v1",
+                    json!({ "name": "Kanon", "aliases": ["Summer Snow Story"] }),
+                )],
             ),
         )
         .await
         .unwrap();
-        let second = GamesRepository::insert(
+        let by_name = GamesRepository::insert(
             &database,
             insert_data(
-                "bgm",
+                "custom",
                 Some(CustomData {
-                    name: Some("Alpha".to_string()),
+                    name: Some("Snow Globe".to_string()),
                     ..Default::default()
                 }),
-                vec![source("bgm", "2", json!({"name": "Zulu", "name_cn": "甲"}))],
+                Vec::new(),
             ),
         )
         .await
         .unwrap();
 
-        let ids = GamesRepository::find_ids(
+        let hits = GamesRepository::search(
             &database,
+            " SNOW ",
             GameType::All,
-            SortOption::Namesort,
-            SortOrder::Asc,
-            Some("en-US".to_string()),
+            &GameFilter::default(),
+            None,
         )
         .await
         .unwrap();
-        assert_eq!(ids, vec![second.id, first.id]);
+        let summary = hits
+            .iter()
+            .map(|hit| {
+                (
+                    hit.game.id,
+                    hit.matched_field.as_str(),
+                    hit.snippet.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (by_name.id, "custom.name", "Snow Globe"),
+                (by_alias.id, "vndb.aliases", "Summer Snow Story"),
+            ]
+        );
+        assert!(
+            GamesRepository::search(&database, "  ", GameType::All, &GameFilter::default(), None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn keyword_snippet_trims_long_text_around_match() {
+        let text = format!("{}Key{}", "あ".repeat(20), "い".repeat(20));
+        assert_eq!(
+            keyword_snippet(&text, "key"),
+            Some(format!("…{}Key{}…", "あ".repeat(16), "い".repeat(16)))
+        );
+        assert_eq!(keyword_snippet("Kanon", "air"), None);
     }
 
     #[tokio::test]
-    async fn sorts_user_rating_from_generated_column() {
+    async fn facet_counts_merge_custom_and_source_values() {
         let database = setup_database().await;
-        let low = GamesRepository::insert(
+        GamesRepository::insert(
             &database,
             insert_data(
-                "custom",
+                "bgm",
                 Some(CustomData {
-                    name: Some("Low".to_string()),
-                    user_rating: Some(4.0),
+                    developer: Some(" Key ".to_string()),
+                    tags: Some(vec!["Nakige".to_string()]),
                     ..Default::default()
                 }),
-                Vec::new(),
+                vec![source(
+                    "bgm",
+                    "1",
+                    json!({ "developer": "Key", "tags": ["Nakige", "RPG"] }),
+                )],
             ),
         )
         .await
         .unwrap();
-        let high = GamesRepository::insert(
+        GamesRepository::insert(
             &database,
             insert_data(
-                "custom",
-                Some(CustomData {
-                    name: Some("High".to_string()),
-                    user_rating: Some(9.0),
-                    ..Default::default()
-                }),
-                Vec::new(),
+                "vndb",
+                None,
+                vec![source(
+                    "vndb",
+                    "v1",
+                    json!({ "developer": "Alicesoft", "tags": ["RPG"] }),
+                )],
             ),
         )
         .await
         .unwrap();
-
-        let ids = GamesRepository::find_ids(
+        let archived = GamesRepository::insert(
             &database,
-            GameType::All,
-            SortOption::UserRatingRank,
-            SortOrder::Asc,
-            None,
+            insert_data(
+                "vndb",
+                None,
+                vec![source(
+                    "vndb",
+                    "v2",
+                    json!({ "developer": "Hidden", "tags": "RPG" }),
+                )],
+            ),
         )
         .await
         .unwrap();
-        assert_eq!(ids, vec![high.id, low.id]);
+        GamesRepository::archive(&database, archived.id)
+            .await
+            .unwrap();
+
+        let facet = |value: &str, count| FacetCount {
+            value: value.to_string(),
+            count,
+        };
+        assert_eq!(
+            GamesRepository::find_developer_counts(&database)
+                .await
+                .unwrap(),
+            vec![facet("Alicesoft", 1), facet("Key", 1)]
+        );
+        assert_eq!(
+            GamesRepository::find_tag_counts(&database).await.unwrap(),
+            vec![facet("Nakige", 1), facet("RPG", 2)]
+        );
+
+        // 精确匹配（去除首尾空白后），大小写不同或部分匹配的不会被修改
+        assert_eq!(
+            GamesRepository::rename_developer(&database, "key", "Visual Art's/Key")
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            GamesRepository::rename_developer(&database, "Key", "Visual Art's/Key")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            GamesRepository::find_developer_counts(&database)
+                .await
+                .unwrap(),
+            vec![facet("Alicesoft", 1), facet("Visual Art's/Key", 1)]
+        );
     }
 
     #[tokio::test]
-    async fn sorts_last_played_chronologically_with_unplayed_last() {
+    async fn tag_filter_matches_whole_custom_or_source_tags() {
         let database = setup_database().await;
-        let oldest = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+        let tagged = [
+            insert_data(
+                "custom",
+                Some(CustomData {
+                    tags: Some(vec![" Nakige ".to_string()]),
+                    ..Default::default()
+                }),
+                Vec::new(),
+            ),
+            insert_data(
+                "bgm",
+                None,
+                vec![source("bgm", "1", json!({ "tags": ["RPG", "nakige"] }))],
+            ),
+            // 非数组的标签与仅部分匹配的标签都不算
+            insert_data(
+                "vndb",
+                None,
+                vec![source("vndb", "v1", json!({ "tags": "Nakige" }))],
+            ),
+            insert_data(
+                "vndb",
+                None,
+                vec![source("vndb", "v2", json!({ "tags": ["Nakige RPG"] }))],
+            ),
+        ];
+        let mut ids = Vec::new();
+        for data in tagged {
+            ids.push(GamesRepository::insert(&database, data).await.unwrap().id);
+        }
+
+        let filter = GameFilter {
+            tag: Some("NAKIGE".to_string()),
+            ..Default::default()
+        };
+        for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+            let matched = GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                sort_option,
+                SortOrder::Asc,
+                None,
+                &filter,
+            )
             .await
             .unwrap();
-        let newest = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            assert_eq!(matched, vec![ids[0], ids[1]]);
+        }
+        assert_eq!(
+            GamesRepository::count_matches(&database, GameType::All, &filter)
+                .await
+                .unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn games_can_be_read_page_by_page() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for name in ["E", "D", "C", "B", "A"] {
+            let custom_data = CustomData {
+                name: Some(name.to_string()),
+                ..Default::default()
+            };
+            let game = GamesRepository::insert(
+                &database,
+                insert_data("custom", Some(custom_data), Vec::new()),
+            )
             .await
             .unwrap();
-        let unplayed = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            ids.push(game.id);
+        }
+
+        let page = |sort_option, page| {
+            let database = database.clone();
+            async move {
+                GamesRepository::find_page(
+                    &database,
+                    GameType::All,
+                    sort_option,
+                    SortOrder::Asc,
+                    None,
+                    &GameFilter::default(),
+                    page,
+                    2,
+                )
+                .await
+                .unwrap()
+            }
+        };
+        let page_ids = |page: &GamePage| page.games.iter().map(|game| game.id).collect::<Vec<_>>();
+
+        let second = page(SortOption::Addtime, 1).await;
+        assert_eq!(second.total, 5);
+        assert_eq!(page_ids(&second), vec![ids[2], ids[3]]);
+
+        let first_by_name = page(SortOption::Namesort, 0).await;
+        assert_eq!(first_by_name.total, 5);
+        assert_eq!(page_ids(&first_by_name), vec![ids[4], ids[3]]);
+
+        for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+            let last = page(sort_option, 2).await;
+            assert_eq!(last.games.len(), 1);
+            assert!(page(sort_option, 3).await.games.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn favorites_can_be_listed_alone_or_sorted_first() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for name in ["A", "B", "C"] {
+            let custom_data = CustomData {
+                name: Some(name.to_string()),
+                ..Default::default()
+            };
+            let game = GamesRepository::insert(
+                &database,
+                insert_data("custom", Some(custom_data), Vec::new()),
+            )
             .await
             .unwrap();
+            ids.push(game.id);
+        }
 
-        for (game_id, last_played) in [(oldest.id, 100), (newest.id, 200)] {
-            game_statistics::ActiveModel {
-                game_id: Set(game_id),
-                total_time: Set(Some(0)),
-                session_count: Set(Some(1)),
-                last_played: Set(Some(last_played)),
-                daily_stats: Set(None),
+        assert_eq!(
+            GamesRepository::toggle_favorite(&database, ids[2])
+                .await
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            GamesRepository::toggle_favorite(&database, 999)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(
+            GamesRepository::find_by_id(&database, ids[2])
+                .await
+                .unwrap()
+                .unwrap()
+                .favorite
+        );
+
+        let favorites_first = GameFilter {
+            favorites_first: true,
+            ..Default::default()
+        };
+        for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+            let only_favorites = GamesRepository::find_ids(
+                &database,
+                GameType::Favorite,
+                sort_option,
+                SortOrder::Asc,
+                None,
+                &GameFilter::default(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(only_favorites, vec![ids[2]]);
+
+            let sorted = GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                sort_option,
+                SortOrder::Asc,
+                None,
+                &favorites_first,
+            )
+            .await
+            .unwrap();
+            assert_eq!(sorted, vec![ids[2], ids[0], ids[1]]);
+        }
+
+        assert_eq!(
+            GamesRepository::toggle_favorite(&database, ids[2])
+                .await
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[tokio::test]
+    async fn set_local_path_moves_game_between_local_and_online() {
+        let database = setup_database().await;
+        let game = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let ids_of = |game_type: GameType| {
+            let database = database.clone();
+            async move {
+                GamesRepository::find_ids(
+                    &database,
+                    game_type,
+                    SortOption::Addtime,
+                    SortOrder::Asc,
+                    None,
+                    &GameFilter::default(),
+                )
+                .await
+                .unwrap()
             }
-            .insert(&database)
+        };
+        assert_eq!(ids_of(GameType::Online).await, vec![game.id]);
+
+        let affected =
+            GamesRepository::set_local_path(&database, game.id, Some("/games/a.exe".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(affected, 1);
+        assert_eq!(ids_of(GameType::Local).await, vec![game.id]);
+        assert!(ids_of(GameType::Online).await.is_empty());
+
+        GamesRepository::set_local_path(&database, game.id, None)
+            .await
+            .unwrap();
+        assert_eq!(ids_of(GameType::Online).await, vec![game.id]);
+        assert_eq!(
+            GamesRepository::set_local_path(&database, 999, None)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn play_status_can_be_set_filtered_and_grouped() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for name in ["A", "B", "C", "D"] {
+            let custom_data = CustomData {
+                name: Some(name.to_string()),
+                ..Default::default()
+            };
+            let game = GamesRepository::insert(
+                &database,
+                insert_data("custom", Some(custom_data), Vec::new()),
+            )
             .await
             .unwrap();
+            ids.push(game.id);
         }
 
-        let ascending = GamesRepository::find_ids(
+        assert_eq!(
+            GamesRepository::get_play_status(&database, ids[0])
+                .await
+                .unwrap(),
+            Some(PlayStatus::Planning)
+        );
+        for (id, status) in [
+            (ids[0], PlayStatus::Cleared),
+            (ids[1], PlayStatus::Playing),
+            (ids[3], PlayStatus::Playing),
+        ] {
+            assert_eq!(
+                GamesRepository::set_play_status(&database, id, status)
+                    .await
+                    .unwrap(),
+                1
+            );
+        }
+        assert_eq!(
+            GamesRepository::set_play_status(&database, 999, PlayStatus::Dropped)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            GamesRepository::find_by_id(&database, ids[0])
+                .await
+                .unwrap()
+                .unwrap()
+                .clear,
+            Some(PlayStatus::Cleared.value())
+        );
+
+        let playing = GameFilter {
+            play_status: Some(PlayStatus::Playing),
+            ..Default::default()
+        };
+        for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+            let filtered = GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                sort_option,
+                SortOrder::Asc,
+                None,
+                &playing,
+            )
+            .await
+            .unwrap();
+            assert_eq!(filtered, vec![ids[1], ids[3]]);
+        }
+        assert_eq!(
+            GamesRepository::count_matches(&database, GameType::All, &playing)
+                .await
+                .unwrap(),
+            2
+        );
+
+        let grouped = GamesRepository::find_ids(
             &database,
             GameType::All,
-            SortOption::LastPlayed,
+            SortOption::Status,
             SortOrder::Asc,
             None,
+            &GameFilter::default(),
         )
         .await
         .unwrap();
-        assert_eq!(ascending, vec![oldest.id, newest.id, unplayed.id]);
+        assert_eq!(grouped, vec![ids[1], ids[3], ids[2], ids[0]]);
+    }
 
-        let descending = GamesRepository::find_ids(
-            &database,
-            GameType::All,
-            SortOption::LastPlayed,
-            SortOrder::Desc,
-            None,
-        )
-        .await
-        .unwrap();
-        assert_eq!(descending, vec![newest.id, oldest.id, unplayed.id]);
+    #[tokio::test]
+    async fn archived_games_are_hidden_until_unarchived() {
+        let database = setup_database().await;
+        let kept = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+        let archived = GamesRepository::insert(&database, insert_data("custom", None, Vec::new()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            GamesRepository::archive(&database, archived.id)
+                .await
+                .unwrap(),
+            1
+        );
+        // 重复归档不刷新归档时间
+        assert_eq!(
+            GamesRepository::archive(&database, archived.id)
+                .await
+                .unwrap(),
+            0
+        );
+
+        for sort_option in [SortOption::Addtime, SortOption::Namesort] {
+            let visible = GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                sort_option,
+                SortOrder::Asc,
+                None,
+                &GameFilter::default(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(visible, vec![kept.id]);
+
+            let only_archived = GamesRepository::find_ids(
+                &database,
+                GameType::All,
+                sort_option,
+                SortOrder::Asc,
+                None,
+                &GameFilter {
+                    archived: ArchivedFilter::Only,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+            assert_eq!(only_archived, vec![archived.id]);
+        }
+        assert_eq!(GamesRepository::count(&database).await.unwrap(), 1);
+        assert!(
+            GamesRepository::find_by_id(&database, archived.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .archived_at
+                .is_some()
+        );
+
+        GamesRepository::unarchive(&database, archived.id)
+            .await
+            .unwrap();
+        assert_eq!(GamesRepository::count(&database).await.unwrap(), 2);
     }
 }
@@ -0,0 +1,123 @@
+//! SQLite 忙/锁定错误的重试。
+//!
+//! 备份与查询并发时 SQLite 可能返回 `SQLITE_BUSY` / `SQLITE_LOCKED`，
+//! 写操作在放弃前按指数退避重试，避免偶发的写入失败直接暴露给用户。
+
+use sea_orm::DbErr;
+use std::future::Future;
+use std::time::Duration;
+
+/// 写操作默认最大尝试次数（含首次）
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+/// 首次重试前的等待时间，之后每次翻倍
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(50);
+
+/// 判断错误是否为 SQLite 忙/锁定错误
+pub fn is_busy_error(error: &DbErr) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("database is locked")
+        || message.contains("database table is locked")
+        || message.contains("database is busy")
+        || message.contains("sqlite_busy")
+        || message.contains("sqlite_locked")
+}
+
+/// 执行操作，遇到忙/锁定错误时按指数退避重试
+///
+/// `op` 每次调用都应开启新的事务；其他错误立即返回，不会重试。
+pub async fn with_retry<T, F, Fut>(
+    mut op: F,
+    max_attempts: u32,
+    backoff: Duration,
+) -> Result<T, DbErr>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DbErr>>,
+{
+    let max_attempts = max_attempts.max(1);
+    let mut delay = backoff;
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(error) if attempt < max_attempts && is_busy_error(&error) => {
+                log::warn!(
+                    "数据库忙，{}ms 后重试 ({}/{}): {}",
+                    delay.as_millis(),
+                    attempt,
+                    max_attempts,
+                    error
+                );
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// 使用默认参数的 [`with_retry`]
+pub async fn with_write_retry<T, F, Fut>(op: F) -> Result<T, DbErr>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DbErr>>,
+{
+    with_retry(op, DEFAULT_MAX_ATTEMPTS, DEFAULT_BACKOFF).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn retries_only_busy_errors() {
+        let counter = AtomicU32::new(0);
+        let calls = &counter;
+        let result = with_retry(
+            || async move {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(DbErr::Custom("database is locked".to_string()))
+                } else {
+                    Ok(42)
+                }
+            },
+            5,
+            Duration::from_millis(1),
+        )
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let counter = AtomicU32::new(0);
+        let calls = &counter;
+        let result: Result<(), DbErr> = with_retry(
+            || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(DbErr::Custom("constraint failed".to_string()))
+            },
+            5,
+            Duration::from_millis(1),
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let counter = AtomicU32::new(0);
+        let calls = &counter;
+        let result: Result<(), DbErr> = with_retry(
+            || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(DbErr::Custom("database is locked".to_string()))
+            },
+            3,
+            Duration::from_millis(1),
+        )
+        .await;
+        assert!(is_busy_error(&result.unwrap_err()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,18 +1,40 @@
+use chrono::NaiveDate;
 use sea_orm::DatabaseConnection;
-use tauri::State;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Runtime, State};
 
+use crate::backup::incremental::manifest_path;
+use crate::backup::savedata::{
+    BackupInfo, SAVEDATA_BACKUP_CREATED_EVENT, SavedataBackupCreated, delete_game_backup_dir,
+    resolve_savedata_backup_root,
+};
 use crate::database::dto::{
-    BatchOperationResult, FullGameData, InsertCollectionData, InsertGameData, UpdateCollectionData,
-    UpdateGameData, UpdateSettingsData,
+    BackupSizeSummary, BatchOperationResult, CheckedInsertResult, DashboardGameData,
+    DeleteGameCompleteResult, FullGameData, GamePage, GamePlayedOnDate, InsertCollectionData,
+    InsertGameData, SearchHit, SessionFeedItem, TotalPlaytime, UpdateCollectionData,
+    UpdateGameData, UpdateSettingsData, clean_local_path,
 };
 use crate::database::repository::{
-    collections_repository::{CategoryWithCount, CollectionsRepository},
-    game_stats_repository::{GameLastPlayed, GameStatsRepository},
-    games_repository::{GameType, GamesRepository, SortOption, SortOrder},
+    collections_repository::{
+        CategoryWithCount, CollectionWithDepth, CollectionsRepository, MergeCollectionsResult,
+        MoveGamesResult,
+    },
+    game_stats_repository::{
+        CompletionStats, GameLastPlayed, GamePlaytimeSince, GameStatsRepository, HeatmapDay,
+        PlaytimeSince, parse_imported_sessions,
+    },
+    games_repository::{
+        FacetCount, GameFilter, GameType, GamesRepository, LaunchHooks, MergeGamesResult,
+        PlayStatus, SortOption, SortOrder,
+    },
     settings_repository::SettingsRepository,
 };
 use crate::entity::{savedata, user};
+use crate::game::cover::cloud::get_game_cover_dir;
 use crate::game::cover::{DownloadState, delete_game_cover_dir};
+use crate::utils::fs::{FileCleanupReport, move_file, remove_dir_with_report};
+use crate::utils::playtime::format_playtime;
 
 // ==================== 游戏数据相关 ====================
 
@@ -27,6 +49,17 @@ pub async fn insert_game(
         .map_err(|e| format!("插入游戏数据失败: {}", e))
 }
 
+/// 插入游戏数据，来源 ID 已被其他游戏绑定时不插入并返回冲突的游戏 ID
+#[tauri::command]
+pub async fn insert_game_checked(
+    db: State<'_, DatabaseConnection>,
+    game: InsertGameData,
+) -> Result<CheckedInsertResult, String> {
+    GamesRepository::insert_checked(&db, game)
+        .await
+        .map_err(|e| format!("插入游戏数据失败: {}", e))
+}
+
 #[tauri::command]
 pub async fn insert_games_batch(
     db: State<'_, DatabaseConnection>,
@@ -54,12 +87,47 @@ pub async fn find_all_games(
     sort_option: SortOption,
     sort_order: SortOrder,
     language: Option<String>,
+    filter: Option<GameFilter>,
 ) -> Result<Vec<FullGameData>, String> {
-    GamesRepository::find_all(&db, game_type, sort_option, sort_order, language)
+    let filter = filter.unwrap_or_default();
+    GamesRepository::find_all(&db, game_type, sort_option, sort_order, language, &filter)
         .await
         .map_err(|e| format!("获取游戏数据失败: {}", e))
 }
 
+/// 分页获取游戏数据，`page` 从 0 开始
+///
+/// 大型游戏库无需一次性加载全部游戏及关联数据，返回值附带总数便于计算页数。
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn find_games_page(
+    db: State<'_, DatabaseConnection>,
+    game_type: GameType,
+    sort_option: SortOption,
+    sort_order: SortOrder,
+    language: Option<String>,
+    filter: Option<GameFilter>,
+    page: u64,
+    page_size: u64,
+) -> Result<GamePage, String> {
+    if page_size == 0 {
+        return Err("每页数量必须大于 0".to_string());
+    }
+    let filter = filter.unwrap_or_default();
+    GamesRepository::find_page(
+        &db,
+        game_type,
+        sort_option,
+        sort_order,
+        language,
+        &filter,
+        page,
+        page_size,
+    )
+    .await
+    .map_err(|e| format!("分页获取游戏数据失败: {}", e))
+}
+
 /// 只返回排序/筛选后的游戏 ID 列表
 ///
 /// 前端已缓存完整游戏数据，切换排序/筛选时只需传输 ID 数组，
@@ -71,8 +139,10 @@ pub async fn find_game_ids(
     sort_option: SortOption,
     sort_order: SortOrder,
     language: Option<String>,
+    filter: Option<GameFilter>,
 ) -> Result<Vec<i32>, String> {
-    GamesRepository::find_ids(&db, game_type, sort_option, sort_order, language)
+    let filter = filter.unwrap_or_default();
+    GamesRepository::find_ids(&db, game_type, sort_option, sort_order, language, &filter)
         .await
         .map_err(|e| format!("获取游戏 ID 列表失败: {}", e))
 }
@@ -119,6 +189,132 @@ pub async fn delete_game(
     Ok(rows_affected)
 }
 
+/// 彻底删除游戏，并清理其封面目录与存档备份目录中的文件
+///
+/// 先删除数据库记录，文件清理失败不会阻止游戏从库中移除，失败项记录在返回结果中
+#[tauri::command]
+pub async fn delete_game_complete(
+    db: State<'_, DatabaseConnection>,
+    cover_state: State<'_, DownloadState>,
+    game_id: i32,
+) -> Result<DeleteGameCompleteResult, String> {
+    let rows_affected = GamesRepository::delete(&db, game_id)
+        .await
+        .map(|result| result.rows_affected)
+        .map_err(|e| format!("删除游戏失败: {}", e))?;
+
+    let mut files = FileCleanupReport::default();
+    if rows_affected == 0 {
+        return Ok(DeleteGameCompleteResult {
+            rows_affected,
+            files,
+        });
+    }
+    cover_state.mark_game_deleted(game_id as u32).await;
+
+    let mut dirs = Vec::new();
+    match get_game_cover_dir(game_id as u32) {
+        Ok(cover_dir) => dirs.push(cover_dir),
+        Err(err) => log::warn!("获取游戏封面目录失败 game_id={}: {}", game_id, err),
+    }
+    match resolve_savedata_backup_root(&db).await {
+        Ok(backup_root) => dirs.push(backup_root.join(format!("game_{}", game_id))),
+        Err(err) => log::warn!("获取存档备份目录失败 game_id={}: {}", game_id, err),
+    }
+    let files = tokio::task::spawn_blocking(move || {
+        for dir in dirs {
+            remove_dir_with_report(&dir, &mut files);
+        }
+        files
+    })
+    .await
+    .map_err(|e| format!("清理游戏文件任务失败: {}", e))?;
+
+    log::info!(
+        "游戏彻底删除完成 game_id={} removed={} failed={}",
+        game_id,
+        files.removed.len(),
+        files.failed.len()
+    );
+
+    Ok(DeleteGameCompleteResult {
+        rows_affected,
+        files,
+    })
+}
+
+/// 合并重复游戏，将被移除游戏的会话、存档备份与合集关联转移到保留的游戏
+///
+/// 数据库合并在单个事务中完成；备份文件随后移动到保留游戏的备份目录，
+/// 文件移动失败只记录日志，不回滚已完成的合并
+#[tauri::command]
+pub async fn merge_games(
+    db: State<'_, DatabaseConnection>,
+    cover_state: State<'_, DownloadState>,
+    keep_id: i32,
+    remove_id: i32,
+) -> Result<MergeGamesResult, String> {
+    let result = GamesRepository::merge(&db, keep_id, remove_id)
+        .await
+        .map_err(|e| format!("合并游戏失败: {}", e))?;
+    cover_state.mark_game_deleted(remove_id as u32).await;
+
+    match resolve_savedata_backup_root(&db).await {
+        Ok(backup_root) => {
+            let from_dir = backup_root.join(format!("game_{}", remove_id));
+            let to_dir = backup_root.join(format!("game_{}", keep_id));
+            tokio::task::spawn_blocking(move || move_backup_files(&from_dir, &to_dir))
+                .await
+                .map_err(|e| format!("移动存档备份文件任务失败: {}", e))?;
+        }
+        Err(err) => log::warn!("获取存档备份目录失败 game_id={}: {}", remove_id, err),
+    }
+
+    if let Err(err) = delete_game_cover_dir(remove_id).await {
+        log::warn!("删除游戏封面目录失败 game_id={}: {}", remove_id, err);
+    }
+
+    log::info!(
+        "游戏合并完成 keep_id={} remove_id={} sessions={} backups={} links_moved={} links_dropped={}",
+        keep_id,
+        remove_id,
+        result.sessions_moved,
+        result.backups_moved,
+        result.links_moved,
+        result.links_dropped
+    );
+
+    Ok(result)
+}
+
+/// 将一个游戏备份目录中的文件移动到另一个游戏的备份目录，同名文件保留在原处
+fn move_backup_files(from_dir: &Path, to_dir: &Path) {
+    let entries = match std::fs::read_dir(from_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+        Err(err) => {
+            log::warn!("读取备份目录失败 {}: {}", from_dir.display(), err);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let to = to_dir.join(entry.file_name());
+        if to.exists() {
+            log::warn!("目标备份文件已存在，跳过移动: {}", to.display());
+            continue;
+        }
+        if let Err(err) = move_file(&from, &to) {
+            log::warn!("移动备份文件失败 {}: {}", from.display(), err);
+        }
+    }
+
+    if let Err(err) = std::fs::remove_dir(from_dir) {
+        log::debug!("备份目录未清空，保留 {}: {}", from_dir.display(), err);
+    }
+}
+
 /// 批量删除游戏
 #[tauri::command]
 pub async fn delete_games_batch(
@@ -157,6 +353,228 @@ pub async fn delete_games_batch(
     Ok(rows_affected)
 }
 
+/// 读取游戏保存的启动参数，未保存时返回 null
+#[tauri::command]
+pub async fn get_launch_args(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+) -> Result<Option<Vec<String>>, String> {
+    GamesRepository::get_launch_args(&db, game_id)
+        .await
+        .map_err(|e| format!("读取启动参数失败: {}", e))
+}
+
+/// 保存游戏的启动参数，传空数组清除
+#[tauri::command]
+pub async fn set_launch_args(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    args: Vec<String>,
+) -> Result<u64, String> {
+    GamesRepository::set_launch_args(&db, game_id, args)
+        .await
+        .map_err(|e| format!("保存启动参数失败: {}", e))
+}
+
+/// 读取游戏的启动前与退出后命令
+#[tauri::command]
+pub async fn get_launch_hooks(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+) -> Result<LaunchHooks, String> {
+    GamesRepository::get_launch_hooks(&db, game_id)
+        .await
+        .map_err(|e| format!("读取钩子命令失败: {}", e))
+}
+
+/// 保存游戏的启动前与退出后命令，空白命令视为未设置
+#[tauri::command]
+pub async fn set_launch_hooks(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    hooks: LaunchHooks,
+) -> Result<u64, String> {
+    GamesRepository::set_launch_hooks(&db, game_id, hooks)
+        .await
+        .map_err(|e| format!("保存钩子命令失败: {}", e))
+}
+
+/// 设置游戏的主数据源（来源名或 `custom`），传 null 恢复自动选择
+#[tauri::command]
+pub async fn set_primary_source(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    source: Option<String>,
+) -> Result<u64, String> {
+    GamesRepository::set_primary_source(&db, game_id, source)
+        .await
+        .map_err(|e| format!("设置主数据源失败: {}", e))
+}
+
+/// 切换游戏的收藏标记，返回切换后的值
+#[tauri::command]
+pub async fn toggle_favorite(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+) -> Result<bool, String> {
+    GamesRepository::toggle_favorite(&db, game_id)
+        .await
+        .map_err(|e| format!("切换收藏失败: {}", e))?
+        .ok_or_else(|| format!("游戏不存在: game_id={}", game_id))
+}
+
+/// 设置或清除游戏的本地路径
+///
+/// 用于关联已安装的游戏或标记游戏已卸载。设置时路径必须存在；
+/// 传 null 或空字符串清除，游戏随即归为"在线"类型。
+#[tauri::command]
+pub async fn set_local_path(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    path: Option<String>,
+) -> Result<(), String> {
+    let path = path.and_then(clean_local_path);
+    if let Some(path) = &path
+        && !std::path::Path::new(path).exists()
+    {
+        return Err(format!("路径不存在: {}", path));
+    }
+
+    let rows_affected = GamesRepository::set_local_path(&db, game_id, path)
+        .await
+        .map_err(|e| format!("设置本地路径失败: {}", e))?;
+    if rows_affected == 0 {
+        return Err(format!("游戏不存在: game_id={}", game_id));
+    }
+    Ok(())
+}
+
+/// 获取游戏状态，状态值无效时返回 None
+#[tauri::command]
+pub async fn get_game_status(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+) -> Result<Option<PlayStatus>, String> {
+    GamesRepository::get_play_status(&db, game_id)
+        .await
+        .map_err(|e| format!("获取游戏状态失败: {}", e))
+}
+
+/// 设置游戏状态
+#[tauri::command]
+pub async fn set_game_status(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    status: PlayStatus,
+) -> Result<(), String> {
+    let affected = GamesRepository::set_play_status(&db, game_id, status)
+        .await
+        .map_err(|e| format!("设置游戏状态失败: {}", e))?;
+    if affected == 0 {
+        return Err(format!("游戏不存在: game_id={}", game_id));
+    }
+    Ok(())
+}
+
+/// 重命名游戏，返回生效的显示名称
+///
+/// 名称会去除首尾空白；空字符串表示清除自定义名称，回退到数据源名称。
+#[tauri::command]
+pub async fn rename_game(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    name: String,
+    language: Option<String>,
+) -> Result<Option<String>, String> {
+    let name = Some(name.trim().to_string()).filter(|name| !name.is_empty());
+    let affected = GamesRepository::set_custom_name(&db, game_id, name)
+        .await
+        .map_err(|e| format!("重命名游戏失败: {}", e))?;
+    if affected == 0 {
+        return Err(format!("游戏不存在: game_id={}", game_id));
+    }
+
+    GamesRepository::find_display_name(&db, game_id, language.as_deref())
+        .await
+        .map_err(|e| format!("获取游戏名称失败: {}", e))
+}
+
+/// 归档（软删除）游戏
+///
+/// 归档后游戏默认不再出现在列表中，游玩记录与存档备份保留，可通过 `unarchive_game` 恢复
+#[tauri::command]
+pub async fn archive_game(db: State<'_, DatabaseConnection>, id: i32) -> Result<u64, String> {
+    GamesRepository::archive(&db, id)
+        .await
+        .map_err(|e| format!("归档游戏失败: {}", e))
+}
+
+/// 取消归档游戏
+#[tauri::command]
+pub async fn unarchive_game(db: State<'_, DatabaseConnection>, id: i32) -> Result<u64, String> {
+    GamesRepository::unarchive(&db, id)
+        .await
+        .map_err(|e| format!("取消归档游戏失败: {}", e))
+}
+
+/// 获取最近一段时间内归档的游戏，用于删除后的"撤销"提示
+#[tauri::command]
+pub async fn get_recently_archived(
+    db: State<'_, DatabaseConnection>,
+    within_seconds: i64,
+) -> Result<Vec<FullGameData>, String> {
+    let since = chrono::Utc::now().timestamp() - within_seconds.max(0);
+    GamesRepository::find_archived_since(&db, since as i32)
+        .await
+        .map_err(|e| format!("获取最近归档游戏失败: {}", e))
+}
+
+/// 彻底删除归档超过指定时长的游戏，并清理封面与存档备份文件
+///
+/// 返回被删除的游戏数量
+#[tauri::command]
+pub async fn purge_archived(
+    db: State<'_, DatabaseConnection>,
+    cover_state: State<'_, DownloadState>,
+    older_than_seconds: i64,
+) -> Result<u64, String> {
+    purge_archived_games(&db, &cover_state, older_than_seconds).await
+}
+
+/// 删除归档超过指定时长的游戏及其封面与存档备份，供命令与定期维护共用
+pub(crate) async fn purge_archived_games(
+    db: &DatabaseConnection,
+    cover_state: &DownloadState,
+    older_than_seconds: i64,
+) -> Result<u64, String> {
+    let before = chrono::Utc::now().timestamp() - older_than_seconds.max(0);
+    let ids = GamesRepository::find_archived_before(db, before as i32)
+        .await
+        .map_err(|e| format!("获取待清理的归档游戏失败: {}", e))?;
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    let rows_affected = GamesRepository::delete_many(&db, ids.clone())
+        .await
+        .map(|result| result.rows_affected)
+        .map_err(|e| format!("清理归档游戏失败: {}", e))?;
+
+    for game_id in ids {
+        cover_state.mark_game_deleted(game_id as u32).await;
+        if let Err(err) = delete_game_cover_dir(game_id).await {
+            log::warn!("清理归档游戏封面目录失败 game_id={}: {}", game_id, err);
+        }
+        if let Err(err) = delete_game_backup_dir(db, game_id).await {
+            log::warn!("清理归档游戏存档备份失败 game_id={}: {}", game_id, err);
+        }
+    }
+
+    log::info!("归档游戏清理完成 rows_affected={}", rows_affected);
+
+    Ok(rows_affected)
+}
+
 /// 获取游戏总数
 #[tauri::command]
 pub async fn count_games(db: State<'_, DatabaseConnection>) -> Result<u64, String> {
@@ -165,6 +583,88 @@ pub async fn count_games(db: State<'_, DatabaseConnection>) -> Result<u64, Strin
         .map_err(|e| format!("获取游戏总数失败: {}", e))
 }
 
+/// 统计关键词搜索的匹配数量，用于搜索框实时显示结果数
+///
+/// 筛选条件与 `find_game_ids` 一致，只执行 COUNT，不读取游戏数据
+#[tauri::command]
+pub async fn count_search_matches(
+    db: State<'_, DatabaseConnection>,
+    keyword: String,
+    game_type: Option<GameType>,
+    filter: Option<GameFilter>,
+) -> Result<u64, String> {
+    let filter = GameFilter {
+        keyword: Some(keyword),
+        ..filter.unwrap_or_default()
+    };
+    GamesRepository::count_matches(&db, game_type.unwrap_or(GameType::All), &filter)
+        .await
+        .map_err(|e| format!("统计搜索结果失败: {}", e))
+}
+
+/// 关键词搜索游戏，返回命中的字段与片段，便于界面说明匹配原因
+///
+/// 名称命中排在别名、标签等其他字段命中之前
+#[tauri::command]
+pub async fn search_games(
+    db: State<'_, DatabaseConnection>,
+    keyword: String,
+    game_type: Option<GameType>,
+    filter: Option<GameFilter>,
+    limit: Option<u64>,
+) -> Result<Vec<SearchHit>, String> {
+    GamesRepository::search(
+        &db,
+        &keyword,
+        game_type.unwrap_or(GameType::All),
+        &filter.unwrap_or_default(),
+        limit,
+    )
+    .await
+    .map_err(|e| format!("搜索游戏失败: {}", e))
+}
+
+/// 获取全部开发商及对应游戏数，用于筛选下拉框
+#[tauri::command]
+pub async fn get_all_developers(
+    db: State<'_, DatabaseConnection>,
+) -> Result<Vec<FacetCount>, String> {
+    GamesRepository::find_developer_counts(&db)
+        .await
+        .map_err(|e| format!("获取开发商列表失败: {}", e))
+}
+
+/// 将某个开发商名称统一改为规范写法，返回受影响的游戏数
+///
+/// 按去除首尾空白后的完全匹配修改，避免误改相似名称
+#[tauri::command]
+pub async fn rename_developer(
+    db: State<'_, DatabaseConnection>,
+    from: String,
+    to: String,
+) -> Result<u64, String> {
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() {
+        return Err("开发商名称不能为空".to_string());
+    }
+    if from == to {
+        return Ok(0);
+    }
+    let affected = GamesRepository::rename_developer(&db, from, to)
+        .await
+        .map_err(|e| format!("修改开发商名称失败: {}", e))?;
+    log::info!("开发商已改名 from={} to={} games={}", from, to, affected);
+    Ok(affected)
+}
+
+/// 获取全部标签及对应游戏数，用于筛选下拉框
+#[tauri::command]
+pub async fn get_all_tags(db: State<'_, DatabaseConnection>) -> Result<Vec<FacetCount>, String> {
+    GamesRepository::find_tag_counts(&db)
+        .await
+        .map_err(|e| format!("获取标签列表失败: {}", e))
+}
+
 /// 获取指定 source 的全部游戏绑定
 #[tauri::command]
 pub async fn get_source_bindings(
@@ -178,7 +678,8 @@ pub async fn get_source_bindings(
 
 /// 批量更新游戏数据
 ///
-/// 使用单个事务处理所有更新操作，性能远优于逐个更新
+/// 使用单个事务处理所有更新操作，性能远优于逐个更新；
+/// 任一游戏失败时整批回滚，错误信息中带有失败的游戏 ID
 #[tauri::command]
 pub async fn update_games_batch(
     db: State<'_, DatabaseConnection>,
@@ -192,17 +693,79 @@ pub async fn update_games_batch(
 // ==================== 存档备份相关 ====================
 
 /// 保存存档备份记录
+///
+/// 保存成功后发送 `savedata-backup-created` 事件，其他打开的视图可据此刷新备份列表
+///
+/// `base_backup_id` 为增量备份所依赖的基础备份，取自 `create_savedata_backup` 的返回值
 #[tauri::command]
 pub async fn save_savedata_record(
+    app: AppHandle,
     db: State<'_, DatabaseConnection>,
     game_id: i32,
     file_name: String,
     backup_time: i32,
     file_size: i32,
+    base_backup_id: Option<i32>,
 ) -> Result<i32, String> {
-    GamesRepository::save_savedata_record(&db, game_id, &file_name, backup_time, file_size)
-        .await
-        .map_err(|e| format!("保存存档备份记录失败: {}", e))
+    let backup_path = match resolve_savedata_backup_root(&db).await {
+        Ok(root) => Some(root.join(format!("game_{}", game_id)).join(&file_name)),
+        Err(e) => {
+            log::warn!("解析存档备份目录失败: {}", e);
+            None
+        }
+    };
+    let manifest = backup_path
+        .as_deref()
+        .map(manifest_path)
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string());
+    let backup = BackupInfo {
+        folder_name: file_name,
+        backup_time: i64::from(backup_time),
+        file_size: file_size as u64,
+        backup_path: backup_path
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        base_backup_id,
+        manifest,
+        verified: false,
+    };
+
+    record_savedata_backup(&app, &db, game_id, backup, false).await
+}
+
+/// 保存存档备份记录并发送 `savedata-backup-created` 事件，返回记录 ID
+///
+/// `automatic` 标记游戏退出后自动创建的备份，前端据此提示
+pub(crate) async fn record_savedata_backup<R: Runtime>(
+    app: &AppHandle<R>,
+    db: &DatabaseConnection,
+    game_id: i32,
+    backup: BackupInfo,
+    automatic: bool,
+) -> Result<i32, String> {
+    let record_id = GamesRepository::save_savedata_record(
+        db,
+        game_id,
+        &backup.folder_name,
+        backup.backup_time as i32,
+        backup.file_size as i32,
+        backup.base_backup_id,
+    )
+    .await
+    .map_err(|e| format!("保存存档备份记录失败: {}", e))?;
+
+    let event = SavedataBackupCreated {
+        game_id,
+        record_id,
+        backup,
+        automatic,
+    };
+    if let Err(e) = app.emit(SAVEDATA_BACKUP_CREATED_EVENT, &event) {
+        log::warn!("无法发送 {} 事件: {}", SAVEDATA_BACKUP_CREATED_EVENT, e);
+    }
+
+    Ok(record_id)
 }
 
 /// 获取指定游戏的备份数量
@@ -216,6 +779,54 @@ pub async fn get_savedata_count(
         .map_err(|e| format!("获取备份数量失败: {}", e))
 }
 
+/// 统计存档备份占用，`game_id` 为空时统计全部游戏
+///
+/// 大小取自备份记录中保存的 `file_size`，不遍历备份目录；只检查每条记录的文件是否存在，
+/// 文件已被手动删除的记录计入 `stale_count`
+#[tauri::command]
+pub async fn get_backup_size_summary(
+    db: State<'_, DatabaseConnection>,
+    game_id: Option<i32>,
+) -> Result<BackupSizeSummary, String> {
+    let (total_size, records) = match game_id {
+        Some(game_id) => (
+            GamesRepository::get_backup_size_by_game(&db, game_id).await,
+            GamesRepository::get_savedata_records(&db, game_id).await,
+        ),
+        None => (
+            GamesRepository::get_total_backup_size(&db).await,
+            GamesRepository::get_all_savedata_records(&db).await,
+        ),
+    };
+    let total_size = total_size.map_err(|e| format!("统计备份大小失败: {}", e))?;
+    let records = records.map_err(|e| format!("获取备份记录失败: {}", e))?;
+    let record_count = records.len();
+    let backup_root = resolve_savedata_backup_root(&db).await?;
+
+    let (stale_count, stale_size) = tokio::task::spawn_blocking(move || {
+        records
+            .iter()
+            .filter(|record| {
+                !backup_root
+                    .join(format!("game_{}", record.game_id))
+                    .join(&record.file)
+                    .is_file()
+            })
+            .fold((0usize, 0i64), |(count, size), record| {
+                (count + 1, size + i64::from(record.file_size))
+            })
+    })
+    .await
+    .map_err(|e| format!("检查备份文件任务失败: {}", e))?;
+
+    Ok(BackupSizeSummary {
+        total_size,
+        record_count,
+        stale_count,
+        stale_size,
+    })
+}
+
 /// 获取指定游戏的所有备份记录
 #[tauri::command]
 pub async fn get_savedata_records(
@@ -227,6 +838,17 @@ pub async fn get_savedata_records(
         .map_err(|e| format!("获取备份记录失败: {}", e))
 }
 
+/// 批量获取各游戏最新的备份记录，没有备份的游戏不在结果中
+#[tauri::command]
+pub async fn get_latest_backups(
+    db: State<'_, DatabaseConnection>,
+    game_ids: Vec<i32>,
+) -> Result<HashMap<i32, savedata::Model>, String> {
+    GamesRepository::get_latest_savedata_records(&db, &game_ids)
+        .await
+        .map_err(|e| format!("获取最新备份记录失败: {}", e))
+}
+
 // ==================== 游戏统计相关 ====================
 
 /// 手动创建游戏会话
@@ -243,6 +865,22 @@ pub async fn create_manual_game_session(
         .map_err(|e| format!("创建游戏会话失败: {}", e))
 }
 
+/// 从 CSV / JSON 导入游玩会话并重建统计
+///
+/// 任一行无效时整体拒绝，错误信息包含行序号；返回导入的会话数量
+#[tauri::command]
+pub async fn import_sessions(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    sessions_json: String,
+) -> Result<usize, String> {
+    let sessions =
+        parse_imported_sessions(&sessions_json).map_err(|e| format!("解析导入会话失败: {}", e))?;
+    GameStatsRepository::import_sessions(&db, game_id, &sessions)
+        .await
+        .map_err(|e| format!("导入游玩会话失败: {}", e))
+}
+
 /// 修复/调试命令：从全部事实会话重建指定游戏的统计投影
 ///
 /// 常规会话增删已在事务内同步维护统计，不应调用此命令。
@@ -269,6 +907,38 @@ pub async fn get_game_sessions(
         .map_err(|e| format!("获取游戏会话历史失败: {}", e))
 }
 
+/// 分页获取全库的游玩会话，按开始时间倒序，附带游戏名称与封面
+#[tauri::command]
+pub async fn get_all_sessions_paged(
+    db: State<'_, DatabaseConnection>,
+    limit: u64,
+    offset: u64,
+    language: Option<String>,
+) -> Result<Vec<SessionFeedItem>, String> {
+    let sessions = GameStatsRepository::get_all_sessions_paged(&db, limit, offset)
+        .await
+        .map_err(|e| format!("获取全库会话失败: {}", e))?;
+
+    let mut ids: Vec<i32> = sessions.iter().map(|session| session.game_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let summaries = GamesRepository::find_display_summaries(&db, &ids, language.as_deref())
+        .await
+        .map_err(|e| format!("获取游戏名称失败: {}", e))?;
+
+    Ok(sessions
+        .into_iter()
+        .map(|session| {
+            let summary = summaries.get(&session.game_id).cloned().unwrap_or_default();
+            SessionFeedItem {
+                session,
+                game_name: summary.name,
+                cover: summary.image,
+            }
+        })
+        .collect())
+}
+
 /// 获取指定游戏范围内的全局最近会话
 #[tauri::command]
 pub async fn get_recent_sessions_for_all(
@@ -324,6 +994,109 @@ pub async fn get_all_game_last_played(
         .map_err(|e| format!("获取所有游戏最近游玩时间失败: {}", e))
 }
 
+/// 获取全库累计游玩时长
+#[tauri::command]
+pub async fn get_total_playtime(
+    db: State<'_, DatabaseConnection>,
+) -> Result<TotalPlaytime, String> {
+    let total_time = GameStatsRepository::get_total_playtime(&db)
+        .await
+        .map_err(|e| format!("获取累计游玩时长失败: {}", e))?;
+    Ok(TotalPlaytime {
+        total_time,
+        total_time_display: format_playtime(total_time * 60),
+    })
+}
+
+/// 获取全库自 `since_timestamp`（Unix 秒）起的游玩时长（秒），附带各游戏明细
+#[tauri::command]
+pub async fn get_playtime_since(
+    db: State<'_, DatabaseConnection>,
+    since_timestamp: i32,
+) -> Result<PlaytimeSince, String> {
+    GameStatsRepository::get_playtime_since(&db, since_timestamp)
+        .await
+        .map_err(|e| format!("获取游玩时长失败: {}", e))
+}
+
+/// 获取单个游戏自 `since_timestamp`（Unix 秒）起的游玩时长（秒）
+#[tauri::command]
+pub async fn get_game_playtime_since(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    since_timestamp: i32,
+) -> Result<GamePlaytimeSince, String> {
+    GameStatsRepository::get_game_playtime_since(&db, game_id, since_timestamp)
+        .await
+        .map_err(|e| format!("获取游玩时长失败: {}", e))
+}
+
+/// 获取全库完成率：通关、进行中与未开始的游戏数量（不含已归档游戏）
+#[tauri::command]
+pub async fn get_completion_stats(
+    db: State<'_, DatabaseConnection>,
+) -> Result<CompletionStats, String> {
+    GameStatsRepository::get_completion_stats(&db)
+        .await
+        .map_err(|e| format!("获取完成率失败: {}", e))
+}
+
+/// 获取全库活跃度热力图数据
+///
+/// 日期格式为 `YYYY-MM-DD`，区间为闭区间
+#[tauri::command]
+pub async fn get_activity_heatmap(
+    db: State<'_, DatabaseConnection>,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<HeatmapDay>, String> {
+    let parse_date = |date: &str| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("日期格式无效: {}", e))
+    };
+    GameStatsRepository::get_activity_heatmap(&db, parse_date(&start_date)?, parse_date(&end_date)?)
+        .await
+        .map_err(|e| format!("获取活跃度热力图失败: {}", e))
+}
+
+/// 获取指定日期（`YYYY-MM-DD`）游玩过的游戏，按当天游玩时长降序
+#[tauri::command]
+pub async fn find_games_played_on(
+    db: State<'_, DatabaseConnection>,
+    date: String,
+) -> Result<Vec<GamePlayedOnDate>, String> {
+    let date =
+        NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|e| format!("日期格式无效: {}", e))?;
+    let played = GameStatsRepository::get_playtime_on(&db, date)
+        .await
+        .map_err(|e| format!("获取当日游玩记录失败: {}", e))?;
+
+    let ids: Vec<i32> = played.iter().map(|(game_id, _)| *game_id).collect();
+    let games = GamesRepository::find_by_ids(&db, &ids)
+        .await
+        .map_err(|e| format!("获取游戏数据失败: {}", e))?;
+    let playtimes: std::collections::HashMap<i32, i32> = played.into_iter().collect();
+
+    Ok(games
+        .into_iter()
+        .map(|game| GamePlayedOnDate {
+            playtime: playtimes.get(&game.id).copied().unwrap_or_default(),
+            game,
+        })
+        .collect())
+}
+
+/// 获取首页仪表盘数据（最近游玩的游戏及其统计）
+#[tauri::command]
+pub async fn get_dashboard_data(
+    db: State<'_, DatabaseConnection>,
+    limit: u64,
+    language: Option<String>,
+) -> Result<Vec<DashboardGameData>, String> {
+    GamesRepository::find_dashboard_games(&db, limit, language.as_deref())
+        .await
+        .map_err(|e| format!("获取首页仪表盘数据失败: {}", e))
+}
+
 // ==================== 用户设置相关 ====================
 
 /// 获取所有设置
@@ -349,7 +1122,9 @@ pub async fn update_settings(
 
 // ==================== 合集相关 ====================
 
-/// 创建合集
+/// 创建合集，返回值附带层级信息（`depth` / `is_group`）
+///
+/// 父级不是根合集时拒绝创建，合集最多两级
 #[tauri::command]
 pub async fn create_collection(
     db: State<'_, DatabaseConnection>,
@@ -357,7 +1132,7 @@ pub async fn create_collection(
     parent_id: Option<i32>,
     sort_order: i32,
     icon: Option<String>,
-) -> Result<crate::entity::collections::Model, String> {
+) -> Result<CollectionWithDepth, String> {
     let data = InsertCollectionData {
         name,
         parent_id,
@@ -426,6 +1201,36 @@ pub async fn remove_games_from_collection(
         .map_err(|e| format!("从合集中批量移除游戏失败: {}", e))
 }
 
+/// 将游戏从一个合集移动到另一个合集，返回移动与跳过的数量
+#[tauri::command]
+pub async fn move_games_between_collections(
+    db: State<'_, DatabaseConnection>,
+    game_ids: Vec<i32>,
+    from_collection_id: i32,
+    to_collection_id: i32,
+) -> Result<MoveGamesResult, String> {
+    CollectionsRepository::move_games_between_collections(
+        &db,
+        game_ids,
+        from_collection_id,
+        to_collection_id,
+    )
+    .await
+    .map_err(|e| format!("移动合集中的游戏失败: {}", e))
+}
+
+/// 将合集 `remove_id` 合并到 `keep_id` 后删除 `remove_id`，两者须同为分组或同为分类
+#[tauri::command]
+pub async fn merge_collections(
+    db: State<'_, DatabaseConnection>,
+    keep_id: i32,
+    remove_id: i32,
+) -> Result<MergeCollectionsResult, String> {
+    CollectionsRepository::merge_collections(&db, keep_id, remove_id)
+        .await
+        .map_err(|e| format!("合并合集失败: {}", e))
+}
+
 /// 获取合集中的所有游戏 ID
 #[tauri::command]
 pub async fn get_games_in_collection(
@@ -437,6 +1242,72 @@ pub async fn get_games_in_collection(
         .map_err(|e| format!("获取合集中的游戏失败: {}", e))
 }
 
+/// 获取合集中游戏的完整数据
+///
+/// 未传入排序参数时使用合集保存的排序方式；合集也未设置时按合集内的手动顺序返回。
+/// 按排序方式返回时不包含已归档游戏，与游戏库列表一致。
+#[tauri::command]
+pub async fn get_full_games_in_collection(
+    db: State<'_, DatabaseConnection>,
+    collection_id: i32,
+    sort_option: Option<SortOption>,
+    sort_order: Option<SortOrder>,
+    language: Option<String>,
+) -> Result<Vec<FullGameData>, String> {
+    let collection = CollectionsRepository::find_by_id(&db, collection_id)
+        .await
+        .map_err(|e| format!("获取合集失败: {}", e))?
+        .ok_or_else(|| format!("合集 {} 不存在", collection_id))?;
+    let saved_sort = CollectionsRepository::view_sort(&collection);
+    let member_ids = CollectionsRepository::get_games_in_collection(&db, collection_id)
+        .await
+        .map_err(|e| format!("获取合集中的游戏失败: {}", e))?;
+
+    let ids = match sort_option.or(saved_sort.map(|(option, _)| option)) {
+        Some(option) => {
+            let order = sort_order
+                .or(saved_sort.map(|(_, order)| order))
+                .unwrap_or(SortOrder::Asc);
+            let members: std::collections::HashSet<i32> = member_ids.into_iter().collect();
+            let mut ids = GamesRepository::find_ids(
+                &db,
+                GameType::All,
+                option,
+                order,
+                language,
+                &GameFilter::default(),
+            )
+            .await
+            .map_err(|e| format!("获取游戏 ID 列表失败: {}", e))?;
+            ids.retain(|id| members.contains(id));
+            ids
+        }
+        None => member_ids,
+    };
+
+    GamesRepository::find_by_ids(&db, &ids)
+        .await
+        .map_err(|e| format!("获取游戏数据失败: {}", e))
+}
+
+/// 设置合集视图的排序方式，两者都为空时清除，恢复为手动顺序
+#[tauri::command]
+pub async fn set_collection_view_sort(
+    db: State<'_, DatabaseConnection>,
+    collection_id: i32,
+    sort_option: Option<SortOption>,
+    sort_order: Option<SortOrder>,
+) -> Result<crate::entity::collections::Model, String> {
+    let sort = match (sort_option, sort_order) {
+        (Some(option), Some(order)) => Some((option, order)),
+        (None, None) => None,
+        _ => return Err("排序字段与排序方向需同时设置".to_string()),
+    };
+    CollectionsRepository::set_view_sort(&db, collection_id, sort)
+        .await
+        .map_err(|e| format!("设置合集排序失败: {}", e))
+}
+
 /// 获取游戏所在的所有合集 ID
 #[tauri::command]
 pub async fn get_game_collection_ids(
@@ -9,6 +9,7 @@ pub mod custom_data;
 // === SeaORM 实体（对应数据库表）===
 pub mod collections;
 pub mod game_collection_link;
+pub mod game_media;
 pub mod game_sessions;
 pub mod game_sources;
 pub mod game_statistics;
@@ -16,6 +16,10 @@ pub struct Model {
     pub icon: Option<String>,
     pub created_at: Option<i32>,
     pub updated_at: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub view_sort_option: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub view_sort_order: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
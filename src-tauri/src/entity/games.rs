@@ -37,12 +37,29 @@ pub struct Model {
     // === 时间戳 ===
     pub created_at: Option<i32>,
     pub updated_at: Option<i32>,
+    /// 归档（软删除）时间，NULL 表示未归档
+    pub archived_at: Option<i32>,
+    /// 显示名称与封面所用的主数据源（来源名或 `custom`），NULL 表示自动选择
+    pub primary_source: Option<String>,
+    /// 是否收藏，独立于合集
+    pub favorite: bool,
+    /// 启动参数，JSON 字符串数组，NULL 表示无启动参数
+    #[sea_orm(column_type = "Text", nullable)]
+    pub launch_args: Option<String>,
+    /// 启动游戏前执行的命令
+    #[sea_orm(column_type = "Text", nullable)]
+    pub pre_launch_cmd: Option<String>,
+    /// 游戏退出后执行的命令
+    #[sea_orm(column_type = "Text", nullable)]
+    pub post_exit_cmd: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::game_collection_link::Entity")]
     GameCollectionLink,
+    #[sea_orm(has_many = "super::game_media::Entity")]
+    GameMedia,
     #[sea_orm(has_many = "super::game_sources::Entity")]
     GameSources,
     #[sea_orm(has_many = "super::game_sessions::Entity")]
@@ -59,6 +76,12 @@ impl Related<super::game_collection_link::Entity> for Entity {
     }
 }
 
+impl Related<super::game_media::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GameMedia.def()
+    }
+}
+
 impl Related<super::game_sources::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::GameSources.def()
@@ -5,6 +5,7 @@
 // === SeaORM 实体 ===
 pub use super::collections::Entity as Collections;
 pub use super::game_collection_link::Entity as GameCollectionLink;
+pub use super::game_media::Entity as GameMedia;
 pub use super::game_sessions::Entity as GameSessions;
 pub use super::game_sources::Entity as GameSources;
 pub use super::game_statistics::Entity as GameStatistics;
@@ -13,6 +13,7 @@ pub struct Model {
     pub file: String,
     pub backup_time: i32,
     pub file_size: i32,
+    pub base_backup_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
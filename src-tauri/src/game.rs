@@ -1,5 +1,7 @@
 pub mod cover;
+pub mod hooks;
 pub mod launch;
 pub mod local_path;
+pub mod media;
 pub mod monitor;
 pub mod scan;
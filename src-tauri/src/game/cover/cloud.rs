@@ -15,7 +15,7 @@ use crate::utils::image::{
     content_type_for_extension, content_type_for_file, infer_image_extension, make_image_response,
     make_status_response,
 };
-use reina_path::get_base_data_dir;
+use reina_path::get_covers_dir;
 
 const DEFAULT_COVER_EXTENSION: &str = "jpg";
 const DEFAULT_CLOUD_COVER_FILE_NAME: &str = "cloud_cover";
@@ -72,6 +72,11 @@ impl DownloadState {
         self.cache_generation(game_id).await == generation
     }
 
+    /// 清空已缓存集合，封面目录变更后由请求重新检查磁盘
+    pub async fn clear_cached_ids(&self) {
+        self.cached_ids.write().await.clear();
+    }
+
     async fn clear_game_deleted(&self, game_id: u32) {
         self.tombstoned_ids.write().await.remove(&game_id);
     }
@@ -107,10 +112,8 @@ fn cloud_cover_file_stem(game_id: u32) -> String {
     format!("{DEFAULT_CLOUD_COVER_FILE_NAME}_{game_id}")
 }
 
-fn get_game_cover_dir(game_id: u32) -> Result<PathBuf, String> {
-    Ok(get_base_data_dir()?
-        .join("covers")
-        .join(format!("game_{}", game_id)))
+pub(crate) fn get_game_cover_dir(game_id: u32) -> Result<PathBuf, String> {
+    Ok(get_covers_dir()?.join(format!("game_{}", game_id)))
 }
 
 fn build_cache_path(game_cover_dir: &Path, game_id: u32, extension: &str) -> PathBuf {
@@ -663,9 +666,7 @@ pub fn register_game_cover_protocol<R: tauri::Runtime>(
 
 /// 删除指定游戏的封面目录（包含云端缓存和自定义封面）
 pub async fn delete_game_cover_dir(game_id: i32) -> Result<(), String> {
-    let game_cover_dir = reina_path::get_base_data_dir()?
-        .join("covers")
-        .join(format!("game_{}", game_id));
+    let game_cover_dir = get_covers_dir()?.join(format!("game_{}", game_id));
 
     if !game_cover_dir.exists() {
         return Ok(());
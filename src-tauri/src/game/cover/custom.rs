@@ -1,10 +1,58 @@
-use image::{ColorType, ImageFormat};
+use crate::database::repository::games_repository::GamesRepository;
+use crate::utils::image::{content_type_for_extension, download_image};
+use image::{ColorType, DynamicImage, GenericImageView, ImageFormat, RgbaImage, imageops};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::command;
+use tauri::{State, command};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+/// 从 URL 设置封面时允许的最大图片大小
+const MAX_URL_COVER_BYTES: usize = 20 * 1024 * 1024;
+
+/// 封面目标宽高比
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum CoverAspect {
+    /// 2:3 海报比例
+    #[serde(rename = "2:3")]
+    Poster,
+    /// 1:1 方形
+    #[serde(rename = "1:1")]
+    Square,
+}
+
+impl CoverAspect {
+    fn ratio(self) -> (u32, u32) {
+        match self {
+            Self::Poster => (2, 3),
+            Self::Square => (1, 1),
+        }
+    }
+}
+
+/// 比例不一致时的处理方式
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverFit {
+    /// 居中裁剪
+    #[default]
+    Crop,
+    /// 透明填充
+    Pad,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessedCover {
+    /// 写入 `custom_data.image` 的版本化标识（扩展名_时间戳）
+    pub image: String,
+    /// 处理后封面的完整路径
+    pub path: String,
+    /// 保留的原图路径，用于重新裁剪
+    pub original_path: String,
+}
+
 /// 从剪贴板读取图片并写入临时 PNG 文件。
 ///
 /// 该文件只用于前端保存前预览，保存成功后仍由现有上传逻辑复制到正式封面目录。
@@ -53,15 +101,179 @@ pub async fn import_clipboard_image_to_temp(
     Ok(target_path.to_string_lossy().to_string())
 }
 
-/// 删除指定游戏的所有自定义封面文件，但保留封面目录
+/// 将封面按目标比例居中裁剪或填充，并保存为自定义封面
+///
+/// 原图会以 `original_{game_id}.{ext}` 保存在封面目录中（不受 `delete_game_covers` 影响），
+/// 之后可传入该路径重新裁剪。结果统一输出为 PNG，命名规则与前端上传保持一致。
 #[command]
-pub async fn delete_game_covers(game_id: u32, covers_dir: String) -> Result<(), String> {
+pub async fn process_cover(
+    source_path: String,
+    covers_dir: String,
+    game_id: u32,
+    target_aspect: CoverAspect,
+    fit: Option<CoverFit>,
+) -> Result<ProcessedCover, String> {
     let dir_path = Path::new(&covers_dir);
+    ensure_cover_dir_matches(dir_path, game_id)?;
+    fs::create_dir_all(dir_path).map_err(|e| format!("创建封面目录失败: {}", e))?;
 
-    if !dir_path.exists() {
-        return Ok(());
+    let source = Path::new(&source_path);
+    // 自定义封面文件不带扩展名，需按内容识别格式
+    let bytes = fs::read(source).map_err(|e| format!("无法读取封面图片: {}", e))?;
+    let source_image = decode_cover_image(&bytes)?;
+
+    // 保留原图；重新裁剪时 source 本身就是原图，无需再次复制
+    let extension = source
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("png")
+        .to_lowercase();
+    let original_path = dir_path.join(format!("original_{}.{}", game_id, extension));
+    if source != original_path {
+        fs::copy(source, &original_path).map_err(|e| format!("保存原始封面失败: {}", e))?;
+    }
+
+    let processed = fit_to_aspect(&source_image, target_aspect, fit.unwrap_or_default());
+
+    delete_game_covers(game_id, covers_dir.clone()).await?;
+
+    let timestamp_millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("获取系统时间失败: {}", e))?
+        .as_millis();
+    let image = format!("png_{}", timestamp_millis);
+    let target_path = dir_path.join(format!("cover_{}_{}", game_id, image));
+    processed
+        .save_with_format(&target_path, ImageFormat::Png)
+        .map_err(|e| format!("保存处理后的封面失败: {}", e))?;
+
+    Ok(ProcessedCover {
+        image,
+        path: target_path.to_string_lossy().to_string(),
+        original_path: original_path.to_string_lossy().to_string(),
+    })
+}
+
+/// 从图片 URL 下载封面并设为游戏的自定义封面
+///
+/// 图片按内容识别格式，非图片或超过大小限制时拒绝；新封面写入并更新 `custom_data.image`
+/// 之后才删除该游戏旧的自定义封面文件，任一步失败都不会丢失原封面。返回新的版本化标识
+#[command]
+pub async fn set_cover_from_url(
+    db: State<'_, DatabaseConnection>,
+    game_id: u32,
+    url: String,
+    covers_dir: String,
+) -> Result<String, String> {
+    let dir_path = Path::new(&covers_dir);
+    ensure_cover_dir_matches(dir_path, game_id)?;
+
+    let (bytes, content_type) = download_image(url.trim(), MAX_URL_COVER_BYTES).await?;
+    // Content-Type 可能与实际内容不符，以文件头为准
+    let format = image::guess_format(&bytes)
+        .map_err(|_| format!("链接内容不是可识别的图片: {}", content_type))?;
+    let extension = format.extensions_str().first().copied().unwrap_or("png");
+    // 原样保存，只需确认能显示：能解码的格式先试解码，其余格式需 WebView 能直接显示（如 AVIF）
+    if format.reading_enabled() {
+        decode_cover_image(&bytes)?;
+    } else if !content_type_for_extension(extension).starts_with("image/") {
+        return Err(unsupported_format_error(format));
+    }
+
+    fs::create_dir_all(dir_path).map_err(|e| format!("创建封面目录失败: {}", e))?;
+
+    let timestamp_millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("获取系统时间失败: {}", e))?
+        .as_millis();
+    let image = format!("{}_{}", extension, timestamp_millis);
+    let file_name = format!("cover_{}_{}", game_id, image);
+    let target_path = dir_path.join(&file_name);
+    fs::write(&target_path, &bytes).map_err(|e| format!("保存封面失败: {}", e))?;
+
+    if let Err(e) =
+        GamesRepository::set_custom_image(&db, game_id as i32, Some(image.clone())).await
+    {
+        let _ = fs::remove_file(&target_path);
+        return Err(format!("更新自定义封面失败: {}", e));
+    }
+
+    if let Err(e) = remove_cover_files(dir_path, game_id, Some(&file_name)) {
+        log::warn!("清理旧封面文件失败: {}", e);
+    }
+
+    Ok(image)
+}
+
+/// 按文件头识别格式并解码封面
+///
+/// 格式可识别但未启用解码时返回“不支持的图片格式”错误，并注明识别到的格式
+fn decode_cover_image(bytes: &[u8]) -> Result<DynamicImage, String> {
+    let format = image::guess_format(bytes).map_err(|_| "无法识别的图片格式".to_string())?;
+    if !format.reading_enabled() {
+        return Err(unsupported_format_error(format));
+    }
+    image::load_from_memory_with_format(bytes, format)
+        .map_err(|e| format!("无法解码 {} 封面图片: {}", format_name(format), e))
+}
+
+fn unsupported_format_error(format: ImageFormat) -> String {
+    format!("不支持的图片格式: {}", format_name(format))
+}
+
+fn format_name(format: ImageFormat) -> String {
+    format
+        .extensions_str()
+        .first()
+        .map(|ext| ext.to_uppercase())
+        .unwrap_or_else(|| format!("{:?}", format))
+}
+
+fn fit_to_aspect(source: &DynamicImage, aspect: CoverAspect, fit: CoverFit) -> RgbaImage {
+    let (width, height) = source.dimensions();
+    let (ratio_w, ratio_h) = aspect.ratio();
+    let (width, height, ratio_w, ratio_h) = (
+        u64::from(width),
+        u64::from(height),
+        u64::from(ratio_w),
+        u64::from(ratio_h),
+    );
+    let too_wide = width * ratio_h > height * ratio_w;
+
+    match fit {
+        CoverFit::Crop => {
+            let (crop_w, crop_h) = if too_wide {
+                (height * ratio_w / ratio_h, height)
+            } else {
+                (width, width * ratio_h / ratio_w)
+            };
+            let x = (width - crop_w) / 2;
+            let y = (height - crop_h) / 2;
+            source
+                .crop_imm(
+                    x as u32,
+                    y as u32,
+                    crop_w.max(1) as u32,
+                    crop_h.max(1) as u32,
+                )
+                .to_rgba8()
+        }
+        CoverFit::Pad => {
+            let (canvas_w, canvas_h) = if too_wide {
+                (width, (width * ratio_h).div_ceil(ratio_w))
+            } else {
+                ((height * ratio_w).div_ceil(ratio_h), height)
+            };
+            let mut canvas = RgbaImage::new(canvas_w as u32, canvas_h as u32);
+            let x = (canvas_w - width) / 2;
+            let y = (canvas_h - height) / 2;
+            imageops::overlay(&mut canvas, &source.to_rgba8(), x as i64, y as i64);
+            canvas
+        }
     }
+}
 
+fn ensure_cover_dir_matches(dir_path: &Path, game_id: u32) -> Result<(), String> {
     let expected_folder_name = format!("game_{}", game_id);
     let dir_name = dir_path
         .file_name()
@@ -71,10 +283,29 @@ pub async fn delete_game_covers(game_id: u32, covers_dir: String) -> Result<(),
     if dir_name != expected_folder_name {
         return Err(format!(
             "封面目录与游戏ID不匹配: game_id={}, covers_dir={}",
-            game_id, covers_dir
+            game_id,
+            dir_path.display()
         ));
     }
 
+    Ok(())
+}
+
+/// 删除指定游戏的所有自定义封面文件，但保留封面目录
+#[command]
+pub async fn delete_game_covers(game_id: u32, covers_dir: String) -> Result<(), String> {
+    let dir_path = Path::new(&covers_dir);
+
+    if !dir_path.exists() {
+        return Ok(());
+    }
+
+    ensure_cover_dir_matches(dir_path, game_id)?;
+    remove_cover_files(dir_path, game_id, None)
+}
+
+/// 删除封面目录中该游戏的封面文件，`keep` 指定的文件名除外
+fn remove_cover_files(dir_path: &Path, game_id: u32, keep: Option<&str>) -> Result<(), String> {
     let expected_file_prefix = format!("cover_{}_", game_id);
     let entries = fs::read_dir(dir_path).map_err(|e| format!("无法读取封面目录: {}", e))?;
 
@@ -88,7 +319,8 @@ pub async fn delete_game_covers(game_id: u32, covers_dir: String) -> Result<(),
 
         let file_name = entry.file_name();
         let file_name_str = file_name.to_string_lossy();
-        if !file_name_str.starts_with(&expected_file_prefix) {
+        if !file_name_str.starts_with(&expected_file_prefix) || keep == Some(file_name_str.as_ref())
+        {
             continue;
         }
 
@@ -97,3 +329,35 @@ pub async fn delete_game_covers(game_id: u32, covers_dir: String) -> Result<(),
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn decodes_webp_covers() {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(RgbaImage::new(4, 6))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::WebP)
+            .unwrap();
+
+        let decoded = decode_cover_image(&bytes).unwrap();
+        assert_eq!(decoded.dimensions(), (4, 6));
+    }
+
+    #[test]
+    fn names_detected_format_when_decoder_is_missing() {
+        let mut avif_header = b"\0\0\0\x1cftypavif".to_vec();
+        avif_header.resize(32, 0);
+
+        assert_eq!(
+            decode_cover_image(&avif_header).unwrap_err(),
+            "不支持的图片格式: AVIF"
+        );
+        assert_eq!(
+            decode_cover_image(b"not an image").unwrap_err(),
+            "无法识别的图片格式"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! 封面目录位置。
+//!
+//! 自定义目录保存在 `settings.json` 的 `covers_path` 键下，启动时载入到 `reina_path`，
+//! 之后所有封面读写都通过 `reina_path::get_covers_dir` 解析。
+
+use super::cloud::DownloadState;
+use crate::utils::fs::move_dir_recursive;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime, State, command};
+use tauri_plugin_store::StoreExt;
+
+const SETTINGS_STORE: &str = "settings.json";
+const COVERS_PATH_KEY: &str = "covers_path";
+
+/// 封面目录迁移结果
+#[derive(Debug, Serialize)]
+pub struct CoversMoveResult {
+    pub covers_dir: String,
+    /// 已移动的文件数量
+    pub moved_files: usize,
+}
+
+/// 启动时载入保存的自定义封面目录
+pub fn load_custom_covers_path<R: Runtime>(app: &AppHandle<R>) {
+    let custom = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(COVERS_PATH_KEY))
+        .and_then(|value| value.as_str().map(str::to_string))
+        .filter(|path| !path.trim().is_empty());
+    if let Some(path) = &custom {
+        log::info!("使用自定义封面目录: {}", path);
+    }
+    reina_path::set_custom_covers_dir(custom.map(PathBuf::from));
+}
+
+/// 获取当前封面目录
+#[command]
+pub fn get_covers_path() -> Result<String, String> {
+    Ok(reina_path::get_covers_dir()?.to_string_lossy().to_string())
+}
+
+/// 修改封面目录，并将现有游戏封面移动到新位置
+///
+/// 只移动 `game_*` 子目录（自定义封面、原图与云端缓存），目录中的其他文件保持不动。
+/// 移动成功后才保存设置；`new_path` 为空时恢复默认目录。
+/// 自定义封面在数据库中只记录文件标识，不含目录，因此无需改写数据库。
+#[command]
+pub async fn change_covers_path<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, DownloadState>,
+    new_path: Option<String>,
+) -> Result<CoversMoveResult, String> {
+    let new_path = new_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    let old_dir = reina_path::get_covers_dir()?;
+    let new_dir = match &new_path {
+        Some(path) => PathBuf::from(path),
+        None => reina_path::get_default_covers_dir()?,
+    };
+
+    let moved_files = if old_dir == new_dir {
+        0
+    } else {
+        if new_dir.starts_with(&old_dir) || old_dir.starts_with(&new_dir) {
+            return Err("新封面目录不能与当前目录互相包含".to_string());
+        }
+        let (old, new) = (old_dir.clone(), new_dir.clone());
+        tokio::task::spawn_blocking(move || move_game_cover_dirs(&old, &new))
+            .await
+            .map_err(|e| format!("移动封面任务失败: {}", e))??
+    };
+
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("打开设置存储失败: {}", e))?;
+    match &new_path {
+        Some(path) => store.set(COVERS_PATH_KEY, path.clone()),
+        None => {
+            store.delete(COVERS_PATH_KEY);
+        }
+    }
+    store
+        .save()
+        .map_err(|e| format!("封面已移动到 {}，但保存设置失败: {}", new_dir.display(), e))?;
+
+    reina_path::set_custom_covers_dir(new_path.map(PathBuf::from));
+    state.clear_cached_ids().await;
+
+    log::info!(
+        "封面目录已修改 old={} new={} moved_files={}",
+        old_dir.display(),
+        new_dir.display(),
+        moved_files
+    );
+    Ok(CoversMoveResult {
+        covers_dir: new_dir.to_string_lossy().to_string(),
+        moved_files,
+    })
+}
+
+/// 将 `game_*` 子目录逐个移动到新目录，返回移动的文件数量
+///
+/// 遇到错误时立即停止，已移动的游戏目录保留在新位置。
+fn move_game_cover_dirs(old_dir: &Path, new_dir: &Path) -> Result<usize, String> {
+    fs::create_dir_all(new_dir).map_err(|e| format!("创建封面目录失败: {}", e))?;
+    if !old_dir.exists() {
+        return Ok(0);
+    }
+
+    let mut moved_files = 0;
+    let entries = fs::read_dir(old_dir).map_err(|e| format!("无法读取封面目录: {}", e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("读取目录项失败: {}", e))?;
+        let path = entry.path();
+        let name = entry.file_name();
+        if !path.is_dir() || !name.to_string_lossy().starts_with("game_") {
+            continue;
+        }
+        moved_files += move_dir_recursive(&path, &new_dir.join(&name))?;
+    }
+
+    // 默认目录移空后一并删除；自定义目录可能是用户已有的文件夹，保持不动
+    if reina_path::get_default_covers_dir().is_ok_and(|dir| dir == old_dir) {
+        let _ = fs::remove_dir(old_dir);
+    }
+    Ok(moved_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_only_game_cover_dirs() {
+        let root = std::env::temp_dir().join(format!("reina-covers-move-{}", std::process::id()));
+        let old_dir = root.join("old");
+        let new_dir = root.join("new");
+        fs::create_dir_all(old_dir.join("game_1")).unwrap();
+        fs::write(old_dir.join("game_1").join("cover_1_png_1"), b"a").unwrap();
+        fs::write(old_dir.join("game_1").join("cloud_cover_1.jpg"), b"b").unwrap();
+        fs::write(old_dir.join("notes.txt"), b"c").unwrap();
+
+        assert_eq!(move_game_cover_dirs(&old_dir, &new_dir).unwrap(), 2);
+        assert!(new_dir.join("game_1").join("cover_1_png_1").is_file());
+        assert!(!old_dir.join("game_1").exists());
+        assert!(old_dir.join("notes.txt").is_file());
+        assert!(!new_dir.join("notes.txt").exists());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
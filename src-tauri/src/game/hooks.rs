@@ -0,0 +1,173 @@
+//! 游戏启动前与退出后执行的用户命令（钩子）。
+
+use crate::database::repository::games_repository::GamesRepository;
+use crate::game::local_path::{GameLaunchTarget, resolve_launch_target};
+use crate::utils::command_ext::CommandGuiExt;
+use log::{info, warn};
+use sea_orm::DatabaseConnection;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+/// 钩子命令的最长执行时间，超时后终止命令
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+/// 等待钩子命令结束时的轮询间隔
+const HOOK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 执行游戏的启动前命令，未设置时直接返回
+///
+/// 命令在游戏工作目录中运行，失败或超时返回错误，调用方应取消启动。
+pub async fn run_pre_launch_hook(
+    db: &DatabaseConnection,
+    game_id: u32,
+    working_dir: &Path,
+) -> Result<(), String> {
+    let hooks = GamesRepository::get_launch_hooks(db, game_id as i32)
+        .await
+        .map_err(|e| format!("读取游戏钩子命令失败: {}", e))?;
+    let Some(command) = hooks.pre_launch_cmd else {
+        return Ok(());
+    };
+
+    info!("执行启动前命令 game_id={} command={}", game_id, command);
+    run_hook_command(&command, Some(working_dir), HOOK_TIMEOUT)
+        .await
+        .map_err(|e| format!("启动前命令执行失败，已取消启动: {}", e))
+}
+
+/// 在后台执行游戏的退出后命令，未设置时不做任何事，失败只记录日志
+pub fn spawn_post_exit_hook(db: DatabaseConnection, game_id: u32) {
+    tokio::spawn(async move {
+        let hooks = match GamesRepository::get_launch_hooks(&db, game_id as i32).await {
+            Ok(hooks) => hooks,
+            Err(e) => {
+                warn!("读取游戏钩子命令失败 game_id={}: {}", game_id, e);
+                return;
+            }
+        };
+        let Some(command) = hooks.post_exit_cmd else {
+            return;
+        };
+
+        let working_dir = game_working_dir(&db, game_id).await;
+        info!("执行退出后命令 game_id={} command={}", game_id, command);
+        if let Err(e) = run_hook_command(&command, working_dir.as_deref(), HOOK_TIMEOUT).await {
+            warn!("退出后命令执行失败 game_id={}: {}", game_id, e);
+        }
+    });
+}
+
+/// 游戏的工作目录，路径未设置或不存在时返回 `None`
+async fn game_working_dir(db: &DatabaseConnection, game_id: u32) -> Option<PathBuf> {
+    let game = GamesRepository::find_by_id(db, game_id as i32)
+        .await
+        .ok()
+        .flatten()?;
+    match resolve_launch_target(game.localpath.as_deref()) {
+        GameLaunchTarget::NormalExecutable { working_dir, .. } => Some(working_dir),
+        GameLaunchTarget::DirectoryOnly { game_dir } => Some(game_dir),
+        _ => None,
+    }
+}
+
+/// 通过系统 shell 执行钩子命令并等待结束
+///
+/// Windows 使用 `cmd /C`，其他平台使用 `sh -c`。命令无法启动、以非零状态退出或超时均返回错误，
+/// 超时的命令会被终止。
+pub async fn run_hook_command(
+    command: &str,
+    working_dir: Option<&Path>,
+    timeout: Duration,
+) -> Result<(), String> {
+    let command = command.to_string();
+    let working_dir = working_dir.map(Path::to_path_buf);
+    tokio::task::spawn_blocking(move || {
+        run_hook_command_blocking(&command, working_dir.as_deref(), timeout)
+    })
+    .await
+    .map_err(|e| format!("钩子命令任务失败: {}", e))?
+}
+
+fn run_hook_command_blocking(
+    command: &str,
+    working_dir: Option<&Path>,
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut shell = shell_command(command);
+    if let Some(dir) = working_dir {
+        shell.current_dir(dir);
+    }
+    let mut child = shell
+        .gui_safe()
+        .spawn()
+        .map_err(|e| format!("无法执行命令 `{}`: {}", command, e))?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return check_exit_status(command, status),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "命令 `{}` 超过 {} 秒未结束，已终止",
+                    command,
+                    timeout.as_secs()
+                ));
+            }
+            Ok(None) => std::thread::sleep(HOOK_POLL_INTERVAL),
+            Err(e) => return Err(format!("等待命令 `{}` 结束失败: {}", command, e)),
+        }
+    }
+}
+
+fn check_exit_status(command: &str, status: ExitStatus) -> Result<(), String> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("命令 `{}` 执行失败: {}", command, status))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn shell_command(command: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    /// 不为 cmd 创建控制台窗口
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").raw_arg(command);
+    shell.creation_flags(CREATE_NO_WINDOW);
+    shell
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hook_command_reports_failure_and_timeout() {
+        let dir = std::env::temp_dir();
+        run_hook_command("true", Some(&dir), HOOK_TIMEOUT)
+            .await
+            .unwrap();
+
+        let failed = run_hook_command("exit 3", Some(&dir), HOOK_TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(failed.contains("执行失败"), "{}", failed);
+
+        let timed_out = run_hook_command("sleep 5", None, Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert!(timed_out.contains("已终止"), "{}", timed_out);
+    }
+}
@@ -1,9 +1,19 @@
+mod diagnostics;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+mod env;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+mod stored_args;
+
 #[cfg(target_os = "windows")]
 mod windows;
 
 #[cfg(target_os = "linux")]
 mod linux;
 
+pub use diagnostics::{LaunchDiagnostics, make_executable};
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub use stored_args::launch_game_by_id;
+
 #[cfg(target_os = "windows")]
 pub use windows::*;
 
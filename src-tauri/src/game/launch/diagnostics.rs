@@ -0,0 +1,118 @@
+//! 启动失败时的诊断信息。
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::command;
+
+/// 启动程序缺少可执行权限时返回的错误码
+#[cfg(unix)]
+pub const NOT_EXECUTABLE: &str = "NOT_EXECUTABLE";
+/// 创建进程失败时返回的错误码
+pub const LAUNCH_FAILED: &str = "LAUNCH_FAILED";
+/// 游戏已在运行、未重复启动时返回的错误码
+pub const ALREADY_RUNNING: &str = "ALREADY_RUNNING";
+/// 启动脚本已运行，但等待超时仍未检测到游戏进程时返回的错误码
+#[cfg(target_os = "windows")]
+pub const LAUNCHER_TIMEOUT: &str = "LAUNCHER_TIMEOUT";
+
+/// 启动失败时附带的路径诊断，便于前端定位问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchDiagnostics {
+    /// 解析后的启动程序路径
+    pub executable_path: String,
+    /// 解析后的工作目录
+    pub working_dir: String,
+    pub working_dir_exists: bool,
+    pub exe_exists: bool,
+    /// Unix 上检查可执行位，其他平台只要是文件即视为可执行
+    pub exe_executable: bool,
+}
+
+impl LaunchDiagnostics {
+    pub fn collect(executable_path: &Path, working_dir: &Path) -> Self {
+        let exe_exists = executable_path.is_file();
+        Self {
+            executable_path: executable_path.to_string_lossy().to_string(),
+            working_dir: working_dir.to_string_lossy().to_string(),
+            working_dir_exists: working_dir.is_dir(),
+            exe_exists,
+            exe_executable: exe_exists && is_executable(executable_path),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+/// 为启动程序添加可执行权限（`chmod +x`），对应 `NOT_EXECUTABLE` 错误
+///
+/// 仅 Unix 平台有效。
+#[command]
+pub fn make_executable(path: String) -> Result<(), String> {
+    set_executable(Path::new(&path))
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path).map_err(|e| format!("读取文件权限失败: {}", e))?;
+    if !metadata.is_file() {
+        return Err(format!("启动程序不存在: {}", path.display()));
+    }
+
+    let mut permissions = metadata.permissions();
+    // 与 chmod +x 一致：对已有读权限的用户类别添加执行权限
+    let mode = permissions.mode();
+    permissions.set_mode(mode | ((mode & 0o444) >> 2));
+    std::fs::set_permissions(path, permissions)
+        .map_err(|e| format!("设置可执行权限失败: {}", e))?;
+    log::info!("已为启动程序添加可执行权限: {}", path.display());
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<(), String> {
+    Err("当前平台无需设置可执行权限".to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn make_executable_fixes_missing_exec_bit() {
+        let dir = std::env::temp_dir().join(format!("reina-launch-diag-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let exe = dir.join("game.sh");
+        std::fs::write(&exe, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&exe, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let before = LaunchDiagnostics::collect(&exe, &dir);
+        assert!(before.exe_exists);
+        assert!(before.working_dir_exists);
+        assert!(!before.exe_executable);
+
+        set_executable(&exe).unwrap();
+        assert_eq!(
+            std::fs::metadata(&exe).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+        assert!(LaunchDiagnostics::collect(&exe, &dir).exe_executable);
+
+        let missing = LaunchDiagnostics::collect(&dir.join("missing"), &dir);
+        assert!(!missing.exe_exists && !missing.exe_executable);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
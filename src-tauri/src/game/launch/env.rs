@@ -0,0 +1,52 @@
+//! 启动游戏时附加的环境变量。
+
+/// 校验启动时附加的环境变量，返回可直接传给 `Command::envs` 的列表
+///
+/// 变量名去除首尾空白后不能为空，且不能包含 `=` 或 NUL；变量值不能包含 NUL。
+pub fn validate_launch_env(
+    env: Option<Vec<(String, String)>>,
+) -> Result<Vec<(String, String)>, String> {
+    env.unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| {
+            let key = key.trim().to_string();
+            if key.is_empty() {
+                return Err("环境变量名不能为空".to_string());
+            }
+            if key.contains(['=', '\0']) {
+                return Err(format!("环境变量名包含非法字符: {}", key));
+            }
+            if value.contains('\0') {
+                return Err(format!("环境变量 {} 的值包含非法字符", key));
+            }
+            Ok((key, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_launch_env_trims_keys_and_rejects_invalid_names() {
+        assert_eq!(validate_launch_env(None).unwrap(), Vec::new());
+        assert_eq!(
+            validate_launch_env(Some(vec![(
+                " LANG ".to_string(),
+                "ja_JP.UTF-8".to_string()
+            )]))
+            .unwrap(),
+            vec![("LANG".to_string(), "ja_JP.UTF-8".to_string())]
+        );
+
+        for key in ["", "  ", "A=B", "A\0"] {
+            assert!(
+                validate_launch_env(Some(vec![(key.to_string(), "1".to_string())])).is_err(),
+                "key {:?} should be rejected",
+                key
+            );
+        }
+        assert!(validate_launch_env(Some(vec![("A".to_string(), "\0".to_string())])).is_err());
+    }
+}
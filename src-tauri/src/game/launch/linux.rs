@@ -1,9 +1,13 @@
+use super::diagnostics::{ALREADY_RUNNING, LAUNCH_FAILED, LaunchDiagnostics, NOT_EXECUTABLE};
+use super::env::validate_launch_env;
 use crate::database::repository::games_repository::GamesRepository;
+use crate::game::hooks::run_pre_launch_hook;
 use crate::game::local_path::{GameLaunchTarget, resolve_launch_target};
 use crate::game::monitor::{TimeTrackingMode, monitor_game, stop_game_session};
 use log::{debug, info};
 use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tauri::{AppHandle, Manager, Runtime, State, command};
 use tauri_plugin_store::StoreExt;
@@ -16,6 +20,8 @@ pub struct LaunchResult {
 
     process_id: Option<u32>,
     systemd_scope: Option<String>,
+    /// 启动失败时的路径诊断
+    diagnostics: Option<LaunchDiagnostics>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,14 +48,29 @@ pub async fn adopt_external_running_games<R: Runtime>(
     Ok(Vec::new())
 }
 
+/// 启动游戏
+///
+/// 设置 `launcher_path`（如 `.sh` 脚本）时运行该脚本而非游戏程序。脚本与其启动的游戏进程
+/// 都在同一个 systemd scope 中，监控等待整个 scope 结束，脚本退出后游戏仍在运行时不会提前结束会话；
+/// 脚本未启动游戏就退出时 scope 随之结束，会话也随之结束。
+///
+/// `env` 中的环境变量会附加到启动的进程（经由 Wine 启动时同样生效），变量名不能为空。
+///
+/// `force` 为 `false` 时若游戏的 systemd scope 仍在运行则不再启动，返回 `ALREADY_RUNNING`；
+/// 为 `true` 时在独立的 scope 中启动新实例。
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn launch_game<R: Runtime>(
     app_handle: AppHandle<R>,
     db: State<'_, DatabaseConnection>,
     game_id: u32,
     args: Option<Vec<String>>,
     time_tracking_mode: TimeTrackingMode,
+    launcher_path: Option<String>,
+    env: Option<Vec<(String, String)>>,
+    force: bool,
 ) -> Result<LaunchResult, String> {
+    let env = validate_launch_env(env)?;
     let game = GamesRepository::find_by_id(db.inner(), game_id as i32)
         .await
         .map_err(|e| format!("查询游戏失败: {}", e))?
@@ -68,6 +89,7 @@ pub async fn launch_game<R: Runtime>(
                 code: Some("NEED_EXECUTABLE".to_string()),
                 process_id: None,
                 systemd_scope: None,
+                diagnostics: None,
             }),
             GameLaunchTarget::MissingLocalPath => Err("游戏路径未设置".to_string()),
             GameLaunchTarget::MissingPath { raw_path } => {
@@ -76,15 +98,77 @@ pub async fn launch_game<R: Runtime>(
             GameLaunchTarget::NormalExecutable { .. } => unreachable!(),
         };
     };
-    let game_path = executable_path.to_string_lossy().to_string();
-
     let exe_name = match executable_path.file_name() {
         Some(name) => name,
         None => return Err("无法获取游戏可执行文件名".to_string()),
     };
+    let launcher_path = launcher_path
+        .map(|path| PathBuf::from(path.trim()))
+        .filter(|path| !path.as_os_str().is_empty());
+    if let Some(launcher) = &launcher_path
+        && !launcher.is_file()
+    {
+        return Err(format!("启动脚本不存在: {}", launcher.display()));
+    }
+    let is_windows_exe = launcher_path.is_none() && exe_name.to_string_lossy().ends_with(".exe");
+    // 通过脚本启动时实际运行的是脚本，工作目录为脚本所在目录
+    let (spawn_path, spawn_dir) = match &launcher_path {
+        Some(launcher) => (
+            launcher.clone(),
+            launcher
+                .parent()
+                .map_or_else(|| game_dir.clone(), Path::to_path_buf),
+        ),
+        None => (executable_path.clone(), game_dir.clone()),
+    };
 
-    let systemd_unit_name = format!("reina_game_{}.scope", game_id);
-    let _ = check_scope_or_reset_failed(&systemd_unit_name).await;
+    // 原生程序缺少可执行位时无法启动，交由前端提示 chmod +x
+    if !is_windows_exe {
+        let diagnostics = LaunchDiagnostics::collect(&spawn_path, &spawn_dir);
+        if !diagnostics.exe_executable {
+            return Ok(LaunchResult {
+                success: false,
+                message: format!("启动程序没有可执行权限: {}", spawn_path.display()),
+                code: Some(NOT_EXECUTABLE.to_string()),
+                process_id: None,
+                systemd_scope: None,
+                diagnostics: Some(diagnostics),
+            });
+        }
+    }
+
+    let mut systemd_unit_name = format!("reina_game_{}.scope", game_id);
+    let scope_running = check_scope_or_reset_failed(&systemd_unit_name)
+        .await
+        .unwrap_or(false);
+    if scope_running {
+        if !force {
+            info!(
+                "游戏已在运行，跳过启动 game_id={} scope={}",
+                game_id, systemd_unit_name
+            );
+            return Ok(LaunchResult {
+                success: false,
+                message: format!(
+                    "游戏已在运行: {}，systemd scope: {}",
+                    exe_name.to_string_lossy(),
+                    systemd_unit_name
+                ),
+                code: Some(ALREADY_RUNNING.to_string()),
+                process_id: None,
+                systemd_scope: Some(systemd_unit_name),
+                diagnostics: None,
+            });
+        }
+        // 同名 scope 仍在运行时无法再次创建，强制启动的新实例使用独立的 scope
+        systemd_unit_name = format!(
+            "reina_game_{}_{}.scope",
+            game_id,
+            chrono::Utc::now().timestamp()
+        );
+    }
+
+    run_pre_launch_hook(db.inner(), game_id, &game_dir).await?;
 
     let mut command = {
         let linux_launch_command = app_handle
@@ -104,11 +188,11 @@ pub async fn launch_game<R: Runtime>(
         cmd.arg("--unit");
         cmd.arg(&systemd_unit_name);
 
-        if exe_name.to_string_lossy().ends_with(".exe") {
+        if is_windows_exe {
             cmd.arg(&linux_launch_command);
         }
-        cmd.arg(&game_path);
-        cmd.current_dir(&game_dir);
+        cmd.arg(&spawn_path);
+        cmd.current_dir(&spawn_dir);
         cmd
     };
 
@@ -116,18 +200,21 @@ pub async fn launch_game<R: Runtime>(
     if let Some(arguments) = &args_clone {
         command.args(arguments);
     }
+    command.envs(env.iter().map(|(key, value)| (key, value)));
 
     debug!(
-        "准备启动游戏 game_id={} scope={} command={} arg_count={} cwd={}",
+        "准备启动游戏 game_id={} scope={} command={} launcher={} arg_count={} env_count={} cwd={}",
         game_id,
         systemd_unit_name,
-        if exe_name.to_string_lossy().ends_with(".exe") {
+        if is_windows_exe {
             "systemd-run+wine"
         } else {
             "systemd-run"
         },
+        launcher_path.is_some(),
         args_clone.as_ref().map_or(0, Vec::len),
-        game_dir.display()
+        env.len(),
+        spawn_dir.display()
     );
 
     match command.spawn() {
@@ -158,9 +245,17 @@ pub async fn launch_game<R: Runtime>(
                 code: None,
                 process_id: Some(process_id),
                 systemd_scope: Some(systemd_unit_name),
+                diagnostics: None,
             })
         }
-        Err(e) => Err(format!("启动游戏失败: {}，目录: {:?}", e, game_dir)),
+        Err(e) => Ok(LaunchResult {
+            success: false,
+            message: format!("启动游戏失败: {}，目录: {:?}", e, spawn_dir),
+            code: Some(LAUNCH_FAILED.to_string()),
+            process_id: None,
+            systemd_scope: None,
+            diagnostics: Some(LaunchDiagnostics::collect(&spawn_path, &spawn_dir)),
+        }),
     }
 }
 
@@ -190,12 +285,12 @@ fn expand_path<R: Runtime>(app_handle: &AppHandle<R>, path: &str) -> String {
 }
 
 /// 在 Linux 上检查 systemd scope 的状态，如果是 failed 则重置它
-/// 返回bool值表示scope是否已经存在
+/// 返回bool值表示scope是否仍在运行
 /// # Arguments
 /// * `systemd_unit_name` - systemd 单元名称
 ///
 /// # Returns
-/// bool - 如果 scope 已存在则返回 true，否则返回 false
+/// bool - 如果 scope 处于 active/activating 状态则返回 true，否则返回 false
 async fn check_scope_or_reset_failed(systemd_unit_name: &str) -> Result<bool, String> {
     use crate::game::monitor::{get_connection, get_manager_proxy};
     let proxy = get_manager_proxy().await.map_err(|e| {
@@ -227,7 +322,7 @@ async fn check_scope_or_reset_failed(systemd_unit_name: &str) -> Result<bool, St
                             })?;
                         info!("单元 {} 已被重置", systemd_unit_name);
                     }
-                    Ok(true)
+                    Ok(matches!(active_state.as_str(), "active" | "activating"))
                 }
                 Err(_) => Ok(false),
             }
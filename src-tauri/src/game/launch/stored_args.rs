@@ -0,0 +1,81 @@
+//! 使用数据库中保存的启动参数启动游戏。
+
+use super::LaunchResult;
+use crate::database::repository::games_repository::GamesRepository;
+use crate::game::monitor::TimeTrackingMode;
+use sea_orm::DatabaseConnection;
+use tauri::{AppHandle, Runtime, State, command};
+
+/// 按游戏 ID 启动游戏，启动参数与路径均从数据库读取
+///
+/// 前端只需传入游戏 ID；未保存启动参数时不带参数启动，游戏路径未设置时返回错误。
+/// 游戏已在运行时不会重复启动。
+#[command]
+pub async fn launch_game_by_id<R: Runtime>(
+    app_handle: AppHandle<R>,
+    db: State<'_, DatabaseConnection>,
+    game_id: u32,
+    time_tracking_mode: TimeTrackingMode,
+) -> Result<LaunchResult, String> {
+    let game = GamesRepository::find_by_id(db.inner(), game_id as i32)
+        .await
+        .map_err(|e| format!("查询游戏失败: {}", e))?
+        .ok_or_else(|| format!("游戏不存在: {}", game_id))?;
+    if game
+        .localpath
+        .as_deref()
+        .is_none_or(|path| path.trim().is_empty())
+    {
+        return Err(format!("游戏路径未设置，无法启动游戏: {}", game_id));
+    }
+    let args = GamesRepository::get_launch_args(db.inner(), game_id as i32)
+        .await
+        .map_err(|e| format!("读取启动参数失败: {}", e))?;
+
+    launch_with_args(app_handle, db, game_id, args, time_tracking_mode).await
+}
+
+/// 以默认的启动脚本、环境变量与转区设置调用平台的 `launch_game`
+#[cfg(target_os = "windows")]
+async fn launch_with_args<R: Runtime>(
+    app_handle: AppHandle<R>,
+    db: State<'_, DatabaseConnection>,
+    game_id: u32,
+    args: Option<Vec<String>>,
+    time_tracking_mode: TimeTrackingMode,
+) -> Result<LaunchResult, String> {
+    super::launch_game(
+        app_handle,
+        db,
+        game_id,
+        args,
+        time_tracking_mode,
+        None,
+        None,
+        None,
+        false,
+    )
+    .await
+}
+
+/// 以默认的启动脚本与环境变量调用平台的 `launch_game`
+#[cfg(target_os = "linux")]
+async fn launch_with_args<R: Runtime>(
+    app_handle: AppHandle<R>,
+    db: State<'_, DatabaseConnection>,
+    game_id: u32,
+    args: Option<Vec<String>>,
+    time_tracking_mode: TimeTrackingMode,
+) -> Result<LaunchResult, String> {
+    super::launch_game(
+        app_handle,
+        db,
+        game_id,
+        args,
+        time_tracking_mode,
+        None,
+        None,
+        false,
+    )
+    .await
+}
@@ -1,20 +1,31 @@
+use super::diagnostics::{ALREADY_RUNNING, LAUNCH_FAILED, LAUNCHER_TIMEOUT, LaunchDiagnostics};
+use super::env::validate_launch_env;
 use crate::database::dto::UpdateSettingsData;
 use crate::database::repository::games_repository::GamesRepository;
 use crate::entity::prelude::Games;
 use crate::database::repository::settings_repository::{DbSettingsExt, SettingsRepository};
+use crate::game::hooks::run_pre_launch_hook;
 use crate::game::local_path::{GameLaunchTarget, resolve_launch_target};
-use crate::game::monitor::{TimeTrackingMode, is_game_monitored, monitor_game, stop_game_session};
+use crate::game::monitor::{
+    TimeTrackingMode, is_game_monitored, monitor_game, monitored_process_id, stop_game_session,
+};
 use crate::utils::command_ext::CommandGuiExt;
 use sea_orm::{DatabaseConnection, EntityTrait};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Runtime, State, command};
 use {
     log::{debug, info, warn},
     tokio::time,
 };
 
+/// 通过启动脚本启动时，等待真正的游戏进程出现的最长时间
+const LAUNCHER_PROCESS_TIMEOUT: Duration = Duration::from_secs(60);
+/// 等待游戏进程时的轮询间隔
+const LAUNCHER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LaunchResult {
     success: bool,
@@ -22,6 +33,8 @@ pub struct LaunchResult {
     code: Option<String>,
 
     process_id: Option<u32>, // 添加进程ID字段
+    /// 启动失败时的路径诊断
+    diagnostics: Option<LaunchDiagnostics>,
 }
 
 #[derive(Clone, Copy)]
@@ -469,6 +482,39 @@ async fn resolve_tool_path(
     Ok(path.to_string())
 }
 
+/// 本次启动指定的转区程序（Locale Emulator 的 `LEProc.exe`、ntleah 等）
+enum LocaleWrapper {
+    /// 未指定，沿用游戏的 LE 转区设置
+    NotRequested,
+    /// 通过该程序启动游戏
+    Use(String),
+    /// 指定的程序不可用，回退为直接启动，附带提示信息
+    Unavailable(String),
+}
+
+impl LocaleWrapper {
+    fn resolve(path: Option<String>) -> Self {
+        let Some(path) = path else {
+            return Self::NotRequested;
+        };
+        let path = path.trim();
+        if path.is_empty() {
+            Self::Unavailable("转区程序路径为空，已直接启动".to_string())
+        } else if Path::new(path).is_file() {
+            Self::Use(path.to_string())
+        } else {
+            Self::Unavailable(format!("转区程序不存在，已直接启动: {}", path))
+        }
+    }
+
+    fn warning(&self) -> Option<&str> {
+        match self {
+            Self::Unavailable(warning) => Some(warning),
+            _ => None,
+        }
+    }
+}
+
 /// 启动游戏
 ///
 /// # Arguments
@@ -476,18 +522,39 @@ async fn resolve_tool_path(
 /// * `app_handle` - Tauri应用句柄
 /// * `game_id` - 游戏ID (数据库记录ID)
 /// * `args` - 可选的游戏启动参数
+/// * `launcher_path` - 可选的启动脚本（如 `.bat`），设置后运行该脚本而非游戏程序，
+///   并等待游戏程序的进程出现后再开始监控
+/// * `env` - 可选的环境变量，附加到启动的进程；变量名不能为空。提权启动时无法传递
+/// * `locale_emulator_path` - 可选的转区程序，设置后以 `<程序> <游戏路径>` 启动，覆盖游戏的
+///   LE 转区设置；路径为空或不存在时直接启动游戏，并在结果消息中提示
+/// * `force` - 为 `false` 时若游戏已在运行（已有监控会话或检测到游戏进程），不再启动，
+///   返回 `ALREADY_RUNNING` 与已有进程的 PID；为 `true` 时跳过检查
 ///
 /// # Returns
 ///
 /// 启动结果，包含成功标志、消息和进程ID
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn launch_game<R: Runtime>(
     app_handle: AppHandle<R>,
     db: State<'_, DatabaseConnection>,
     game_id: u32,
     args: Option<Vec<String>>,
     time_tracking_mode: TimeTrackingMode,
+    launcher_path: Option<String>,
+    env: Option<Vec<(String, String)>>,
+    locale_emulator_path: Option<String>,
+    force: bool,
 ) -> Result<LaunchResult, String> {
+    let env = validate_launch_env(env)?;
+    let locale_wrapper = LocaleWrapper::resolve(locale_emulator_path);
+    if let Some(warning) = locale_wrapper.warning() {
+        warn!("{} game_id={}", warning, game_id);
+    }
+    let wrapper_note = locale_wrapper
+        .warning()
+        .map(|warning| format!("（{}）", warning))
+        .unwrap_or_default();
     let game = GamesRepository::find_by_id(db.inner(), game_id as i32)
         .await
         .map_err(|e| format!("查询游戏失败: {}", e))?
@@ -505,6 +572,7 @@ pub async fn launch_game<R: Runtime>(
                 message: format!("请选择启动程序: {}", game_dir.display()),
                 code: Some("NEED_EXECUTABLE".to_string()),
                 process_id: None,
+                diagnostics: None,
             }),
             GameLaunchTarget::MissingLocalPath => Err("游戏路径未设置".to_string()),
             GameLaunchTarget::MissingPath { raw_path } => {
@@ -515,15 +583,39 @@ pub async fn launch_game<R: Runtime>(
     };
     let game_path = executable_path.to_string_lossy().to_string();
 
-    let use_le = game.le_launch.unwrap_or(0) == 1;
+    if !force
+        && let Some(process_id) = monitored_process_id(game_id)
+            .or_else(|| find_external_process_for_game(&executable_path, &detection_dir))
+    {
+        info!(
+            "游戏已在运行，跳过启动 game_id={} pid={}",
+            game_id, process_id
+        );
+        return Ok(LaunchResult {
+            success: false,
+            message: format!("游戏已在运行: {}，进程 ID: {}", game_path, process_id),
+            code: Some(ALREADY_RUNNING.to_string()),
+            process_id: Some(process_id),
+            diagnostics: None,
+        });
+    }
+
+    let use_le = match &locale_wrapper {
+        LocaleWrapper::NotRequested => game.le_launch.unwrap_or(0) == 1,
+        LocaleWrapper::Use(_) => true,
+        LocaleWrapper::Unavailable(_) => false,
+    };
     let use_magpie = game.magpie.unwrap_or(0) == 1;
+    let le_from_settings = use_le && matches!(locale_wrapper, LocaleWrapper::NotRequested);
 
-    let settings = if use_le || use_magpie {
+    let settings = if le_from_settings || use_magpie {
         Some(db.inner().get_settings().await?)
     } else {
         None
     };
-    let le_path = if use_le {
+    let le_path = if let LocaleWrapper::Use(path) = &locale_wrapper {
+        Some(path.clone())
+    } else if le_from_settings {
         Some(
             resolve_tool_path(
                 db.inner(),
@@ -555,6 +647,58 @@ pub async fn launch_game<R: Runtime>(
         None => return Err("无法获取游戏可执行文件名".to_string()),
     };
 
+    run_pre_launch_hook(db.inner(), game_id, &game_dir).await?;
+
+    if let Some(launcher_path) = launcher_path.filter(|path| !path.trim().is_empty()) {
+        let launched = launch_via_launcher(
+            Path::new(launcher_path.trim()),
+            args.as_deref(),
+            &env,
+            &executable_path,
+            &detection_dir,
+        )
+        .await?;
+        let process_id = match launched {
+            Ok(process_id) => process_id,
+            Err(result) => return Ok(result),
+        };
+        info!(
+            "通过启动脚本启动游戏成功 game_id={} pid={} launcher={}",
+            game_id, process_id, launcher_path
+        );
+
+        monitor_game(
+            app_handle.clone(),
+            db.inner().clone(),
+            time_tracking_mode,
+            game_id,
+            process_id,
+            detection_dir.to_string_lossy().to_string(),
+        )
+        .await;
+
+        if let Some(magpie_path) = magpie_path.clone() {
+            tokio::spawn(async move {
+                time::sleep(time::Duration::from_secs(1)).await;
+                if let Err(e) = start_magpie_for_game(&magpie_path).await {
+                    warn!("启动Magpie失败: {}", e);
+                }
+            });
+        }
+
+        return Ok(LaunchResult {
+            success: true,
+            message: format!(
+                "已通过启动脚本启动游戏: {}，工作目录: {:?}",
+                exe_name.to_string_lossy(),
+                game_dir
+            ),
+            code: None,
+            process_id: Some(process_id),
+            diagnostics: None,
+        });
+    }
+
     // 根据启动选项决定启动方式
     let mut command = if use_le {
         let le_path = le_path
@@ -576,13 +720,15 @@ pub async fn launch_game<R: Runtime>(
     if let Some(arguments) = &args_clone {
         command.args(arguments);
     }
+    command.envs(env.iter().map(|(key, value)| (key, value)));
 
     debug!(
-        "准备启动游戏 game_id={} mode={} magpie={} arg_count={} cwd={}",
+        "准备启动游戏 game_id={} mode={} magpie={} arg_count={} env_count={} cwd={}",
         game_id,
         if use_le { "le" } else { "normal" },
         use_magpie,
         args_clone.as_ref().map_or(0, Vec::len),
+        env.len(),
         game_dir.display()
     );
 
@@ -622,13 +768,15 @@ pub async fn launch_game<R: Runtime>(
             Ok(LaunchResult {
                 success: true,
                 message: format!(
-                    "成功启动游戏: {}，工作目录: {:?}{}",
+                    "成功启动游戏: {}，工作目录: {:?}{}{}",
                     exe_name.to_string_lossy(),
                     game_dir,
-                    if use_le { " (LE转区)" } else { "" }
+                    if use_le { " (LE转区)" } else { "" },
+                    wrapper_note
                 ),
                 code: None,
                 process_id: Some(process_id),
+                diagnostics: None,
             })
         }
         Err(e) => {
@@ -639,6 +787,13 @@ pub async fn launch_game<R: Runtime>(
                     "普通启动需要提权，准备回退到管理员启动 game_id={}: {}",
                     game_id, e
                 );
+                if !env.is_empty() {
+                    warn!(
+                        "提权启动无法传递自定义环境变量，已忽略 game_id={} env_count={}",
+                        game_id,
+                        env.len()
+                    );
+                }
                 // 对于LE启动，需要用LE路径作为执行文件，游戏路径作为参数
                 let (exec_path, exec_args) = if use_le {
                     let mut args = vec![game_path.clone()];
@@ -693,24 +848,98 @@ pub async fn launch_game<R: Runtime>(
                         Ok(LaunchResult {
                             success: true,
                             message: format!(
-                                "已使用管理员权限启动游戏: {}{}，工作目录: {:?}",
+                                "已使用管理员权限启动游戏: {}{}，工作目录: {:?}{}",
                                 exe_name.to_string_lossy(),
                                 if use_le { " (LE转区)" } else { "" },
-                                game_dir
+                                game_dir,
+                                wrapper_note
                             ),
                             code: None,
                             process_id: Some(pid),
+                            diagnostics: None,
                         })
                     }
                     Err(err2) => Err(format!("普通启动失败且提权启动失败: {} | {}", e, err2)),
                 }
             } else {
-                Err(format!("启动游戏失败: {}，目录: {:?}", e, game_dir))
+                Ok(LaunchResult {
+                    success: false,
+                    message: format!("启动游戏失败: {}，目录: {:?}", e, game_dir),
+                    code: Some(LAUNCH_FAILED.to_string()),
+                    process_id: None,
+                    diagnostics: Some(LaunchDiagnostics::collect(&executable_path, &game_dir)),
+                })
             }
         }
     }
 }
 
+/// 运行启动脚本，并等待游戏程序的进程出现
+///
+/// 脚本在自身所在目录运行（LE 转区不适用于脚本）。返回找到的游戏进程 PID；
+/// 脚本无法启动或超时仍未出现游戏进程时，返回交给前端的失败结果
+async fn launch_via_launcher(
+    launcher_path: &Path,
+    args: Option<&[String]>,
+    env: &[(String, String)],
+    executable_path: &Path,
+    detection_dir: &Path,
+) -> Result<Result<u32, LaunchResult>, String> {
+    if !launcher_path.is_file() {
+        return Err(format!("启动脚本不存在: {}", launcher_path.display()));
+    }
+    let launcher_dir = launcher_path.parent().unwrap_or(detection_dir);
+
+    let mut command = Command::new(launcher_path);
+    command.current_dir(launcher_dir);
+    if let Some(arguments) = args {
+        command.args(arguments);
+    }
+    command.envs(env.iter().map(|(key, value)| (key, value)));
+    if let Err(e) = command.gui_safe().spawn() {
+        return Ok(Err(LaunchResult {
+            success: false,
+            message: format!("启动脚本运行失败: {}，脚本: {}", e, launcher_path.display()),
+            code: Some(LAUNCH_FAILED.to_string()),
+            process_id: None,
+            diagnostics: Some(LaunchDiagnostics::collect(launcher_path, launcher_dir)),
+        }));
+    }
+
+    debug!(
+        "启动脚本已运行，等待游戏进程 launcher={} exe={}",
+        launcher_path.display(),
+        executable_path.display()
+    );
+    let deadline = Instant::now() + LAUNCHER_PROCESS_TIMEOUT;
+    loop {
+        if let Some(process_id) = find_external_process_for_game(executable_path, detection_dir) {
+            return Ok(Ok(process_id));
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+        time::sleep(LAUNCHER_POLL_INTERVAL).await;
+    }
+
+    warn!(
+        "启动脚本运行后未检测到游戏进程 launcher={} exe={}",
+        launcher_path.display(),
+        executable_path.display()
+    );
+    Ok(Err(LaunchResult {
+        success: false,
+        message: format!(
+            "启动脚本已运行，但 {} 秒内未检测到游戏进程: {}",
+            LAUNCHER_PROCESS_TIMEOUT.as_secs(),
+            executable_path.display()
+        ),
+        code: Some(LAUNCHER_TIMEOUT.to_string()),
+        process_id: None,
+        diagnostics: None,
+    }))
+}
+
 /// 停止游戏
 ///
 /// # Arguments
@@ -0,0 +1,137 @@
+//! 游戏媒体库（截图等）。
+//!
+//! 文件复制到封面目录的 `game_<id>/media` 下，数据库只记录文件名与顺序。
+//! 删除游戏时会随游戏封面目录一并清理，修改封面目录时也会一起迁移。
+
+use crate::database::repository::game_media_repository::{GameMediaRepository, GameMediaType};
+use crate::entity::game_media;
+use crate::game::cover::cloud::get_game_cover_dir;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{State, command};
+
+const MEDIA_SUBDIR: &str = "media";
+
+/// 返回给前端的媒体项，`path` 为解析后的完整路径
+#[derive(Debug, Serialize)]
+pub struct GameMediaItem {
+    pub id: i32,
+    pub game_id: i32,
+    pub media_type: String,
+    pub path: String,
+    pub sort_order: i32,
+    pub created_at: Option<i32>,
+}
+
+fn game_media_dir(game_id: i32) -> Result<PathBuf, String> {
+    Ok(get_game_cover_dir(game_id as u32)?.join(MEDIA_SUBDIR))
+}
+
+fn to_item(media_dir: &Path, media: game_media::Model) -> GameMediaItem {
+    GameMediaItem {
+        id: media.id,
+        game_id: media.game_id,
+        media_type: media.media_type,
+        path: media_dir
+            .join(&media.file_name)
+            .to_string_lossy()
+            .to_string(),
+        sort_order: media.sort_order,
+        created_at: media.created_at,
+    }
+}
+
+/// 获取游戏的全部媒体，按顺序排列
+#[command]
+pub async fn get_game_media(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+) -> Result<Vec<GameMediaItem>, String> {
+    let media_dir = game_media_dir(game_id)?;
+    let media = GameMediaRepository::find_by_game(&db, game_id)
+        .await
+        .map_err(|e| format!("获取游戏媒体失败: {}", e))?;
+    Ok(media
+        .into_iter()
+        .map(|item| to_item(&media_dir, item))
+        .collect())
+}
+
+/// 复制文件到游戏媒体目录并添加到媒体列表末尾
+#[command]
+pub async fn add_game_media(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    source_path: String,
+    media_type: GameMediaType,
+) -> Result<GameMediaItem, String> {
+    let source = Path::new(&source_path);
+    if !source.is_file() {
+        return Err(format!("媒体文件不存在: {}", source_path));
+    }
+
+    let media_dir = game_media_dir(game_id)?;
+    fs::create_dir_all(&media_dir).map_err(|e| format!("创建媒体目录失败: {}", e))?;
+    let timestamp_nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("获取系统时间失败: {}", e))?
+        .as_nanos();
+    let file_name = match source.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => format!(
+            "{}_{}.{}",
+            media_type.as_str(),
+            timestamp_nanos,
+            ext.to_lowercase()
+        ),
+        None => format!("{}_{}", media_type.as_str(), timestamp_nanos),
+    };
+    let target = media_dir.join(&file_name);
+    fs::copy(source, &target).map_err(|e| format!("复制媒体文件失败: {}", e))?;
+
+    match GameMediaRepository::add(&db, game_id, file_name, media_type).await {
+        Ok(media) => Ok(to_item(&media_dir, media)),
+        Err(e) => {
+            let _ = fs::remove_file(&target);
+            Err(format!("添加游戏媒体失败: {}", e))
+        }
+    }
+}
+
+/// 删除媒体记录及其文件，返回是否删除了记录
+///
+/// 文件删除失败只记录日志，不影响记录删除
+#[command]
+pub async fn remove_game_media(
+    db: State<'_, DatabaseConnection>,
+    media_id: i32,
+) -> Result<bool, String> {
+    let Some(media) = GameMediaRepository::remove(&db, media_id)
+        .await
+        .map_err(|e| format!("删除游戏媒体失败: {}", e))?
+    else {
+        return Ok(false);
+    };
+
+    let path = game_media_dir(media.game_id)?.join(&media.file_name);
+    if let Err(e) = fs::remove_file(&path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        log::warn!("删除媒体文件失败 {}: {}", path.display(), e);
+    }
+    Ok(true)
+}
+
+/// 按给定 ID 顺序重排游戏媒体，列表必须包含该游戏的全部媒体
+#[command]
+pub async fn reorder_game_media(
+    db: State<'_, DatabaseConnection>,
+    game_id: i32,
+    media_ids: Vec<i32>,
+) -> Result<(), String> {
+    GameMediaRepository::reorder(&db, game_id, media_ids)
+        .await
+        .map_err(|e| format!("重排游戏媒体失败: {}", e))
+}
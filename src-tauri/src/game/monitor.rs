@@ -1,3 +1,4 @@
+mod running;
 mod session;
 
 #[cfg(target_os = "windows")]
@@ -6,8 +7,11 @@ mod windows;
 #[cfg(target_os = "linux")]
 mod linux;
 
+pub use running::{RunningGames, pause_tracking, resume_tracking};
 pub use session::TimeTrackingMode;
-pub(crate) use session::{MonitoredSession, finalize_monitored_session};
+pub(crate) use session::{
+    MonitoredSession, finalize_monitored_session, is_tracking_paused, mark_game_running,
+};
 
 #[cfg(target_os = "windows")]
 pub use windows::*;
@@ -6,7 +6,10 @@
 // ============================================================================
 // 外部依赖导入
 // ============================================================================
-use super::{MonitoredSession, TimeTrackingMode, finalize_monitored_session};
+use super::{
+    MonitoredSession, TimeTrackingMode, finalize_monitored_session, is_tracking_paused,
+    mark_game_running,
+};
 use log::{debug, error, info, warn};
 use sea_orm::DatabaseConnection;
 use serde_json::json;
@@ -69,6 +72,7 @@ pub async fn monitor_game<R: Runtime>(
                     start_time: timestamp,
                     end_time: timestamp,
                     accumulated_seconds: 0,
+                    paused_seconds: 0,
                 },
             )
             .await;
@@ -429,6 +433,7 @@ async fn run_game_monitor(
     // Linux 版本的监控逻辑实现
     // {
     let mut accumulated_seconds = 0u64;
+    let mut paused_seconds = 0u64;
     let start_time = get_timestamp();
     tokio::time::sleep(Duration::from_secs(MONITOR_CHECK_INTERVAL_SECS * 3)).await;
 
@@ -448,6 +453,8 @@ async fn run_game_monitor(
         game_id, best_pid, candidate_pids
     );
 
+    mark_game_running(app_handle, db, game_id).await;
+
     // 通知前端会话开始
     if let Err(error) = app_handle.emit(
         "game-session-started",
@@ -501,9 +508,12 @@ async fn run_game_monitor(
 
             // 2. 清理候选列表中已失活的 PID（轻量级维护）
 
-            // 3. 前台判定：检查候选列表中是否有任何进程在前台
+            // 3. 用户手动暂停计时期间不累计时长
+            // 4. 前台判定：检查候选列表中是否有任何进程在前台
             //    这是关键优化点 - 即使最佳 PID 不在前台，其他候选 PID 在前台也算数
-            if let Some(foreground_pid) = check_any_foreground(&candidate_pids) {
+            if is_tracking_paused(&app_handle, game_id) {
+                paused_seconds += 1;
+            } else if let Some(foreground_pid) = check_any_foreground(&candidate_pids) {
                 accumulated_seconds += 1;
 
                 // 如果前台进程不是当前的最佳 PID，考虑切换
@@ -554,6 +564,7 @@ async fn run_game_monitor(
             start_time,
             end_time: get_timestamp(),
             accumulated_seconds,
+            paused_seconds,
         },
     )
     .await;
@@ -0,0 +1,129 @@
+use parking_lot::RwLock;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State, command};
+
+/// 前端创建的托盘图标 ID（见 `trayService.ts`）
+const TRAY_ID: &str = "main";
+
+struct RunningGame {
+    name: String,
+    /// 用户手动暂停计时，监控循环暂停期间不累计时长
+    paused: AtomicBool,
+}
+
+/// 当前正在游玩的游戏（game_id -> 显示名称与暂停标记），由监控会话维护
+#[derive(Default)]
+pub struct RunningGames {
+    games: RwLock<BTreeMap<u32, RunningGame>>,
+}
+
+impl RunningGames {
+    pub fn insert(&self, game_id: u32, name: String) {
+        self.games.write().insert(
+            game_id,
+            RunningGame {
+                name,
+                paused: AtomicBool::new(false),
+            },
+        );
+    }
+
+    pub fn remove(&self, game_id: u32) {
+        self.games.write().remove(&game_id);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.games
+            .read()
+            .values()
+            .map(|game| {
+                if game.paused.load(Ordering::Acquire) {
+                    format!("{}（已暂停）", game.name)
+                } else {
+                    game.name.clone()
+                }
+            })
+            .collect()
+    }
+
+    pub fn is_paused(&self, game_id: u32) -> bool {
+        self.games
+            .read()
+            .get(&game_id)
+            .is_some_and(|game| game.paused.load(Ordering::Acquire))
+    }
+
+    /// 设置暂停标记，游戏未在运行时返回 false
+    fn set_paused(&self, game_id: u32, paused: bool) -> bool {
+        match self.games.read().get(&game_id) {
+            Some(game) => {
+                game.paused.store(paused, Ordering::Release);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 手动暂停游戏计时，暂停期间不计入会话时长
+#[command]
+pub fn pause_tracking(
+    app_handle: AppHandle,
+    running: State<'_, RunningGames>,
+    game_id: u32,
+) -> Result<(), String> {
+    set_tracking_paused(&app_handle, &running, game_id, true)
+}
+
+/// 恢复游戏计时
+#[command]
+pub fn resume_tracking(
+    app_handle: AppHandle,
+    running: State<'_, RunningGames>,
+    game_id: u32,
+) -> Result<(), String> {
+    set_tracking_paused(&app_handle, &running, game_id, false)
+}
+
+fn set_tracking_paused(
+    app_handle: &AppHandle,
+    running: &RunningGames,
+    game_id: u32,
+    paused: bool,
+) -> Result<(), String> {
+    if !running.set_paused(game_id, paused) {
+        return Err(format!("游戏未在运行: game_id={}", game_id));
+    }
+
+    refresh_tray_tooltip(app_handle);
+    if let Err(error) = app_handle.emit(
+        "game-tracking-paused",
+        json!({ "gameId": game_id, "paused": paused }),
+    ) {
+        log::warn!("无法发送 game-tracking-paused 事件: {error}");
+    }
+    Ok(())
+}
+
+/// 根据正在游玩的游戏刷新托盘提示文字
+///
+/// 托盘由前端创建，尚未创建时直接跳过。
+pub(crate) fn refresh_tray_tooltip<R: Runtime>(app_handle: &AppHandle<R>) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let mut tooltip = format!("ReinaManager v{}", app_handle.package_info().version);
+    if let Some(running) = app_handle.try_state::<RunningGames>() {
+        let names = running.names();
+        if !names.is_empty() {
+            tooltip.push_str(&format!("\n正在游玩: {}", names.join(", ")));
+        }
+    }
+
+    if let Err(error) = tray.set_tooltip(Some(tooltip)) {
+        log::warn!("更新托盘提示失败: {error}");
+    }
+}
@@ -1,12 +1,22 @@
+use super::running::{RunningGames, refresh_tray_tooltip};
+use crate::backup::autosave::backup_on_exit;
 use crate::database::repository::game_stats_repository::GameStatsRepository;
+use crate::database::repository::games_repository::GamesRepository;
+use crate::game::hooks::spawn_post_exit_hook;
+use crate::utils::playtime::format_playtime;
 use log::{error, info, warn};
 use sea_orm::DatabaseConnection;
 use serde::Deserialize;
 use serde_json::json;
-use tauri::{AppHandle, Emitter, Runtime};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_store::StoreExt;
 
 const MIN_SESSION_SECONDS: u64 = 60;
 
+/// 前端 settings.json 中控制会话结束通知的键
+const SESSION_END_NOTIFICATION_KEY: &str = "session_end_notification";
+
 #[derive(Debug, Clone, Copy, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TimeTrackingMode {
@@ -31,6 +41,8 @@ pub(crate) struct MonitoredSession {
     pub start_time: u64,
     pub end_time: u64,
     pub accumulated_seconds: u64,
+    /// 用户手动暂停计时的累计秒数
+    pub paused_seconds: u64,
 }
 
 fn calculate_session_duration(
@@ -38,12 +50,15 @@ fn calculate_session_duration(
     start_time: u64,
     end_time: u64,
     accumulated_seconds: u64,
+    paused_seconds: u64,
 ) -> Result<Option<SessionDuration>, String> {
     let effective_seconds = match mode {
+        // 暂停期间本就不累计前台时长
         TimeTrackingMode::Playtime => accumulated_seconds,
         TimeTrackingMode::Elapsed => end_time
             .checked_sub(start_time)
-            .ok_or_else(|| "会话结束时间早于开始时间".to_string())?,
+            .ok_or_else(|| "会话结束时间早于开始时间".to_string())?
+            .saturating_sub(paused_seconds),
     };
 
     if effective_seconds < MIN_SESSION_SECONDS {
@@ -56,17 +71,51 @@ fn calculate_session_duration(
     }))
 }
 
+/// 游戏计时是否被用户手动暂停
+pub(crate) fn is_tracking_paused<R: Runtime>(app_handle: &AppHandle<R>, game_id: u32) -> bool {
+    app_handle
+        .try_state::<RunningGames>()
+        .is_some_and(|running| running.is_paused(game_id))
+}
+
+/// 登记正在游玩的游戏，并刷新托盘提示
+pub(crate) async fn mark_game_running<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    db: &DatabaseConnection,
+    game_id: u32,
+) {
+    let name = match GamesRepository::find_display_name(db, game_id as i32, None).await {
+        Ok(Some(name)) => name,
+        Ok(None) => format!("#{game_id}"),
+        Err(error) => {
+            warn!("获取游戏名称失败: game_id={game_id}, error={error}");
+            format!("#{game_id}")
+        }
+    };
+
+    if let Some(running) = app_handle.try_state::<RunningGames>() {
+        running.insert(game_id, name);
+    }
+    refresh_tray_tooltip(app_handle);
+}
+
 pub(crate) async fn finalize_monitored_session<R: Runtime>(
     app_handle: &AppHandle<R>,
     db: &DatabaseConnection,
     session: MonitoredSession,
 ) {
+    if let Some(running) = app_handle.try_state::<RunningGames>() {
+        running.remove(session.game_id);
+    }
+    refresh_tray_tooltip(app_handle);
+
     let foreground_minutes = round_seconds_to_minutes(session.accumulated_seconds);
     let session_duration = calculate_session_duration(
         session.time_tracking_mode,
         session.start_time,
         session.end_time,
         session.accumulated_seconds,
+        session.paused_seconds,
     );
     let mut recorded = false;
     let mut session_id = None;
@@ -128,6 +177,10 @@ pub(crate) async fn finalize_monitored_session<R: Runtime>(
         }
     }
 
+    if recorded {
+        notify_session_ended(app_handle, db, session.game_id, duration_minutes).await;
+    }
+
     if let Err(error) = app_handle.emit(
         "game-session-ended",
         json!({
@@ -145,6 +198,60 @@ pub(crate) async fn finalize_monitored_session<R: Runtime>(
     ) {
         warn!("无法发送 game-session-ended 事件: {error}");
     }
+
+    // 先备份存档，再执行可能改动或卸载存档所在位置的退出后命令
+    backup_on_exit(app_handle, db, session.game_id).await;
+    spawn_post_exit_hook(db.clone(), session.game_id);
+}
+
+/// 会话记录完成后发送系统通知（需在设置中开启）
+///
+/// 必须在会话写入数据库之后调用，保证今日累计时长已包含本次会话。
+async fn notify_session_ended<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    db: &DatabaseConnection,
+    game_id: u32,
+    duration_minutes: u64,
+) {
+    let enabled = app_handle
+        .store("settings.json")
+        .ok()
+        .and_then(|store| store.get(SESSION_END_NOTIFICATION_KEY))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+
+    let Ok(game_id) = i32::try_from(game_id) else {
+        return;
+    };
+    let name = GamesRepository::find_display_name(db, game_id, None)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| format!("#{game_id}"));
+    let today_minutes = match GameStatsRepository::get_today_playtime(db, game_id).await {
+        Ok(minutes) => minutes,
+        Err(error) => {
+            warn!("获取今日游玩时长失败: game_id={game_id}, error={error}");
+            return;
+        }
+    };
+
+    if let Err(error) = app_handle
+        .notification()
+        .builder()
+        .title(name)
+        .body(format!(
+            "本次游玩 {}，今日累计 {}",
+            format_playtime(duration_minutes as i64 * 60),
+            format_playtime(i64::from(today_minutes) * 60)
+        ))
+        .show()
+    {
+        warn!("发送会话结束通知失败: {error}");
+    }
 }
 
 #[cfg(test)]
@@ -153,7 +260,7 @@ mod tests {
 
     #[test]
     fn playtime_mode_uses_accumulated_foreground_time() {
-        let duration = calculate_session_duration(TimeTrackingMode::Playtime, 100, 1000, 95)
+        let duration = calculate_session_duration(TimeTrackingMode::Playtime, 100, 1000, 95, 0)
             .expect("计算应成功")
             .expect("应达到记录阈值");
 
@@ -168,7 +275,7 @@ mod tests {
 
     #[test]
     fn elapsed_mode_uses_wall_clock_time() {
-        let duration = calculate_session_duration(TimeTrackingMode::Elapsed, 100, 195, 10)
+        let duration = calculate_session_duration(TimeTrackingMode::Elapsed, 100, 195, 10, 0)
             .expect("计算应成功")
             .expect("应达到记录阈值");
 
@@ -181,10 +288,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn elapsed_mode_excludes_paused_time() {
+        let duration = calculate_session_duration(TimeTrackingMode::Elapsed, 100, 400, 10, 180)
+            .expect("计算应成功")
+            .expect("应达到记录阈值");
+
+        assert_eq!(
+            duration,
+            SessionDuration {
+                effective_seconds: 120,
+                duration_minutes: 2,
+            }
+        );
+    }
+
     #[test]
     fn duration_below_threshold_is_not_recorded() {
         assert_eq!(
-            calculate_session_duration(TimeTrackingMode::Playtime, 100, 159, 59)
+            calculate_session_duration(TimeTrackingMode::Playtime, 100, 159, 59, 0)
                 .expect("计算应成功"),
             None
         );
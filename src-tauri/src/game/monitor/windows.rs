@@ -3,7 +3,10 @@
 //! 使用事件驱动架构监控游戏进程的运行状态，追踪游戏时间。
 //! 包含前台窗口检测、进程切换处理、逃逸进程检测等功能。
 
-use super::{MonitoredSession, TimeTrackingMode, finalize_monitored_session};
+use super::{
+    MonitoredSession, TimeTrackingMode, finalize_monitored_session, is_tracking_paused,
+    mark_game_running,
+};
 use sea_orm::DatabaseConnection;
 
 // ============================================================================
@@ -136,6 +139,18 @@ pub fn is_game_monitored(game_id: u32) -> bool {
     get_sessions().read().contains_key(&game_id)
 }
 
+/// 返回指定游戏监控会话中仍在运行的进程 PID，没有活跃会话时返回 `None`
+pub fn monitored_process_id(game_id: u32) -> Option<u32> {
+    get_sessions().read().get(&game_id).and_then(|session| {
+        session
+            .candidate_pids
+            .read()
+            .iter()
+            .copied()
+            .find(|pid| is_process_running(*pid))
+    })
+}
+
 // ============================================================================
 // 公共 API
 // ============================================================================
@@ -263,6 +278,7 @@ async fn run_game_monitor<R: Runtime>(
     detection_dir: String,
 ) -> Result<(), String> {
     let mut accumulated_seconds = 0u64;
+    let mut paused_seconds = 0u64;
     let start_time = get_timestamp();
 
     // 等待游戏进程充分启动（例如 Launcher -> Game 的切换）
@@ -314,6 +330,8 @@ async fn run_game_monitor<R: Runtime>(
     // 获取当前最佳 PID
     let best_pid = monitor_state.read().best_pid;
 
+    mark_game_running(&app_handle, &db, game_id).await;
+
     // 通知前端会话开始
     if let Err(error) = app_handle.emit(
         "game-session-started",
@@ -399,8 +417,11 @@ async fn run_game_monitor<R: Runtime>(
                 last_best_pid = current_best_pid;
             }
 
-            // 前台判定：仅检查共享状态（性能优化的关键）
-            if is_foreground {
+            // 用户手动暂停计时期间不累计时长
+            if is_tracking_paused(&app_handle, game_id) {
+                paused_seconds += 1;
+            } else if is_foreground {
+                // 前台判定：仅检查共享状态（性能优化的关键）
                 accumulated_seconds += 1;
 
                 // 发送时间更新
@@ -439,6 +460,7 @@ async fn run_game_monitor<R: Runtime>(
             start_time,
             end_time: get_timestamp(),
             accumulated_seconds,
+            paused_seconds,
         },
     )
     .await;
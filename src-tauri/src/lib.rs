@@ -4,29 +4,63 @@ mod entity;
 mod game;
 mod utils;
 
+use backup::active::{ActiveBackups, cancel_backup};
 use backup::covers::backup_custom_covers;
 use backup::database::{backup_database, import_database};
+use backup::disk_usage::get_backup_disk_usage;
+use backup::incremental::compact_backup_chain;
+use backup::reconcile::reconcile_savedata_backups;
+use backup::save_detect::detect_save_folder;
 use backup::savedata::{
-    create_savedata_backup, delete_savedata_backup, move_backup_folder, restore_savedata_backup,
+    change_save_root_path, create_savedata_backup, delete_savedata_backup,
+    diff_save_against_backup, list_backup_contents, move_backup_folder, prune_savedata_backups,
+    restore_savedata_backup, restore_to_game,
 };
+use backup::size_estimate::estimate_backup_size;
+use backup::verify::verify_all_backups;
+use database::app_meta::get_schema_owner_version;
+use database::collection_import::import_collection;
+use database::library_export::{export_library_to_file, import_library, import_library_from_file};
+use database::migration_guard::{MigrationIntegrityReport, get_migration_integrity_report};
 use database::*;
-use game::cover::custom::{delete_game_covers, import_clipboard_image_to_temp};
+use game::cover::custom::{
+    delete_game_covers, import_clipboard_image_to_temp, process_cover, set_cover_from_url,
+};
+use game::cover::location::{change_covers_path, get_covers_path, load_custom_covers_path};
 use game::cover::{delete_cloud_cache, register_game_cover_protocol};
-use game::launch::{adopt_external_running_games, launch_game, stop_game};
+use game::launch::{
+    adopt_external_running_games, launch_game, launch_game_by_id, make_executable, stop_game,
+};
+use game::media::{add_game_media, get_game_media, remove_game_media, reorder_game_media};
+use game::monitor::{RunningGames, pause_tracking, resume_tracking};
 use game::scan::scan_directory_for_games;
 use migration::MigratorTrait;
 use tauri::Manager;
 use tauri_plugin_log::{RotationStrategy, Target, TargetKind, TimezoneStrategy};
 use utils::{
+    autostart::{get_autostart_enabled, set_autostart_enabled, sync_autostart_preference},
     bgm_auth::{bgm_oauth_exchange_code, bgm_oauth_refresh_token, bgm_oauth_start_login},
     fs::{
-        copy_file, delete_file, is_portable_mode, open_directory, resolve_dropped_local_path,
-        resolve_local_path_directory,
+        copy_file, delete_file, get_app_paths, get_save_folder_mtime, is_portable_mode,
+        open_directory, resolve_dropped_local_path, resolve_local_path_directory,
+        reveal_in_explorer, validate_save_path,
     },
+    health::{get_connection_diagnostics, run_health_check},
     http::update_proxy_config,
     image::register_image_proxy_protocol,
     legacy_migration::run_startup_migrations,
     logs::{get_reina_log_level, set_reina_log_level},
+    maintenance::{
+        get_maintenance_config, run_maintenance_now, set_maintenance_config,
+        spawn_maintenance_scheduler,
+    },
+    mode_migration::{finish_pending_mode_migration, migrate_to_portable, migrate_to_standard},
+    refresh_progress::{
+        finish_refresh_metadata, get_pending_refresh_metadata, mark_refresh_metadata_progress,
+        resume_refresh_metadata, start_refresh_metadata,
+    },
+    reset::{request_reset_phrase, reset_all_data},
+    storage::{get_database_stats, get_library_disk_usage, vacuum_database},
 };
 
 const LOG_MAX_FILE_SIZE: u128 = 1_000_000;
@@ -42,6 +76,7 @@ pub fn run() {
         .plugin(tauri_plugin_window_state::Builder::new().build())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            // 窗口可能已最小化到托盘（隐藏），需先显示再聚焦
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
                 let _ = window.unminimize();
@@ -56,53 +91,142 @@ pub fn run() {
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
+        .manage(RunningGames::default())
+        .manage(ActiveBackups::default())
         .invoke_handler(tauri::generate_handler![
             // 工具类 commands
             launch_game,
+            launch_game_by_id,
+            make_executable,
             stop_game,
+            pause_tracking,
+            resume_tracking,
             adopt_external_running_games,
             open_directory,
+            reveal_in_explorer,
             resolve_local_path_directory,
             resolve_dropped_local_path,
             is_portable_mode,
+            get_app_paths,
+            migrate_to_portable,
+            migrate_to_standard,
+            validate_save_path,
+            get_save_folder_mtime,
             scan_directory_for_games,
             move_backup_folder,
+            change_save_root_path,
             copy_file,
             create_savedata_backup,
+            estimate_backup_size,
+            get_backup_disk_usage,
+            cancel_backup,
+            detect_save_folder,
             delete_savedata_backup,
+            prune_savedata_backups,
             restore_savedata_backup,
+            restore_to_game,
+            compact_backup_chain,
+            verify_all_backups,
+            reconcile_savedata_backups,
+            list_backup_contents,
+            diff_save_against_backup,
             delete_file,
             import_clipboard_image_to_temp,
             delete_game_covers,
+            process_cover,
+            set_cover_from_url,
             delete_cloud_cache,
+            get_covers_path,
+            change_covers_path,
+            get_game_media,
+            add_game_media,
+            remove_game_media,
+            reorder_game_media,
             backup_database,
             backup_custom_covers,
             import_database,
+            export_library_to_file,
+            import_library_from_file,
+            import_library,
+            get_library_disk_usage,
+            get_database_stats,
+            vacuum_database,
+            request_reset_phrase,
+            reset_all_data,
+            run_health_check,
+            get_connection_diagnostics,
+            get_schema_owner_version,
+            get_migration_integrity_report,
+            get_autostart_enabled,
+            set_autostart_enabled,
             // 游戏数据相关 commands
             insert_game,
+            insert_game_checked,
             insert_games_batch,
             find_game_by_id,
             find_all_games,
+            find_games_page,
             find_game_ids,
             update_game,
             delete_game,
+            delete_game_complete,
+            merge_games,
             delete_games_batch,
+            get_launch_args,
+            set_launch_args,
+            get_launch_hooks,
+            set_launch_hooks,
+            set_primary_source,
+            rename_game,
+            toggle_favorite,
+            get_game_status,
+            set_game_status,
+            set_local_path,
+            archive_game,
+            unarchive_game,
+            get_recently_archived,
+            purge_archived,
+            get_maintenance_config,
+            set_maintenance_config,
+            run_maintenance_now,
             count_games,
+            count_search_matches,
+            search_games,
+            get_all_developers,
+            rename_developer,
+            get_all_tags,
             get_source_bindings,
             update_games_batch,
+            start_refresh_metadata,
+            mark_refresh_metadata_progress,
+            resume_refresh_metadata,
+            get_pending_refresh_metadata,
+            finish_refresh_metadata,
             // 存档备份相关 commands
             save_savedata_record,
             get_savedata_count,
+            get_backup_size_summary,
             get_savedata_records,
+            get_latest_backups,
             // 游戏统计相关 commands
             create_manual_game_session,
+            import_sessions,
             rebuild_game_statistics,
             get_game_sessions,
             get_recent_sessions_for_all,
+            get_all_sessions_paged,
             delete_game_session,
             get_game_statistics,
             get_all_game_statistics,
             get_all_game_last_played,
+            get_total_playtime,
+            get_completion_stats,
+            get_playtime_since,
+            get_game_playtime_since,
+            get_activity_heatmap,
+            find_games_played_on,
+            get_dashboard_data,
             // 用户设置相关 commands
             get_all_settings,
             update_settings,
@@ -120,7 +244,12 @@ pub fn run() {
             update_collection,
             delete_collection,
             remove_games_from_collection,
+            move_games_between_collections,
+            merge_collections,
+            import_collection,
             get_games_in_collection,
+            get_full_games_in_collection,
+            set_collection_view_sort,
             get_game_collection_ids,
             add_games_to_collections,
             set_game_collections,
@@ -180,6 +309,14 @@ pub fn run() {
                 log::set_max_level(log::LevelFilter::Info);
             }
 
+            sync_autostart_preference(app.handle());
+            load_custom_covers_path(app.handle());
+
+            // 先清理上次模式迁移遗留的数据目录，确保后续按新模式解析路径
+            if let Err(err) = finish_pending_mode_migration() {
+                log::error!("清理模式迁移遗留数据失败: {}", err);
+            }
+
             match run_startup_migrations() {
                 Ok(result) if result.executed == 0 => {
                     log::debug!("启动迁移检查完成，无需执行");
@@ -206,18 +343,43 @@ pub fn run() {
                     Ok(conn) => {
                         log::debug!("数据库连接建立成功");
 
-                        // 执行数据库迁移
-                        log::debug!("开始执行数据库迁移...");
-                        match migration::Migrator::up(&conn, None).await {
-                            Ok(_) => log::info!("数据库迁移完成"),
-                            Err(e) => {
-                                log::error!("数据库迁移失败: {}", e);
-                                panic!("数据库迁移失败，已停止启动: {}", e);
+                        // 数据库由更新版本的应用写入时不执行迁移，避免旧版本误改数据
+                        let owner_version = app_meta::read_schema_owner_version(&conn)
+                            .await
+                            .unwrap_or_else(|e| {
+                                log::warn!("读取数据库版本信息失败: {}", e);
+                                None
+                            });
+                        let integrity_report = if let Some(version) = owner_version.as_deref()
+                            && app_meta::is_newer_version(version, app_meta::APP_VERSION)
+                        {
+                            log::warn!(
+                                "数据库由更新版本 {} 写入，当前版本为 {}，已跳过自动迁移，请升级应用",
+                                version,
+                                app_meta::APP_VERSION
+                            );
+                            MigrationIntegrityReport {
+                                integrity_ok: true,
+                                ..Default::default()
                             }
-                        }
+                        } else {
+                            // 备份数据库后执行迁移，迁移后检查完整性
+                            let report = migration_guard::run_guarded_migrations(&conn)
+                                .await
+                                .unwrap_or_else(|e| {
+                                    log::error!("数据库迁移失败: {}", e);
+                                    panic!("数据库迁移失败，已停止启动: {}", e);
+                                });
+                            if let Err(e) = app_meta::record_schema_owner_version(&conn).await {
+                                log::warn!("记录数据库版本信息失败: {}", e);
+                            }
+                            report
+                        };
+                        app_handle.manage(integrity_report);
 
                         // 将数据库连接注册到 Tauri 状态管理
                         app_handle.manage(conn.clone());
+                        spawn_maintenance_scheduler(app_handle.clone());
                     }
                     Err(e) => {
                         log::error!("无法建立数据库连接: {}", e);
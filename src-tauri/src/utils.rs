@@ -1,9 +1,17 @@
 #[cfg(target_os = "windows")]
 pub mod command_ext;
 
+pub mod autostart;
 pub mod bgm_auth;
 pub mod fs;
+pub mod health;
 pub mod http;
 pub mod image;
 pub mod legacy_migration;
 pub mod logs;
+pub mod maintenance;
+pub mod mode_migration;
+pub mod playtime;
+pub mod refresh_progress;
+pub mod reset;
+pub mod storage;
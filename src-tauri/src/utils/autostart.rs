@@ -0,0 +1,72 @@
+//! 开机自启设置。
+//!
+//! 系统注册（Windows 注册表 / macOS LaunchAgent / Linux desktop 文件）交由
+//! `tauri_plugin_autostart` 处理；用户偏好另存于 `settings.json`，重装后可据此恢复。
+
+use tauri::{AppHandle, Runtime, command};
+use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_store::StoreExt;
+
+const SETTINGS_STORE: &str = "settings.json";
+const AUTOSTART_KEY: &str = "autostart_enabled";
+
+/// 查询系统中是否已注册开机自启
+#[command]
+pub fn get_autostart_enabled(app: AppHandle) -> Result<bool, String> {
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("读取开机自启状态失败: {}", e))
+}
+
+/// 启用或禁用开机自启，并持久化用户偏好
+#[command]
+pub fn set_autostart_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    apply_autostart(&app, enabled)?;
+
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("打开设置存储失败: {}", e))?;
+    store.set(AUTOSTART_KEY, enabled);
+    store
+        .save()
+        .map_err(|e| format!("保存开机自启设置失败: {}", e))
+}
+
+/// 启动时按已保存的偏好校正系统注册状态（如重装后注册项丢失）
+///
+/// 未保存过偏好时不做任何改动。
+pub fn sync_autostart_preference<R: Runtime>(app: &AppHandle<R>) {
+    let preferred = app
+        .store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(AUTOSTART_KEY))
+        .and_then(|value| value.as_bool());
+    let Some(preferred) = preferred else {
+        return;
+    };
+
+    match app.autolaunch().is_enabled() {
+        Ok(current) if current == preferred => {}
+        Ok(_) => {
+            if let Err(e) = apply_autostart(app, preferred) {
+                log::warn!("{}", e);
+            } else {
+                log::info!("已按保存的偏好恢复开机自启状态: {}", preferred);
+            }
+        }
+        Err(e) => log::warn!("读取开机自启状态失败: {}", e),
+    }
+}
+
+fn apply_autostart<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let manager = app.autolaunch();
+    let result = if enabled {
+        manager.enable()
+    } else {
+        manager.disable()
+    };
+    result.map_err(|e| {
+        let action = if enabled { "启用" } else { "禁用" };
+        format!("{}开机自启失败（系统拒绝或权限不足）: {}", action, e)
+    })
+}
@@ -1,13 +1,17 @@
+use crate::backup::common::configured_db_backup_dir;
+use crate::backup::savedata::resolve_savedata_backup_root;
 use crate::game::local_path::resolve_game_directory;
 
 #[cfg(target_os = "windows")]
 use crate::utils::command_ext::CommandGuiExt;
 
+use sea_orm::DatabaseConnection;
 use serde::Serialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use tauri::command;
+use tauri::{State, command};
+use walkdir::WalkDir;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct PortableModeResult {
@@ -31,6 +35,17 @@ pub enum DroppedLocalPathKind {
     Invalid,
 }
 
+/// 存档路径检查结果
+#[derive(Debug, Serialize)]
+pub struct SavePathStatus {
+    pub exists: bool,
+    pub is_dir: bool,
+    pub readable: bool,
+    pub writable: bool,
+    /// 目录大小（字节），不可读时为 None
+    pub size: Option<u64>,
+}
+
 const LOCAL_EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "bat", "cmd"];
 
 fn is_supported_local_executable(path: &Path) -> bool {
@@ -43,15 +58,31 @@ fn is_supported_local_executable(path: &Path) -> bool {
 
 /// 打开目录
 ///
+/// `dir_path` 指向文件时打开其所在目录。路径不存在时默认返回错误；
+/// 传入 `create_if_missing = true` 时将其视为目录并先行创建（用于存档、备份等目录）。
+///
 /// # Arguments
 ///
 /// * `dir_path` - 要打开的目录路径
+/// * `create_if_missing` - 路径不存在时是否创建目录，默认 `false`
 ///
 /// # Returns
 ///
 /// 操作结果
 #[command]
-pub async fn open_directory(dir_path: String) -> Result<(), String> {
+pub async fn open_directory(
+    dir_path: String,
+    create_if_missing: Option<bool>,
+) -> Result<(), String> {
+    let target_path = Path::new(dir_path.trim());
+    if create_if_missing.unwrap_or(false)
+        && !target_path.as_os_str().is_empty()
+        && !target_path.exists()
+    {
+        fs::create_dir_all(target_path)
+            .map_err(|e| format!("创建目录 '{}' 失败: {}", target_path.display(), e))?;
+    }
+
     let open_path = resolve_game_directory(&dir_path)?;
 
     #[cfg(target_os = "windows")]
@@ -85,6 +116,15 @@ pub async fn open_directory(dir_path: String) -> Result<(), String> {
             }
         }
     }
+    #[cfg(target_os = "macos")]
+    {
+        let result = Command::new("open").arg(&open_path).spawn();
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(format!("无法打开目录 '{}': {}", open_path.display(), e)),
+        }
+    }
     #[cfg(target_os = "linux")]
     {
         let result = Command::new("xdg-open").arg(&open_path).spawn();
@@ -94,6 +134,76 @@ pub async fn open_directory(dir_path: String) -> Result<(), String> {
             Err(e) => Err(format!("无法打开目录 '{}': {}", open_path.display(), e)),
         }
     }
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        Err(format!("当前平台不支持打开目录 '{}'", open_path.display()))
+    }
+}
+
+/// 在文件管理器中定位文件
+///
+/// Windows 与 macOS 会打开所在目录并选中该文件；Linux 没有统一的选中方式，
+/// 退化为用 `xdg-open` 打开所在目录。
+///
+/// # Arguments
+///
+/// * `file_path` - 要定位的文件路径
+///
+/// # Returns
+///
+/// 操作结果
+#[command]
+pub async fn reveal_in_explorer(file_path: String) -> Result<(), String> {
+    let target_path = PathBuf::from(file_path.trim());
+    if target_path.as_os_str().is_empty() || !target_path.exists() {
+        return Err(format!("路径 '{}' 不存在", target_path.display()));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+
+        // explorer 不识别整体加引号的 /select 参数，需原样拼接
+        let normalized_path = target_path.to_string_lossy().replace('/', "\\");
+        Command::new("explorer")
+            .raw_arg(format!("/select,\"{}\"", normalized_path))
+            .gui_safe()
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("无法定位文件 '{}': {}", target_path.display(), e))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg("-R")
+            .arg(&target_path)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("无法定位文件 '{}': {}", target_path.display(), e))
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let open_path = if target_path.is_dir() {
+            target_path.as_path()
+        } else {
+            target_path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new("."))
+        };
+        Command::new("xdg-open")
+            .arg(open_path)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("无法打开目录 '{}': {}", open_path.display(), e))
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        Err(format!(
+            "当前平台不支持定位文件 '{}'",
+            target_path.display()
+        ))
+    }
 }
 
 #[command]
@@ -170,6 +280,141 @@ pub async fn resolve_dropped_local_path(
     .map_err(|e| format!("解析拖拽路径任务失败: {}", e))?
 }
 
+/// 通过创建并删除临时文件探测目录是否可写
+pub(crate) fn is_dir_writable(dir: &Path) -> bool {
+    let probe = dir.join(".reina_write_test");
+    let created = fs::write(&probe, b"").is_ok();
+    if created {
+        let _ = fs::remove_file(&probe);
+    }
+    created
+}
+
+/// 删除失败的文件
+#[derive(Debug, Serialize)]
+pub struct FileRemovalFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 文件清理结果：已删除的文件与删除失败的文件
+#[derive(Debug, Default, Serialize)]
+pub struct FileCleanupReport {
+    pub removed: Vec<String>,
+    pub failed: Vec<FileRemovalFailure>,
+}
+
+/// 逐个删除目录中的文件后移除目录，结果记入 `report`
+///
+/// 单个文件删除失败不会中断清理；目录不存在时视为无需清理
+pub(crate) fn remove_dir_with_report(dir: &Path, report: &mut FileCleanupReport) {
+    if !dir.exists() {
+        return;
+    }
+
+    for entry in WalkDir::new(dir).contents_first(true) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.failed.push(FileRemovalFailure {
+                    path: e
+                        .path()
+                        .map(|path| path.to_string_lossy().to_string())
+                        .unwrap_or_else(|| dir.to_string_lossy().to_string()),
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let path = entry.path();
+
+        if entry.file_type().is_dir() {
+            // 有文件删除失败时目录非空，失败原因已在文件中记录
+            if let Err(e) = fs::remove_dir(path) {
+                log::debug!("移除目录失败 path={} error={}", path.display(), e);
+            }
+            continue;
+        }
+
+        match fs::remove_file(path) {
+            Ok(()) => report.removed.push(path.to_string_lossy().to_string()),
+            Err(e) => report.failed.push(FileRemovalFailure {
+                path: path.to_string_lossy().to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+}
+
+/// 检查存档路径是否可用于自动备份
+///
+/// 依次检查路径是否存在、是否为目录、是否可读写，并统计目录大小。
+/// 可写性通过创建并删除一个临时文件来探测。
+#[command]
+pub async fn validate_save_path(path: String) -> Result<SavePathStatus, String> {
+    tokio::task::spawn_blocking(move || {
+        let path = PathBuf::from(path.trim());
+        let Ok(metadata) = fs::metadata(&path) else {
+            return SavePathStatus {
+                exists: false,
+                is_dir: false,
+                readable: false,
+                writable: false,
+                size: None,
+            };
+        };
+
+        let is_dir = metadata.is_dir();
+        let readable = is_dir && fs::read_dir(&path).is_ok();
+        let writable = is_dir && is_dir_writable(&path);
+        let size = readable
+            .then(|| get_directory_size(&path, &[]).ok())
+            .flatten();
+
+        SavePathStatus {
+            exists: true,
+            is_dir,
+            readable,
+            writable,
+            size,
+        }
+    })
+    .await
+    .map_err(|e| format!("检查存档路径任务失败: {}", e))
+}
+
+/// 获取存档目录内最近的修改时间（Unix 秒）
+///
+/// 递归检查目录树中所有文件，取最新的修改时间；许多引擎只会改动嵌套目录中的文件，
+/// 因此不能只看顶层目录。目录中没有文件时返回目录自身的修改时间。
+#[command]
+pub async fn get_save_folder_mtime(path: String) -> Result<i64, String> {
+    tokio::task::spawn_blocking(move || {
+        let dir = PathBuf::from(&path);
+        let metadata = fs::metadata(&dir).map_err(|_| format!("路径不存在: {}", path))?;
+
+        let latest = WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+            .max();
+        let modified = match latest {
+            Some(modified) => modified,
+            None => metadata
+                .modified()
+                .map_err(|e| format!("读取修改时间失败: {}", e))?,
+        };
+
+        modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .map_err(|e| format!("修改时间早于 Unix 纪元: {}", e))
+    })
+    .await
+    .map_err(|e| format!("读取存档修改时间任务失败: {}", e))?
+}
+
 /// 判断当前是否为便携模式
 #[command]
 pub fn is_portable_mode() -> PortableModeResult {
@@ -178,6 +423,29 @@ pub fn is_portable_mode() -> PortableModeResult {
     }
 }
 
+/// 应用数据所在位置
+#[derive(Debug, Serialize)]
+pub struct AppPaths {
+    pub data_dir: String,
+    pub db_path: String,
+    pub db_backup_dir: String,
+    pub savedata_backup_dir: String,
+    pub is_portable: bool,
+}
+
+/// 获取数据目录、数据库文件及两类备份目录的实际路径，供“数据位置”设置面板展示
+#[command]
+pub async fn get_app_paths(db: State<'_, DatabaseConnection>) -> Result<AppPaths, String> {
+    let to_string = |path: PathBuf| path.to_string_lossy().to_string();
+    Ok(AppPaths {
+        data_dir: to_string(reina_path::get_base_data_dir()?),
+        db_path: to_string(reina_path::get_db_path()?),
+        db_backup_dir: to_string(configured_db_backup_dir(&db).await?),
+        savedata_backup_dir: to_string(resolve_savedata_backup_root(&db).await?),
+        is_portable: reina_path::is_portable_mode(),
+    })
+}
+
 // ==================== 数据迁移相关文件操作 ====================
 
 /// 移动单个文件（剪切操作）
@@ -243,7 +511,6 @@ pub fn move_file(from: &Path, to: &Path) -> Result<(), String> {
 ///
 /// # Returns
 /// * `Result<usize, String>` - 成功移动的文件数量或错误消息
-#[allow(dead_code)]
 pub fn move_dir_recursive(from: &Path, to: &Path) -> Result<usize, String> {
     // 尝试使用 rename（同盘符时性能最好）
     match fs::rename(from, to) {
@@ -318,7 +585,6 @@ pub fn move_dir_recursive(from: &Path, to: &Path) -> Result<usize, String> {
 ///
 /// # Returns
 /// * `Result<usize, String>` - 成功复制的文件数量或致命错误
-#[allow(dead_code)]
 fn copy_dir_with_error_collection(
     from: &Path,
     to: &Path,
@@ -385,7 +651,6 @@ fn copy_dir_with_error_collection(
 }
 
 /// 统计目录中的文件数量（递归）
-#[allow(dead_code)]
 fn count_files_in_dir(dir: &Path) -> Result<usize, String> {
     let mut count = 0;
 
@@ -407,6 +672,95 @@ fn count_files_in_dir(dir: &Path) -> Result<usize, String> {
     Ok(count)
 }
 
+/// 递归计算目录总大小（字节）
+///
+/// 无法读取的条目会被跳过，不会中断统计；路径不存在时返回错误。
+/// `exclude` 为相对 `dir` 的子路径（`/` 或 `\` 分隔），命中的文件或整个子目录不计入：
+/// `redist` 只排除顶层的 redist 目录，`**/redist` 排除任意层级，段内支持 `*` 与 `?`。
+pub fn get_directory_size(dir: &Path, exclude: &[String]) -> Result<u64, String> {
+    if !dir.exists() {
+        return Err(format!("路径不存在: {}", dir.display()));
+    }
+
+    let patterns: Vec<Vec<&str>> = exclude
+        .iter()
+        .map(|pattern| split_relative_path(pattern))
+        .filter(|segments| !segments.is_empty())
+        .collect();
+
+    let total = WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let Ok(relative) = entry.path().strip_prefix(dir) else {
+                return true;
+            };
+            let relative = relative.to_string_lossy();
+            let segments = split_relative_path(&relative);
+            segments.is_empty()
+                || !patterns
+                    .iter()
+                    .any(|pattern| match_path_segments(&segments, pattern))
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    Ok(total)
+}
+
+fn split_relative_path(path: &str) -> Vec<&str> {
+    path.split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect()
+}
+
+/// 逐段匹配相对路径，`**` 匹配任意多个（含零个）路径段
+fn match_path_segments(path: &[&str], pattern: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skip| match_path_segments(&path[skip..], rest))
+        }
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            match_wildcard(name, segment) && match_path_segments(path_rest, rest)
+        }),
+    }
+}
+
+/// 单个路径段的通配符匹配（`*` 任意字符序列，`?` 单个字符），Windows 下不区分大小写
+fn match_wildcard(name: &str, pattern: &str) -> bool {
+    let normalize = |value: &str| -> Vec<char> {
+        if cfg!(windows) {
+            value.to_lowercase().chars().collect()
+        } else {
+            value.chars().collect()
+        }
+    };
+    let name = normalize(name);
+    let pattern = normalize(pattern);
+
+    let (mut n, mut p) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            n += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|character| *character == '*')
+}
+
 #[command]
 pub async fn copy_file(src: String, dst: String) -> Result<(), String> {
     let src_path = Path::new(&src);
@@ -425,14 +779,66 @@ pub async fn copy_file(src: String, dst: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 删除文件或将其移入系统回收站，文件不存在时视为成功
+pub fn remove_file_or_trash(path: &Path, recycle: bool) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    if recycle {
+        trash::delete(path).map_err(|e| format!("无法将文件移入回收站: {}", e))
+    } else {
+        fs::remove_file(path).map_err(|e| format!("无法删除文件: {}", e))
+    }
+}
+
 /// 删除文件
+///
+/// `recycle = true` 时移入系统回收站以便误删后恢复，默认永久删除
 #[command]
-pub async fn delete_file(file_path: String) -> Result<(), String> {
-    let path = Path::new(&file_path);
-    if !path.exists() {
-        return Ok(()); // 文件不存在，视为成功
+pub async fn delete_file(file_path: String, recycle: Option<bool>) -> Result<(), String> {
+    remove_file_or_trash(Path::new(&file_path), recycle.unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(path: &str, pattern: &str) -> bool {
+        match_path_segments(&split_relative_path(path), &split_relative_path(pattern))
     }
 
-    fs::remove_file(path).map_err(|e| format!("无法删除文件: {}", e))?;
-    Ok(())
+    #[test]
+    fn exclude_patterns_match_relative_paths() {
+        assert!(matches("redist", "redist"));
+        assert!(!matches("data/redist", "redist"));
+        assert!(matches("data/redist", "**/redist"));
+        assert!(matches("redist", "**/redist"));
+        assert!(matches("data\\cache", "data/cache"));
+        assert!(matches("movie_01.mpg", "movie_*.mpg"));
+        assert!(matches("save1", "save?"));
+        assert!(!matches("save10", "save?"));
+    }
+
+    #[test]
+    fn remove_dir_with_report_lists_removed_files() {
+        let root = std::env::temp_dir().join(format!(
+            "reina-remove-dir-report-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(root.join("nested")).expect("应能创建测试目录");
+        fs::write(root.join("a.7z"), []).expect("应能创建测试文件");
+        fs::write(root.join("nested").join("b.7z"), []).expect("应能创建测试文件");
+
+        let mut report = FileCleanupReport::default();
+        remove_dir_with_report(&root, &mut report);
+
+        assert_eq!(report.removed.len(), 2);
+        assert!(report.failed.is_empty());
+        assert!(!root.exists());
+
+        remove_dir_with_report(&root, &mut report);
+        assert_eq!(report.removed.len(), 2);
+    }
 }
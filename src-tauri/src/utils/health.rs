@@ -0,0 +1,206 @@
+//! 安装健康检查，汇总数据库与文件系统的一致性，便于用户附在问题反馈中。
+
+use crate::backup::common::configured_db_backup_dir;
+use crate::backup::savedata::resolve_savedata_backup_root;
+use crate::database::db::{ConnectionDiagnostics, collect_connection_diagnostics};
+use crate::database::migration_guard::check_integrity;
+use crate::database::repository::games_repository::GamesRepository;
+use crate::utils::fs::is_dir_writable;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, State, command};
+
+/// 目录可写性检查结果
+#[derive(Debug, Serialize)]
+pub struct DirectoryCheck {
+    pub kind: String,
+    pub path: String,
+    pub exists: bool,
+    pub writable: bool,
+}
+
+/// 健康检查报告
+///
+/// 各项检查相互独立，某项失败时对应字段为 None，失败原因记录在 `errors` 中。
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub app_version: String,
+    pub portable_mode: bool,
+    pub applied_migrations: Option<usize>,
+    pub pending_migrations: Option<Vec<String>>,
+    pub database_path: Option<String>,
+    pub database_size: Option<u64>,
+    /// `PRAGMA integrity_check` 的结果，正常时为 `["ok"]`
+    pub integrity_check: Option<Vec<String>>,
+    /// 日志模式与连接池状态
+    pub connection: Option<ConnectionDiagnostics>,
+    /// 设置了 `localpath` 但文件已不存在的游戏数量
+    pub missing_localpath_games: Option<usize>,
+    /// 备份文件已不存在的存档备份记录数量
+    pub missing_savedata_files: Option<usize>,
+    /// 成功解析路径的备份目录
+    pub backup_directories: Vec<DirectoryCheck>,
+    /// 失败的检查项及原因
+    pub errors: Vec<String>,
+}
+
+/// 运行健康检查
+///
+/// 单项检查失败不会中断整体流程，其余检查照常执行。
+#[command]
+pub async fn run_health_check(
+    app: AppHandle,
+    db: State<'_, DatabaseConnection>,
+) -> Result<HealthReport, String> {
+    let mut errors = Vec::new();
+
+    let applied_migrations = collect(
+        &mut errors,
+        Migrator::get_applied_migrations(&*db)
+            .await
+            .map(|migrations| migrations.len())
+            .map_err(|e| format!("读取已应用迁移失败: {}", e)),
+    );
+    let pending_migrations = collect(
+        &mut errors,
+        Migrator::get_pending_migrations(&*db)
+            .await
+            .map(|migrations| {
+                migrations
+                    .iter()
+                    .map(|migration| migration.name().to_string())
+                    .collect()
+            })
+            .map_err(|e| format!("读取待应用迁移失败: {}", e)),
+    );
+
+    let database_path = reina_path::get_db_path().ok();
+    let database_size = database_path
+        .as_ref()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len());
+
+    let integrity_check = collect(
+        &mut errors,
+        check_integrity(&db)
+            .await
+            .map_err(|e| format!("数据库完整性检查失败: {}", e)),
+    );
+    let connection = collect(
+        &mut errors,
+        collect_connection_diagnostics(&db)
+            .await
+            .map_err(|e| format!("读取数据库连接诊断失败: {}", e)),
+    );
+
+    let local_paths = collect(
+        &mut errors,
+        GamesRepository::get_local_game_paths(&db)
+            .await
+            .map_err(|e| format!("获取本地游戏路径失败: {}", e)),
+    );
+    let savedata_records = collect(
+        &mut errors,
+        GamesRepository::get_all_savedata_records(&db)
+            .await
+            .map_err(|e| format!("获取存档备份记录失败: {}", e)),
+    );
+    let savedata_root = collect(&mut errors, resolve_savedata_backup_root(&db).await);
+    let db_backup_dir = collect(&mut errors, configured_db_backup_dir(&db).await);
+
+    let filesystem = tokio::task::spawn_blocking(move || {
+        let missing_localpath_games = local_paths.map(|local_paths| {
+            local_paths
+                .iter()
+                .filter(|(_, localpath)| !Path::new(localpath).exists())
+                .count()
+        });
+        let missing_savedata_files =
+            savedata_root
+                .as_ref()
+                .zip(savedata_records)
+                .map(|(root, records)| {
+                    records
+                        .iter()
+                        .filter(|record| {
+                            !root
+                                .join(format!("game_{}", record.game_id))
+                                .join(&record.file)
+                                .exists()
+                        })
+                        .count()
+                });
+        let backup_directories = [
+            ("savedata_backup", savedata_root),
+            ("database_backup", db_backup_dir),
+        ]
+        .into_iter()
+        .filter_map(|(kind, path)| path.map(|path| check_directory(kind, &path)))
+        .collect();
+        (
+            missing_localpath_games,
+            missing_savedata_files,
+            backup_directories,
+        )
+    })
+    .await;
+    let (missing_localpath_games, missing_savedata_files, backup_directories) = match filesystem {
+        Ok(result) => result,
+        Err(e) => {
+            errors.push(format!("文件系统检查任务失败: {}", e));
+            (None, None, Vec::new())
+        }
+    };
+
+    if !errors.is_empty() {
+        log::warn!("健康检查部分项目失败: {}", errors.join("; "));
+    }
+
+    Ok(HealthReport {
+        app_version: app.package_info().version.to_string(),
+        portable_mode: reina_path::is_portable_mode(),
+        applied_migrations,
+        pending_migrations,
+        database_path: database_path.map(|path| path.to_string_lossy().to_string()),
+        database_size,
+        integrity_check,
+        connection,
+        missing_localpath_games,
+        missing_savedata_files,
+        backup_directories,
+        errors,
+    })
+}
+
+/// 将单项检查的结果转为 Option，失败原因追加到 `errors`
+fn collect<T>(errors: &mut Vec<String>, result: Result<T, String>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            errors.push(e);
+            None
+        }
+    }
+}
+
+/// 获取数据库连接诊断：日志模式、忙等待超时、WAL 文件大小与连接池使用情况
+#[command]
+pub async fn get_connection_diagnostics(
+    db: State<'_, DatabaseConnection>,
+) -> Result<ConnectionDiagnostics, String> {
+    collect_connection_diagnostics(&db)
+        .await
+        .map_err(|e| format!("读取数据库连接诊断失败: {}", e))
+}
+
+fn check_directory(kind: &str, path: &Path) -> DirectoryCheck {
+    let exists = path.is_dir();
+    DirectoryCheck {
+        kind: kind.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists,
+        writable: exists && is_dir_writable(path),
+    }
+}
@@ -23,9 +23,8 @@ static GLOBAL_HTTP_CLIENT: OnceLock<RwLock<Client>> = OnceLock::new();
 #[tauri::command]
 pub fn update_proxy_config(config: ProxyConfig) -> Result<(), String> {
     let client = build_client(config.url.trim())?;
-    let mut guard = http_client()
-        .write()
-        .map_err(|_| "更新 HTTP 客户端失败".to_string())?;
+    // 与 get_client 一致：锁被此前的 panic 污染时直接取回内部值，避免代理设置永久失效
+    let mut guard = http_client().write().unwrap_or_else(|e| e.into_inner());
     *guard = client;
     Ok(())
 }
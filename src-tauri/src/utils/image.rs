@@ -144,7 +144,20 @@ async fn fetch_image(url: &str) -> Result<(Vec<u8>, String), StatusCode> {
         return Err(StatusCode::BAD_GATEWAY);
     }
 
-    let content_type = response
+    let content_type =
+        image_content_type(&response, url).ok_or(StatusCode::UNSUPPORTED_MEDIA_TYPE)?;
+
+    let bytes = response.bytes().await.map_err(|e| {
+        log::warn!("读取代理图片响应失败 url={}: {}", url, e);
+        StatusCode::BAD_GATEWAY
+    })?;
+
+    Ok((bytes.to_vec(), content_type))
+}
+
+/// 取响应的图片 Content-Type，缺失或非图片时按 URL 扩展名推断
+fn image_content_type(response: &reqwest::Response, url: &str) -> Option<String> {
+    response
         .headers()
         .get("content-type")
         .and_then(|value| value.to_str().ok())
@@ -158,14 +171,48 @@ async fn fetch_image(url: &str) -> Result<(Vec<u8>, String), StatusCode> {
                 .filter(|value| value.starts_with("image/"))
                 .map(str::to_owned)
         })
-        .ok_or(StatusCode::UNSUPPORTED_MEDIA_TYPE)?;
+}
 
-    let bytes = response.bytes().await.map_err(|e| {
-        log::warn!("读取代理图片响应失败 url={}: {}", url, e);
-        StatusCode::BAD_GATEWAY
-    })?;
+/// 下载图片并限制大小，返回图片字节与 Content-Type
+///
+/// 与图片代理共用请求重试与类型判断逻辑；非图片响应或超过 `max_bytes` 时返回错误
+pub async fn download_image(url: &str, max_bytes: usize) -> Result<(Vec<u8>, String), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("图片地址无效: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("不支持的图片地址协议: {}", parsed.scheme()));
+    }
 
-    Ok((bytes.to_vec(), content_type))
+    let mut response = send_image_request(url)
+        .await
+        .map_err(|_| "请求图片失败".to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP 状态码异常: {}", response.status()));
+    }
+
+    let content_type =
+        image_content_type(&response, url).ok_or_else(|| "链接内容不是图片".to_string())?;
+    let too_large = || format!("图片过大，超过 {} MB 限制", max_bytes / 1024 / 1024);
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(too_large());
+    }
+
+    // Content-Length 可能缺失或不可信，边读边检查
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("读取图片响应失败: {}", e))?
+    {
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok((bytes, content_type))
 }
 
 pub fn register_image_proxy_protocol<R: tauri::Runtime>(
@@ -0,0 +1,222 @@
+//! 定期维护任务。
+//!
+//! 配置保存在 `settings.json` 的 `maintenance` 键下，启动后由后台任务按间隔执行，
+//! 也可通过 `run_maintenance_now` 手动触发。有存档备份进行中时跳过，避免争用数据库与磁盘。
+//! 元数据刷新依赖前端调用各数据源 API，不在此处调度。
+
+use crate::backup::active::ActiveBackups;
+use crate::backup::common::{cleanup_auto_backup_files, resolve_backup_dir};
+use crate::database::purge_archived_games;
+use crate::database::repository::game_stats_repository::GameStatsRepository;
+use crate::game::cover::DownloadState;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, command};
+use tauri_plugin_store::StoreExt;
+
+const SETTINGS_STORE: &str = "settings.json";
+const MAINTENANCE_KEY: &str = "maintenance";
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// 防止定时与手动维护同时执行
+static MAINTENANCE_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 定期维护配置，各任务可单独开关
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    /// 是否按间隔自动执行
+    pub enabled: bool,
+    pub interval_hours: u64,
+    /// 清理超出保留数量的数据库自动备份
+    pub prune_db_backups: bool,
+    pub max_db_auto_backups: usize,
+    /// 彻底删除归档超过保留天数的游戏
+    pub purge_archived: bool,
+    pub archived_retention_days: u32,
+    /// 从会话记录重建统计数据
+    pub rebuild_statistics: bool,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            prune_db_backups: true,
+            max_db_auto_backups: 10,
+            purge_archived: false,
+            archived_retention_days: 30,
+            rebuild_statistics: true,
+        }
+    }
+}
+
+/// 单个维护任务的执行结果
+#[derive(Debug, Serialize)]
+pub struct MaintenanceJobOutcome {
+    pub job: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// 获取定期维护配置，未保存过时返回默认值
+#[command]
+pub fn get_maintenance_config(app: AppHandle) -> Result<MaintenanceConfig, String> {
+    load_config(&app)
+}
+
+/// 保存定期维护配置，下一轮调度时生效
+#[command]
+pub fn set_maintenance_config(app: AppHandle, config: MaintenanceConfig) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("打开设置存储失败: {}", e))?;
+    store.set(
+        MAINTENANCE_KEY,
+        serde_json::to_value(&config).map_err(|e| format!("序列化维护配置失败: {}", e))?,
+    );
+    store.save().map_err(|e| format!("保存维护配置失败: {}", e))
+}
+
+/// 立即执行已开启的维护任务（不受 `enabled` 影响）
+#[command]
+pub async fn run_maintenance_now(app: AppHandle) -> Result<Vec<MaintenanceJobOutcome>, String> {
+    let config = load_config(&app)?;
+    run_maintenance(&app, &config).await
+}
+
+/// 启动定期维护的后台任务
+///
+/// 每轮等待配置的间隔后重新读取配置，关闭自动执行时仅跳过本轮。
+pub fn spawn_maintenance_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval_hours = load_config(&app)
+                .map(|config| config.interval_hours)
+                .unwrap_or_else(|_| MaintenanceConfig::default().interval_hours);
+            tokio::time::sleep(Duration::from_secs(
+                interval_hours.clamp(1, 24 * 365) * 60 * 60,
+            ))
+            .await;
+
+            let config = match load_config(&app) {
+                Ok(config) if config.enabled => config,
+                Ok(_) => continue,
+                Err(e) => {
+                    log::warn!("读取维护配置失败: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = run_maintenance(&app, &config).await {
+                log::info!("跳过定期维护: {}", e);
+            }
+        }
+    });
+}
+
+async fn run_maintenance(
+    app: &AppHandle,
+    config: &MaintenanceConfig,
+) -> Result<Vec<MaintenanceJobOutcome>, String> {
+    if app
+        .try_state::<ActiveBackups>()
+        .is_some_and(|backups| !backups.is_empty())
+    {
+        return Err("存档备份进行中，请稍后再试".to_string());
+    }
+    if MAINTENANCE_RUNNING.swap(true, Ordering::AcqRel) {
+        return Err("维护任务正在执行".to_string());
+    }
+
+    let outcomes = run_jobs(app, config).await;
+    MAINTENANCE_RUNNING.store(false, Ordering::Release);
+    Ok(outcomes)
+}
+
+async fn run_jobs(app: &AppHandle, config: &MaintenanceConfig) -> Vec<MaintenanceJobOutcome> {
+    let db = app.state::<DatabaseConnection>();
+    let mut outcomes = Vec::new();
+
+    if config.prune_db_backups {
+        let max_count = config.max_db_auto_backups;
+        let result = async {
+            let backup_dir = resolve_backup_dir(&db).await?;
+            tokio::task::spawn_blocking(move || {
+                cleanup_auto_backup_files(&backup_dir, "reina_manager_auto_", ".db", max_count)
+            })
+            .await
+            .map_err(|e| format!("清理任务失败: {}", e))?
+        }
+        .await
+        .map(|deleted| format!("删除了 {} 个旧的数据库自动备份", deleted.len()));
+        outcomes.push(job_outcome("prune_db_backups", result));
+    }
+
+    if config.purge_archived {
+        let older_than = i64::from(config.archived_retention_days) * SECONDS_PER_DAY;
+        let result = purge_archived_games(&db, &app.state::<DownloadState>(), older_than)
+            .await
+            .map(|count| format!("删除了 {} 个归档游戏", count));
+        outcomes.push(job_outcome("purge_archived", result));
+    }
+
+    if config.rebuild_statistics {
+        let result = GameStatsRepository::rebuild_all_statistics(&db)
+            .await
+            .map(|count| format!("重建了 {} 个游戏的统计数据", count))
+            .map_err(|e| format!("重建统计数据失败: {}", e));
+        outcomes.push(job_outcome("rebuild_statistics", result));
+    }
+
+    outcomes
+}
+
+fn job_outcome(job: &str, result: Result<String, String>) -> MaintenanceJobOutcome {
+    let (success, message) = match result {
+        Ok(message) => {
+            log::info!("维护任务 {} 完成: {}", job, message);
+            (true, message)
+        }
+        Err(error) => {
+            log::warn!("维护任务 {} 失败: {}", job, error);
+            (false, error)
+        }
+    };
+    MaintenanceJobOutcome {
+        job: job.to_string(),
+        success,
+        message,
+    }
+}
+
+fn load_config(app: &AppHandle) -> Result<MaintenanceConfig, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("打开设置存储失败: {}", e))?;
+    match store.get(MAINTENANCE_KEY) {
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("解析维护配置失败: {}", e))
+        }
+        None => Ok(MaintenanceConfig::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_config_falls_back_to_defaults() {
+        let config: MaintenanceConfig =
+            serde_json::from_value(serde_json::json!({ "enabled": true, "purge_archived": true }))
+                .unwrap();
+
+        assert!(config.enabled);
+        assert!(config.purge_archived);
+        assert_eq!(config.interval_hours, 24);
+        assert!(config.rebuild_statistics);
+    }
+}
@@ -0,0 +1,402 @@
+//! 便携模式与标准模式之间的数据迁移。
+//!
+//! 运行中先把数据库、存档备份与封面复制到目标模式数据目录下的暂存目录并逐个校验，
+//! 全部通过后才移入正式位置并删除源端的备份与封面。源数据库仍被当前连接占用，
+//! 只写入完成标记，由下次启动时在连接数据库之前删除（见 `finish_pending_mode_migration`）。
+
+use crate::database::dto::UpdateSettingsData;
+use crate::database::repository::settings_repository::SettingsRepository;
+use reina_path::{DB_DATA_DIR, DB_FILE_NAME, get_base_data_dir_for_mode, get_db_data_dir_for_mode};
+use sea_orm::{ConnectionTrait, Database, DatabaseBackend, DatabaseConnection, Statement};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::{State, command};
+use walkdir::WalkDir;
+
+/// 源数据库目录迁移完成后写入的标记文件
+const MIGRATED_MARKER: &str = ".migrated";
+/// 目标数据目录下的暂存目录，校验通过前不会影响模式判断
+const STAGING_DIR: &str = ".mode_migration";
+/// 随数据库一起迁移的目录（相对基础数据目录）
+const MIGRATED_DIRS: [&str; 2] = ["backups", "covers"];
+/// 预留空间，避免迁移后磁盘被写满
+const FREE_SPACE_MARGIN: u64 = 64 * 1024 * 1024;
+
+/// 模式迁移结果
+#[derive(Debug, Serialize)]
+pub struct ModeMigrationResult {
+    pub from_dir: String,
+    pub to_dir: String,
+    pub copied_files: usize,
+    pub copied_bytes: u64,
+    /// 新数据目录需重启应用后才会生效
+    pub restart_required: bool,
+}
+
+/// 将数据迁移到可执行文件旁的 `resources` 目录，重启后进入便携模式
+#[command]
+pub async fn migrate_to_portable(
+    db: State<'_, DatabaseConnection>,
+) -> Result<ModeMigrationResult, String> {
+    migrate_data_dir(&db, true).await
+}
+
+/// 将数据迁移到系统应用数据目录，重启后进入标准模式
+#[command]
+pub async fn migrate_to_standard(
+    db: State<'_, DatabaseConnection>,
+) -> Result<ModeMigrationResult, String> {
+    migrate_data_dir(&db, false).await
+}
+
+async fn migrate_data_dir(
+    db: &DatabaseConnection,
+    to_portable: bool,
+) -> Result<ModeMigrationResult, String> {
+    if reina_path::is_portable_mode() == to_portable {
+        return Err(if to_portable {
+            "当前已是便携模式".to_string()
+        } else {
+            "当前已是标准模式".to_string()
+        });
+    }
+
+    let source = get_base_data_dir_for_mode(!to_portable)?;
+    let target = get_base_data_dir_for_mode(to_portable)?;
+    if source.join(DB_DATA_DIR).join(MIGRATED_MARKER).exists() {
+        return Err("数据已迁移，请重启应用".to_string());
+    }
+    for name in std::iter::once(DB_DATA_DIR).chain(MIGRATED_DIRS) {
+        let path = target.join(name);
+        if !is_missing_or_empty_dir(&path) {
+            return Err(format!(
+                "目标位置已存在数据，请先手动处理: {}",
+                path.display()
+            ));
+        }
+    }
+
+    let staging = target.join(STAGING_DIR);
+    let (copied_files, copied_bytes) = {
+        let source = source.clone();
+        let staging = staging.clone();
+        tokio::task::spawn_blocking(move || prepare_staging(&source, &staging))
+            .await
+            .map_err(|e| format!("复制数据任务失败: {}", e))??
+    };
+
+    let staged_db = staging.join(DB_DATA_DIR).join(DB_FILE_NAME);
+    if let Err(e) = copy_database(db, &staged_db, &source, &target).await {
+        discard_staging(&staging);
+        return Err(e);
+    }
+
+    let (source_dir, target_dir) = (source.clone(), target.clone());
+    tokio::task::spawn_blocking(move || commit_staging(&source_dir, &staging, &target_dir))
+        .await
+        .map_err(|e| format!("提交迁移任务失败: {}", e))??;
+
+    log::info!(
+        "数据迁移完成，重启后生效: {} -> {} ({} 个文件, {} 字节)",
+        source.display(),
+        target.display(),
+        copied_files,
+        copied_bytes
+    );
+
+    Ok(ModeMigrationResult {
+        from_dir: source.to_string_lossy().to_string(),
+        to_dir: target.to_string_lossy().to_string(),
+        copied_files,
+        copied_bytes,
+        restart_required: true,
+    })
+}
+
+/// 删除上次模式迁移遗留的源数据库目录
+///
+/// 必须在连接数据库之前调用；只有另一模式下的数据库已存在时才会删除。
+pub fn finish_pending_mode_migration() -> Result<(), String> {
+    for portable in [true, false] {
+        let data_dir = get_db_data_dir_for_mode(portable)?;
+        if !data_dir.join(MIGRATED_MARKER).is_file() {
+            continue;
+        }
+
+        let migrated_db = get_db_data_dir_for_mode(!portable)?.join(DB_FILE_NAME);
+        if !migrated_db.is_file() {
+            log::warn!(
+                "迁移后的数据库不存在，保留源数据目录: {}",
+                data_dir.display()
+            );
+            continue;
+        }
+
+        fs::remove_dir_all(&data_dir)
+            .map_err(|e| format!("删除已迁移的数据目录 {} 失败: {}", data_dir.display(), e))?;
+        log::info!("已删除迁移前的数据目录: {}", data_dir.display());
+    }
+    Ok(())
+}
+
+fn is_missing_or_empty_dir(path: &Path) -> bool {
+    match fs::read_dir(path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(_) => !path.exists(),
+    }
+}
+
+/// 检查剩余空间，并把数据库以外的文件复制到暂存目录
+fn prepare_staging(source: &Path, staging: &Path) -> Result<(usize, u64), String> {
+    if staging.exists() {
+        fs::remove_dir_all(staging).map_err(|e| format!("清理旧的暂存目录失败: {}", e))?;
+    }
+
+    let required = std::iter::once(DB_DATA_DIR)
+        .chain(MIGRATED_DIRS)
+        .map(|name| source.join(name))
+        .filter(|dir| dir.exists())
+        .map(|dir| crate::utils::fs::get_directory_size(&dir, &[]))
+        .sum::<Result<u64, String>>()?;
+    check_free_space(staging, required)?;
+
+    fs::create_dir_all(staging.join(DB_DATA_DIR))
+        .map_err(|e| format!("创建暂存目录失败: {}", e))?;
+
+    let result: Result<(usize, u64), String> = (|| {
+        let mut total = (0, 0);
+        for name in std::iter::once(DB_DATA_DIR).chain(MIGRATED_DIRS) {
+            let (files, bytes) = copy_tree(&source.join(name), &staging.join(name))?;
+            total.0 += files;
+            total.1 += bytes;
+        }
+        Ok(total)
+    })();
+    if result.is_err() {
+        discard_staging(staging);
+    }
+    result
+}
+
+fn check_free_space(target: &Path, required: u64) -> Result<(), String> {
+    let existing = target
+        .ancestors()
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("目标路径无效: {}", target.display()))?;
+    let available =
+        fs4::available_space(existing).map_err(|e| format!("获取磁盘剩余空间失败: {}", e))?;
+
+    if available < required.saturating_add(FREE_SPACE_MARGIN) {
+        return Err(format!(
+            "目标磁盘空间不足：需要约 {} MB，剩余 {} MB",
+            required.div_ceil(1024 * 1024),
+            available / 1024 / 1024
+        ));
+    }
+    Ok(())
+}
+
+/// 复制目录树并逐个校验文件大小，返回文件数与字节数
+///
+/// 跳过正在使用的数据库文件及其日志，数据库由 `copy_database` 单独热备份。
+fn copy_tree(from: &Path, to: &Path) -> Result<(usize, u64), String> {
+    if !from.exists() {
+        return Ok((0, 0));
+    }
+
+    let mut files = 0;
+    let mut bytes = 0;
+    for entry in WalkDir::new(from).min_depth(1) {
+        let entry = entry.map_err(|e| format!("读取目录失败: {}", e))?;
+        let relative = entry
+            .path()
+            .strip_prefix(from)
+            .map_err(|e| format!("计算相对路径失败: {}", e))?;
+        let target = to.join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| format!("创建目录 {} 失败: {}", target.display(), e))?;
+            continue;
+        }
+        if is_live_database_file(relative) {
+            continue;
+        }
+
+        let expected = entry
+            .metadata()
+            .map_err(|e| format!("读取文件信息 {} 失败: {}", entry.path().display(), e))?
+            .len();
+        let copied = fs::copy(entry.path(), &target)
+            .map_err(|e| format!("复制文件 {} 失败: {}", entry.path().display(), e))?;
+        let written = fs::metadata(&target)
+            .map_err(|e| format!("校验文件 {} 失败: {}", target.display(), e))?
+            .len();
+        if copied != expected || written != expected {
+            return Err(format!("文件校验失败，大小不一致: {}", target.display()));
+        }
+
+        files += 1;
+        bytes += expected;
+    }
+    Ok((files, bytes))
+}
+
+fn is_live_database_file(relative: &Path) -> bool {
+    let Some(name) = relative.to_str() else {
+        return false;
+    };
+    name == DB_FILE_NAME
+        || ["-wal", "-shm", "-journal"]
+            .iter()
+            .any(|suffix| name.strip_suffix(suffix) == Some(DB_FILE_NAME))
+}
+
+/// 热备份数据库到暂存目录，校验副本完整性并改写其中位于旧数据目录下的路径设置
+async fn copy_database(
+    db: &DatabaseConnection,
+    staged_db: &Path,
+    source: &Path,
+    target: &Path,
+) -> Result<(), String> {
+    let staged_path = staged_db
+        .to_str()
+        .ok_or("暂存路径包含无效字符")?
+        .replace('\\', "/");
+    db.execute_unprepared(&format!(
+        "VACUUM INTO '{}'",
+        staged_path.replace('\'', "''")
+    ))
+    .await
+    .map_err(|e| format!("复制数据库失败: {}", e))?;
+
+    let url = url::Url::from_file_path(staged_db)
+        .map_err(|_| format!("无效的数据库路径: {}", staged_db.display()))?;
+    let copy = Database::connect(format!("sqlite:{}?mode=rw", url.path()))
+        .await
+        .map_err(|e| format!("打开数据库副本失败: {}", e))?;
+
+    let result = verify_and_rebase_settings(&copy, source, target).await;
+    if let Err(e) = copy.close().await {
+        log::warn!("关闭数据库副本失败: {}", e);
+    }
+    result
+}
+
+async fn verify_and_rebase_settings(
+    copy: &DatabaseConnection,
+    source: &Path,
+    target: &Path,
+) -> Result<(), String> {
+    let check = copy
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            "PRAGMA quick_check".to_string(),
+        ))
+        .await
+        .map_err(|e| format!("校验数据库副本失败: {}", e))?
+        .and_then(|row| row.try_get_by_index::<String>(0).ok());
+    if check.as_deref() != Some("ok") {
+        return Err(format!(
+            "数据库副本校验未通过: {}",
+            check.unwrap_or_default()
+        ));
+    }
+
+    let settings = SettingsRepository::get_all_settings(copy)
+        .await
+        .map_err(|e| format!("读取设置失败: {}", e))?;
+    let rebase = |path: Option<String>| {
+        path.and_then(|path| rebase_path(&path, source, target))
+            .map(Some)
+    };
+    let data = UpdateSettingsData {
+        save_root_path: rebase(settings.save_root_path),
+        db_backup_path: rebase(settings.db_backup_path),
+        ..Default::default()
+    };
+    if data.save_root_path.is_some() || data.db_backup_path.is_some() {
+        SettingsRepository::update_settings(copy, data)
+            .await
+            .map_err(|e| format!("更新路径设置失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 位于 `from` 之下的路径改写到 `to` 之下，其他路径返回 None
+fn rebase_path(path: &str, from: &Path, to: &Path) -> Option<String> {
+    let relative = Path::new(path).strip_prefix(from).ok()?;
+    Some(to.join(relative).to_string_lossy().to_string())
+}
+
+/// 将暂存目录移入正式位置（数据库目录最后移入），随后清理源端数据
+fn commit_staging(source: &Path, staging: &Path, target: &Path) -> Result<(), String> {
+    for name in MIGRATED_DIRS
+        .into_iter()
+        .chain(std::iter::once(DB_DATA_DIR))
+    {
+        let staged = staging.join(name);
+        let final_path = target.join(name);
+        if !staged.exists() {
+            continue;
+        }
+        if final_path.exists() {
+            fs::remove_dir(&final_path)
+                .map_err(|e| format!("清理目标目录 {} 失败: {}", final_path.display(), e))?;
+        }
+        fs::rename(&staged, &final_path)
+            .map_err(|e| format!("移动数据到 {} 失败: {}", final_path.display(), e))?;
+    }
+    discard_staging(staging);
+
+    fs::write(source.join(DB_DATA_DIR).join(MIGRATED_MARKER), b"")
+        .map_err(|e| format!("数据已复制，但写入迁移标记失败: {}", e))?;
+
+    for name in MIGRATED_DIRS {
+        let dir = source.join(name);
+        if dir.exists()
+            && let Err(e) = fs::remove_dir_all(&dir)
+        {
+            log::warn!("删除迁移前的目录失败 path={} error={}", dir.display(), e);
+        }
+    }
+    Ok(())
+}
+
+fn discard_staging(staging: &Path) {
+    if let Err(e) = fs::remove_dir_all(staging) {
+        log::warn!("清理暂存目录失败 path={} error={}", staging.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn rebase_path_only_rewrites_paths_under_source() {
+        let from = PathBuf::from("old").join("base");
+        let to = PathBuf::from("new");
+        let inside = from.join("backups").to_string_lossy().to_string();
+
+        assert_eq!(
+            rebase_path(&inside, &from, &to),
+            Some(to.join("backups").to_string_lossy().to_string())
+        );
+        assert_eq!(rebase_path("elsewhere/backups", &from, &to), None);
+    }
+
+    #[test]
+    fn live_database_files_are_skipped() {
+        assert!(is_live_database_file(Path::new(DB_FILE_NAME)));
+        assert!(is_live_database_file(Path::new(&format!(
+            "{}-wal",
+            DB_FILE_NAME
+        ))));
+        assert!(!is_live_database_file(
+            &Path::new("backups").join(DB_FILE_NAME)
+        ));
+    }
+}
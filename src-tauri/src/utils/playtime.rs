@@ -0,0 +1,35 @@
+//! 游玩时长格式化，保证各界面显示一致。
+
+/// 将秒数格式化为 `Xh Ym`
+///
+/// 0 及负数显示为 `0m`，不足一分钟显示为 `<1m`，整小时省略分钟部分。
+pub fn format_playtime(seconds: i64) -> String {
+    if seconds <= 0 {
+        return "0m".to_string();
+    }
+    if seconds < 60 {
+        return "<1m".to_string();
+    }
+
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_seconds_as_hours_and_minutes() {
+        assert_eq!(format_playtime(0), "0m");
+        assert_eq!(format_playtime(-5), "0m");
+        assert_eq!(format_playtime(59), "<1m");
+        assert_eq!(format_playtime(45 * 60), "45m");
+        assert_eq!(format_playtime(120 * 60), "2h");
+        assert_eq!(format_playtime(83 * 60 + 30), "1h 23m");
+    }
+}
@@ -0,0 +1,163 @@
+//! 元数据批量刷新的进度持久化。
+//!
+//! 刷新本身由前端调用各数据源 API 完成；后端只在 `settings.json` 中记录本轮待刷新的游戏，
+//! 中断（断网、关闭应用）后可凭令牌取回剩余部分继续，避免从头重跑触发限流。
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use tauri::{AppHandle, command};
+use tauri_plugin_store::StoreExt;
+
+const SETTINGS_STORE: &str = "settings.json";
+const REFRESH_RUN_KEY: &str = "metadata_refresh_run";
+
+/// 一轮元数据刷新的进度
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefreshRun {
+    pub token: String,
+    /// 数据源，如 `bgm`、`vndb`
+    pub source: String,
+    /// 尚未刷新的游戏 ID
+    pub pending: Vec<i32>,
+    /// 已刷新的游戏数量
+    pub completed: usize,
+}
+
+impl RefreshRun {
+    /// 将已刷新的游戏移出待处理列表，返回本轮是否已全部完成
+    fn mark_refreshed(&mut self, game_ids: &[i32]) -> bool {
+        let before = self.pending.len();
+        self.pending.retain(|id| !game_ids.contains(id));
+        self.completed += before - self.pending.len();
+        self.pending.is_empty()
+    }
+}
+
+/// 开始一轮新的元数据刷新并返回令牌
+///
+/// 同一时间只保留一轮进度，未完成的旧进度会被覆盖。
+#[command]
+pub fn start_refresh_metadata(
+    app: AppHandle,
+    source: String,
+    game_ids: Vec<i32>,
+) -> Result<RefreshRun, String> {
+    let mut pending = game_ids;
+    pending.sort_unstable();
+    pending.dedup();
+
+    let run = RefreshRun {
+        token: generate_token()?,
+        source,
+        pending,
+        completed: 0,
+    };
+    save_run(&app, Some(&run))?;
+    Ok(run)
+}
+
+/// 记录已刷新的游戏，全部完成时清除进度标记
+///
+/// 返回剩余进度；本轮已完成时返回 None。
+#[command]
+pub fn mark_refresh_metadata_progress(
+    app: AppHandle,
+    token: String,
+    game_ids: Vec<i32>,
+) -> Result<Option<RefreshRun>, String> {
+    let mut run = load_run_by_token(&app, &token)?;
+    if run.mark_refreshed(&game_ids) {
+        save_run(&app, None)?;
+        return Ok(None);
+    }
+    save_run(&app, Some(&run))?;
+    Ok(Some(run))
+}
+
+/// 取回中断的刷新进度，继续处理剩余游戏
+#[command]
+pub fn resume_refresh_metadata(app: AppHandle, token: String) -> Result<RefreshRun, String> {
+    load_run_by_token(&app, &token)
+}
+
+/// 查询是否存在未完成的刷新，用于启动时提示继续
+#[command]
+pub fn get_pending_refresh_metadata(app: AppHandle) -> Result<Option<RefreshRun>, String> {
+    load_run(&app)
+}
+
+/// 放弃或结束当前刷新，清除进度标记
+#[command]
+pub fn finish_refresh_metadata(app: AppHandle, token: String) -> Result<(), String> {
+    if load_run(&app)?.is_some_and(|run| run.token == token) {
+        save_run(&app, None)?;
+    }
+    Ok(())
+}
+
+fn load_run(app: &AppHandle) -> Result<Option<RefreshRun>, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("打开设置存储失败: {}", e))?;
+    let Some(value) = store.get(REFRESH_RUN_KEY) else {
+        return Ok(None);
+    };
+    serde_json::from_value(value)
+        .map(Some)
+        .map_err(|e| format!("解析刷新进度失败: {}", e))
+}
+
+fn load_run_by_token(app: &AppHandle, token: &str) -> Result<RefreshRun, String> {
+    load_run(app)?
+        .filter(|run| run.token == token)
+        .ok_or_else(|| "刷新进度不存在或已完成".to_string())
+}
+
+fn save_run(app: &AppHandle, run: Option<&RefreshRun>) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("打开设置存储失败: {}", e))?;
+    match run {
+        Some(run) => store.set(
+            REFRESH_RUN_KEY,
+            serde_json::to_value(run).map_err(|e| format!("序列化刷新进度失败: {}", e))?,
+        ),
+        None => {
+            store.delete(REFRESH_RUN_KEY);
+        }
+    }
+    store.save().map_err(|e| format!("保存刷新进度失败: {}", e))
+}
+
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| format!("生成刷新令牌失败: {}", e))?;
+
+    let mut token = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(&mut token, "{byte:02x}").map_err(|e| format!("生成刷新令牌失败: {}", e))?;
+    }
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mark_refreshed_counts_only_pending_ids() {
+        let mut run = RefreshRun {
+            token: "t".to_string(),
+            source: "bgm".to_string(),
+            pending: vec![1, 2, 3],
+            completed: 0,
+        };
+
+        assert!(!run.mark_refreshed(&[2, 2, 9]));
+        assert_eq!(run.pending, vec![1, 3]);
+        assert_eq!(run.completed, 1);
+
+        assert!(run.mark_refreshed(&[1, 3]));
+        assert_eq!(run.completed, 3);
+    }
+}
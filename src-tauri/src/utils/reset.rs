@@ -0,0 +1,215 @@
+//! 清空全部数据。
+//!
+//! 操作不可撤销，需先通过 `request_reset_phrase` 获取一次性确认短语，
+//! 再将短语原样传给 `reset_all_data`。短语只保存在内存中，5 分钟后失效，
+//! 校验后无论成功与否都会作废，避免误发的 IPC 调用直接清空用户的游戏库。
+
+use crate::backup::active::ActiveBackups;
+use crate::backup::savedata::resolve_savedata_backup_root;
+use crate::database::repository::settings_repository::SettingsRepository;
+use crate::game::cover::DownloadState;
+use crate::utils::fs::{FileCleanupReport, remove_dir_with_report};
+use parking_lot::{Mutex, const_mutex};
+use sea_orm::{ConnectionTrait, DatabaseConnection, TransactionTrait};
+use serde::Serialize;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::{State, command};
+
+/// 确认短语的有效期
+const PHRASE_TTL: Duration = Duration::from_secs(5 * 60);
+const PHRASE_PREFIX: &str = "RESET-";
+
+/// 按外键依赖顺序清空的表，子表在前
+const RESET_TABLES: [&str; 9] = [
+    "game_media",
+    "game_collection_link",
+    "collections",
+    "game_sessions",
+    "game_statistics",
+    "savedata",
+    "game_sources",
+    "games",
+    "user",
+];
+
+/// 当前有效的确认短语
+static PENDING_RESET: Mutex<Option<PendingReset>> = const_mutex(None);
+
+struct PendingReset {
+    phrase: String,
+    expires_at: Instant,
+}
+
+/// 清空结果
+#[derive(Debug, Serialize)]
+pub struct ResetResult {
+    /// 封面与存档备份文件的删除情况
+    pub files: FileCleanupReport,
+    /// 内存中的缓存与状态需重启应用后才会重新初始化
+    pub restart_required: bool,
+}
+
+/// 生成一次性确认短语，前端需要用户手动输入该短语
+///
+/// 再次调用会使之前的短语失效
+#[command]
+pub fn request_reset_phrase() -> Result<String, String> {
+    let phrase = generate_phrase()?;
+    *PENDING_RESET.lock() = Some(PendingReset {
+        phrase: phrase.clone(),
+        expires_at: Instant::now() + PHRASE_TTL,
+    });
+    Ok(phrase)
+}
+
+/// 清空全部游戏数据、封面与存档备份，并重建默认用户记录
+///
+/// 完成后必须重启应用。
+///
+/// # Arguments
+/// * `confirmation` - `request_reset_phrase` 返回的确认短语
+#[command]
+pub async fn reset_all_data(
+    db: State<'_, DatabaseConnection>,
+    backups: State<'_, ActiveBackups>,
+    cover_state: State<'_, DownloadState>,
+    confirmation: String,
+) -> Result<ResetResult, String> {
+    let pending = PENDING_RESET.lock().take();
+    verify_phrase(pending.as_ref(), &confirmation, Instant::now())?;
+    if !backups.is_empty() {
+        return Err("存档备份进行中，请稍后再试".to_string());
+    }
+
+    // 备份目录可能保存在用户设置中，需在清空前解析
+    let backup_root = resolve_savedata_backup_root(&db).await?;
+    let covers_dir = reina_path::get_covers_dir()?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| format!("开启事务失败: {}", e))?;
+    for table in RESET_TABLES {
+        txn.execute_unprepared(&format!("DELETE FROM {}", table))
+            .await
+            .map_err(|e| format!("清空表 {} 失败: {}", table, e))?;
+    }
+    txn.commit()
+        .await
+        .map_err(|e| format!("提交事务失败: {}", e))?;
+
+    SettingsRepository::get_all_settings(&db)
+        .await
+        .map_err(|e| format!("重建默认用户失败: {}", e))?;
+
+    let mut files = FileCleanupReport::default();
+    remove_game_dirs(&covers_dir, &mut files);
+    remove_game_dirs(&backup_root, &mut files);
+    cover_state.clear_cached_ids().await;
+
+    log::warn!(
+        "已清空全部数据 removed_files={} failed_files={}",
+        files.removed.len(),
+        files.failed.len()
+    );
+    Ok(ResetResult {
+        files,
+        restart_required: true,
+    })
+}
+
+fn generate_phrase() -> Result<String, String> {
+    let mut bytes = [0u8; 4];
+    getrandom::fill(&mut bytes).map_err(|e| format!("生成确认短语失败: {}", e))?;
+
+    let mut phrase = String::from(PHRASE_PREFIX);
+    for byte in bytes {
+        write!(&mut phrase, "{byte:02X}").map_err(|e| format!("生成确认短语失败: {}", e))?;
+    }
+    Ok(phrase)
+}
+
+fn verify_phrase(
+    pending: Option<&PendingReset>,
+    confirmation: &str,
+    now: Instant,
+) -> Result<(), String> {
+    let Some(pending) = pending else {
+        return Err("请先获取确认短语".to_string());
+    };
+    if now >= pending.expires_at {
+        return Err("确认短语已过期，请重新获取".to_string());
+    }
+    if confirmation.trim() != pending.phrase {
+        return Err("确认短语不匹配，请重新获取".to_string());
+    }
+    Ok(())
+}
+
+/// 删除目录下的 `game_*` 子目录，目录本身及其他文件保持不动
+fn remove_game_dirs(root: &Path, report: &mut FileCleanupReport) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() && entry.file_name().to_string_lossy().starts_with("game_") {
+            remove_dir_with_report(&path, report);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(phrase: &str, expires_at: Instant) -> PendingReset {
+        PendingReset {
+            phrase: phrase.to_string(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn generated_phrase_has_prefix_and_random_suffix() {
+        let phrase = generate_phrase().unwrap();
+        assert!(phrase.starts_with(PHRASE_PREFIX));
+        assert_eq!(phrase.len(), PHRASE_PREFIX.len() + 8);
+        assert_ne!(phrase, generate_phrase().unwrap());
+    }
+
+    #[test]
+    fn verify_requires_matching_unexpired_phrase() {
+        let now = Instant::now();
+        let valid = pending("RESET-0A1B2C3D", now + PHRASE_TTL);
+
+        assert!(verify_phrase(None, "RESET-0A1B2C3D", now).is_err());
+        assert!(verify_phrase(Some(&valid), "RESET-00000000", now).is_err());
+        assert!(verify_phrase(Some(&valid), "", now).is_err());
+        assert!(verify_phrase(Some(&valid), " RESET-0A1B2C3D ", now).is_ok());
+
+        let expired = pending("RESET-0A1B2C3D", now);
+        assert!(verify_phrase(Some(&expired), "RESET-0A1B2C3D", now).is_err());
+    }
+
+    #[test]
+    fn removes_only_game_dirs() {
+        let root = std::env::temp_dir().join(format!("reina-reset-{}", std::process::id()));
+        fs::create_dir_all(root.join("game_1")).unwrap();
+        fs::write(root.join("game_1").join("save.7z"), b"a").unwrap();
+        fs::write(root.join("notes.txt"), b"b").unwrap();
+
+        let mut report = FileCleanupReport::default();
+        remove_game_dirs(&root, &mut report);
+
+        assert!(!root.join("game_1").exists());
+        assert!(root.join("notes.txt").is_file());
+        assert_eq!(report.removed.len(), 1);
+        assert!(report.failed.is_empty());
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
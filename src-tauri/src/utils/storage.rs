@@ -0,0 +1,243 @@
+//! 存储占用统计。
+
+use crate::backup::savedata::resolve_savedata_backup_root;
+use crate::database::repository::games_repository::GamesRepository;
+use crate::game::local_path::resolve_game_directory;
+use crate::utils::fs::get_directory_size;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{State, command};
+use tokio::task::JoinSet;
+
+#[derive(Debug, Serialize)]
+pub struct GameDiskUsage {
+    pub game_id: i32,
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnavailableGamePath {
+    pub game_id: i32,
+    pub path: String,
+    pub reason: String,
+}
+
+/// 游戏库磁盘占用明细（单位：字节）
+#[derive(Debug, Serialize)]
+pub struct LibraryDiskUsage {
+    pub games_size: u64,
+    pub backups_size: u64,
+    pub database_size: u64,
+    pub total_size: u64,
+    pub games: Vec<GameDiskUsage>,
+    pub unavailable: Vec<UnavailableGamePath>,
+}
+
+/// 统计游戏库磁盘占用
+///
+/// 包含各本地游戏安装目录（`localpath` 所在目录）、存档备份目录与数据库文件。
+/// 路径缺失的游戏计为 0 并列入 `unavailable`；目录遍历并发执行。
+/// `exclude` 为游戏目录内不计入的相对子路径（如 `redist`、`**/cache`）。
+#[command]
+pub async fn get_library_disk_usage(
+    db: State<'_, DatabaseConnection>,
+    exclude: Option<Vec<String>>,
+) -> Result<LibraryDiskUsage, String> {
+    let local_games = GamesRepository::get_local_game_paths(&db)
+        .await
+        .map_err(|e| format!("获取本地游戏路径失败: {}", e))?;
+    let backup_root = resolve_savedata_backup_root(&db).await?;
+
+    let exclude = Arc::new(exclude.unwrap_or_default());
+    let mut tasks = JoinSet::new();
+    for (game_id, localpath) in local_games {
+        let exclude = Arc::clone(&exclude);
+        tasks.spawn_blocking(move || {
+            let size = resolve_game_directory(&localpath)
+                .and_then(|game_dir| get_directory_size(&game_dir, &exclude));
+            (game_id, localpath, size)
+        });
+    }
+
+    let backups_task = tokio::task::spawn_blocking(move || directory_size_or_zero(backup_root));
+
+    let mut games = Vec::new();
+    let mut unavailable = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (game_id, path, size) = joined.map_err(|e| format!("统计游戏目录任务失败: {}", e))?;
+        match size {
+            Ok(size) => games.push(GameDiskUsage {
+                game_id,
+                path,
+                size,
+            }),
+            Err(reason) => unavailable.push(UnavailableGamePath {
+                game_id,
+                path,
+                reason,
+            }),
+        }
+    }
+    games.sort_by_key(|game| game.game_id);
+    unavailable.sort_by_key(|game| game.game_id);
+
+    let backups_size = backups_task
+        .await
+        .map_err(|e| format!("统计备份目录任务失败: {}", e))?;
+    let database_size = reina_path::get_db_path()
+        .map(|path| file_size_or_zero(&path))
+        .unwrap_or(0);
+
+    let games_size = games.iter().map(|game| game.size).sum();
+    Ok(LibraryDiskUsage {
+        games_size,
+        backups_size,
+        database_size,
+        total_size: games_size + backups_size + database_size,
+        games,
+        unavailable,
+    })
+}
+
+/// 数据库统计中列出行数的表
+const STATS_TABLES: [&str; 8] = [
+    "games",
+    "game_sources",
+    "game_sessions",
+    "game_statistics",
+    "savedata",
+    "collections",
+    "game_collection_link",
+    "game_media",
+];
+
+#[derive(Debug, Serialize)]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows: i64,
+}
+
+/// SQLite 数据库占用统计（单位：字节）
+#[derive(Debug, Serialize)]
+pub struct DatabaseStats {
+    /// 数据库文件在磁盘上的大小，不含 WAL
+    pub file_size: u64,
+    pub wal_size: u64,
+    pub page_size: i64,
+    pub page_count: i64,
+    pub freelist_count: i64,
+    /// `page_count * page_size`
+    pub allocated_size: i64,
+    /// 空闲页占用，可通过 `vacuum_database` 回收
+    pub reclaimable_size: i64,
+    pub tables: Vec<TableRowCount>,
+}
+
+/// 统计数据库文件大小、页面使用情况与主要表的行数
+#[command]
+pub async fn get_database_stats(
+    db: State<'_, DatabaseConnection>,
+) -> Result<DatabaseStats, String> {
+    let page_size = query_i64(&db, "PRAGMA page_size").await?;
+    let page_count = query_i64(&db, "PRAGMA page_count").await?;
+    let freelist_count = query_i64(&db, "PRAGMA freelist_count").await?;
+
+    let mut tables = Vec::with_capacity(STATS_TABLES.len());
+    for table in STATS_TABLES {
+        tables.push(TableRowCount {
+            table: table.to_string(),
+            rows: query_i64(&db, &format!("SELECT COUNT(*) FROM {}", table)).await?,
+        });
+    }
+
+    let db_path = reina_path::get_db_path()?;
+    Ok(DatabaseStats {
+        file_size: file_size_or_zero(&db_path),
+        wal_size: file_size_or_zero(&wal_path(&db_path)),
+        page_size,
+        page_count,
+        freelist_count,
+        allocated_size: page_count * page_size,
+        reclaimable_size: freelist_count * page_size,
+        tables,
+    })
+}
+
+/// VACUUM 前后的数据库文件大小
+#[derive(Debug, Serialize)]
+pub struct VacuumResult {
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+/// 执行 `VACUUM` 回收大量删除后留下的空闲页
+///
+/// VACUUM 会先写出完整的临时副本，数据库所在磁盘需要至少与数据库大小相当的剩余空间；
+/// 空间不足时直接返回错误。执行期间数据库被独占，耗时随数据库大小增长。
+#[command]
+pub async fn vacuum_database(db: State<'_, DatabaseConnection>) -> Result<VacuumResult, String> {
+    let db_path = reina_path::get_db_path()?;
+    let size_before = file_size_or_zero(&db_path);
+
+    let data_dir = db_path
+        .parent()
+        .ok_or_else(|| "无法获取数据库目录".to_string())?;
+    let available =
+        fs4::available_space(data_dir).map_err(|e| format!("获取磁盘剩余空间失败: {}", e))?;
+    if available < size_before {
+        return Err(format!(
+            "磁盘空间不足：VACUUM 需要约 {} MB 剩余空间，当前剩余 {} MB",
+            size_before.div_ceil(1024 * 1024),
+            available / 1024 / 1024
+        ));
+    }
+
+    db.execute_unprepared("VACUUM")
+        .await
+        .map_err(|e| format!("压缩数据库失败: {}", e))?;
+
+    let size_after = file_size_or_zero(&db_path);
+    log::info!(
+        "数据库 VACUUM 完成: {} -> {} bytes",
+        size_before,
+        size_after
+    );
+    Ok(VacuumResult {
+        size_before,
+        size_after,
+    })
+}
+
+async fn query_i64(db: &DatabaseConnection, sql: &str) -> Result<i64, String> {
+    db.query_one(Statement::from_string(DatabaseBackend::Sqlite, sql))
+        .await
+        .map_err(|e| format!("查询数据库统计失败: {}", e))?
+        .ok_or_else(|| format!("查询数据库统计无结果: {}", sql))?
+        .try_get_by_index::<i64>(0)
+        .map_err(|e| format!("读取数据库统计失败: {}", e))
+}
+
+fn wal_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push("-wal");
+    PathBuf::from(path)
+}
+
+fn file_size_or_zero(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+fn directory_size_or_zero(dir: PathBuf) -> u64 {
+    if !dir.exists() {
+        return 0;
+    }
+    get_directory_size(&dir, &[]).unwrap_or_else(|e| {
+        log::warn!("统计目录大小失败 path={} error={}", dir.display(), e);
+        0
+    })
+}
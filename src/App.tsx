@@ -10,12 +10,16 @@ import { appRoutes } from "@/providers/router"; // 引入新的统一配置
 import { SnackbarUtilsConfigurator } from "@/providers/snackBar";
 import { ToolpadReactRouterAppProvider } from "@/providers/ToolpadReactRouterAppProvider";
 import { initBgmAuthRefresh } from "@/services/bgmAuthSession";
+import { checkDatabaseOnStartup } from "@/services/databaseStartupCheck";
 
 const App: React.FC = () => {
 	const { t } = useTranslation();
 
 	useEffect(() => {
 		void initBgmAuthRefresh();
+		if (isTauri()) {
+			void checkDatabaseOnStartup();
+		}
 	}, []);
 
 	// 从路由配置动态生成导航菜单
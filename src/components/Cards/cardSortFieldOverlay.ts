@@ -71,6 +71,8 @@ export function getCardSortFieldOverlay({
 			value = formatLastPlayedOverlay(lastPlayed, language, t);
 			break;
 		case "namesort":
+		case "relevance":
+		case "status":
 			value = null;
 			break;
 	}
@@ -56,6 +56,7 @@ const sortOptions: Array<{ value: SortOption; labelKey: string }> = [
 	{ value: "namesort", labelKey: "nameSort" },
 	{ value: "datetime", labelKey: "releaseTime" },
 	{ value: "lastplayed", labelKey: "lastPlayed" },
+	{ value: "playtime", labelKey: "playtimeSort" },
 	{ value: "bgmrank", labelKey: "bgmRank" },
 	{ value: "vndbrank", labelKey: "vndbRank" },
 	{ value: "userratingrank", labelKey: "userRatingRank" },
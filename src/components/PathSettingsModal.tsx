@@ -39,8 +39,7 @@ import { useTranslation } from "react-i18next";
 import { useAllSettings, useUpdateSettings } from "@/hooks/queries/useSettings";
 import { snackbar } from "@/providers/snackBar";
 import { handleExeFile, handleFolder } from "@/services/fs/fileDialog";
-import { getAppDataDirPath } from "@/services/fs/pathCache";
-import { moveBackupFolder } from "@/services/fs/savedataBackup";
+import { fileService } from "@/services/invoke";
 import { getUserErrorMessage } from "@/utils/errors";
 
 /**
@@ -135,8 +134,16 @@ export const PathSettingsModal: React.FC<PathSettingsModalProps> = ({
 		try {
 			isSubmittingRef.current = true;
 			setIsSubmitting(true);
+			// 存档根目录由后端迁移备份成功后再写入设置
+			if (inSettingsPage && previousDraft.savePath !== nextDraft.savePath) {
+				const moveResult = await fileService.changeSaveRootPath(
+					nextDraft.savePath || null,
+				);
+				if (!moveResult.success) {
+					throw new Error(moveResult.message);
+				}
+			}
 			await updateSettingsMutation.mutateAsync({
-				saveRootPath: inSettingsPage ? nextDraft.savePath || null : undefined,
 				dbBackupPath: inSettingsPage
 					? nextDraft.dbBackupPath || null
 					: undefined,
@@ -148,27 +155,6 @@ export const PathSettingsModal: React.FC<PathSettingsModalProps> = ({
 			setInitialDraft(nextDraft);
 			initialDraftRef.current = nextDraft;
 
-			if (inSettingsPage && previousDraft.savePath !== nextDraft.savePath) {
-				try {
-					await moveBackupFolder(
-						previousDraft.savePath,
-						nextDraft.savePath === ""
-							? getAppDataDirPath()
-							: nextDraft.savePath,
-					);
-				} catch (moveError) {
-					snackbar.warning(
-						t(
-							"components.PathSettingsModal.savePath.moveBackupWarning",
-							"备份路径已保存，但迁移旧备份失败：{{error}}",
-							{
-								error: getUserErrorMessage(moveError, t),
-							},
-						),
-					);
-				}
-			}
-
 			return true;
 		} catch (error) {
 			snackbar.error(
@@ -1,3 +1,4 @@
+import { ask } from "@tauri-apps/plugin-dialog";
 import { useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import { useUpdateGame } from "@/hooks/queries/useGames";
@@ -6,6 +7,7 @@ import {
 	getLocalPathPickerDirectory,
 	handleExeFile,
 } from "@/services/fs/fileDialog";
+import { statsService } from "@/services/invoke";
 import { useGamePlayStore } from "@/store/gamePlayStore";
 import type { GameData, UpdateGameParams } from "@/types";
 import { getUserErrorMessage } from "@/utils/errors";
@@ -59,6 +61,42 @@ export function useGameLaunchFlow() {
 		[t, updateGame],
 	);
 
+	/**
+	 * 启动程序缺少可执行权限时询问是否添加，添加成功返回 true
+	 */
+	const fixExecutablePermission = useCallback(
+		async (path: string) => {
+			const confirmed = await ask(
+				t(
+					"components.LaunchModal.notExecutable.message",
+					"启动程序没有可执行权限：{{path}}\n是否为其添加可执行权限（chmod +x）并重新启动？",
+					{ path },
+				),
+				{
+					title: t("components.LaunchModal.notExecutable.title", "无法启动"),
+					kind: "warning",
+					okLabel: t(
+						"components.LaunchModal.notExecutable.makeExecutable",
+						"添加权限并启动",
+					),
+					cancelLabel: t("components.LaunchModal.notExecutable.cancel", "取消"),
+				},
+			);
+			if (!confirmed) return false;
+
+			try {
+				await statsService.makeExecutable(path);
+				return true;
+			} catch (error) {
+				snackbar.error(
+					`${t("components.LaunchModal.notExecutable.failed", "添加可执行权限失败")}: ${getUserErrorMessage(error, t)}`,
+				);
+				return false;
+			}
+		},
+		[t],
+	);
+
 	const runLaunch = useCallback(
 		async (game: GameData) => {
 			try {
@@ -77,6 +115,14 @@ export function useGameLaunchFlow() {
 					if (result.success) return;
 				}
 
+				const executablePath = result.diagnostics?.executable_path;
+				if (result.code === "NOT_EXECUTABLE" && executablePath) {
+					const fixed = await fixExecutablePermission(executablePath);
+					if (!fixed) return;
+					result = await launchGame(game.id);
+					if (result.success) return;
+				}
+
 				snackbar.error(result.message);
 			} catch (error) {
 				snackbar.error(
@@ -84,7 +130,7 @@ export function useGameLaunchFlow() {
 				);
 			}
 		},
-		[launchGame, syncLocalPath, t],
+		[fixExecutablePermission, launchGame, syncLocalPath, t],
 	);
 
 	return {
@@ -15,10 +15,16 @@ import {
 	useQuery,
 	useQueryClient,
 } from "@tanstack/react-query";
+import { listen } from "@tauri-apps/api/event";
 import { join } from "pathe";
+import { useEffect } from "react";
 import { getSavedataBackupPath } from "@/services/fs/pathCache";
 import { createGameSavedataBackup } from "@/services/fs/savedataBackup";
 import { savedataService } from "@/services/invoke";
+import {
+	SAVEDATA_BACKUP_CREATED_EVENT,
+	type SavedataBackupCreatedEvent,
+} from "@/services/invoke/savedataService";
 import type { SavedataRecord } from "@/types";
 
 // ============================================================================
@@ -119,8 +125,8 @@ function useDeleteBackup() {
 
 	return useMutation({
 		mutationFn: async ({ backup }: DeleteBackupParams) => {
-			// 直接调用后端二合一接口，同时删除文件和数据库记录
-			await savedataService.deleteBackup(backup.id);
+			// 直接调用后端二合一接口，同时删除文件和数据库记录；文件移入回收站，误删可恢复
+			await savedataService.deleteBackup(backup.id, true);
 		},
 		onSettled: (_, __, variables) => {
 			// 无论成功失败都刷新备份列表
@@ -144,18 +150,45 @@ function useRestoreBackup() {
 			const savedataBackupPath = await getSavedataBackupPath(gameId);
 			const backupFilePath = join(savedataBackupPath, backup.file);
 
-			// 恢复备份
-			await savedataService.restoreBackup(backupFilePath, savePath);
+			// 恢复备份（界面上已由用户确认覆盖）
+			return savedataService.restoreBackup(backupFilePath, savePath, true);
 		},
 	});
 }
 
+/**
+ * 监听后端的备份创建事件（如游戏退出后的自动备份），刷新对应游戏的备份列表
+ */
+function useBackupCreatedListener() {
+	const queryClient = useQueryClient();
+
+	useEffect(() => {
+		const unlisten = listen<SavedataBackupCreatedEvent>(
+			SAVEDATA_BACKUP_CREATED_EVENT,
+			(event) => {
+				const { gameId } = event.payload;
+				queryClient.invalidateQueries({
+					queryKey: saveDataKeys.backups(gameId),
+				});
+				queryClient.invalidateQueries({
+					queryKey: saveDataKeys.backupCount(gameId),
+				});
+			},
+		);
+
+		return () => {
+			unlisten.then((fn) => fn());
+		};
+	}, [queryClient]);
+}
+
 /**
  * 组合存档备份查询 + mutations
  * 用于页面层单入口消费
  */
 export function useSaveDataResources(gameId: number) {
 	const backupsQuery = useSaveDataBackups(gameId);
+	useBackupCreatedListener();
 
 	const createBackupMutation = useCreateBackup();
 	const deleteBackupMutation = useDeleteBackup();
@@ -83,8 +83,17 @@ export function getDisplayGameData(fullData: FullGameData): GameData {
 		nsfw: undefined,
 	};
 
-	// 根据 id_type 决定数据来源
-	if (fullData.id_type && isSourceType(fullData.id_type)) {
+	// primary_source 指定的数据源优先，否则根据 id_type 决定数据来源
+	const primarySource = fullData.primary_source;
+	const primaryData =
+		primarySource && isSourceType(primarySource)
+			? sourceDataMap[primarySource]
+			: undefined;
+	if (primarySource && isSourceType(primarySource) && primaryData) {
+		applySingleSourceDisplay(baseData, primarySource, primaryData);
+	} else if (primarySource === "custom" && custom_data) {
+		applyCustomSourceDisplay(baseData, custom_data);
+	} else if (fullData.id_type && isSourceType(fullData.id_type)) {
 		const sourceData = sourceDataMap[fullData.id_type];
 		if (sourceData) {
 			applySingleSourceDisplay(baseData, fullData.id_type, sourceData);
@@ -4,6 +4,7 @@ import { gameKeys } from "@/hooks/queries/useGames";
 import { queryClient } from "@/providers/queryClient";
 import { withBgmAuth } from "@/services/bgmAuthSession";
 import { gameService } from "@/services/invoke";
+import type { RefreshRun } from "@/services/invoke/types";
 import type { GameMetadataDraft, UpdateGameParams } from "@/types";
 import { toError } from "@/utils/errors";
 import { fetchBgmByIds } from "../api/bgm";
@@ -13,16 +14,58 @@ import {
 	getCandidateSourceRecord,
 } from "../sourceCandidate";
 
+/** 每批刷新的游戏数量，完成一批即记录进度 */
+const REFRESH_CHUNK_SIZE = 50;
+
+/**
+ * 同一数据源存在中断的刷新时的处理方式：
+ * - resume：继续上次未完成的游戏（本次新增的游戏不会刷新）
+ * - restart：放弃上次进度，按本次选择重新开始
+ * - cancel：不执行本次刷新
+ */
+export type RefreshResumeChoice = "resume" | "restart" | "cancel";
+
+export interface BatchUpdateResult {
+	total: number;
+	success: number;
+	failed: number;
+	/** 用户取消了本次刷新 */
+	cancelled?: boolean;
+}
+
+/**
+ * 开始新的刷新；同一数据源上次的刷新未完成时询问用户继续还是重新开始
+ *
+ * 用户取消时返回 null
+ */
+async function startOrResumeRefresh(
+	source: string,
+	idPairs: Array<[number, string]>,
+	confirmResume: (run: RefreshRun) => Promise<RefreshResumeChoice>,
+): Promise<RefreshRun | null> {
+	const pending = await gameService.getPendingRefreshMetadata();
+	if (pending?.source === source) {
+		const choice = await confirmResume(pending);
+		if (choice === "cancel") {
+			return null;
+		}
+		if (choice === "resume") {
+			return gameService.resumeRefreshMetadata(pending.token);
+		}
+	}
+	return gameService.startRefreshMetadata(
+		source,
+		idPairs.map(([gameId]) => gameId),
+	);
+}
+
 async function batchUpdateCommon(
 	type: "vndb" | "bgm",
 	fetchFunction: (ids: string[]) => Promise<GameMetadataDraft[]>,
 	getAllIdsFunction: () => Promise<Array<[number, string]>>,
 	source: "vndb" | "bgm",
-): Promise<{
-	total: number;
-	success: number;
-	failed: number;
-}> {
+	confirmResume: (run: RefreshRun) => Promise<RefreshResumeChoice>,
+): Promise<BatchUpdateResult> {
 	try {
 		const idPairs = await getAllIdsFunction();
 		console.log(`Found ${type.toUpperCase()} ID pairs:`, idPairs);
@@ -35,44 +78,63 @@ async function batchUpdateCommon(
 			};
 		}
 
-		const ids = idPairs.map(([_, id]) => id);
-		const resultsTemp = await fetchFunction(ids);
-		const resultByApiId = new Map<string, (typeof resultsTemp)[number]>();
-		for (const result of resultsTemp) {
-			const sourceRecord = getCandidateSourceRecord(result, source);
-			if (sourceRecord?.external_id) {
-				resultByApiId.set(sourceRecord.external_id, result);
-			}
+		// 中断后重新运行时由用户决定只处理上次未完成的游戏还是重新开始
+		const run = await startOrResumeRefresh(source, idPairs, confirmResume);
+		if (!run) {
+			return { total: 0, success: 0, failed: 0, cancelled: true };
 		}
+		const pendingIds = new Set(run.pending);
+		const pendingPairs = idPairs.filter(([gameId]) => pendingIds.has(gameId));
+		let success = 0;
 
-		const updates: Array<[number, UpdateGameParams]> = [];
-
-		for (const [gameId, apiId] of idPairs) {
-			const data = resultByApiId.get(apiId);
-			const sourceRecord = data
-				? getCandidateSourceRecord(data, source)
-				: undefined;
-
-			if (sourceRecord) {
-				updates.push([
-					gameId,
-					{
-						upsert_sources: candidateSourcesToGameSources([sourceRecord]),
-					},
-				]);
+		for (let i = 0; i < pendingPairs.length; i += REFRESH_CHUNK_SIZE) {
+			const chunk = pendingPairs.slice(i, i + REFRESH_CHUNK_SIZE);
+			const resultsTemp = await fetchFunction(chunk.map(([_, id]) => id));
+			const resultByApiId = new Map<string, (typeof resultsTemp)[number]>();
+			for (const result of resultsTemp) {
+				const sourceRecord = getCandidateSourceRecord(result, source);
+				if (sourceRecord?.external_id) {
+					resultByApiId.set(sourceRecord.external_id, result);
+				}
 			}
-		}
 
-		if (updates.length > 0) {
-			const updatedGames = await gameService.updateBatch(updates);
-			patchManyGameCaches(queryClient, gameKeys, updatedGames);
-			queryClient.invalidateQueries({ queryKey: gameKeys.idLists() });
+			const updates: Array<[number, UpdateGameParams]> = [];
+
+			for (const [gameId, apiId] of chunk) {
+				const data = resultByApiId.get(apiId);
+				const sourceRecord = data
+					? getCandidateSourceRecord(data, source)
+					: undefined;
+
+				if (sourceRecord) {
+					updates.push([
+						gameId,
+						{
+							upsert_sources: candidateSourcesToGameSources([sourceRecord]),
+						},
+					]);
+				}
+			}
+
+			if (updates.length > 0) {
+				const updatedGames = await gameService.updateBatch(updates);
+				patchManyGameCaches(queryClient, gameKeys, updatedGames);
+				queryClient.invalidateQueries({ queryKey: gameKeys.idLists() });
+			}
+			success += updates.length;
+
+			await gameService.markRefreshMetadataProgress(
+				run.token,
+				chunk.map(([gameId]) => gameId),
+			);
 		}
+		// 本轮游戏已不在库中等情况下，确保进度被清除
+		await gameService.finishRefreshMetadata(run.token);
 
 		return {
-			total: idPairs.length,
-			success: updates.length,
-			failed: idPairs.length - updates.length,
+			total: pendingPairs.length,
+			success,
+			failed: pendingPairs.length - success,
 		};
 	} catch (error) {
 		console.error(`批量更新 ${type.toUpperCase()} 数据失败:`, error);
@@ -80,30 +142,28 @@ async function batchUpdateCommon(
 	}
 }
 
-export async function batchUpdateVndbData(): Promise<{
-	total: number;
-	success: number;
-	failed: number;
-}> {
+export async function batchUpdateVndbData(
+	confirmResume: (run: RefreshRun) => Promise<RefreshResumeChoice>,
+): Promise<BatchUpdateResult> {
 	return batchUpdateCommon(
 		"vndb",
 		fetchVNDBByIds,
 		() => gameService.getAllVndbIds(),
 		"vndb",
+		confirmResume,
 	);
 }
 
-export async function batchUpdateBgmData(): Promise<{
-	total: number;
-	success: number;
-	failed: number;
-}> {
+export async function batchUpdateBgmData(
+	confirmResume: (run: RefreshRun) => Promise<RefreshResumeChoice>,
+): Promise<BatchUpdateResult> {
 	return withBgmAuth((token) =>
 		batchUpdateCommon(
 			"bgm",
 			(ids: string[]) => fetchBgmByIds(ids, token),
 			() => gameService.getAllBgmIds(),
 			"bgm",
+			confirmResume,
 		),
 	);
 }
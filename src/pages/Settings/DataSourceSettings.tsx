@@ -20,14 +20,17 @@ import type React from "react";
 import { useState } from "react";
 import { useTranslation } from "react-i18next";
 import { useShallow } from "zustand/react/shallow";
+import { AlertBox } from "@/components/AlertBox";
 import {
 	getRuntimeSourceAdapter,
 	MIXED_SOURCE_KEYS,
 	MIXED_SOURCE_MAX_COUNT,
 	MIXED_SOURCE_MIN_COUNT,
 } from "@/metadata";
+import type { RefreshResumeChoice } from "@/metadata/data/metadataBatchUpdate";
 import { snackbar } from "@/providers/snackBar";
 import { isBgmAuthExpiredError } from "@/services/bgmAuthSession";
+import type { RefreshRun } from "@/services/invoke/types";
 import { useStore } from "@/store/appStore";
 import { getUserErrorMessage } from "@/utils/errors";
 import { SettingsGroup, SettingsItem } from "./SettingsLayout";
@@ -183,6 +186,23 @@ const BatchUpdateSettings: React.FC = () => {
 	const [isUpdatingVndb, setIsUpdatingVndb] = useState(false);
 	const [isUpdatingBgm, setIsUpdatingBgm] = useState(false);
 	const [updateStatus, setUpdateStatus] = useState<string>("");
+	const [resumePrompt, setResumePrompt] = useState<{
+		run: RefreshRun;
+		resolve: (choice: RefreshResumeChoice) => void;
+	} | null>(null);
+
+	/**
+	 * 上次同一数据源的刷新未完成时，询问继续还是按本次选择重新开始
+	 */
+	const confirmResume = (run: RefreshRun) =>
+		new Promise<RefreshResumeChoice>((resolve) => {
+			setResumePrompt({ run, resolve });
+		});
+
+	const settleResumePrompt = (choice: RefreshResumeChoice) => {
+		resumePrompt?.resolve(choice);
+		setResumePrompt(null);
+	};
 
 	const handleBatchUpdateVndb = async () => {
 		setIsUpdatingVndb(true);
@@ -201,7 +221,10 @@ const BatchUpdateSettings: React.FC = () => {
 				),
 			);
 
-			const result = await batchUpdateVndbData();
+			const result = await batchUpdateVndbData(confirmResume);
+			if (result.cancelled) {
+				return;
+			}
 
 			if (result.success > 0) {
 				const message = t(
@@ -266,7 +289,10 @@ const BatchUpdateSettings: React.FC = () => {
 				t("pages.Settings.batchUpdate.updatingBgm", "正在批量更新 BGM 数据..."),
 			);
 
-			const result = await batchUpdateBgmData();
+			const result = await batchUpdateBgmData(confirmResume);
+			if (result.cancelled) {
+				return;
+			}
 
 			if (result.success > 0) {
 				const message = t(
@@ -392,6 +418,26 @@ const BatchUpdateSettings: React.FC = () => {
 					{updateStatus}
 				</Typography>
 			)}
+			<AlertBox
+				open={resumePrompt !== null}
+				setOpen={(open) => {
+					if (!open) settleResumePrompt("cancel");
+				}}
+				title={t("pages.Settings.batchUpdate.resumeTitle", "继续上次的更新？")}
+				message={t(
+					"pages.Settings.batchUpdate.resumeMessage",
+					"上次的批量更新未完成，已更新 {{completed}} 个游戏，剩余 {{pending}} 个。继续将只更新剩余的游戏，重新开始将按当前游戏库更新全部游戏。",
+					{
+						completed: resumePrompt?.run.completed ?? 0,
+						pending: resumePrompt?.run.pending.length ?? 0,
+					},
+				)}
+				confirmText={t("pages.Settings.batchUpdate.resume", "继续")}
+				onConfirm={() => settleResumePrompt("resume")}
+				showExtraButton
+				extraButtonText={t("pages.Settings.batchUpdate.restart", "重新开始")}
+				onExtraButtonClick={() => settleResumePrompt("restart")}
+			/>
 		</SettingsGroup>
 	);
 };
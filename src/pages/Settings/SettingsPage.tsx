@@ -34,6 +34,7 @@ import {
 	LogLevelSettings,
 	ProxySettings,
 	ExternalLaunchMonitorSettings,
+	SessionEndNotificationSettings,
 	TimeTrackingModeSettings,
 } from "./SystemSettings";
 
@@ -165,6 +166,7 @@ export const Settings: React.FC = () => {
 						<SettingsDivider />
 						<TimeTrackingModeSettings />
 						<ExternalLaunchMonitorSettings />
+						<SessionEndNotificationSettings />
 						{import.meta.env.TAURI_ENV_PLATFORM === "linux" && (
 							<>
 								<SettingsDivider />
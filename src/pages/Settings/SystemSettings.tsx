@@ -16,7 +16,6 @@ import Select, { type SelectChangeEvent } from "@mui/material/Select";
 import Stack from "@mui/material/Stack";
 import TextField from "@mui/material/TextField";
 import { path } from "@tauri-apps/api";
-import { isEnabled } from "@tauri-apps/plugin-autostart";
 import { load } from "@tauri-apps/plugin-store";
 import { join } from "pathe";
 import { useEffect, useState } from "react";
@@ -24,7 +23,7 @@ import { useTranslation } from "react-i18next";
 import { useShallow } from "zustand/react/shallow";
 import { useLogLevel, useSetLogLevel } from "@/hooks/queries/useSettings";
 import { snackbar } from "@/providers/snackBar";
-import { fileService } from "@/services/invoke";
+import { fileService, settingsService } from "@/services/invoke";
 import { toggleAutostart } from "@/services/plugins/autoStartService";
 import { useStore } from "@/store/appStore";
 import { getUserErrorMessage } from "@/utils/errors";
@@ -36,7 +35,7 @@ export const AutoStartSettings = () => {
 
 	useEffect(() => {
 		const checkAutoStart = async () => {
-			setAutoStart(await isEnabled());
+			setAutoStart(await settingsService.getAutostartEnabled());
 		};
 		checkAutoStart();
 	}, []);
@@ -263,6 +262,59 @@ export const ExternalLaunchMonitorSettings = () => {
 	);
 };
 
+export const SessionEndNotificationSettings = () => {
+	const { t } = useTranslation();
+	const [enabled, setEnabled] = useState(false);
+
+	// 与后端 game/monitor/session.rs 中读取的键保持一致
+	const STORE_KEY = "session_end_notification";
+	const STORE_PATH = "settings.json";
+
+	useEffect(() => {
+		const loadEnabled = async () => {
+			try {
+				const store = await load(STORE_PATH, { autoSave: false, defaults: {} });
+				setEnabled((await store.get<boolean>(STORE_KEY)) ?? false);
+			} catch (error) {
+				console.error("加载会话结束通知设置失败:", error);
+			}
+		};
+		loadEnabled();
+	}, []);
+
+	const handleChange = async (checked: boolean) => {
+		setEnabled(checked);
+		try {
+			const store = await load(STORE_PATH, { autoSave: false, defaults: {} });
+			await store.set(STORE_KEY, checked);
+			await store.save();
+		} catch (error) {
+			console.error("保存会话结束通知设置失败:", error);
+			setEnabled(!checked);
+			snackbar.error(getUserErrorMessage(error, t));
+		}
+	};
+
+	return (
+		<SettingsItem
+			title={t(
+				"pages.Settings.sessionEndNotification.title",
+				"游戏结束时发送通知",
+			)}
+			description={t(
+				"pages.Settings.sessionEndNotification.description",
+				"游戏退出并记录游玩时长后，发送包含本次时长与今日累计时长的系统通知。默认关闭。",
+			)}
+		>
+			<Switch
+				checked={enabled}
+				onChange={(e) => void handleChange(e.target.checked)}
+				color="primary"
+			/>
+		</SettingsItem>
+	);
+};
+
 export const TimeTrackingModeSettings = () => {
 	const { t } = useTranslation();
 	const timeTrackingMode = useStore((s) => s.timeTrackingMode);
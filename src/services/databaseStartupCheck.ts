@@ -0,0 +1,50 @@
+import i18next from "i18next";
+import { snackbar } from "@/providers/snackBar";
+import { fileService } from "@/services/invoke";
+
+/**
+ * 启动时检查数据库状态
+ * - 数据库由更新版本的应用写入时提示用户升级
+ * - 迁移后完整性检查未通过时提示用户使用迁移前的备份恢复
+ */
+export async function checkDatabaseOnStartup() {
+	try {
+		const schema = await fileService.getSchemaOwnerVersion();
+		if (schema.newer_than_app) {
+			snackbar.warning(
+				i18next.t(
+					"app.databaseCheck.newerSchema",
+					"数据库由更新版本的 ReinaManager（{{ownerVersion}}）写入，当前版本（{{appVersion}}）未执行数据库迁移，请升级应用",
+					{
+						ownerVersion: schema.owner_version,
+						appVersion: schema.app_version,
+					},
+				),
+				{ autoHideDuration: 10000 },
+			);
+		}
+	} catch (error) {
+		console.error("读取数据库版本信息失败:", error);
+	}
+
+	try {
+		const report = await fileService.getMigrationIntegrityReport();
+		if (!report.integrity_ok) {
+			snackbar.error(
+				report.backup_path
+					? i18next.t(
+							"app.databaseCheck.integrityFailedWithBackup",
+							"数据库迁移后完整性检查未通过，数据可能已损坏，可使用迁移前的备份恢复：{{backupPath}}",
+							{ backupPath: report.backup_path },
+						)
+					: i18next.t(
+							"app.databaseCheck.integrityFailed",
+							"数据库迁移后完整性检查未通过，数据可能已损坏，请尽快从备份恢复",
+						),
+				{ autoHideDuration: 20000 },
+			);
+		}
+	} catch (error) {
+		console.error("读取数据库迁移检查结果失败:", error);
+	}
+}
@@ -6,6 +6,7 @@ import { queryClient } from "@/providers/queryClient";
 import { fileService } from "@/services/invoke";
 
 let cachedAppDataDir: string | null = null;
+let cachedCoversDir: string | null = null;
 
 interface PathInitResult {
 	resourceDir: string;
@@ -17,9 +18,10 @@ interface PathInitResult {
  * 应该在应用启动时调用一次
  */
 export const initPathCache = async (): Promise<PathInitResult> => {
-	const [resourceDirPath, systemAppDataDir] = await Promise.all([
+	const [resourceDirPath, systemAppDataDir, coversDir] = await Promise.all([
 		resourceDir(),
 		path.appDataDir(),
+		fileService.getCoversPath(),
 	]);
 	const baseResourceDir = join(resourceDirPath, "resources");
 	const portableModeResult = await fileService.isPortableMode();
@@ -28,6 +30,7 @@ export const initPathCache = async (): Promise<PathInitResult> => {
 		? baseResourceDir
 		: appDataDir;
 	cachedAppDataDir = resolvedAppDataDir;
+	cachedCoversDir = coversDir;
 
 	return {
 		resourceDir: baseResourceDir,
@@ -44,6 +47,22 @@ export const getAppDataDirPath = (): string => {
 	return cachedAppDataDir;
 };
 
+/**
+ * 获取封面目录（可能是用户自定义的位置）
+ */
+export const getCoversDirPath = (): string => {
+	return cachedCoversDir ?? join(getAppDataDirPath(), "covers");
+};
+
+/**
+ * 修改封面目录并刷新路径缓存
+ */
+export const changeCoversDir = async (newPath: string | null) => {
+	const result = await fileService.changeCoversPath(newPath);
+	cachedCoversDir = result.covers_dir;
+	return result;
+};
+
 export const getDbBackupPath = async (): Promise<string> => {
 	try {
 		const settings = await fetchAllSettings(queryClient);
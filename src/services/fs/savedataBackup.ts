@@ -19,6 +19,7 @@ export async function createGameSavedataBackup(
 			backupInfo.folder_name,
 			backupInfo.backup_time,
 			backupInfo.file_size,
+			backupInfo.base_backup_id,
 		);
 
 		return backupInfo;
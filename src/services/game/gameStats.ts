@@ -1,9 +1,12 @@
 import { listen } from "@tauri-apps/api/event";
 import i18n from "i18next";
-import { createBackupAndSync } from "@/hooks/queries/useSavedata";
 import { queryClient } from "@/providers/queryClient";
 import { snackbar } from "@/providers/snackBar";
-import { gameService, statsService } from "@/services/invoke";
+import { statsService } from "@/services/invoke";
+import {
+	SAVEDATA_BACKUP_CREATED_EVENT,
+	type SavedataBackupCreatedEvent,
+} from "@/services/invoke/savedataService";
 import type { GameSession, GameStatistics, GameTimeStats } from "@/types";
 import { formatPlayTime, getLocalDateString } from "@/utils/dateTime";
 
@@ -217,28 +220,6 @@ export function initGameTimeTracking(
 				queryClient.invalidateQueries({ queryKey: ["stats"] }),
 				queryClient.invalidateQueries({ queryKey: ["games", "idList"] }),
 			]);
-
-			void (async () => {
-				try {
-					const fullgame = await gameService.getGameById(gameId);
-					if (!fullgame) {
-						console.error("游戏数据未找到，无法进行自动备份");
-						return;
-					}
-					if (fullgame.autosave === 1 && fullgame.savepath) {
-						console.log(
-							`开始自动备份游戏 ${gameId}，存档路径: ${fullgame.savepath}`,
-						);
-						await createBackupAndSync(queryClient, {
-							gameId,
-							savePath: fullgame.savepath,
-						});
-						console.log(`游戏 ${gameId} 自动备份完成`);
-					}
-				} catch (backupError) {
-					console.error("自动备份失败:", backupError);
-				}
-			})();
 		} catch (error) {
 			console.error("处理游戏结束事件失败:", error);
 
@@ -248,10 +229,23 @@ export function initGameTimeTracking(
 		}
 	});
 
+	// 游戏退出后由后端自动创建存档备份
+	const unlistenAutosave = listen<SavedataBackupCreatedEvent>(
+		SAVEDATA_BACKUP_CREATED_EVENT,
+		(event) => {
+			if (event.payload.automatic) {
+				snackbar.success(
+					i18n.t("pages.Detail.autosaveCreated", "已自动备份存档"),
+				);
+			}
+		},
+	);
+
 	// 返回清理函数
 	return () => {
 		unlistenStart.then((fn) => fn());
 		unlistenUpdate.then((fn) => fn());
 		unlistenEnd.then((fn) => fn());
+		unlistenAutosave.then((fn) => fn());
 	};
 }
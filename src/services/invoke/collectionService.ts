@@ -3,19 +3,28 @@
  * @description 封装所有合集相关的后端调用
  */
 
-import type { CollectionCategory, CollectionGroup } from "@/types/collection";
+import type { FullGameData } from "@/types";
+import type {
+	CollectionCategory,
+	CollectionGroup,
+	CollectionImportResult,
+	CreatedCollection,
+	MergeCollectionsResult,
+	SharedCollectionEntry,
+} from "@/types/collection";
 import { BaseService } from "./base";
+import type { SortOption, SortOrder } from "./types";
 
 class CollectionService extends BaseService {
 	/**
-	 * 创建合集
+	 * 创建合集，父级必须是根合集（最多两级）
 	 */
 	async createCollection(
 		name: string,
 		parentId: number | null = null,
 		sortOrder: number = 0,
-	): Promise<CollectionGroup> {
-		return this.invoke<CollectionGroup>("create_collection", {
+	): Promise<CreatedCollection> {
+		return this.invoke<CreatedCollection>("create_collection", {
 			name,
 			parentId,
 			sortOrder,
@@ -59,6 +68,52 @@ class CollectionService extends BaseService {
 		});
 	}
 
+	/**
+	 * 将游戏从一个合集移动到另一个合集
+	 * 不在源合集中的游戏计入 skipped
+	 */
+	async moveGamesBetweenCollections(
+		gameIds: number[],
+		fromCollectionId: number,
+		toCollectionId: number,
+	): Promise<{ moved: number; skipped: number }> {
+		return this.invoke<{ moved: number; skipped: number }>(
+			"move_games_between_collections",
+			{ gameIds, fromCollectionId, toCollectionId },
+		);
+	}
+
+	/**
+	 * 将合集 removeId 合并到 keepId 后删除 removeId
+	 * 两者须同为分组或同为分类，已在保留合集中的游戏计入 skipped
+	 */
+	async mergeCollections(
+		keepId: number,
+		removeId: number,
+	): Promise<MergeCollectionsResult> {
+		return this.invoke<MergeCollectionsResult>("merge_collections", {
+			keepId,
+			removeId,
+		});
+	}
+
+	/**
+	 * 按分享清单导入合集
+	 * ID 匹配的游戏直接加入合集，仅标题相似的候选在 fuzzy 中返回，由用户确认
+	 * @param fuzzyThreshold 标题相似度阈值（0-1），默认 0.88
+	 */
+	async importCollection(
+		collectionId: number,
+		entries: SharedCollectionEntry[],
+		fuzzyThreshold?: number,
+	): Promise<CollectionImportResult> {
+		return this.invoke<CollectionImportResult>("import_collection", {
+			collectionId,
+			entries,
+			fuzzyThreshold: fuzzyThreshold ?? null,
+		});
+	}
+
 	/**
 	 * 获取合集中的所有游戏 ID
 	 */
@@ -66,6 +121,38 @@ class CollectionService extends BaseService {
 		return this.invoke<number[]>("get_games_in_collection", { collectionId });
 	}
 
+	/**
+	 * 获取合集中游戏的完整数据
+	 * 不传排序参数时使用合集保存的排序方式，未保存时按手动顺序
+	 */
+	async getFullGamesInCollection(
+		collectionId: number,
+		sortOption?: SortOption,
+		sortOrder?: SortOrder,
+		language?: string,
+	): Promise<FullGameData[]> {
+		return this.invoke<FullGameData[]>("get_full_games_in_collection", {
+			collectionId,
+			sortOption: sortOption ?? null,
+			sortOrder: sortOrder ?? null,
+			language: language ?? null,
+		});
+	}
+
+	/**
+	 * 设置合集视图的排序方式，传 null 清除
+	 */
+	async setCollectionViewSort(
+		collectionId: number,
+		sort: { sortOption: SortOption; sortOrder: SortOrder } | null,
+	): Promise<CollectionGroup> {
+		return this.invoke<CollectionGroup>("set_collection_view_sort", {
+			collectionId,
+			sortOption: sort?.sortOption ?? null,
+			sortOrder: sort?.sortOrder ?? null,
+		});
+	}
+
 	/**
 	 * 获取游戏所在的所有合集 ID
 	 */
@@ -5,6 +5,7 @@
 
 import type { GameScanMode, ScanResult } from "@/types";
 import { BaseService } from "./base";
+import type { FileCleanupReport } from "./types";
 
 export interface BackupResult {
 	success: boolean;
@@ -26,12 +27,102 @@ export interface ImportResult {
 export interface MoveBackupFolderResult {
 	success: boolean;
 	message: string;
+	bytes_moved: number;
+	items_moved: number;
 }
 
 export interface PortableModeResult {
 	is_portable: boolean;
 }
 
+export interface AppPaths {
+	data_dir: string;
+	db_path: string;
+	db_backup_dir: string;
+	savedata_backup_dir: string;
+	is_portable: boolean;
+}
+
+export interface ModeMigrationResult {
+	from_dir: string;
+	to_dir: string;
+	copied_files: number;
+	copied_bytes: number;
+	/** 需重启应用后新数据目录才会生效 */
+	restart_required: boolean;
+}
+
+export interface ResetResult {
+	files: FileCleanupReport;
+	/** 必须重启应用后才能继续使用 */
+	restart_required: boolean;
+}
+
+/**
+ * 游戏库导出/导入的各表记录数
+ */
+export interface LibraryCounts {
+	games: number;
+	sources: number;
+	collections: number;
+	links: number;
+	sessions: number;
+}
+
+/** 游戏库导入模式 */
+export type LibraryImportMode = "merge" | "replace";
+
+/** 游戏库导入结果，skipped 为合并模式下因已存在而跳过的记录数 */
+export interface LibraryImportResult {
+	inserted: LibraryCounts;
+	skipped: LibraryCounts;
+}
+
+/** 游戏库导出的压缩方式 */
+export type LibraryCompression = "gzip" | "zstd";
+
+export interface CoversMoveResult {
+	covers_dir: string;
+	moved_files: number;
+}
+
+export interface DatabaseStats {
+	/** 数据库文件大小（字节），不含 WAL */
+	file_size: number;
+	wal_size: number;
+	page_size: number;
+	page_count: number;
+	freelist_count: number;
+	allocated_size: number;
+	/** 空闲页占用，可通过 VACUUM 回收 */
+	reclaimable_size: number;
+	tables: Array<{ table: string; rows: number }>;
+}
+
+export interface VacuumResult {
+	size_before: number;
+	size_after: number;
+}
+
+export interface SchemaOwnerVersion {
+	/** 最后写入数据库的应用版本，旧数据库没有记录时为 null */
+	owner_version: string | null;
+	app_version: string;
+	/** 数据库由更新版本的应用写入，本次启动未执行迁移 */
+	newer_than_app: boolean;
+}
+
+export interface MigrationIntegrityReport {
+	/** 本次启动执行的迁移数量 */
+	executed_migrations: number;
+	/** 迁移前的数据库备份，没有待执行的迁移时为 null */
+	backup_path: string | null;
+	backup_error: string | null;
+	/** 迁移后 PRAGMA integrity_check 的结果，未执行迁移时为空 */
+	integrity_check: string[];
+	integrity_ok: boolean;
+}
+
 export interface DroppedLocalPathResult {
 	kind:
 		| "executable"
@@ -61,9 +152,20 @@ class FileService extends BaseService {
 
 	/**
 	 * 打开目录
+	 * @param createIfMissing 目录不存在时是否先创建，默认 false
 	 */
-	async openDirectory(dirPath: string): Promise<void> {
-		return this.invoke<void>("open_directory", { dirPath });
+	async openDirectory(
+		dirPath: string,
+		createIfMissing = false,
+	): Promise<void> {
+		return this.invoke<void>("open_directory", { dirPath, createIfMissing });
+	}
+
+	/**
+	 * 在文件管理器中定位文件（Linux 下打开所在目录）
+	 */
+	async revealInExplorer(filePath: string): Promise<void> {
+		return this.invoke<void>("reveal_in_explorer", { filePath });
 	}
 
 	/**
@@ -91,6 +193,75 @@ class FileService extends BaseService {
 		return this.invoke<PortableModeResult>("is_portable_mode");
 	}
 
+	/**
+	 * 获取数据目录、数据库文件及备份目录的实际路径
+	 */
+	async getAppPaths(): Promise<AppPaths> {
+		return this.invoke<AppPaths>("get_app_paths");
+	}
+
+	/**
+	 * 获取数据库文件大小、页面使用情况与主要表的行数
+	 */
+	async getDatabaseStats(): Promise<DatabaseStats> {
+		return this.invoke<DatabaseStats>("get_database_stats");
+	}
+
+	/**
+	 * 执行 VACUUM 回收空闲页
+	 * 需要与数据库大小相当的磁盘剩余空间，执行期间数据库被独占
+	 */
+	async vacuumDatabase(): Promise<VacuumResult> {
+		return this.invoke<VacuumResult>("vacuum_database");
+	}
+
+	/**
+	 * 获取最后写入数据库的应用版本
+	 * newer_than_app 为 true 时应提示用户升级应用
+	 */
+	async getSchemaOwnerVersion(): Promise<SchemaOwnerVersion> {
+		return this.invoke<SchemaOwnerVersion>("get_schema_owner_version");
+	}
+
+	/**
+	 * 获取本次启动的数据库迁移保护结果
+	 * integrity_ok 为 false 时应提示用户数据库可能已损坏，可使用 backup_path 恢复
+	 */
+	async getMigrationIntegrityReport(): Promise<MigrationIntegrityReport> {
+		return this.invoke<MigrationIntegrityReport>(
+			"get_migration_integrity_report",
+		);
+	}
+
+	/**
+	 * 生成清空全部数据所需的一次性确认短语，5 分钟内有效
+	 */
+	async requestResetPhrase(): Promise<string> {
+		return this.invoke<string>("request_reset_phrase");
+	}
+
+	/**
+	 * 清空全部游戏数据、封面与存档备份，完成后必须重启应用
+	 * @param confirmation requestResetPhrase 返回的确认短语
+	 */
+	async resetAllData(confirmation: string): Promise<ResetResult> {
+		return this.invoke<ResetResult>("reset_all_data", { confirmation });
+	}
+
+	/**
+	 * 将数据迁移到便携模式目录，完成后需重启应用
+	 */
+	async migrateToPortable(): Promise<ModeMigrationResult> {
+		return this.invoke<ModeMigrationResult>("migrate_to_portable");
+	}
+
+	/**
+	 * 将数据迁移到系统应用数据目录，完成后需重启应用
+	 */
+	async migrateToStandard(): Promise<ModeMigrationResult> {
+		return this.invoke<ModeMigrationResult>("migrate_to_standard");
+	}
+
 	/**
 	 * 复制文件
 	 */
@@ -100,9 +271,10 @@ class FileService extends BaseService {
 
 	/**
 	 * 删除文件
+	 * @param recycle 是否移入系统回收站，默认 false（永久删除）
 	 */
-	async deleteFile(filePath: string): Promise<void> {
-		return this.invoke<void>("delete_file", { filePath });
+	async deleteFile(filePath: string, recycle?: boolean): Promise<void> {
+		return this.invoke<void>("delete_file", { filePath, recycle });
 	}
 
 	/**
@@ -119,6 +291,36 @@ class FileService extends BaseService {
 		return this.invoke<void>("delete_game_covers", { gameId, coversDir });
 	}
 
+	/**
+	 * 从图片 URL 下载并设为自定义封面
+	 * @returns 写入 custom_data.image 的版本化标识
+	 */
+	async setCoverFromUrl(
+		gameId: number,
+		url: string,
+		coversDir: string,
+	): Promise<string> {
+		return this.invoke<string>("set_cover_from_url", {
+			gameId,
+			url,
+			coversDir,
+		});
+	}
+
+	/**
+	 * 获取当前封面目录
+	 */
+	async getCoversPath(): Promise<string> {
+		return this.invoke<string>("get_covers_path");
+	}
+
+	/**
+	 * 修改封面目录并移动现有封面，newPath 为空时恢复默认目录
+	 */
+	async changeCoversPath(newPath: string | null): Promise<CoversMoveResult> {
+		return this.invoke<CoversMoveResult>("change_covers_path", { newPath });
+	}
+
 	/**
 	 * 删除本地的云端封面缓存
 	 */
@@ -135,6 +337,39 @@ class FileService extends BaseService {
 		return this.invoke<BackupResult>("backup_database", { options });
 	}
 
+	/**
+	 * 将整个游戏库流式导出为 JSON 文件
+	 * @param compress 可选压缩方式，对应 .json.gz / .json.zst
+	 */
+	async exportLibraryToFile(
+		path: string,
+		compress?: LibraryCompression,
+	): Promise<LibraryCounts> {
+		return this.invoke<LibraryCounts>("export_library_to_file", {
+			path,
+			compress: compress ?? null,
+		});
+	}
+
+	/**
+	 * 从导出的 JSON 文件导入游戏库，导入的记录分配新 ID
+	 * gzip / zstd 压缩的文件会自动识别并解压
+	 */
+	async importLibraryFromFile(path: string): Promise<LibraryCounts> {
+		return this.invoke<LibraryCounts>("import_library_from_file", { path });
+	}
+
+	/**
+	 * 从导出的 JSON 字符串导入游戏库
+	 * @param mode merge 跳过来源 ID 已存在的游戏；replace 先清空现有游戏库
+	 */
+	async importLibrary(
+		json: string,
+		mode: LibraryImportMode,
+	): Promise<LibraryImportResult> {
+		return this.invoke<LibraryImportResult>("import_library", { json, mode });
+	}
+
 	/**
 	 * 备份自定义封面（仅自定义封面，不含云端缓存）
 	 */
@@ -163,6 +398,18 @@ class FileService extends BaseService {
 			newPath,
 		});
 	}
+
+	/**
+	 * 修改存档根目录并迁移现有备份，迁移失败时设置不会被修改
+	 * @param newRoot 新的存档根目录，null 表示恢复默认目录
+	 */
+	async changeSaveRootPath(
+		newRoot: string | null,
+	): Promise<MoveBackupFolderResult> {
+		return this.invoke<MoveBackupFolderResult>("change_save_root_path", {
+			newRoot,
+		});
+	}
 }
 
 export const fileService = new FileService();
@@ -14,10 +14,62 @@ import type {
 	BatchOperationResult,
 	FullGameData,
 	InsertGameParams,
+	SourceType,
 	UpdateGameParams,
 } from "@/types";
 import { BaseService } from "./base";
-import type { GameType, SortOption, SortOrder } from "./types";
+import type {
+	DeleteGameCompleteResult,
+	FacetCount,
+	GameFilter,
+	GameMediaItem,
+	GameMediaType,
+	GameStatus,
+	GameType,
+	RefreshRun,
+	SortOption,
+	SortOrder,
+} from "./types";
+
+export interface GamePage {
+	games: FullGameData[];
+	/** 符合筛选条件的游戏总数 */
+	total: number;
+}
+
+/** 带重复检查的插入结果 */
+export type CheckedInsertResult =
+	| { status: "inserted"; game: FullGameData }
+	| {
+			status: "duplicate";
+			/** 已绑定相同来源 ID 的游戏 */
+			game_id: number;
+			source: string;
+			external_id: string;
+	  };
+
+/** 游戏启动前与退出后执行的命令，启动前命令失败会取消启动 */
+export interface LaunchHooks {
+	pre_launch_cmd: string | null;
+	post_exit_cmd: string | null;
+}
+
+/** 合并重复游戏的结果 */
+export interface MergeGamesResult {
+	sessions_moved: number;
+	backups_moved: number;
+	links_moved: number;
+	/** 保留游戏已在同一合集中而删除的关联数量 */
+	links_dropped: number;
+}
+
+export interface SearchHit {
+	game: FullGameData;
+	/** 命中字段，如 "custom.name"、"vndb.aliases" */
+	matched_field: string;
+	/** 命中字段中关键词前后的文本，截断处以 "…" 表示 */
+	snippet: string;
+}
 
 type WireBatchOperationResult = Omit<BatchOperationResult, "games"> & {
 	games: FullGameData[];
@@ -34,6 +86,17 @@ class GameService extends BaseService {
 		});
 	}
 
+	/**
+	 * 插入游戏数据，来源 ID 已被其他游戏绑定时不插入，返回冲突的游戏
+	 */
+	async insertGameChecked(
+		game: InsertGameParams,
+	): Promise<CheckedInsertResult> {
+		return this.invoke<CheckedInsertResult>("insert_game_checked", {
+			game,
+		});
+	}
+
 	/**
 	 * 批量插入游戏数据
 	 */
@@ -65,12 +128,71 @@ class GameService extends BaseService {
 		sortOption: SortOption = "addtime",
 		sortOrder: SortOrder = "asc",
 		language?: string,
+		filter?: GameFilter,
 	): Promise<FullGameData[]> {
 		return this.invoke<FullGameData[]>("find_all_games", {
 			gameType,
 			sortOption,
 			sortOrder,
 			language: language ?? null,
+			filter: filter ?? null,
+		});
+	}
+
+	/**
+	 * 统计关键词搜索的匹配数量，条件与 getGameIds 一致，只执行 COUNT
+	 * filter 中的 keyword 会被 keyword 参数覆盖
+	 */
+	async countSearchMatches(
+		keyword: string,
+		gameType: GameType = "all",
+		filter?: GameFilter,
+	): Promise<number> {
+		return this.invoke<number>("count_search_matches", {
+			keyword,
+			gameType,
+			filter: filter ?? null,
+		});
+	}
+
+	/**
+	 * 关键词搜索，返回每个游戏命中的字段与片段，名称命中优先
+	 */
+	async searchGames(
+		keyword: string,
+		gameType: GameType = "all",
+		filter?: GameFilter,
+		limit?: number,
+	): Promise<SearchHit[]> {
+		return this.invoke<SearchHit[]>("search_games", {
+			keyword,
+			gameType,
+			filter: filter ?? null,
+			limit: limit ?? null,
+		});
+	}
+
+	/**
+	 * 分页获取游戏数据，只加载当前页的关联数据
+	 * @param page - 页码，从 0 开始
+	 */
+	async getGamesPage(
+		page: number,
+		pageSize: number,
+		gameType: GameType = "all",
+		sortOption: SortOption = "addtime",
+		sortOrder: SortOrder = "asc",
+		language?: string,
+		filter?: GameFilter,
+	): Promise<GamePage> {
+		return this.invoke<GamePage>("find_games_page", {
+			gameType,
+			sortOption,
+			sortOrder,
+			language: language ?? null,
+			filter: filter ?? null,
+			page,
+			pageSize,
 		});
 	}
 
@@ -85,12 +207,14 @@ class GameService extends BaseService {
 		sortOption: SortOption = "addtime",
 		sortOrder: SortOrder = "asc",
 		language?: string,
+		filter?: GameFilter,
 	): Promise<number[]> {
 		return this.invoke<number[]>("find_game_ids", {
 			gameType,
 			sortOption,
 			sortOrder,
 			language: language ?? null,
+			filter: filter ?? null,
 		});
 	}
 
@@ -122,6 +246,142 @@ class GameService extends BaseService {
 		return this.invoke<number>("delete_game", { id });
 	}
 
+	/**
+	 * 彻底删除游戏，并清理封面与存档备份文件
+	 */
+	async deleteGameComplete(gameId: number): Promise<DeleteGameCompleteResult> {
+		return this.invoke<DeleteGameCompleteResult>("delete_game_complete", {
+			gameId,
+		});
+	}
+
+	/**
+	 * 合并重复游戏：removeId 的会话、备份与合集关联转移到 keepId 后删除 removeId
+	 */
+	async mergeGames(
+		keepId: number,
+		removeId: number,
+	): Promise<MergeGamesResult> {
+		return this.invoke<MergeGamesResult>("merge_games", { keepId, removeId });
+	}
+
+	/**
+	 * 读取游戏保存的启动参数，未保存时返回 null
+	 */
+	async getLaunchArgs(gameId: number): Promise<string[] | null> {
+		return this.invoke<string[] | null>("get_launch_args", { gameId });
+	}
+
+	/**
+	 * 保存游戏的启动参数，传空数组清除
+	 */
+	async setLaunchArgs(gameId: number, args: string[]): Promise<number> {
+		return this.invoke<number>("set_launch_args", { gameId, args });
+	}
+
+	/**
+	 * 读取游戏的启动前与退出后命令
+	 */
+	async getLaunchHooks(gameId: number): Promise<LaunchHooks> {
+		return this.invoke<LaunchHooks>("get_launch_hooks", { gameId });
+	}
+
+	/**
+	 * 保存游戏的启动前与退出后命令，空白命令视为未设置
+	 */
+	async setLaunchHooks(gameId: number, hooks: LaunchHooks): Promise<number> {
+		return this.invoke<number>("set_launch_hooks", { gameId, hooks });
+	}
+
+	/**
+	 * 设置游戏的主数据源，传 null 恢复自动选择
+	 */
+	async setPrimarySource(
+		gameId: number,
+		source: SourceType | "custom" | null,
+	): Promise<number> {
+		return this.invoke<number>("set_primary_source", { gameId, source });
+	}
+
+	/**
+	 * 切换游戏的收藏标记，返回切换后的值
+	 */
+	async toggleFavorite(gameId: number): Promise<boolean> {
+		return this.invoke<boolean>("toggle_favorite", { gameId });
+	}
+
+	/**
+	 * 获取游戏状态，状态值无效时返回 null
+	 */
+	async getGameStatus(gameId: number): Promise<GameStatus | null> {
+		return this.invoke<GameStatus | null>("get_game_status", { gameId });
+	}
+
+	/**
+	 * 设置游戏状态
+	 */
+	async setGameStatus(gameId: number, status: GameStatus): Promise<void> {
+		return this.invoke<void>("set_game_status", { gameId, status });
+	}
+
+	/**
+	 * 获取游戏的媒体（截图等），按顺序排列
+	 */
+	async getGameMedia(gameId: number): Promise<GameMediaItem[]> {
+		return this.invoke<GameMediaItem[]>("get_game_media", { gameId });
+	}
+
+	/**
+	 * 复制文件到游戏媒体目录，并追加到媒体列表末尾
+	 */
+	async addGameMedia(
+		gameId: number,
+		sourcePath: string,
+		mediaType: GameMediaType = "screenshot",
+	): Promise<GameMediaItem> {
+		return this.invoke<GameMediaItem>("add_game_media", {
+			gameId,
+			sourcePath,
+			mediaType,
+		});
+	}
+
+	/**
+	 * 删除媒体及其文件，返回是否删除了记录
+	 */
+	async removeGameMedia(mediaId: number): Promise<boolean> {
+		return this.invoke<boolean>("remove_game_media", { mediaId });
+	}
+
+	/**
+	 * 按给定顺序重排游戏媒体，需包含该游戏的全部媒体 ID
+	 */
+	async reorderGameMedia(gameId: number, mediaIds: number[]): Promise<void> {
+		return this.invoke<void>("reorder_game_media", { gameId, mediaIds });
+	}
+
+	/**
+	 * 设置游戏的本地路径（须已存在），传 null 清除使其归为在线游戏
+	 */
+	async setLocalPath(gameId: number, path: string | null): Promise<void> {
+		return this.invoke<void>("set_local_path", { gameId, path });
+	}
+
+	/**
+	 * 重命名游戏，传空字符串清除自定义名称，返回生效的显示名称
+	 */
+	async renameGame(
+		gameId: number,
+		name: string,
+		language?: string,
+	): Promise<string | null> {
+		return this.invoke<string | null>("rename_game", {
+			gameId,
+			name,
+			language,
+		});
+	}
+
 	/**
 	 * 批量删除游戏
 	 */
@@ -136,6 +396,74 @@ class GameService extends BaseService {
 		return this.invoke<number>("count_games");
 	}
 
+	/**
+	 * 开始一轮元数据刷新，记录待刷新的游戏
+	 */
+	async startRefreshMetadata(
+		source: string,
+		gameIds: number[],
+	): Promise<RefreshRun> {
+		return this.invoke<RefreshRun>("start_refresh_metadata", {
+			source,
+			gameIds,
+		});
+	}
+
+	/**
+	 * 记录已刷新的游戏，全部完成时返回 null
+	 */
+	async markRefreshMetadataProgress(
+		token: string,
+		gameIds: number[],
+	): Promise<RefreshRun | null> {
+		return this.invoke<RefreshRun | null>("mark_refresh_metadata_progress", {
+			token,
+			gameIds,
+		});
+	}
+
+	/**
+	 * 取回中断的刷新进度
+	 */
+	async resumeRefreshMetadata(token: string): Promise<RefreshRun> {
+		return this.invoke<RefreshRun>("resume_refresh_metadata", { token });
+	}
+
+	/**
+	 * 查询未完成的刷新进度
+	 */
+	async getPendingRefreshMetadata(): Promise<RefreshRun | null> {
+		return this.invoke<RefreshRun | null>("get_pending_refresh_metadata");
+	}
+
+	/**
+	 * 结束或放弃刷新，清除进度
+	 */
+	async finishRefreshMetadata(token: string): Promise<void> {
+		return this.invoke<void>("finish_refresh_metadata", { token });
+	}
+
+	/**
+	 * 获取全部开发商及对应游戏数
+	 */
+	async getAllDevelopers(): Promise<FacetCount[]> {
+		return this.invoke<FacetCount[]>("get_all_developers");
+	}
+
+	/**
+	 * 将开发商名称统一改为规范写法（去除首尾空白后完全匹配），返回受影响的游戏数
+	 */
+	async renameDeveloper(from: string, to: string): Promise<number> {
+		return this.invoke<number>("rename_developer", { from, to });
+	}
+
+	/**
+	 * 获取全部标签及对应游戏数
+	 */
+	async getAllTags(): Promise<FacetCount[]> {
+		return this.invoke<FacetCount[]>("get_all_tags");
+	}
+
 	/**
 	 * 获取指定 source 的游戏绑定
 	 */
@@ -164,7 +492,8 @@ class GameService extends BaseService {
 	/**
 	 * 批量更新游戏数据
 	 *
-	 * 使用单个事务处理所有更新操作，性能远优于逐个更新
+	 * 使用单个事务处理所有更新操作，性能远优于逐个更新；
+	 * 任一游戏失败时整批回滚，错误信息中带有失败的游戏 ID
 	 *
 	 * @param updates 更新列表 [[gameId, updates], ...]
 	 * @returns 返回更新后的完整游戏数据
@@ -12,6 +12,174 @@ export interface BackupInfo {
 	backup_time: number;
 	file_size: number;
 	backup_path: string;
+	/** 增量备份所依赖的基础备份记录 ID，完整备份为 null */
+	base_backup_id: number | null;
+	/** 备份清单文件路径，旧版本创建的备份没有清单时为 null */
+	manifest: string | null;
+	/** 创建后是否已重新读取压缩包并通过校验 */
+	verified: boolean;
+}
+
+/** 存档恢复结果 */
+export interface RestoreResult {
+	/** 恢复后目标目录中的文件数量 */
+	files_extracted: number;
+	/** 写入的总字节数 */
+	bytes_written: number;
+}
+
+/** 备份链合并结果 */
+export interface CompactChainResult {
+	/** 新完整备份的记录 ID */
+	record_id: number;
+	backup: BackupInfo;
+	/** 删除的增量备份数量 */
+	removed: number;
+}
+
+/** 备份大小估算结果 */
+export interface BackupSizeEstimate {
+	file_count: number;
+	/** 未压缩的总大小（字节） */
+	uncompressed_size: number;
+	/** 按抽样压缩率推算的压缩包大小，未抽样时为 null */
+	estimated_size: number | null;
+	/** 备份目录所在磁盘的剩余空间 */
+	available_space: number;
+	/** 剩余空间是否足够 */
+	fits: boolean;
+}
+
+/** 按备份记录统计的存档备份占用（字节） */
+export interface BackupSizeSummary {
+	total_size: number;
+	record_count: number;
+	/** 记录仍在但备份文件已不存在的数量 */
+	stale_count: number;
+	stale_size: number;
+}
+
+/** 备份目录磁盘占用（字节） */
+export interface BackupDiskUsage {
+	root: string;
+	total_bytes: number;
+	/** 各游戏的占用，按占用从大到小排列 */
+	games: { game_id: number; bytes: number; file_count: number }[];
+	/** 不属于任何 game_<id> 目录的文件占用 */
+	other_bytes: number;
+	largest_archive: {
+		game_id: number | null;
+		path: string;
+		bytes: number;
+	} | null;
+}
+
+/** 存档备份记录保存后发送的事件名 */
+export const SAVEDATA_BACKUP_CREATED_EVENT = "savedata-backup-created";
+
+/** savedata-backup-created 事件内容 */
+export interface SavedataBackupCreatedEvent {
+	gameId: number;
+	/** savedata 表中的记录 ID */
+	recordId: number;
+	backup: BackupInfo;
+	/** 是否为游戏退出后自动创建的备份 */
+	automatic: boolean;
+}
+
+/** 压缩存档时发送的进度事件名（已节流，最后一个文件总会发送） */
+export const SAVEDATA_BACKUP_PROGRESS_EVENT = "savedata-backup-progress";
+
+/** savedata-backup-progress 事件内容 */
+export interface SavedataBackupProgressEvent {
+	gameId: number;
+	/** 正在压缩的文件（相对存档目录） */
+	currentFile: string;
+	filesProcessed: number;
+	totalFiles: number;
+}
+
+/** 备份压缩包内的条目 */
+export interface BackupEntry {
+	name: string;
+	size: number;
+	is_dir: boolean;
+	modified: number | null;
+}
+
+export interface ChangedSaveFile {
+	name: string;
+	save_size: number;
+	backup_size: number;
+	save_modified: number | null;
+	backup_modified: number | null;
+}
+
+/**
+ * 存档目录相对备份的差异
+ */
+export interface SaveBackupDiff {
+	/** 仅存在于存档目录中的文件 */
+	added: string[];
+	/** 仅存在于备份中的文件 */
+	removed: string[];
+	changed: ChangedSaveFile[];
+}
+
+export type BackupVerifyStatus = "ok" | "corrupted" | "missing" | "encrypted";
+
+export interface BackupVerifyEntry {
+	backup_id: number;
+	game_id: number;
+	file: string;
+	status: BackupVerifyStatus;
+	/** 损坏原因 */
+	detail: string | null;
+}
+
+/**
+ * 存档备份批量校验报告，entries 按备份 ID 排序
+ */
+export interface BackupVerifyReport {
+	ok: number;
+	corrupted: number;
+	missing: number;
+	encrypted: number;
+	entries: BackupVerifyEntry[];
+}
+
+/** 磁盘上存在但没有对应记录的备份文件 */
+export interface OrphanBackupFile {
+	game_id: number;
+	file: string;
+	path: string;
+	bytes: number;
+}
+
+/** 备份文件已不存在的记录 */
+export interface MissingBackupRecord {
+	backup_id: number;
+	game_id: number;
+	file: string;
+}
+
+/** 存档备份核对结果 */
+export interface BackupReconcileReport {
+	orphan_files: OrphanBackupFile[];
+	missing_files: MissingBackupRecord[];
+	deleted_files: number;
+	deleted_records: number;
+	errors: string[];
+}
+
+/**
+ * 自动检测到的存档目录候选
+ */
+export interface SaveFolderCandidate {
+	path: string;
+	/** 0-100，越高越可信 */
+	confidence: number;
+	reason: string;
 }
 
 class SavedataService extends BaseService {
@@ -19,34 +187,187 @@ class SavedataService extends BaseService {
 	 * 创建存档备份
 	 * @param gameId 游戏ID
 	 * @param sourcePath 存档文件夹路径
+	 * @param incremental 是否只备份相对最新备份变化的文件，默认 false
+	 * @param compressionLevel 压缩等级 0-9，0 只打包不压缩，越高越小越慢，默认 3
+	 * @param verify 是否在压缩后重新读取压缩包校验，校验失败时删除压缩包并报错，默认 false
+	 * @param excludeGlobs 排除规则，如 "*.log"、"cache/**"，匹配的文件与目录不会写入备份
 	 */
-	async createBackup(gameId: number, sourcePath: string): Promise<BackupInfo> {
+	async createBackup(
+		gameId: number,
+		sourcePath: string,
+		incremental?: boolean,
+		compressionLevel?: number,
+		verify?: boolean,
+		excludeGlobs?: string[],
+	): Promise<BackupInfo> {
 		return this.invoke<BackupInfo>("create_savedata_backup", {
 			gameId,
 			sourcePath,
+			incremental,
+			compressionLevel,
+			verify,
+			excludeGlobs,
 		});
 	}
 
+	/**
+	 * 估算存档备份大小并检查备份目录剩余空间
+	 * @param sourcePath 存档目录
+	 * @param sample 是否抽样压缩估算压缩后大小，默认 true
+	 * @param compressionLevel 压缩等级 0-9，应与创建备份时一致，默认 3
+	 * @param excludeGlobs 排除规则，应与创建备份时一致，匹配的文件不计入
+	 */
+	async estimateBackupSize(
+		sourcePath: string,
+		sample?: boolean,
+		compressionLevel?: number,
+		excludeGlobs?: string[],
+	): Promise<BackupSizeEstimate> {
+		return this.invoke<BackupSizeEstimate>("estimate_backup_size", {
+			sourcePath,
+			sample,
+			compressionLevel,
+			excludeGlobs,
+		});
+	}
+
+	/**
+	 * 统计存档备份目录的磁盘占用（总量、各游戏占用与最大的压缩包）
+	 */
+	async getBackupDiskUsage(): Promise<BackupDiskUsage> {
+		return this.invoke<BackupDiskUsage>("get_backup_disk_usage");
+	}
+
+	/**
+	 * 取消正在进行的存档备份
+	 * 被取消的 createBackup 会以 "BACKUP_CANCELLED" 错误结束
+	 * @param gameId 游戏ID
+	 */
+	async cancelBackup(gameId: number): Promise<void> {
+		return this.invoke<void>("cancel_backup", { gameId });
+	}
+
 	/**
 	 * 删除备份文件和数据库记录（二合一）
 	 * @param backupId 备份记录ID
+	 * @param recycle 是否将备份文件移入系统回收站，默认 false（永久删除）
 	 */
-	async deleteBackup(backupId: number): Promise<void> {
-		return this.invoke<void>("delete_savedata_backup", { backupId });
+	async deleteBackup(backupId: number, recycle?: boolean): Promise<void> {
+		return this.invoke<void>("delete_savedata_backup", { backupId, recycle });
+	}
+
+	/**
+	 * 只保留最新的 keepCount 个备份，删除更旧的备份文件与记录
+	 * 被保留的增量备份依赖的基础备份不会删除
+	 * @param keepCount 保留数量，不能为 0
+	 * @returns 被删除的备份记录（从新到旧）
+	 */
+	async pruneBackups(
+		gameId: number,
+		keepCount: number,
+	): Promise<SavedataRecord[]> {
+		return this.invoke<SavedataRecord[]>("prune_savedata_backups", {
+			gameId,
+			keepCount,
+		});
 	}
 
 	/**
 	 * 恢复存档备份
 	 * @param backupFilePath 备份文件完整路径
 	 * @param targetPath 目标恢复路径
+	 * @param overwrite 目标目录不为空时是否覆盖，为 false 时会直接报错
 	 */
 	async restoreBackup(
 		backupFilePath: string,
 		targetPath: string,
-	): Promise<void> {
-		return this.invoke<void>("restore_savedata_backup", {
+		overwrite: boolean,
+	): Promise<RestoreResult> {
+		return this.invoke<RestoreResult>("restore_savedata_backup", {
 			backupFilePath,
 			targetPath,
+			overwrite,
+		});
+	}
+
+	/**
+	 * 将备份恢复到指定游戏当前设置的存档目录（可与备份所属游戏不同）
+	 * @returns 实际恢复到的路径
+	 */
+	async restoreToGame(backupId: number, gameId: number): Promise<string> {
+		return this.invoke<string>("restore_to_game", { backupId, gameId });
+	}
+
+	/**
+	 * 将最新备份所在的增量链合并为一个完整备份
+	 * @returns 最新备份已是完整备份时返回 null
+	 */
+	async compactBackupChain(gameId: number): Promise<CompactChainResult | null> {
+		return this.invoke<CompactChainResult | null>("compact_backup_chain", {
+			gameId,
+		});
+	}
+
+	/**
+	 * 校验全部存档备份文件，损坏或缺失的记录可再通过 deleteBackup 删除
+	 * @param backupRootDir 备份根目录，不传时使用当前设置
+	 */
+	async verifyAllBackups(backupRootDir?: string): Promise<BackupVerifyReport> {
+		return this.invoke<BackupVerifyReport>("verify_all_backups", {
+			backupRootDir,
+		});
+	}
+
+	/**
+	 * 核对存档备份文件与数据库记录
+	 * @param deleteOrphans 是否将没有记录的文件移入回收站，并删除文件已不存在的记录
+	 */
+	async reconcileSavedataBackups(
+		deleteOrphans = false,
+	): Promise<BackupReconcileReport> {
+		return this.invoke<BackupReconcileReport>("reconcile_savedata_backups", {
+			deleteOrphans,
+		});
+	}
+
+	/**
+	 * 列出存档备份内容（不解压）
+	 * @param backupFilePath 备份文件完整路径
+	 * @param password 加密备份的密码
+	 */
+	async listBackupContents(
+		backupFilePath: string,
+		password?: string,
+	): Promise<BackupEntry[]> {
+		return this.invoke<BackupEntry[]>("list_backup_contents", {
+			backupFilePath,
+			password,
+		});
+	}
+
+	/**
+	 * 比较存档目录与备份的差异（只读取备份文件头）
+	 * @param password 加密备份的密码
+	 */
+	async diffSaveAgainstBackup(
+		savePath: string,
+		backupFilePath: string,
+		password?: string,
+	): Promise<SaveBackupDiff> {
+		return this.invoke<SaveBackupDiff>("diff_save_against_backup", {
+			savePath,
+			backupFilePath,
+			password,
+		});
+	}
+
+	/**
+	 * 根据常见引擎的存档位置检测存档目录，按可信度从高到低返回
+	 * @param gamePath 游戏启动程序或游戏目录路径
+	 */
+	async detectSaveFolder(gamePath: string): Promise<SaveFolderCandidate[]> {
+		return this.invoke<SaveFolderCandidate[]>("detect_save_folder", {
+			gamePath,
 		});
 	}
 
@@ -58,12 +379,14 @@ class SavedataService extends BaseService {
 		fileName: string,
 		backupTime: number,
 		fileSize: number,
+		baseBackupId?: number | null,
 	): Promise<number> {
 		return this.invoke<number>("save_savedata_record", {
 			gameId,
 			fileName,
 			backupTime,
 			fileSize,
+			baseBackupId,
 		});
 	}
 
@@ -74,12 +397,33 @@ class SavedataService extends BaseService {
 		return this.invoke<number>("get_savedata_count", { gameId });
 	}
 
+	/**
+	 * 按备份记录统计存档备份占用，不传 gameId 时统计全部游戏
+	 * stale_count 为备份文件已不存在的记录数量
+	 */
+	async getBackupSizeSummary(gameId?: number): Promise<BackupSizeSummary> {
+		return this.invoke<BackupSizeSummary>("get_backup_size_summary", {
+			gameId,
+		});
+	}
+
 	/**
 	 * 获取指定游戏的所有备份记录
 	 */
 	async getSavedataRecords(gameId: number): Promise<SavedataRecord[]> {
 		return this.invoke<SavedataRecord[]>("get_savedata_records", { gameId });
 	}
+
+	/**
+	 * 批量获取各游戏最新的备份记录，没有备份的游戏不在结果中
+	 */
+	async getLatestBackups(
+		gameIds: number[],
+	): Promise<Record<number, SavedataRecord>> {
+		return this.invoke<Record<number, SavedataRecord>>("get_latest_backups", {
+			gameIds,
+		});
+	}
 }
 
 // 导出单例
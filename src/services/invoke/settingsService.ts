@@ -19,6 +19,23 @@ export interface ProxyConfig {
 	url: string;
 }
 
+export interface MaintenanceConfig {
+	/** 是否按间隔自动执行 */
+	enabled: boolean;
+	interval_hours: number;
+	prune_db_backups: boolean;
+	max_db_auto_backups: number;
+	purge_archived: boolean;
+	archived_retention_days: number;
+	rebuild_statistics: boolean;
+}
+
+export interface MaintenanceJobOutcome {
+	job: "prune_db_backups" | "purge_archived" | "rebuild_statistics";
+	success: boolean;
+	message: string;
+}
+
 class SettingsService extends BaseService {
 	/**
 	 * 动态设置日志输出级别（不持久化）
@@ -34,6 +51,41 @@ class SettingsService extends BaseService {
 		return this.invoke<LogLevel>("get_reina_log_level");
 	}
 
+	/**
+	 * 查询系统中是否已注册开机自启
+	 */
+	async getAutostartEnabled(): Promise<boolean> {
+		return this.invoke<boolean>("get_autostart_enabled");
+	}
+
+	/**
+	 * 启用或禁用开机自启（偏好会持久化，重装后自动恢复）
+	 */
+	async setAutostartEnabled(enabled: boolean): Promise<void> {
+		return this.invoke<void>("set_autostart_enabled", { enabled });
+	}
+
+	/**
+	 * 获取定期维护配置
+	 */
+	async getMaintenanceConfig(): Promise<MaintenanceConfig> {
+		return this.invoke<MaintenanceConfig>("get_maintenance_config");
+	}
+
+	/**
+	 * 保存定期维护配置，下一轮调度时生效
+	 */
+	async setMaintenanceConfig(config: MaintenanceConfig): Promise<void> {
+		return this.invoke<void>("set_maintenance_config", { config });
+	}
+
+	/**
+	 * 立即执行已开启的维护任务；有存档备份进行中时会被拒绝
+	 */
+	async runMaintenanceNow(): Promise<MaintenanceJobOutcome[]> {
+		return this.invoke<MaintenanceJobOutcome[]>("run_maintenance_now");
+	}
+
 	/**
 	 * 获取所有设置
 	 */
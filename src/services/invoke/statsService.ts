@@ -3,14 +3,32 @@
  * @description 封装所有游戏统计相关的后端调用
  */
 
-import type { GameLastPlayed, GameSession, GameStatistics } from "@/types";
+import type {
+	FullGameData,
+	GameLastPlayed,
+	GameSession,
+	GameStatistics,
+} from "@/types";
 import { BaseService } from "./base";
 
+/**
+ * 启动失败时的路径诊断
+ */
+export interface LaunchDiagnostics {
+	executable_path: string;
+	working_dir: string;
+	working_dir_exists: boolean;
+	exe_exists: boolean;
+	exe_executable: boolean;
+}
+
 export interface LaunchGameResult {
 	success: boolean;
 	message: string;
+	/** NEED_EXECUTABLE / NOT_EXECUTABLE / LAUNCH_FAILED / LAUNCHER_TIMEOUT / ALREADY_RUNNING */
 	code?: string | null;
 	process_id?: number;
+	diagnostics?: LaunchDiagnostics | null;
 }
 
 export interface StopGameResult {
@@ -19,6 +37,69 @@ export interface StopGameResult {
 	terminated_count: number;
 }
 
+export interface HeatmapDay {
+	date: string;
+	total_playtime: number;
+	games_played: number;
+}
+
+export interface TotalPlaytime {
+	/** 累计时长（分钟） */
+	total_time: number;
+	total_time_display: string;
+}
+
+/**
+ * 全库完成情况（不含已归档游戏）
+ */
+export interface CompletionStats {
+	total_games: number;
+	/** 状态为“玩过”的游戏 */
+	cleared: number;
+	/** 未通关但有游玩会话 */
+	in_progress: number;
+	/** 未通关且没有游玩会话 */
+	untouched: number;
+	/** 通关百分比（0-100） */
+	cleared_percent: number;
+}
+
+export interface GamePlaytimeSince {
+	game_id: number;
+	seconds: number;
+	session_count: number;
+}
+
+export interface PlaytimeSince {
+	total_seconds: number;
+	/** 按游玩时长降序 */
+	games: GamePlaytimeSince[];
+}
+
+export interface GamePlayedOnDate {
+	game: FullGameData;
+	playtime: number;
+}
+
+export interface DashboardGameData {
+	id: number;
+	name?: string | null;
+	image?: string | null;
+	total_time?: number | null;
+	total_time_display: string;
+	session_count?: number | null;
+	last_played?: number | null;
+}
+
+/**
+ * 全库活动流中的一条会话
+ */
+export interface SessionFeedItem {
+	session: GameSession;
+	game_name?: string | null;
+	cover?: string | null;
+}
+
 export interface ExternalRunningGameMatch {
 	game_id: number;
 	process_id: number;
@@ -28,19 +109,51 @@ export interface ExternalRunningGameMatch {
 class StatsService extends BaseService {
 	/**
 	 * 启动游戏并开始监控
+	 * @param launcherPath 可选的启动脚本（.bat/.sh），设置后运行脚本，监控仍跟踪游戏程序的进程
+	 * @param env 可选的环境变量（[变量名, 值]），附加到启动的进程
+	 * @param localeEmulatorPath 可选的转区程序（如 LEProc.exe），仅 Windows 有效；路径无效时直接启动
+	 * @param force 为 true 时游戏已在运行也再次启动，否则返回 ALREADY_RUNNING
 	 */
 	async launchGame(
 		gameId: number,
 		args: string[] = [],
 		timeTrackingMode: "playtime" | "elapsed",
+		launcherPath?: string,
+		env?: [string, string][],
+		localeEmulatorPath?: string,
+		force = false,
 	): Promise<LaunchGameResult> {
 		return this.invoke<LaunchGameResult>("launch_game", {
 			gameId,
 			args,
 			timeTrackingMode,
+			launcherPath,
+			env,
+			localeEmulatorPath,
+			force,
+		});
+	}
+
+	/**
+	 * 使用已保存的启动参数启动游戏并开始监控，只需传入游戏 ID
+	 */
+	async launchGameById(
+		gameId: number,
+		timeTrackingMode: "playtime" | "elapsed",
+	): Promise<LaunchGameResult> {
+		return this.invoke<LaunchGameResult>("launch_game_by_id", {
+			gameId,
+			timeTrackingMode,
 		});
 	}
 
+	/**
+	 * 为启动程序添加可执行权限（chmod +x），仅 Unix 平台有效
+	 */
+	async makeExecutable(path: string): Promise<void> {
+		return this.invoke<void>("make_executable", { path });
+	}
+
 	/**
 	 * 停止游戏监控
 	 */
@@ -50,6 +163,20 @@ class StatsService extends BaseService {
 		});
 	}
 
+	/**
+	 * 手动暂停游戏计时，暂停期间不计入会话时长
+	 */
+	async pauseTracking(gameId: number): Promise<void> {
+		return this.invoke<void>("pause_tracking", { gameId });
+	}
+
+	/**
+	 * 恢复游戏计时
+	 */
+	async resumeTracking(gameId: number): Promise<void> {
+		return this.invoke<void>("resume_tracking", { gameId });
+	}
+
 	/**
 	 * 扫描并接管外部启动的游戏进程
 	 */
@@ -77,6 +204,15 @@ class StatsService extends BaseService {
 		});
 	}
 
+	/**
+	 * 从 CSV / JSON 文本导入游玩会话并重建统计
+	 * @param sessionsJson JSON 数组或带 start_time,end_time[,duration] 表头的 CSV
+	 * @returns 导入的会话数量
+	 */
+	async importSessions(gameId: number, sessionsJson: string): Promise<number> {
+		return this.invoke<number>("import_sessions", { gameId, sessionsJson });
+	}
+
 	/**
 	 * 获取游戏会话历史
 	 */
@@ -107,6 +243,21 @@ class StatsService extends BaseService {
 		});
 	}
 
+	/**
+	 * 分页获取全库的游玩会话，按开始时间倒序，附带游戏名称与封面
+	 */
+	async getAllSessionsPaged(
+		limit: number = 20,
+		offset: number = 0,
+		language?: string,
+	): Promise<SessionFeedItem[]> {
+		return this.invoke<SessionFeedItem[]>("get_all_sessions_paged", {
+			limit,
+			offset,
+			language,
+		});
+	}
+
 	// 暂时无用
 	/**
 	 * 删除游戏会话
@@ -137,6 +288,78 @@ class StatsService extends BaseService {
 	async getAllGameLastPlayed(): Promise<GameLastPlayed[]> {
 		return this.invoke<GameLastPlayed[]>("get_all_game_last_played");
 	}
+
+	/**
+	 * 获取全库累计游玩时长
+	 */
+	async getTotalPlaytime(): Promise<TotalPlaytime> {
+		return this.invoke<TotalPlaytime>("get_total_playtime");
+	}
+
+	/**
+	 * 获取全库完成率
+	 */
+	async getCompletionStats(): Promise<CompletionStats> {
+		return this.invoke<CompletionStats>("get_completion_stats");
+	}
+
+	/**
+	 * 获取全库自指定时间起的游玩时长（秒）及各游戏明细
+	 * @param sinceTimestamp Unix 时间戳（秒）
+	 */
+	async getPlaytimeSince(sinceTimestamp: number): Promise<PlaytimeSince> {
+		return this.invoke<PlaytimeSince>("get_playtime_since", { sinceTimestamp });
+	}
+
+	/**
+	 * 获取单个游戏自指定时间起的游玩时长（秒）
+	 * @param sinceTimestamp Unix 时间戳（秒）
+	 */
+	async getGamePlaytimeSince(
+		gameId: number,
+		sinceTimestamp: number,
+	): Promise<GamePlaytimeSince> {
+		return this.invoke<GamePlaytimeSince>("get_game_playtime_since", {
+			gameId,
+			sinceTimestamp,
+		});
+	}
+
+	/**
+	 * 获取全库活跃度热力图数据
+	 * @param startDate 开始日期（YYYY-MM-DD）
+	 * @param endDate 结束日期（YYYY-MM-DD，含当天）
+	 */
+	async getActivityHeatmap(
+		startDate: string,
+		endDate: string,
+	): Promise<HeatmapDay[]> {
+		return this.invoke<HeatmapDay[]>("get_activity_heatmap", {
+			startDate,
+			endDate,
+		});
+	}
+
+	/**
+	 * 获取指定日期游玩过的游戏，按当天游玩时长降序
+	 * @param date 日期（YYYY-MM-DD）
+	 */
+	async findGamesPlayedOn(date: string): Promise<GamePlayedOnDate[]> {
+		return this.invoke<GamePlayedOnDate[]>("find_games_played_on", { date });
+	}
+
+	/**
+	 * 获取首页仪表盘数据（最近游玩的游戏及其统计）
+	 */
+	async getDashboardData(
+		limit: number,
+		language?: string,
+	): Promise<DashboardGameData[]> {
+		return this.invoke<DashboardGameData[]>("get_dashboard_data", {
+			limit,
+			language,
+		});
+	}
 }
 
 // 导出单例
@@ -6,7 +6,13 @@
 /**
  * 游戏类型筛选（小写，匹配后端 Rust 枚举）
  */
-export type GameType = "all" | "local" | "online" | "iscustom";
+export type GameType =
+	| "all"
+	| "local"
+	| "online"
+	| "iscustom"
+	| "favorite"
+	| "playing";
 
 /**
  * 排序选项（小写，匹配后端 Rust 枚举）
@@ -18,13 +24,101 @@ export type SortOption =
 	| "bgmrank"
 	| "vndbrank"
 	| "userratingrank"
-	| "namesort";
+	| "namesort"
+	| "relevance"
+	| "status"
+	| "playtime";
+
+/**
+ * 游戏状态（小写，匹配后端 Rust 枚举），对应 games.clear 的 PlayStatus 1-5
+ */
+export type GameStatus =
+	| "planning"
+	| "cleared"
+	| "playing"
+	| "onhold"
+	| "dropped";
+
+/**
+ * 归档游戏筛选方式（小写，匹配后端 Rust 枚举）
+ */
+export type ArchivedFilter = "exclude" | "include" | "only";
+
+/**
+ * 游戏列表附加筛选条件
+ */
+export interface GameFilter {
+	archived?: ArchivedFilter;
+	/** 关键词，匹配名称、来源别名/全部标题及自定义开发商/别名/标签；配合 "relevance" 排序按名称匹配程度排序 */
+	keyword?: string;
+	/** 收藏的游戏排在最前，作为所选排序之前的第一排序键 */
+	favorites_first?: boolean;
+	/** 仅返回指定状态的游戏 */
+	play_status?: GameStatus;
+	/** 仅返回带有该标签的游戏，整项匹配且不区分大小写，可取自 getAllTags 的结果 */
+	tag?: string;
+	/** gameType 为 "playing" 时的时间窗口（天），默认 14 天内玩过的游戏 */
+	playing_days?: number;
+}
 
 /**
  * 排序方向（小写，匹配后端 Rust 枚举）
  */
 export type SortOrder = "asc" | "desc";
 
+/**
+ * 筛选项取值及拥有该值的游戏数量
+ */
+export interface FacetCount {
+	value: string;
+	count: number;
+}
+
+/**
+ * 游戏媒体类型
+ */
+export type GameMediaType = "screenshot" | "cover" | "other";
+
+/**
+ * 游戏媒体项，path 为媒体文件的完整路径
+ */
+export interface GameMediaItem {
+	id: number;
+	game_id: number;
+	media_type: GameMediaType;
+	path: string;
+	sort_order: number;
+	created_at: number | null;
+}
+
+/**
+ * 元数据批量刷新进度
+ */
+export interface RefreshRun {
+	token: string;
+	source: string;
+	/** 尚未刷新的游戏 ID */
+	pending: number[];
+	/** 已刷新的游戏数量 */
+	completed: number;
+}
+
+/**
+ * 文件清理结果（已删除与删除失败的文件）
+ */
+export interface FileCleanupReport {
+	removed: string[];
+	failed: { path: string; error: string }[];
+}
+
+/**
+ * 彻底删除游戏的结果
+ */
+export interface DeleteGameCompleteResult {
+	rows_affected: number;
+	files: FileCleanupReport;
+}
+
 /**
  * 统一的服务响应类型
  */
@@ -1,15 +1,10 @@
-import { disable, enable, isEnabled } from "@tauri-apps/plugin-autostart";
+import { settingsService } from "@/services/invoke";
+
 export const toggleAutostart = async () => {
 	try {
-		// 检查当前是否已启用 autostart
-		const enabled = await isEnabled();
-		if (enabled) {
-			// 如果已启用，则禁用 autostart
-			disable();
-		} else {
-			// 如果未启用，则启用 autostart
-			await enable();
-		}
+		// 检查当前是否已启用 autostart，并切换为相反状态
+		const enabled = await settingsService.getAutostartEnabled();
+		await settingsService.setAutostartEnabled(!enabled);
 	} catch (error) {
 		console.error("Error toggling autostart:", error);
 	}
@@ -28,6 +28,7 @@ import {
 	stopGameWithTracking,
 } from "@/services/game/gameRuntime";
 import { initGameTimeTracking } from "@/services/game/gameStats";
+import type { LaunchDiagnostics } from "@/services/invoke/statsService";
 import { useStore } from "@/store/appStore";
 import type { StopGameResult, TimeTrackingMode } from "@/types";
 import { toError } from "@/utils/errors";
@@ -40,6 +41,7 @@ interface LaunchGameResult {
 	message: string;
 	code?: string | null;
 	process_id?: number;
+	diagnostics?: LaunchDiagnostics | null;
 }
 
 /**
@@ -12,6 +12,9 @@ export interface CollectionGroup {
 	id: number;
 	name: string;
 	sort_order: number;
+	/** 合集视图保存的排序字段，为空时使用合集内的手动顺序 */
+	view_sort_option?: string | null;
+	view_sort_order?: string | null;
 }
 
 /**
@@ -25,6 +28,58 @@ export interface CollectionCategory {
 	game_count: number;
 }
 
+/**
+ * 新建合集的返回值，附带层级信息。
+ * 根合集为分组（depth 0），分组下的为分类（depth 1）。
+ */
+export interface CreatedCollection extends CollectionGroup {
+	parent_id: number | null;
+	depth: number;
+	is_group: boolean;
+}
+
+/**
+ * 分享清单中的一个游戏，按 bgm_id / vndb_id 精确匹配，没有 ID 匹配时按标题模糊匹配
+ */
+export interface SharedCollectionEntry {
+	bgm_id?: string | null;
+	vndb_id?: string | null;
+	title?: string | null;
+}
+
+/**
+ * 按标题找到的候选游戏，需用户确认后再加入合集
+ */
+export interface FuzzyCollectionMatch {
+	/** 条目在清单中的下标 */
+	index: number;
+	title: string;
+	candidates: {
+		game_id: number;
+		name: string | null;
+		matched_title: string;
+		/** 相似度（0-1） */
+		score: number;
+	}[];
+}
+
+export interface CollectionImportResult {
+	/** 按 ID 精确匹配并已加入合集的游戏 */
+	linked: number[];
+	fuzzy: FuzzyCollectionMatch[];
+	/** 没有任何匹配的条目下标 */
+	unmatched: number[];
+}
+
+export interface MergeCollectionsResult {
+	/** 追加到保留合集的游戏数量 */
+	added: number;
+	/** 已在保留合集中而跳过的游戏数量 */
+	skipped: number;
+	/** 合并分组时移动到保留分组下的分类数量 */
+	moved_children: number;
+}
+
 export type Group = CollectionGroup;
 export type Category = CollectionCategory;
 
@@ -259,6 +259,12 @@ export interface FullGameData extends GameRuntimePayload {
 	date?: string;
 	created_at?: number;
 	updated_at?: number;
+	/** 归档（软删除）时间，未归档时为 null */
+	archived_at?: Nullable<number>;
+	/** 显示名称与封面所用的主数据源（来源名或 "custom"），null 表示自动选择 */
+	primary_source?: Nullable<SourceType | "custom">;
+	/** 是否收藏，独立于合集 */
+	favorite?: boolean;
 }
 
 /**
@@ -287,6 +293,8 @@ export interface InsertGameParams
 	localpath?: string;
 	savepath?: string;
 	custom_data?: Nullable<CustomData>;
+	/** 导入已导出的游戏时保留收藏标记 */
+	favorite?: boolean;
 }
 
 /**
@@ -447,6 +455,7 @@ export interface SavedataRecord {
 	file: string; // 对应数据库中的 file 列（备份文件名）
 	backup_time: number;
 	file_size: number;
+	base_backup_id: number | null; // 增量备份依赖的基础备份，完整备份为 null
 }
 
 /**
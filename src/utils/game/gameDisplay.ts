@@ -1,7 +1,7 @@
 import { convertFileSrc } from "@tauri-apps/api/core";
 import i18next from "i18next";
 import { join } from "pathe";
-import { getAppDataDirPath } from "@/services/fs/pathCache";
+import { getCoversDirPath } from "@/services/fs/pathCache";
 import type { GameData } from "@/types";
 import { buildTauriProtocolUrl } from "@/utils/tauriProtocol";
 
@@ -15,8 +15,7 @@ export const getGameDisplayName = (game: GameData): string => {
 };
 
 export const getcustomCoverFolder = (gameID: number): string => {
-	const resourceFolder = getAppDataDirPath();
-	return join(resourceFolder, "covers", `game_${gameID}`);
+	return join(getCoversDirPath(), `game_${gameID}`);
 };
 
 export const getGameCover = (game: GameData): string => {